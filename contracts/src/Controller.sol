@@ -30,6 +30,22 @@ contract Controller is Ownable {
     // Coordinators
     mapping(uint256 => address) public coordinators; // maps group index to coordinator address
 
+    // The DKG protocol run by newly emitted groups, see the protocols in Coordinator
+    uint256 public dkgProtocol = 0;
+
+    // * Events
+    // lets the members of a group know to start the DKG with the coordinator
+    event DkgTask(
+        uint256 indexed groupIndex,
+        uint256 groupEpoch,
+        uint256 size,
+        uint256 threshold,
+        address[] members,
+        uint256 assignmentBlockHeight,
+        address coordinatorAddress,
+        uint256 dkgProtocol
+    );
+
     // * Structs
     struct Node {
         address idAddress;
@@ -59,6 +75,10 @@ contract Controller is Ownable {
         uint256 groupEpoch;
         bytes publicKey;
         address[] disqualifiedNodes;
+        // serialized statuses and complaints blaming the disqualified nodes.
+        // It only takes part in the commit consensus here, the disqualified
+        // nodes are slashed by the blamed reasons in the mock controller only.
+        bytes dkgTranscript;
     }
 
     struct CommitCache {
@@ -66,6 +86,10 @@ contract Controller is Ownable {
         CommitResult commitResult;
     }
 
+    function setDkgProtocol(uint256 protocol) external onlyOwner {
+        dkgProtocol = protocol;
+    }
+
     // ! Node Register
     function nodeRegister(bytes calldata dkgPublicKey) public {
         require(
@@ -190,11 +214,18 @@ contract Controller is Ownable {
             groupKeys[i] = nodes[g.members[i].nodeIdAddress].dkgPublicKey;
         }
 
-        coordinator.initialize(groupNodes, groupKeys);
-
-        // TODO: Emit event
-        // dkgtask = {}
-        // emit_dkg_task (dkg_task) -> this let nodes know to start DKG with the coordinator
+        coordinator.initialize(groupNodes, groupKeys, dkgProtocol);
+
+        emit DkgTask(
+            g.index,
+            g.epoch,
+            g.size,
+            g.threshold,
+            groupNodes,
+            block.number,
+            address(coordinator),
+            dkgProtocol
+        );
     }
 
     // ! Commit DKG
@@ -236,7 +267,8 @@ contract Controller is Ownable {
         uint256 groupEpoch,
         bytes calldata publicKey,
         bytes calldata partialPublicKey,
-        address[] calldata disqualifiedNodes
+        address[] calldata disqualifiedNodes,
+        bytes calldata dkgTranscript
     ) external {
         // require group exists
         require(groups[groupIndex].index != 0, "Group does not exist");
@@ -275,7 +307,8 @@ contract Controller is Ownable {
         CommitResult memory commitResult = CommitResult({
             groupEpoch: groupEpoch,
             publicKey: publicKey,
-            disqualifiedNodes: disqualifiedNodes
+            disqualifiedNodes: disqualifiedNodes,
+            dkgTranscript: dkgTranscript
         });
 
         if (!tryAddToExistingCommitCache(groupIndex, commitResult)) {
@@ -356,7 +389,7 @@ contract Controller is Ownable {
                 false,
                 CommitCache(
                     new address[](0),
-                    CommitResult(0, "", new address[](0))
+                    CommitResult(0, "", new address[](0), "")
                 )
             );
         }
@@ -18,6 +18,9 @@ contract Coordinator is Ownable {
     /// Mapping of Ethereum Address => DKG Phase 3 Justifications
     mapping(address => bytes) public justifications;
 
+    /// Mapping of Ethereum Address => DKG Phase 4 Extractions (Gennaro DKG only)
+    mapping(address => bytes) public extractions;
+
     // List of registered Ethereum keys (used for conveniently fetching data)
     address[] public participants;
 
@@ -27,6 +30,14 @@ contract Coordinator is Ownable {
     /// The threshold of the DKG
     uint256 public immutable THRESHOLD;
 
+    /// The DKG protocols a group can run
+    uint256 public constant JOINT_FELDMAN_DKG = 0;
+    uint256 public constant GENNARO_DKG = 1;
+    uint256 public constant PVSS_DKG = 2;
+
+    /// The DKG protocol of the group, only the Gennaro DKG has the extraction phase
+    uint256 public dkgProtocol;
+
     /// If it's 0 then the DKG is still pending start. If >0, it is the DKG's start block
     uint256 public startBlock = 0;
 
@@ -47,11 +58,19 @@ contract Coordinator is Ownable {
         PHASE_DURATION = duration;
     }
 
-    function initialize(address[] calldata nodes, bytes[] calldata publicKeys)
-        external
-        onlyWhenNotStarted
-        onlyOwner
-    {
+    function initialize(
+        address[] calldata nodes,
+        bytes[] calldata publicKeys,
+        uint256 protocol
+    ) external onlyWhenNotStarted onlyOwner {
+        require(
+            protocol == JOINT_FELDMAN_DKG ||
+                protocol == GENNARO_DKG ||
+                protocol == PVSS_DKG,
+            "unknown DKG protocol"
+        );
+        dkgProtocol = protocol;
+
         for (uint256 i = 0; i < nodes.length; i++) {
             participants.push(nodes[i]);
             keys[nodes[i]] = publicKeys[i];
@@ -61,9 +80,10 @@ contract Coordinator is Ownable {
     }
 
     /// Participant publishes their data and depending on the phase the data gets inserted
-    /// in the shares, responses or justifications mapping. Reverts if the participant
+    /// in the shares, responses, justifications or extractions mapping. Reverts if the participant
     /// has already published their data for a phase or if the DKG has ended.
     function publish(bytes calldata value) external onlyGroupMember {
+        require(dkgProtocol != PVSS_DKG, "the PVSS DKG only takes dealings");
         uint256 blocksSinceStart = block.number - startBlock;
 
         if (blocksSinceStart <= PHASE_DURATION) {
@@ -84,11 +104,36 @@ contract Coordinator is Ownable {
                 "you have already published your justifications"
             );
             justifications[msg.sender] = value;
+        } else if (
+            dkgProtocol == GENNARO_DKG &&
+            blocksSinceStart <= 4 * PHASE_DURATION
+        ) {
+            require(
+                extractions[msg.sender].length == 0,
+                "you have already published your extraction"
+            );
+            extractions[msg.sender] = value;
         } else {
             revert("DKG Publish has ended");
         }
     }
 
+    /// Participant publishes their dealing of the non-interactive PVSS DKG in its single round,
+    /// which is inserted in the shares mapping. The dealing is publicly verifiable, every share
+    /// holder verifies it before taking its share.
+    function publishDealing(bytes calldata value) external onlyGroupMember {
+        require(dkgProtocol == PVSS_DKG, "only the PVSS DKG takes dealings");
+        require(
+            block.number - startBlock <= PHASE_DURATION,
+            "DKG Publish has ended"
+        );
+        require(
+            shares[msg.sender].length == 0,
+            "you have already published your shares"
+        );
+        shares[msg.sender] = value;
+    }
+
     // Helpers to fetch data in the mappings. If a participant has registered but not
     // published their data for a phase, the array element at their index is expected to be 0
 
@@ -122,6 +167,16 @@ contract Coordinator is Ownable {
         return _justifications;
     }
 
+    /// Gets the participants' extractions
+    function getExtractions() external view returns (bytes[] memory) {
+        bytes[] memory _extractions = new bytes[](participants.length);
+        for (uint256 i = 0; i < participants.length; i++) {
+            _extractions[i] = extractions[participants[i]];
+        }
+
+        return _extractions;
+    }
+
     /// Gets the participants' ethereum addresses
     function getParticipants() external view returns (address[] memory) {
         return participants;
@@ -151,6 +206,13 @@ contract Coordinator is Ownable {
             return 1; // share
         }
 
+        if (dkgProtocol == PVSS_DKG) {
+            if (blocksSinceStart <= 2 * PHASE_DURATION) {
+                return 2; // Commit DKG: Handled in controller
+            }
+            return -1;
+        }
+
         if (blocksSinceStart <= 2 * PHASE_DURATION) {
             return 2; // response
         }
@@ -158,11 +220,18 @@ contract Coordinator is Ownable {
         if (blocksSinceStart <= 3 * PHASE_DURATION) {
             return 3; // justification
         }
-        if (blocksSinceStart <= 4 * PHASE_DURATION) {
+        if (dkgProtocol == GENNARO_DKG) {
+            if (blocksSinceStart <= 4 * PHASE_DURATION) {
+                return 4; // extraction
+            }
+            if (blocksSinceStart <= 5 * PHASE_DURATION) {
+                return 5; // Commit DKG: Handled in controller
+            }
+        } else if (blocksSinceStart <= 4 * PHASE_DURATION) {
             return 4; // Commit DKG: Handled in controller
         }
 
-        // ! commit_dkg Phase 4
+        // ! commit_dkg Phase 4, Phase 5 after the Gennaro extraction or Phase 2 in the PVSS DKG
 
         // revert("DKG Ended");
         return -1;
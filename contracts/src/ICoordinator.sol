@@ -3,8 +3,11 @@ pragma solidity ^0.8.15;
 interface ICoordinator {
     function inPhase() external view returns (int8);
 
-    function initialize(address[] memory nodes, bytes[] memory publicKeys)
-        external;
+    function initialize(
+        address[] memory nodes,
+        bytes[] memory publicKeys,
+        uint256 protocol
+    ) external;
 
     function startBlock() external view returns (uint256);
 
@@ -153,11 +153,72 @@ contract ControllerTest is Test {
         vm.roll(startBlock + 1 + 2 * PHASE_DURATION);
         assertEq(coordinator.inPhase(), 3);
         vm.roll(startBlock + 1 + 3 * PHASE_DURATION);
-        assertEq(coordinator.inPhase(), 4);
+        assertEq(coordinator.inPhase(), 4); // commit
         vm.roll(startBlock + 1 + 4 * PHASE_DURATION);
         assertEq(coordinator.inPhase(), -1);
     }
 
+    function testGennaroCoordinatorPhase() public {
+        vm.prank(owner);
+        controller.setDkgProtocol(1);
+        testEmitGroupEvent();
+        uint256 groupIndex = 1;
+        address coordinatorAddress = controller.getCoordinator(groupIndex);
+        ICoordinator coordinator = ICoordinator(coordinatorAddress);
+        uint256 startBlock = coordinator.startBlock();
+        assertEq(coordinator.inPhase(), 1);
+        vm.roll(startBlock + 1 + 3 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 4); // extraction
+        vm.roll(startBlock + 1 + 4 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 5); // commit
+        vm.roll(startBlock + 1 + 5 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), -1);
+    }
+
+    function testDkgTaskEvent() public {
+        vm.prank(owner);
+        controller.setDkgProtocol(1);
+
+        vm.prank(node1);
+        controller.nodeRegister(pubkey1);
+        vm.prank(node2);
+        controller.nodeRegister(pubkey2);
+
+        vm.recordLogs();
+        vm.prank(node3);
+        controller.nodeRegister(pubkey3);
+        Vm.Log[] memory entries = vm.getRecordedLogs();
+
+        Vm.Log memory dkgTask = entries[entries.length - 1];
+        assertEq(
+            dkgTask.topics[0],
+            keccak256(
+                "DkgTask(uint256,uint256,uint256,uint256,address[],uint256,address,uint256)"
+            )
+        );
+        assertEq(uint256(dkgTask.topics[1]), 1); // group index
+        (
+            uint256 groupEpoch,
+            uint256 size,
+            uint256 threshold,
+            address[] memory members,
+            uint256 assignmentBlockHeight,
+            address coordinatorAddress,
+            uint256 dkgProtocol
+        ) = abi.decode(
+                dkgTask.data,
+                (uint256, uint256, uint256, address[], uint256, address, uint256)
+            );
+        assertEq(groupEpoch, 1);
+        assertEq(size, 3);
+        assertEq(threshold, 3);
+        assertEq(members.length, 3);
+        assertEq(members[2], node3);
+        assertEq(assignmentBlockHeight, block.number);
+        assertEq(coordinatorAddress, controller.getCoordinator(1));
+        assertEq(dkgProtocol, 1);
+    }
+
     function testCommitDkg() public {
         testEmitGroupEvent();
 
@@ -170,6 +231,7 @@ contract ControllerTest is Test {
         bytes memory partialPublicKey = hex"DECADE";
         bytes memory publicKey = hex"C0FFEE";
         address[] memory disqualifiedNodes = new address[](0);
+        bytes memory dkgTranscript = hex"";
 
         vm.prank(node1);
         vm.expectRevert("Group does not exist");
@@ -178,7 +240,8 @@ contract ControllerTest is Test {
             groupEpoch,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
 
         vm.prank(node1);
@@ -190,7 +253,8 @@ contract ControllerTest is Test {
             3,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
 
         vm.prank(node5);
@@ -200,7 +264,8 @@ contract ControllerTest is Test {
             groupEpoch,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
 
         // Succesful Commit: Node 1
@@ -210,7 +275,8 @@ contract ControllerTest is Test {
             groupEpoch,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
 
         vm.prank(node1);
@@ -222,7 +288,8 @@ contract ControllerTest is Test {
             groupEpoch,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
 
         assertEq(checkIsStrictlyMajorityConsensusReached(groupIndex), false);
@@ -234,7 +301,8 @@ contract ControllerTest is Test {
             groupEpoch,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
 
         assertEq(checkIsStrictlyMajorityConsensusReached(groupIndex), false);
@@ -246,7 +314,8 @@ contract ControllerTest is Test {
             groupEpoch,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
 
         assertEq(checkIsStrictlyMajorityConsensusReached(groupIndex), true);
@@ -260,7 +329,8 @@ contract ControllerTest is Test {
             groupEpoch,
             publicKey,
             partialPublicKey,
-            disqualifiedNodes
+            disqualifiedNodes,
+            dkgTranscript
         );
     }
 
@@ -16,6 +16,11 @@ contract CoordinatorTest is Test {
     uint256 PHASE_DURATION = 10;
     uint256 THRESHOLD = 3;
 
+    // DKG protocols
+    uint256 JOINT_FELDMAN_DKG = 0;
+    uint256 GENNARO_DKG = 1;
+    uint256 PVSS_DKG = 2;
+
     // Create 3 members for initialize()
     address public controller = address(0xCAFEBABE);
     address public node1 = address(0x1);
@@ -43,7 +48,7 @@ contract CoordinatorTest is Test {
 
     function testGetParticipantsAndKeys() public {
         vm.prank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
 
         // Test getParticipants and getDkgKeys after initialize()
         address[] memory participants = coordinator.getParticipants();
@@ -58,27 +63,27 @@ contract CoordinatorTest is Test {
     function testOnlyOwnerCanInitialize() public {
         // Non-Owner can't initialize
         vm.expectRevert("Ownable: caller is not the owner");
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
 
         // Owner can initizlize
         vm.prank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
     }
 
     function testInitializeOnlyWhenNotStarted() public {
         // Initialize with owner
         vm.startPrank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
 
         // Initialize callable onlyWhenNotStarted
         vm.expectRevert("DKG has already started");
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
         vm.stopPrank();
     }
 
     function testOnlyGroupMemberCanPublish() public {
         vm.prank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
 
         // non-registered node can't publish
         vm.expectRevert("you are not a group member!");
@@ -94,7 +99,7 @@ contract CoordinatorTest is Test {
 
     function testDoublePublish() public {
         vm.prank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, GENNARO_DKG);
         uint256 startBlock = coordinator.startBlock();
 
         // Phase 1: Shares
@@ -115,8 +120,14 @@ contract CoordinatorTest is Test {
         vm.expectRevert("you have already published your justifications");
         coordinator.publish(data);
 
-        // DKG End
+        // Phase 4: Extractions
         vm.roll(startBlock + 1 + 3 * PHASE_DURATION);
+        coordinator.publish(data); // succesful extraction
+        vm.expectRevert("you have already published your extraction");
+        coordinator.publish(data);
+
+        // DKG End
+        vm.roll(startBlock + 1 + 4 * PHASE_DURATION);
         vm.expectRevert("DKG Publish has ended");
         coordinator.publish(data); // succesful justification
     }
@@ -127,7 +138,7 @@ contract CoordinatorTest is Test {
 
         // Initialize
         vm.prank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
         uint256 startBlock = coordinator.startBlock();
 
         assertEq(coordinator.inPhase(), 1);
@@ -136,17 +147,99 @@ contract CoordinatorTest is Test {
         vm.roll(startBlock + 1 + 2 * PHASE_DURATION);
         assertEq(coordinator.inPhase(), 3);
         vm.roll(startBlock + 1 + 3 * PHASE_DURATION);
-        assertEq(coordinator.inPhase(), 4);
+        assertEq(coordinator.inPhase(), 4); // commit
+        vm.roll(startBlock + 1 + 4 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), -1);
+    }
+
+    function testGennaroPhases() public {
+        vm.prank(controller);
+        coordinator.initialize(nodes, keys, GENNARO_DKG);
+        uint256 startBlock = coordinator.startBlock();
+
+        assertEq(coordinator.inPhase(), 1);
+        vm.roll(startBlock + 1 + PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 2);
+        vm.roll(startBlock + 1 + 2 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 3);
+        vm.roll(startBlock + 1 + 3 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 4); // extraction
         vm.roll(startBlock + 1 + 4 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 5); // commit
+        vm.roll(startBlock + 1 + 5 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), -1);
+    }
+
+    function testJointFeldmanRejectsExtractions() public {
+        vm.prank(controller);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
+        uint256 startBlock = coordinator.startBlock();
+
+        // the phase after the justifications is the commit phase
+        vm.roll(startBlock + 1 + 3 * PHASE_DURATION);
+        vm.prank(node1);
+        vm.expectRevert("DKG Publish has ended");
+        coordinator.publish(data);
+
+        bytes[] memory extractions = coordinator.getExtractions();
+        assertEq(extractions[0], "");
+    }
+
+    function testInitializeUnknownProtocol() public {
+        vm.prank(controller);
+        vm.expectRevert("unknown DKG protocol");
+        coordinator.initialize(nodes, keys, 3);
+    }
+
+    function testPvssPhases() public {
+        vm.prank(controller);
+        coordinator.initialize(nodes, keys, PVSS_DKG);
+        uint256 startBlock = coordinator.startBlock();
+
+        assertEq(coordinator.inPhase(), 1); // dealing
+        vm.roll(startBlock + 1 + PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 2); // commit
+        vm.roll(startBlock + 1 + 2 * PHASE_DURATION);
         assertEq(coordinator.inPhase(), -1);
     }
 
+    function testPublishDealing() public {
+        // only the PVSS DKG takes dealings
+        vm.prank(controller);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
+        vm.prank(node1);
+        vm.expectRevert("only the PVSS DKG takes dealings");
+        coordinator.publishDealing(data);
+
+        vm.prank(controller);
+        coordinator = new Coordinator(THRESHOLD, PHASE_DURATION);
+        vm.prank(controller);
+        coordinator.initialize(nodes, keys, PVSS_DKG);
+        uint256 startBlock = coordinator.startBlock();
+
+        vm.startPrank(node1);
+        vm.expectRevert("the PVSS DKG only takes dealings");
+        coordinator.publish(data);
+
+        coordinator.publishDealing(data); // succesful dealing
+        vm.expectRevert("you have already published your shares");
+        coordinator.publishDealing(data);
+        assertEq(coordinator.getShares()[0], data);
+
+        // the dealings are published in a single round
+        vm.roll(startBlock + 1 + PHASE_DURATION);
+        vm.stopPrank();
+        vm.prank(node2);
+        vm.expectRevert("DKG Publish has ended");
+        coordinator.publishDealing(data);
+    }
+
     function testEnd2End() public {
         assertEq(coordinator.inPhase(), 0);
 
         // Initialize
         vm.prank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, GENNARO_DKG);
         assertEq(coordinator.inPhase(), 1);
         uint256 startBlock = coordinator.startBlock();
 
@@ -175,11 +268,20 @@ contract CoordinatorTest is Test {
         bytes[] memory justifications = coordinator.getJustifications();
         assertEq(justifications[0], "");
         assertEq(justifications[1], my_justifications);
+
+        // Phase 4: Extractions
+        vm.roll(startBlock + 1 + 3 * PHASE_DURATION);
+        assertEq(coordinator.inPhase(), 4);
+        bytes memory my_extraction = "0xFEEDFACE";
+        coordinator.publish(my_extraction); // only node2 publishes
+        bytes[] memory extractions = coordinator.getExtractions();
+        assertEq(extractions[0], "");
+        assertEq(extractions[1], my_extraction);
     }
 
     function testSelfDestructOnlyOwner() public {
         vm.prank(controller);
-        coordinator.initialize(nodes, keys);
+        coordinator.initialize(nodes, keys, JOINT_FELDMAN_DKG);
         vm.expectRevert("Ownable: caller is not the owner");
         coordinator.selfDestruct();
         vm.prank(controller);
@@ -1,13 +1,18 @@
-use crate::node::error::NodeResult;
-use arpa_node_contract_client::coordinator::{CoordinatorTransactions, CoordinatorViews};
+use crate::node::{error::NodeResult, metrics::DKG_PHASE_DURATION_SECONDS};
+use arpa_node_contract_client::coordinator::{
+    CoordinatorTransactions, CoordinatorViews, DKGContractError,
+};
+use arpa_node_core::{DKGProtocol, PeerRecord};
 use async_trait::async_trait;
 use dkg_core::{
-    primitives::{joint_feldman::*, *},
-    BoardPublisher, DKGPhase, Phase2Result,
+    primitives::{gennaro::*, joint_feldman::*, pvss::*, *},
+    BoardPublisher, DKGPhase, NodeError as DKGNodeError, Phase2Result,
 };
 use log::info;
+use prometheus::HistogramTimer;
 use rand::RngCore;
 use rustc_hex::ToHex;
+use serde::{Deserialize, Serialize};
 use std::io::{self, Write};
 use threshold_bls::{
     curve::bls12381::{Curve, Scalar, G1},
@@ -16,12 +21,14 @@ use threshold_bls::{
 
 #[async_trait]
 pub(crate) trait DKGCore<F, R> {
+    /// Runs the DKG, returns its output and the peer records published by the participants.
     async fn run_dkg(
         &mut self,
         dkg_private_key: Scalar,
-        node_rpc_endpoint: String,
+        peer_record: PeerRecord,
+        dkg_protocol: DKGProtocol,
         rng: F,
-    ) -> NodeResult<DKGOutput<Curve>>
+    ) -> NodeResult<(DKGOutput<Curve>, Vec<PeerRecord>)>
     where
         R: RngCore,
         F: Fn() -> R + Send + 'async_trait;
@@ -49,9 +56,10 @@ where
     async fn run_dkg(
         &mut self,
         dkg_private_key: Scalar,
-        node_rpc_endpoint: String,
+        peer_record: PeerRecord,
+        dkg_protocol: DKGProtocol,
         rng: F,
-    ) -> NodeResult<DKGOutput<Curve>>
+    ) -> NodeResult<(DKGOutput<Curve>, Vec<PeerRecord>)>
     where
         F: Send + 'async_trait,
     {
@@ -60,14 +68,18 @@ where
         // Wait for Phase 0
         wait_for_phase(&self.coordinator_client, 0).await?;
 
+        let phase0_timer = DKG_PHASE_DURATION_SECONDS
+            .with_label_values(&["0"])
+            .start_timer();
+
         // Get the group info
         let group = self.coordinator_client.get_bls_keys().await?;
         let participants = self.coordinator_client.get_participants().await?;
 
         // print some debug info
         info!(
-            "Will run DKG with the group listed below and threshold {}",
-            group.0
+            "Will run {:?} DKG with the group listed below and threshold {}",
+            dkg_protocol, group.0
         );
         for (bls_pubkey, address) in group.1.iter().zip(participants) {
             let key = bls_pubkey.to_hex::<String>();
@@ -92,18 +104,89 @@ where
 
         // Instantiate the DKG with the group info
         info!("Calculating and broadcasting our shares... Running Phase 0.");
-        let phase0 = DKG::new(dkg_private_key, node_rpc_endpoint, group)?;
+        let (output, peer_records) = match dkg_protocol {
+            DKGProtocol::JointFeldman => {
+                let phase0 = DKG::new(dkg_private_key, group)?;
+
+                self.run_phases(phase0, peer_record, rng, phase0_timer)
+                    .await?
+            }
+            DKGProtocol::Gennaro => {
+                let phase0 = GDKG::new(dkg_private_key, group)?;
+
+                let (phase4, peer_records) = self
+                    .run_phases(phase0, peer_record, rng, phase0_timer)
+                    .await?;
+
+                (self.run_extraction(phase4).await?, peer_records)
+            }
+            DKGProtocol::Pvss => {
+                let dkg = NIDKG::new(dkg_private_key, group)?;
+
+                self.run_single_round(dkg, peer_record, rng, phase0_timer)
+                    .await?
+            }
+        };
+
+        info!("Success. Your share and threshold pubkey are ready.");
+
+        write_output(&output)?;
 
+        // info!("{:#?}", output.qual.nodes);
+
+        // info!("public key: {}", output.public.public_key());
+
+        Ok((output, peer_records))
+    }
+}
+
+impl<P> AllPhasesDKGCore<P>
+where
+    P: CoordinatorTransactions + CoordinatorViews + BoardPublisher<Curve> + Sync + Send,
+{
+    /// Runs Phase 0 to Phase 3, which end with the qualified dealers fixed.
+    /// The peer record of the node is published along with its shares in Phase 0.
+    #[allow(clippy::type_complexity)]
+    async fn run_phases<F, R, D>(
+        &mut self,
+        phase0: D,
+        peer_record: PeerRecord,
+        rng: F,
+        phase0_timer: HistogramTimer,
+    ) -> NodeResult<(
+        <<D::Next as Phase1<Curve>>::Next as Phase2<Curve>>::Output,
+        Vec<PeerRecord>,
+    )>
+    where
+        R: RngCore,
+        F: Fn() -> R + Send,
+        D: Phase0<Curve> + Send,
+        D::Next: Send,
+        <D::Next as Phase1<Curve>>::Next: Send,
+        <<D::Next as Phase1<Curve>>::Next as Phase2<Curve>>::Next: Send,
+        <<D::Next as Phase1<Curve>>::Next as Phase2<Curve>>::Output: Send,
+    {
         // Run Phase 0 and publish to the chain
-        let phase1 = phase0.run(&mut self.coordinator_client, rng).await?;
+        let mut board = PeerRecordBoard::new(&self.coordinator_client, peer_record);
+        let phase1 = phase0.run(&mut board, rng).await?;
 
         // Wait for Phase 1
         wait_for_phase(&self.coordinator_client, 1).await?;
 
+        phase0_timer.observe_duration();
+
+        let phase1_timer = DKG_PHASE_DURATION_SECONDS
+            .with_label_values(&["1"])
+            .start_timer();
+
         // Get the shares
         let shares = self.coordinator_client.get_shares().await?;
         info!("Got {} shares...", shares.len());
-        let shares = parse_bundle(&shares)?;
+        let (shares, peer_records): (Vec<_>, Vec<_>) =
+            parse_bundle::<SharesWithPeerRecord>(&shares)?
+                .into_iter()
+                .map(|item| (item.shares, item.peer_record))
+                .unzip();
         info!("Parsed {} shares. Running Phase 1.", shares.len());
 
         // Run Phase 1
@@ -112,6 +195,12 @@ where
         // Wait for Phase 2
         wait_for_phase(&self.coordinator_client, 2).await?;
 
+        phase1_timer.observe_duration();
+
+        let phase2_timer = DKG_PHASE_DURATION_SECONDS
+            .with_label_values(&["2"])
+            .start_timer();
+
         // Get the responses
         let responses = self.coordinator_client.get_responses().await?;
         info!("Got {} responses...", responses.len());
@@ -120,38 +209,210 @@ where
 
         // Run Phase 2
         let result = match phase2.run(&mut self.coordinator_client, &responses).await? {
-            Phase2Result::Output(out) => Ok(out),
+            Phase2Result::Output(out) => {
+                phase2_timer.observe_duration();
+
+                out
+            }
             // Run Phase 3 if Phase 2 errored
             Phase2Result::GoToPhase3(phase3) => {
                 info!("There were complaints. Running Phase 3.");
                 // Wait for Phase 3
                 wait_for_phase(&self.coordinator_client, 3).await?;
 
+                phase2_timer.observe_duration();
+
+                let phase3_timer = DKG_PHASE_DURATION_SECONDS
+                    .with_label_values(&["3"])
+                    .start_timer();
+
                 let justifications = self.coordinator_client.get_justifications().await?;
                 let justifications = parse_bundle(&justifications)?;
 
                 // Run Phase 3
-                phase3
+                let result = phase3
                     .run(&mut self.coordinator_client, &justifications)
-                    .await
+                    .await;
+
+                phase3_timer.observe_duration();
+
+                result?
             }
         };
 
-        match result {
-            Ok(output) => {
-                info!("Success. Your share and threshold pubkey are ready.");
+        Ok((result, peer_records))
+    }
 
-                write_output(&output)?;
+    /// Runs the single round of the PVSS DKG, in which every dealer publishes its dealing once
+    /// and the share holders verify the published dealings themselves.
+    /// The peer record of the node is published along with its dealing.
+    async fn run_single_round<F, R>(
+        &mut self,
+        dkg: NIDKG<Curve>,
+        peer_record: PeerRecord,
+        rng: F,
+        phase0_timer: HistogramTimer,
+    ) -> NodeResult<(DKGOutput<Curve>, Vec<PeerRecord>)>
+    where
+        R: RngCore,
+        F: Fn() -> R + Send,
+    {
+        let value = DealingWithPeerRecord {
+            dealing: dkg.deal(&mut rng())?,
+            peer_record,
+        };
 
-                // info!("{:#?}", output.qual.nodes);
+        self.coordinator_client
+            .publish_dealing(bincode::serialize(&value)?)
+            .await
+            .map_err(|_| DKGNodeError::PublisherError)?;
 
-                // info!("public key: {}", output.public.public_key());
+        // Wait for Phase 1
+        wait_for_phase(&self.coordinator_client, 1).await?;
 
-                Ok(output)
-            }
-            Err(err) => Err(err.into()),
+        phase0_timer.observe_duration();
+
+        let phase1_timer = DKG_PHASE_DURATION_SECONDS
+            .with_label_values(&["1"])
+            .start_timer();
+
+        // Get the dealings
+        let dealings = self.coordinator_client.get_shares().await?;
+        info!("Got {} dealings...", dealings.len());
+        let (dealings, peer_records): (Vec<_>, Vec<_>) =
+            parse_bundle::<DealingWithPeerRecord>(&dealings)?
+                .into_iter()
+                .map(|item| (item.dealing, item.peer_record))
+                .unzip();
+        info!("Parsed {} dealings. Processing them.", dealings.len());
+
+        let output = dkg.process_dealings(&dealings);
+
+        phase1_timer.observe_duration();
+
+        Ok((output?, peer_records))
+    }
+
+    /// Runs Phase 4 and Phase 5 of the Gennaro DKG, in which the distributed
+    /// public key is extracted once the qualified dealers are fixed.
+    async fn run_extraction(
+        &mut self,
+        phase4: GDKGExtraction<Curve>,
+    ) -> NodeResult<DKGOutput<Curve>> {
+        // Every share holder extracts in the same window, after the justifications
+        wait_for_phase(&self.coordinator_client, 3).await?;
+
+        let phase4_timer = DKG_PHASE_DURATION_SECONDS
+            .with_label_values(&["4"])
+            .start_timer();
+
+        info!(
+            "{} dealers qualified. Extracting the public share... Running Phase 4.",
+            phase4.qual().len()
+        );
+        let (phase5, extraction) = phase4.extract(&mut rand::thread_rng())?;
+
+        self.coordinator_client
+            .publish_extraction(extraction)
+            .await
+            .map_err(|_| DKGNodeError::PublisherError)?;
+
+        // Wait for Phase 4
+        wait_for_phase(&self.coordinator_client, 4).await?;
+
+        phase4_timer.observe_duration();
+
+        let phase5_timer = DKG_PHASE_DURATION_SECONDS
+            .with_label_values(&["5"])
+            .start_timer();
+
+        // Get the extractions
+        let extractions = self.coordinator_client.get_extractions().await?;
+        info!("Got {} extractions...", extractions.len());
+        let extractions = parse_bundle(&extractions)?;
+        info!("Parsed {} extractions. Running Phase 5.", extractions.len());
+
+        // Run Phase 5
+        let output = phase5.run(&mut self.coordinator_client, &extractions).await;
+
+        phase5_timer.observe_duration();
+
+        Ok(output?)
+    }
+}
+
+/// What a node publishes in Phase 0. The peer record is not part of the DKG, it rides along with
+/// the shares so that the members can reach each other as soon as the group is ready.
+#[derive(Serialize, Deserialize)]
+struct SharesWithPeerRecord {
+    shares: BundledShares<Curve>,
+    peer_record: PeerRecord,
+}
+
+/// What a node publishes in the PVSS DKG. The dealing leads, so that the coordinator can verify it
+/// without knowing about the peer record.
+#[derive(Serialize, Deserialize)]
+struct DealingWithPeerRecord {
+    dealing: PublicDealing<Curve>,
+    peer_record: PeerRecord,
+}
+
+/// Board which publishes the peer record of the node along with its shares.
+struct PeerRecordBoard<'a, P: CoordinatorTransactions> {
+    coordinator_client: &'a P,
+    peer_record: PeerRecord,
+}
+
+impl<'a, P: CoordinatorTransactions> PeerRecordBoard<'a, P> {
+    fn new(coordinator_client: &'a P, peer_record: PeerRecord) -> Self {
+        PeerRecordBoard {
+            coordinator_client,
+            peer_record,
         }
     }
+
+    async fn publish<D: Serialize + Sync>(&self, value: &D) -> Result<(), DKGContractError> {
+        let serialized = bincode::serialize(value)?;
+        self.coordinator_client
+            .publish(serialized)
+            .await
+            .map_err(|e| e.into())
+    }
+}
+
+#[async_trait]
+impl<'a, P: CoordinatorTransactions + Sync> BoardPublisher<Curve> for PeerRecordBoard<'a, P> {
+    type Error = DKGContractError;
+
+    async fn publish_shares(&mut self, shares: BundledShares<Curve>) -> Result<(), Self::Error> {
+        info!("called publish_shares with the peer record");
+        let value = SharesWithPeerRecord {
+            shares,
+            peer_record: self.peer_record.clone(),
+        };
+        self.publish(&value).await
+    }
+
+    async fn publish_responses(
+        &mut self,
+        responses: BundledResponses<Curve>,
+    ) -> Result<(), Self::Error> {
+        self.publish(&responses).await
+    }
+
+    async fn publish_justifications(
+        &mut self,
+        justifications: BundledJustification<Curve>,
+    ) -> Result<(), Self::Error> {
+        self.publish(&justifications).await
+    }
+
+    async fn publish_extraction(
+        &mut self,
+        extraction: BundledExtraction<Curve>,
+    ) -> Result<(), Self::Error> {
+        self.publish(&extraction).await
+    }
 }
 
 async fn wait_for_phase(dkg: &impl CoordinatorViews, num: usize) -> NodeResult<()> {
@@ -193,6 +454,17 @@ fn write_output(out: &DKGOutput<Curve>) -> NodeResult<()> {
 
     info!("{:?}", output);
 
+    for (dealer, complaints) in out.transcript.blames() {
+        let reasons = complaints
+            .iter()
+            .map(|c| (c.holder_idx, c.reason))
+            .collect::<Vec<_>>();
+        info!(
+            "dealer {} is disqualified by (holder, reason): {:?}",
+            dealer, reasons
+        );
+    }
+
     Ok(())
 }
 
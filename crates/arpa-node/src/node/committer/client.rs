@@ -1,16 +1,33 @@
 use self::committer_stub::committer_service_client::CommitterServiceClient;
-use self::committer_stub::CommitPartialSignatureRequest;
-use super::{CommitterClient, CommitterService, ServiceClient};
-use crate::node::error::NodeResult;
-use arpa_node_core::{address_to_string, TaskType};
+use self::committer_stub::{
+    AnnouncePeerRecordRequest, CommitPartialSignatureRequest, CommitPartialSignaturesRequest,
+};
+use super::{
+    server::MAX_PARTIAL_SIGNATURES_PER_BATCH, CommitterClient, CommitterService, ServiceClient,
+};
+use crate::node::error::{NodeError, NodeResult};
+use arpa_node_core::{address_to_string, PeerRecord, SignedPartialSignature};
 use async_trait::async_trait;
 use ethers::types::Address;
-use tonic::Request;
+use futures::{stream, TryStreamExt};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use tonic::{
+    transport::{Channel, Endpoint},
+    Code, Request, Status,
+};
 
 pub mod committer_stub {
     include!("../../../rpc_stub/committer.rs");
 }
 
+lazy_static! {
+    // a channel multiplexes the requests to a committer over one connection, so it is shared
+    // by all the clients to the same endpoint and reconnects on demand
+    static ref COMMITTER_CHANNELS: std::sync::Mutex<HashMap<String, Channel>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct GeneralCommitterClient {
     id_address: Address,
@@ -45,9 +62,40 @@ impl ServiceClient<CommitterServiceClient<tonic::transport::Channel>> for Genera
     async fn prepare_service_client(
         &self,
     ) -> NodeResult<CommitterServiceClient<tonic::transport::Channel>> {
-        CommitterServiceClient::connect(format!("{}{}", "http://", self.committer_endpoint.clone()))
-            .await
-            .map_err(|err| err.into())
+        let mut channels = COMMITTER_CHANNELS.lock().unwrap();
+
+        let channel = match channels.get(&self.committer_endpoint) {
+            Some(channel) => channel.clone(),
+            None => {
+                let channel = Endpoint::from_shared(format!("http://{}", self.committer_endpoint))?
+                    .connect_lazy();
+
+                channels.insert(self.committer_endpoint.clone(), channel.clone());
+
+                channel
+            }
+        };
+
+        Ok(CommitterServiceClient::new(channel))
+    }
+}
+
+impl GeneralCommitterClient {
+    pub(crate) fn build_request(
+        &self,
+        partial_signature: SignedPartialSignature,
+    ) -> CommitPartialSignatureRequest {
+        CommitPartialSignatureRequest {
+            id_address: address_to_string(self.id_address),
+            chain_id: partial_signature.chain_id as u32,
+            signature_index: partial_signature.signature_index as u32,
+            partial_signature: partial_signature.partial_signature,
+            task_type: partial_signature.task_type.to_i32(),
+            message: partial_signature.message,
+            group_index: partial_signature.group_index as u32,
+            signed_at: partial_signature.signed_at,
+            signature: partial_signature.signature,
+        }
     }
 }
 
@@ -55,20 +103,9 @@ impl ServiceClient<CommitterServiceClient<tonic::transport::Channel>> for Genera
 impl CommitterService for GeneralCommitterClient {
     async fn commit_partial_signature(
         self,
-        chain_id: usize,
-        task_type: TaskType,
-        message: Vec<u8>,
-        signature_index: usize,
-        partial_signature: Vec<u8>,
+        partial_signature: SignedPartialSignature,
     ) -> NodeResult<bool> {
-        let request = Request::new(CommitPartialSignatureRequest {
-            id_address: address_to_string(self.id_address),
-            chain_id: chain_id as u32,
-            signature_index: signature_index as u32,
-            partial_signature,
-            task_type: task_type.to_i32(),
-            message,
-        });
+        let request = Request::new(self.build_request(partial_signature));
 
         let mut committer_client = self.prepare_service_client().await?;
 
@@ -78,4 +115,71 @@ impl CommitterService for GeneralCommitterClient {
             .map(|r| r.into_inner().result)
             .map_err(|status| status.into())
     }
+
+    async fn commit_partial_signatures(
+        self,
+        partial_signatures: Vec<SignedPartialSignature>,
+    ) -> NodeResult<Vec<NodeResult<bool>>> {
+        let count = partial_signatures.len();
+
+        let requests = partial_signatures
+            .into_iter()
+            .map(|partial_signature| self.build_request(partial_signature))
+            .collect::<Vec<_>>();
+
+        let mut committer_client = self.prepare_service_client().await?;
+
+        // a batch is limited in size, so more partial signatures are streamed and acknowledged
+        // one by one instead
+        let results = if count > MAX_PARTIAL_SIGNATURES_PER_BATCH {
+            committer_client
+                .stream_partial_signatures(Request::new(stream::iter(requests)))
+                .await?
+                .into_inner()
+                .try_collect::<Vec<_>>()
+                .await?
+        } else {
+            committer_client
+                .commit_partial_signatures(Request::new(CommitPartialSignaturesRequest {
+                    partial_signatures: requests,
+                }))
+                .await?
+                .into_inner()
+                .results
+        };
+
+        if results.len() != count {
+            return Err(Status::internal("the committer acknowledged a different batch").into());
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|result| match Code::from_i32(result.code) {
+                Code::Ok => Ok(result.result),
+                code => Err(NodeError::RpcResponseError(Status::new(
+                    code,
+                    result.message,
+                ))),
+            })
+            .collect())
+    }
+
+    async fn announce_peer_record(self, peer_record: PeerRecord) -> NodeResult<Option<PeerRecord>> {
+        let request = Request::new(AnnouncePeerRecordRequest {
+            peer_record: bincode::serialize(&peer_record)?,
+        });
+
+        let mut committer_client = self.prepare_service_client().await?;
+
+        let reply = committer_client
+            .announce_peer_record(request)
+            .await?
+            .into_inner();
+
+        if reply.peer_record.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(bincode::deserialize(&reply.peer_record)?))
+    }
 }
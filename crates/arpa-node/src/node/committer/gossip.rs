@@ -0,0 +1,240 @@
+use super::{CommitterClient, CommitterService};
+use crate::node::error::NodeError;
+use arpa_node_core::{now_timestamp, SignedPartialSignature};
+use arpa_node_dal::GroupInfoFetcher;
+use ethers::types::Address;
+use futures::future::join_all;
+use log::debug;
+use rand::seq::SliceRandom;
+use tokio_retry::{strategy::FixedInterval, RetryIf};
+use tonic::Code;
+
+/// Chooses `fanout` random members of the latest committed epoch of the group which can be reached,
+/// other than the excluded ones, and builds clients to push them the partial signature of `signer`.
+pub(crate) fn prepare_gossip_clients<C: CommitterClient, G: GroupInfoFetcher>(
+    group_cache: &G,
+    group_index: usize,
+    signer: Address,
+    excluded: &[Address],
+    fanout: usize,
+) -> Vec<C> {
+    let members = match group_cache
+        .get_ready_epoch(group_index)
+        .and_then(|epoch| group_cache.get_members(group_index, epoch))
+    {
+        Ok(members) => members,
+        Err(_) => return vec![],
+    };
+
+    let now = now_timestamp();
+
+    let peers = members
+        .keys()
+        .filter(|member| **member != signer && !excluded.contains(member))
+        .filter_map(|member| group_cache.get_peer_endpoint(*member, now))
+        .collect::<Vec<_>>();
+
+    // the request is built on behalf of the member which signed the partial signature,
+    // so it is forwarded as is
+    peers
+        .choose_multiple(&mut rand::thread_rng(), fanout)
+        .map(|endpoint| C::build(signer, endpoint.to_string()))
+        .collect()
+}
+
+/// Pushes the partial signature to the peers at once, so that a slow or unreachable peer doesn't
+/// hold back the others. Members which have got it already reject it as a replay, and members
+/// which have not handled the task yet are retried a few times.
+pub(crate) async fn gossip_partial_signature<C: CommitterClient + CommitterService + Clone>(
+    peers: Vec<C>,
+    partial_signature: SignedPartialSignature,
+) {
+    join_all(
+        peers
+            .into_iter()
+            .map(|peer| push_partial_signature(peer, partial_signature.clone())),
+    )
+    .await;
+}
+
+async fn push_partial_signature<C: CommitterClient + CommitterService + Clone>(
+    peer: C,
+    partial_signature: SignedPartialSignature,
+) {
+    let retry_strategy = FixedInterval::from_millis(2000).take(3);
+
+    if let Err(e) = RetryIf::spawn(
+        retry_strategy,
+        || {
+            peer.clone()
+                .commit_partial_signature(partial_signature.clone())
+        },
+        |e: &NodeError| match e {
+            NodeError::RpcResponseError(status) => status.code() != Code::AlreadyExists,
+            _ => true,
+        },
+    )
+    .await
+    {
+        debug!(
+            "gossip partial signature to {} failed: {:?}",
+            peer.get_committer_endpoint(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{gossip_partial_signature, prepare_gossip_clients};
+    use crate::node::{
+        committer::{
+            client::GeneralCommitterClient,
+            server::tests::{build_group, sign_randomness_partial_signature, GROUP_INDEX},
+            CommitterClient, CommitterService,
+        },
+        error::NodeResult,
+    };
+    use arpa_node_core::{now_timestamp, DKGStatus, PeerRecord, SignedPartialSignature};
+    use arpa_node_dal::cache::{GroupInfo, InMemoryGroupInfoCache};
+    use async_trait::async_trait;
+    use ethers::{signers::Signer, types::Address};
+    use std::{
+        collections::HashSet,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn test_prepare_gossip_clients() {
+        let (group, members) = build_group(6, 4);
+
+        let mut peer_records = vec![];
+
+        // the last member has not announced a peer record, and the one before has an expired one
+        for (index, member) in members.iter().enumerate().take(5) {
+            let expires_at = if index == 4 {
+                now_timestamp() - 1
+            } else {
+                now_timestamp() + 60
+            };
+
+            peer_records.push(
+                PeerRecord::sign(&member.wallet, format!("127.0.0.1:{}", index), expires_at)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let group_cache = InMemoryGroupInfoCache::rebuild(
+            vec![GroupInfo::rebuild(None, group, DKGStatus::None, 0, 0, None)],
+            peer_records,
+        );
+
+        let signer = members[1].wallet.address();
+        let excluded = members[0].wallet.address();
+
+        // the signer and the excluded member are skipped, only the members 2 and 3 can be reached
+        let peers: Vec<GeneralCommitterClient> =
+            prepare_gossip_clients(&group_cache, GROUP_INDEX, signer, &[excluded], 10);
+
+        let endpoints = peers
+            .iter()
+            .map(|peer| peer.get_committer_endpoint().to_string())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(
+            endpoints,
+            HashSet::from(["127.0.0.1:2".to_string(), "127.0.0.1:3".to_string()])
+        );
+
+        // the partial signature is forwarded on behalf of its signer
+        assert!(peers.iter().all(|peer| peer.get_id_address() == signer));
+
+        let peers: Vec<GeneralCommitterClient> =
+            prepare_gossip_clients(&group_cache, GROUP_INDEX, signer, &[excluded], 1);
+
+        assert_eq!(peers.len(), 1);
+        assert!(endpoints.contains(peers[0].get_committer_endpoint()));
+
+        let peers: Vec<GeneralCommitterClient> =
+            prepare_gossip_clients(&group_cache, GROUP_INDEX + 1, signer, &[excluded], 10);
+
+        assert!(peers.is_empty());
+    }
+
+    /// Takes a while to accept every partial signature, and counts them.
+    #[derive(Clone)]
+    struct SlowPeer {
+        endpoint: String,
+        received: Arc<AtomicUsize>,
+    }
+
+    impl CommitterClient for SlowPeer {
+        fn get_id_address(&self) -> Address {
+            Address::zero()
+        }
+
+        fn get_committer_endpoint(&self) -> &str {
+            &self.endpoint
+        }
+
+        fn build(_: Address, endpoint: String) -> Self {
+            SlowPeer {
+                endpoint,
+                received: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CommitterService for SlowPeer {
+        async fn commit_partial_signature(self, _: SignedPartialSignature) -> NodeResult<bool> {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+
+            self.received.fetch_add(1, Ordering::SeqCst);
+
+            Ok(true)
+        }
+
+        async fn commit_partial_signatures(
+            self,
+            _: Vec<SignedPartialSignature>,
+        ) -> NodeResult<Vec<NodeResult<bool>>> {
+            unimplemented!()
+        }
+
+        async fn announce_peer_record(self, _: PeerRecord) -> NodeResult<Option<PeerRecord>> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gossip_partial_signature_to_peers_at_once() {
+        let (_, members) = build_group(3, 2);
+
+        let partial_signature = sign_randomness_partial_signature(&members[0], 0, "0x01").await;
+
+        let received = Arc::new(AtomicUsize::new(0));
+
+        let peers = (0..4)
+            .map(|index| SlowPeer {
+                endpoint: index.to_string(),
+                received: received.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        // one at a time the peers would take 2s
+        tokio::time::timeout(
+            Duration::from_millis(1500),
+            gossip_partial_signature(peers, partial_signature),
+        )
+        .await
+        .expect("peers should be pushed to at once");
+
+        assert_eq!(received.load(Ordering::SeqCst), 4);
+    }
+}
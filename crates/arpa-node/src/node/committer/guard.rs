@@ -0,0 +1,210 @@
+use ethers::types::{Address, H256};
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+// requests a single source can burst before being throttled
+pub const DEFAULT_RATE_LIMIT_BURST: f64 = 200.0;
+
+// requests per second a single source is allowed to make in the long run
+pub const DEFAULT_RATE_LIMIT_PER_SEC: f64 = 50.0;
+
+// partial signatures of unknown tasks held for a single member
+pub const DEFAULT_PENDING_PARTIAL_SIGNATURES_PER_MEMBER: usize = 64;
+
+/// Token bucket rate limiter keyed by the source of the requests.
+pub(crate) struct RateLimiter<K: Eq + Hash> {
+    burst: f64,
+    per_sec: f64,
+    buckets: HashMap<K, (f64, Instant)>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    pub fn new(burst: f64, per_sec: f64) -> Self {
+        RateLimiter {
+            burst,
+            per_sec,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Takes a token from the bucket of the source, returns false if it is exhausted.
+    pub fn check(&mut self, source: K, now: Instant) -> bool {
+        // buckets full again are dropped so that the map doesn't grow with every source ever seen
+        let (burst, per_sec) = (self.burst, self.per_sec);
+        self.buckets.retain(|_, (tokens, last)| {
+            *tokens + now.saturating_duration_since(*last).as_secs_f64() * per_sec < burst
+        });
+
+        let (tokens, last) = self.buckets.entry(source).or_insert((burst, now));
+
+        *tokens =
+            (*tokens + now.saturating_duration_since(*last).as_secs_f64() * per_sec).min(burst);
+        *last = now;
+
+        if *tokens < 1.0 {
+            return false;
+        }
+
+        *tokens -= 1.0;
+
+        true
+    }
+}
+
+/// Remembers the partial signatures accepted within the validity window to reject their replays.
+/// Requests out of the window are refused by their timestamp, so they are forgotten then.
+pub(crate) struct ReplayGuard {
+    validity: Duration,
+    accepted: HashMap<(Address, H256), Instant>,
+}
+
+impl ReplayGuard {
+    pub fn new(validity: Duration) -> Self {
+        ReplayGuard {
+            validity,
+            accepted: HashMap::new(),
+        }
+    }
+
+    /// Accepts the partial signature of the sender, returns false if it has been accepted before.
+    /// The check and the insertion are done at once, so that concurrent requests carrying the same
+    /// partial signature can't be accepted both.
+    pub fn accept(&mut self, sender: Address, digest: H256, now: Instant) -> bool {
+        // the window is doubled as a request can be signed ahead of the clock of the committer
+        let validity = self.validity * 2;
+        self.accepted
+            .retain(|_, accepted_at| now.saturating_duration_since(*accepted_at) <= validity);
+
+        match self.accepted.entry((sender, digest)) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(now);
+                true
+            }
+        }
+    }
+
+    /// Forgets a partial signature whose request failed after it was accepted, so that the sender
+    /// can retry it.
+    pub fn forget(&mut self, sender: Address, digest: H256) {
+        self.accepted.remove(&(sender, digest));
+    }
+}
+
+/// Holds the partial signatures of tasks the committer has not seen yet, until the task shows up
+/// or they expire. Every member has a share of its own so that it can't crowd out the others.
+pub(crate) struct PendingPartialSignatures<T> {
+    validity: Duration,
+    capacity_per_member: usize,
+    pending: HashMap<Address, VecDeque<(T, Instant)>>,
+}
+
+impl<T> PendingPartialSignatures<T> {
+    pub fn new(validity: Duration, capacity_per_member: usize) -> Self {
+        PendingPartialSignatures {
+            validity,
+            capacity_per_member,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Keeps the partial signature received at `received_at`, returns false if the share of the
+    /// member is full.
+    pub fn push(&mut self, member: Address, partial_signature: T, received_at: Instant) -> bool {
+        let queue = self.pending.entry(member).or_default();
+
+        if queue.len() >= self.capacity_per_member {
+            return false;
+        }
+
+        queue.push_back((partial_signature, received_at));
+
+        true
+    }
+
+    /// Takes all the partial signatures out, the expired ones are dropped.
+    pub fn take(&mut self, now: Instant) -> Vec<(Address, T, Instant)> {
+        let validity = self.validity;
+
+        self.pending
+            .drain()
+            .flat_map(|(member, queue)| {
+                queue
+                    .into_iter()
+                    .map(move |(partial_signature, received_at)| {
+                        (member, partial_signature, received_at)
+                    })
+            })
+            .filter(|(_, _, received_at)| now.saturating_duration_since(*received_at) <= validity)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{PendingPartialSignatures, RateLimiter, ReplayGuard};
+    use ethers::types::{Address, H256};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_rate_limiter_throttles_each_source() {
+        let mut rate_limiter = RateLimiter::new(2.0, 1.0);
+
+        let now = Instant::now();
+
+        assert!(rate_limiter.check("a", now));
+        assert!(rate_limiter.check("a", now));
+        assert!(!rate_limiter.check("a", now));
+        assert!(rate_limiter.check("b", now));
+
+        assert!(rate_limiter.check("a", now + Duration::from_secs(1)));
+        assert!(!rate_limiter.check("a", now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_replay_guard() {
+        let mut replay_guard = ReplayGuard::new(Duration::from_secs(60));
+
+        let sender = Address::random();
+        let digest = H256::random();
+        let now = Instant::now();
+
+        assert!(replay_guard.accept(sender, digest, now));
+        assert!(!replay_guard.accept(sender, digest, now));
+        assert!(replay_guard.accept(Address::random(), digest, now));
+
+        // a failed request can be retried
+        replay_guard.forget(sender, digest);
+        assert!(replay_guard.accept(sender, digest, now));
+
+        assert!(replay_guard.accept(sender, H256::random(), now + Duration::from_secs(121)));
+        assert!(replay_guard.accept(sender, digest, now + Duration::from_secs(121)));
+    }
+
+    #[test]
+    fn test_pending_partial_signatures() {
+        let mut pending = PendingPartialSignatures::new(Duration::from_secs(60), 2);
+
+        let (member, other_member) = (Address::random(), Address::random());
+        let now = Instant::now();
+
+        assert!(pending.push(member, 1, now));
+        assert!(pending.push(member, 2, now + Duration::from_secs(30)));
+        assert!(!pending.push(member, 3, now + Duration::from_secs(30)));
+        assert!(pending.push(other_member, 4, now + Duration::from_secs(30)));
+
+        let mut taken = pending
+            .take(now + Duration::from_secs(61))
+            .into_iter()
+            .map(|(_, partial_signature, _)| partial_signature)
+            .collect::<Vec<_>>();
+        taken.sort_unstable();
+        assert_eq!(taken, vec![2, 4]);
+
+        assert!(pending.take(now + Duration::from_secs(61)).is_empty());
+        assert!(pending.push(member, 5, now + Duration::from_secs(61)));
+    }
+}
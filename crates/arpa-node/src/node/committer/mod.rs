@@ -1,13 +1,189 @@
 pub mod client;
+pub(crate) mod gossip;
+pub(crate) mod guard;
 pub mod server;
 
-use crate::node::error::NodeResult;
-use arpa_node_core::TaskType;
+use crate::node::{
+    error::{NodeError, NodeResult},
+    metrics::{inc_rpc_retries, task_type_label, PARTIAL_SIGNATURES_SENT},
+};
+use arpa_node_contract_client::adapter::AdapterViews;
+use arpa_node_core::{
+    now_timestamp, ChainIdentity, ContractGroup, GroupRelayConfirmation,
+    GroupRelayConfirmationTask, GroupRelayTask, PeerRecord, SignedPartialSignature, Status,
+    TaskType, PEER_RECORD_TTL_SECS,
+};
 use arpa_node_dal::GroupInfoFetcher;
 use async_trait::async_trait;
 use ethers::types::Address;
-use std::sync::Arc;
+use log::{error, warn};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
+use tonic::Code;
+
+pub const DEFAULT_COMMIT_RETRY_INTERVAL_MILLIS: u64 = 2000;
+
+pub const DEFAULT_COMMIT_RETRIES: usize = 3;
+
+/// Signs a fresh peer record announcing the endpoint the node serves its committer rpc on.
+pub(crate) async fn sign_peer_record<I: ChainIdentity>(
+    chain_identity: &I,
+    rpc_endpoint: String,
+) -> NodeResult<PeerRecord> {
+    PeerRecord::sign(
+        chain_identity.get_signer(),
+        rpc_endpoint,
+        now_timestamp() + PEER_RECORD_TTL_SECS,
+    )
+    .await
+    .map_err(|e| NodeError::PeerRecordSigningError(e.to_string()))
+}
+
+/// Signs the partial signature of a task with the chain key of the node, to be sent to the committers.
+pub(crate) async fn sign_partial_signature<I: ChainIdentity>(
+    chain_identity: &I,
+    chain_id: usize,
+    group_index: usize,
+    task_type: TaskType,
+    signature_index: usize,
+    message: Vec<u8>,
+    partial_signature: Vec<u8>,
+) -> NodeResult<SignedPartialSignature> {
+    SignedPartialSignature::sign(
+        chain_identity.get_signer(),
+        chain_id,
+        group_index,
+        task_type,
+        signature_index,
+        message,
+        partial_signature,
+    )
+    .await
+    .map_err(|e| NodeError::PartialSignatureSigningError(e.to_string()))
+}
+
+/// Reads the group relayed by the task from the main chain, which is the message the group signs.
+/// Returns None if the relayed group has moved on to another epoch since the task was emitted.
+pub(crate) async fn get_group_relay_message<A: AdapterViews + Sync>(
+    main_chain_adapter_client: &A,
+    task: &GroupRelayTask,
+) -> NodeResult<Option<ContractGroup>> {
+    let relayed_group: ContractGroup = main_chain_adapter_client
+        .get_group(task.relayed_group_index)
+        .await?
+        .into();
+
+    if relayed_group.epoch != task.relayed_group_epoch {
+        return Ok(None);
+    }
+
+    Ok(Some(relayed_group))
+}
+
+/// Compares the group in the controller with the one relayed to the adapter,
+/// which gives the confirmation the relayed group signs.
+pub(crate) async fn get_group_relay_confirmation_message<A: AdapterViews + Sync>(
+    main_chain_adapter_client: &A,
+    adapter_client: &A,
+    task: &GroupRelayConfirmationTask,
+) -> NodeResult<GroupRelayConfirmation> {
+    let relayed_group: ContractGroup = main_chain_adapter_client
+        .get_group(task.relayed_group_index)
+        .await?
+        .into();
+
+    let relayed_group_cache: ContractGroup = adapter_client
+        .get_group_relay_cache(task.group_relay_cache_index)
+        .await?
+        .into();
+
+    let status = Status::from(
+        bincode::serialize(&relayed_group)? == bincode::serialize(&relayed_group_cache)?,
+    );
+
+    Ok(GroupRelayConfirmation {
+        group: relayed_group_cache,
+        status,
+    })
+}
+
+/// Commits the partial signatures to the committer in one batch, and retries the ones it has not
+/// accepted, e.g. because it has not handled the task yet.
+pub(crate) async fn commit_partial_signatures_with_retry<
+    C: CommitterClient + CommitterService + Clone,
+>(
+    committer: C,
+    partial_signatures: Vec<SignedPartialSignature>,
+) {
+    let mut pending = partial_signatures;
+
+    for attempt in 0..=DEFAULT_COMMIT_RETRIES {
+        if attempt > 0 {
+            tokio::time::sleep(Duration::from_millis(DEFAULT_COMMIT_RETRY_INTERVAL_MILLIS)).await;
+
+            for chain_id in pending.iter().map(|p| p.chain_id).collect::<HashSet<_>>() {
+                inc_rpc_retries(chain_id, "commit_partial_signatures");
+            }
+        }
+
+        match committer
+            .clone()
+            .commit_partial_signatures(pending.clone())
+            .await
+        {
+            Ok(results) => {
+                let mut rejected = vec![];
+
+                for (partial_signature, result) in pending.into_iter().zip(results) {
+                    match result {
+                        Ok(_) => observe_partial_signature_sent(&partial_signature, "success"),
+                        // accepted already, in an earlier attempt whose reply is lost or through gossip
+                        Err(NodeError::RpcResponseError(status))
+                            if status.code() == Code::AlreadyExists =>
+                        {
+                            observe_partial_signature_sent(&partial_signature, "success")
+                        }
+                        Err(e) => {
+                            error!(
+                                "send partial signature {} to committer {} failed. Error: {:?}",
+                                partial_signature.signature_index,
+                                committer.get_committer_endpoint(),
+                                e
+                            );
+
+                            rejected.push(partial_signature);
+                        }
+                    }
+                }
+
+                pending = rejected;
+            }
+            Err(e) => error!(
+                "send partial signatures to committer {} failed. Error: {:?}",
+                committer.get_committer_endpoint(),
+                e
+            ),
+        }
+
+        if pending.is_empty() {
+            return;
+        }
+    }
+
+    for partial_signature in pending {
+        observe_partial_signature_sent(&partial_signature, "failure");
+    }
+}
+
+fn observe_partial_signature_sent(partial_signature: &SignedPartialSignature, result: &str) {
+    PARTIAL_SIGNATURES_SENT
+        .with_label_values(&[
+            &partial_signature.chain_id.to_string(),
+            task_type_label(&partial_signature.task_type),
+            result,
+        ])
+        .inc();
+}
 
 #[async_trait]
 pub trait ServiceClient<C> {
@@ -18,12 +194,18 @@ pub trait ServiceClient<C> {
 pub(crate) trait CommitterService {
     async fn commit_partial_signature(
         self,
-        chain_id: usize,
-        task_type: TaskType,
-        message: Vec<u8>,
-        signature_index: usize,
-        partial_signature: Vec<u8>,
+        partial_signature: SignedPartialSignature,
     ) -> NodeResult<bool>;
+
+    /// Commits the partial signatures in one request, or over a stream if there are more than a
+    /// batch can take, returns the result of each in the same order.
+    async fn commit_partial_signatures(
+        self,
+        partial_signatures: Vec<SignedPartialSignature>,
+    ) -> NodeResult<Vec<NodeResult<bool>>>;
+
+    /// Announces the peer record of the node, returns the one of the receiver if it has any.
+    async fn announce_peer_record(self, peer_record: PeerRecord) -> NodeResult<Option<PeerRecord>>;
 }
 
 pub(crate) trait CommitterClient {
@@ -44,8 +226,19 @@ pub(crate) trait CommitterClientHandler<
 
     fn get_group_cache(&self) -> Arc<RwLock<G>>;
 
-    async fn prepare_committer_clients(&self) -> NodeResult<Vec<C>> {
-        let mut committers = self.get_group_cache().read().await.get_committers()?;
+    /// Builds clients to the committers of the latest committed epoch of the group.
+    async fn prepare_committer_clients(&self, group_index: usize) -> NodeResult<Vec<C>> {
+        let group_epoch = self
+            .get_group_cache()
+            .read()
+            .await
+            .get_ready_epoch(group_index)?;
+
+        let mut committers = self
+            .get_group_cache()
+            .read()
+            .await
+            .get_committers(group_index, group_epoch)?;
 
         let id_address = self.get_id_address().await;
 
@@ -54,21 +247,34 @@ pub(crate) trait CommitterClientHandler<
         let mut committer_clients = vec![];
 
         for committer in committers {
-            let endpoint = self
-                .get_group_cache()
-                .read()
-                .await
-                .get_member(committer)?
-                .rpc_endpint
-                .as_ref()
-                .unwrap()
-                .to_string();
-
-            let committer_client = C::build(id_address, endpoint.clone());
-
-            committer_clients.push(committer_client);
+            if let Some(client) = self.prepare_member_client(group_index, committer).await {
+                committer_clients.push(client);
+            }
         }
 
         Ok(committer_clients)
     }
+
+    /// Builds a client to the member of the group, None if it has no valid peer record.
+    async fn prepare_member_client(&self, group_index: usize, member: Address) -> Option<C> {
+        // endpoints are resolved from the peer records announced by the members
+        let endpoint = self
+            .get_group_cache()
+            .read()
+            .await
+            .get_peer_endpoint(member, now_timestamp())
+            .map(|endpoint| endpoint.to_string());
+
+        match endpoint {
+            Some(endpoint) => Some(C::build(self.get_id_address().await, endpoint)),
+            None => {
+                warn!(
+                    "no valid peer record of committer {:?} of group {}, skipped",
+                    member, group_index
+                );
+
+                None
+            }
+        }
+    }
 }
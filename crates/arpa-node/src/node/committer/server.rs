@@ -1,6 +1,18 @@
 use self::committer_stub::{
     committer_service_server::{CommitterService, CommitterServiceServer},
-    CommitPartialSignatureReply, CommitPartialSignatureRequest,
+    AnnouncePeerRecordReply, AnnouncePeerRecordRequest, CommitPartialSignatureReply,
+    CommitPartialSignatureRequest, CommitPartialSignatureResult, CommitPartialSignaturesReply,
+    CommitPartialSignaturesRequest,
+};
+use super::{
+    client::GeneralCommitterClient,
+    get_group_relay_confirmation_message, get_group_relay_message,
+    gossip::{gossip_partial_signature, prepare_gossip_clients},
+    guard::{
+        PendingPartialSignatures, RateLimiter, ReplayGuard,
+        DEFAULT_PENDING_PARTIAL_SIGNATURES_PER_MEMBER, DEFAULT_RATE_LIMIT_BURST,
+        DEFAULT_RATE_LIMIT_PER_SEC,
+    },
 };
 use crate::node::context::chain::MainChainFetcher;
 use crate::node::{
@@ -11,21 +23,40 @@ use crate::node::{
         ContextFetcher,
     },
     error::NodeError,
+    metrics::{task_type_label, PARTIAL_SIGNATURES_RECEIVED, PARTIAL_SIGNATURES_REJECTED},
 };
 use arpa_node_contract_client::{
     adapter::AdapterClientBuilder, controller::ControllerClientBuilder,
     coordinator::CoordinatorClientBuilder, provider::ChainProviderBuilder,
 };
-use arpa_node_core::{ChainIdentity, RandomnessTask, TaskError, TaskType};
+use arpa_node_core::{
+    now_timestamp, ChainIdentity, PeerRecord, RandomnessTask, SignedPartialSignature, TaskError,
+    TaskType, PARTIAL_SIGNATURE_VALIDITY_SECS,
+};
 use arpa_node_dal::{
-    BLSTasksFetcher, BLSTasksUpdater, GroupInfoFetcher, GroupInfoUpdater, NodeInfoFetcher,
-    SignatureResultCacheFetcher, SignatureResultCacheUpdater,
+    BLSTasksFetcher, BLSTasksUpdater, GroupInfoFetcher, GroupInfoUpdater, LogCheckpointFetcher,
+    LogCheckpointUpdater, NodeInfoFetcher, SignatureResultCacheFetcher,
+    SignatureResultCacheUpdater,
 };
 use ethers::types::Address;
-use futures::Future;
-use std::sync::Arc;
+use futures::{channel::mpsc, Future, SinkExt, Stream, StreamExt};
+use log::debug;
+use std::{
+    net::IpAddr,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::RwLock;
-use tonic::{transport::Server, Request, Response, Status};
+use tonic::{transport::Server, Code, Request, Response, Status, Streaming};
+
+// partial signatures accepted in one batched request
+pub const MAX_PARTIAL_SIGNATURES_PER_BATCH: usize = 256;
+
+// results buffered in a stream before it waits for the client to read them
+pub const PARTIAL_SIGNATURE_STREAM_BUFFER: usize = 64;
+
+pub const DEFAULT_PENDING_PARTIAL_SIGNATURES_RETRY_INTERVAL_MILLIS: u64 = 1000;
 
 pub mod committer_stub {
     include!("../../../rpc_stub/committer.rs");
@@ -34,7 +65,10 @@ pub mod committer_stub {
 pub(crate) struct BLSCommitterServiceServer<
     N: NodeInfoFetcher,
     G: GroupInfoFetcher + GroupInfoUpdater,
-    T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater,
     I: ChainIdentity
         + ControllerClientBuilder
         + CoordinatorClientBuilder
@@ -44,12 +78,53 @@ pub(crate) struct BLSCommitterServiceServer<
     id_address: Address,
     group_cache: Arc<RwLock<G>>,
     context: Arc<RwLock<GeneralContext<N, G, T, I>>>,
+    // throttles the requests of every remote ip before any work is done on them
+    source_rate_limiter: Arc<RwLock<RateLimiter<IpAddr>>>,
+    // throttles the partial signatures of every authenticated member per remote ip, so that the
+    // copies gossiped by other members don't count against the budget of their signer
+    member_rate_limiter: Arc<RwLock<RateLimiter<(Address, Option<IpAddr>)>>>,
+    replay_guard: Arc<RwLock<ReplayGuard>>,
+    // partial signatures of tasks the committer has not seen on chain yet, with the group epoch
+    // they are verified against
+    pending_partial_signatures:
+        Arc<RwLock<PendingPartialSignatures<(usize, SignedPartialSignature)>>>,
+}
+
+// the server is cloned into the tasks serving streams
+impl<
+        N: NodeInfoFetcher,
+        G: GroupInfoFetcher + GroupInfoUpdater,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater,
+        I: ChainIdentity
+            + ControllerClientBuilder
+            + CoordinatorClientBuilder
+            + AdapterClientBuilder
+            + ChainProviderBuilder,
+    > Clone for BLSCommitterServiceServer<N, G, T, I>
+{
+    fn clone(&self) -> Self {
+        BLSCommitterServiceServer {
+            id_address: self.id_address,
+            group_cache: self.group_cache.clone(),
+            context: self.context.clone(),
+            source_rate_limiter: self.source_rate_limiter.clone(),
+            member_rate_limiter: self.member_rate_limiter.clone(),
+            replay_guard: self.replay_guard.clone(),
+            pending_partial_signatures: self.pending_partial_signatures.clone(),
+        }
+    }
 }
 
 impl<
         N: NodeInfoFetcher,
         G: GroupInfoFetcher + GroupInfoUpdater,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -66,15 +141,35 @@ impl<
             id_address,
             group_cache,
             context,
+            source_rate_limiter: Arc::new(RwLock::new(RateLimiter::new(
+                DEFAULT_RATE_LIMIT_BURST,
+                DEFAULT_RATE_LIMIT_PER_SEC,
+            ))),
+            member_rate_limiter: Arc::new(RwLock::new(RateLimiter::new(
+                DEFAULT_RATE_LIMIT_BURST,
+                DEFAULT_RATE_LIMIT_PER_SEC,
+            ))),
+            replay_guard: Arc::new(RwLock::new(ReplayGuard::new(Duration::from_secs(
+                PARTIAL_SIGNATURE_VALIDITY_SECS,
+            )))),
+            pending_partial_signatures: Arc::new(RwLock::new(PendingPartialSignatures::new(
+                Duration::from_secs(PARTIAL_SIGNATURE_VALIDITY_SECS),
+                DEFAULT_PENDING_PARTIAL_SIGNATURES_PER_MEMBER,
+            ))),
         }
     }
 }
 
-#[tonic::async_trait]
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -83,19 +178,110 @@ impl<
             + Sync
             + Send
             + 'static,
-    > CommitterService for BLSCommitterServiceServer<N, G, T, I>
+    > BLSCommitterServiceServer<N, G, T, I>
 {
-    async fn commit_partial_signature(
+    async fn check_source_rate_limit(&self, remote_ip: Option<IpAddr>) -> Result<(), Status> {
+        if let Some(remote_ip) = remote_ip {
+            if !self
+                .source_rate_limiter
+                .write()
+                .await
+                .check(remote_ip, Instant::now())
+            {
+                return Err(Status::resource_exhausted(
+                    NodeError::RateLimited(remote_ip.to_string()).to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the request is signed by the member it claims to come from, recently,
+    /// and has not been accepted before. The partial signature is accepted from then on, and is
+    /// to be forgotten by the replay guard if the request fails later.
+    async fn authenticate_partial_signature(
         &self,
-        request: Request<CommitPartialSignatureRequest>,
-    ) -> Result<Response<CommitPartialSignatureReply>, Status> {
-        let req = request.into_inner();
+        remote_ip: Option<IpAddr>,
+        req_id_address: Address,
+        req: &CommitPartialSignatureRequest,
+    ) -> Result<SignedPartialSignature, Status> {
+        let signed_partial_signature = SignedPartialSignature {
+            chain_id: req.chain_id as usize,
+            group_index: req.group_index as usize,
+            task_type: TaskType::from(req.task_type),
+            signature_index: req.signature_index as usize,
+            message: req.message.clone(),
+            partial_signature: req.partial_signature.clone(),
+            signed_at: req.signed_at,
+            signature: req.signature.clone(),
+        };
+
+        if !signed_partial_signature.is_fresh(now_timestamp(), PARTIAL_SIGNATURE_VALIDITY_SECS)
+            || signed_partial_signature.recover() != Some(req_id_address)
+        {
+            return Err(Status::unauthenticated(
+                NodeError::UnauthenticatedPartialSignature.to_string(),
+            ));
+        }
+
+        // replays are checked first as a gossiped partial signature is received more than once
+        if !self.replay_guard.write().await.accept(
+            req_id_address,
+            signed_partial_signature.digest(),
+            Instant::now(),
+        ) {
+            return Err(Status::already_exists(
+                NodeError::ReplayedPartialSignature.to_string(),
+            ));
+        }
 
-        if let Err(_) | Ok(false) = self.group_cache.read().await.get_state() {
-            return Err(Status::not_found(NodeError::GroupNotReady.to_string()));
+        if !self
+            .member_rate_limiter
+            .write()
+            .await
+            .check((req_id_address, remote_ip), Instant::now())
+        {
+            self.replay_guard
+                .write()
+                .await
+                .forget(req_id_address, signed_partial_signature.digest());
+
+            return Err(Status::resource_exhausted(
+                NodeError::RateLimited(format!("{:?}", req_id_address)).to_string(),
+            ));
         }
 
-        if let Err(_) | Ok(false) = self.group_cache.read().await.is_committer(self.id_address) {
+        Ok(signed_partial_signature)
+    }
+
+    async fn handle_partial_signature(
+        &self,
+        remote_ip: Option<IpAddr>,
+        req: CommitPartialSignatureRequest,
+    ) -> Result<Response<CommitPartialSignatureReply>, Status> {
+        self.check_source_rate_limit(remote_ip).await?;
+
+        let group_index = req.group_index as usize;
+
+        // partial signatures are always checked against the latest committed epoch of the group
+        let group_epoch = self
+            .group_cache
+            .read()
+            .await
+            .get_ready_epoch(group_index)
+            .map_err(|_| Status::not_found(NodeError::GroupNotReady.to_string()))?;
+
+        // committers of randomness tasks rotate with every task, and are checked against the task
+        // the committer has seen on chain once the partial signature is authenticated
+        if TaskType::from(req.task_type) != TaskType::Randomness
+            && !self
+                .group_cache
+                .read()
+                .await
+                .is_committer(group_index, group_epoch, self.id_address)
+                .unwrap_or(false)
+        {
             return Err(Status::not_found(NodeError::NotCommitter.to_string()));
         }
 
@@ -104,196 +290,792 @@ impl<
             .parse()
             .map_err(|_| Status::invalid_argument(NodeError::AddressFormatError.to_string()))?;
 
-        if let Ok(member) = self.group_cache.read().await.get_member(req_id_address) {
-            let partial_public_key = member.partial_public_key.unwrap();
+        // the sender is authenticated before the pairing check, which is the costly part
+        let signed_partial_signature = self
+            .authenticate_partial_signature(remote_ip, req_id_address, &req)
+            .await?;
+
+        let saved = match self
+            .save_or_hold_partial_signature(req_id_address, group_epoch, &signed_partial_signature)
+            .await
+        {
+            Ok(saved) => saved,
+            Err(e) => {
+                self.replay_guard
+                    .write()
+                    .await
+                    .forget(req_id_address, signed_partial_signature.digest());
 
-            let bls_core = SimpleBLSCore {};
+                return Err(e);
+            }
+        };
 
-            bls_core
-                .partial_verify(&partial_public_key, &req.message, &req.partial_signature)
-                .map_err(|e| Status::internal(e.to_string()))?;
+        if saved {
+            self.gossip(req_id_address, signed_partial_signature).await;
+        }
 
-            let chain_id = req.chain_id as usize;
+        Ok(Response::new(CommitPartialSignatureReply { result: saved }))
+    }
 
-            match TaskType::from(req.task_type) {
-                TaskType::Randomness => {
-                    let randomness_result_cache = match chain_id {
-                        0 => self
-                            .context
-                            .read()
-                            .await
-                            .get_main_chain()
+    /// Saves the authenticated partial signature of the member, or holds it for its task to show
+    /// up. Returns whether it is saved.
+    async fn save_or_hold_partial_signature(
+        &self,
+        member: Address,
+        group_epoch: usize,
+        signed_partial_signature: &SignedPartialSignature,
+    ) -> Result<bool, Status> {
+        let saved = self
+            .save_partial_signature(member, group_epoch, signed_partial_signature)
+            .await?;
+
+        // the partial signature is held for the task to show up, and is not accepted again
+        if !saved
+            && !self.pending_partial_signatures.write().await.push(
+                member,
+                (group_epoch, signed_partial_signature.clone()),
+                Instant::now(),
+            )
+        {
+            return Err(Status::resource_exhausted(
+                NodeError::RateLimited(format!("{:?}", member)).to_string(),
+            ));
+        }
+
+        Ok(saved)
+    }
+
+    /// Pushes a partial signature accepted for the first time on to other members.
+    async fn gossip(&self, member: Address, signed_partial_signature: SignedPartialSignature) {
+        if signed_partial_signature.task_type != TaskType::Randomness {
+            return;
+        }
+
+        if let Some(gossip_config) = self.context.read().await.get_partial_signature_gossip() {
+            let peers: Vec<GeneralCommitterClient> = prepare_gossip_clients(
+                &*self.group_cache.read().await,
+                signed_partial_signature.group_index,
+                member,
+                &[self.id_address],
+                gossip_config.fanout,
+            );
+
+            tokio::spawn(gossip_partial_signature(peers, signed_partial_signature));
+        }
+    }
+
+    /// Verifies the partial signature against the message of its task and adds it to the result
+    /// cache of the task. Returns false if the committer has not seen the task yet.
+    async fn save_partial_signature(
+        &self,
+        member: Address,
+        group_epoch: usize,
+        signed_partial_signature: &SignedPartialSignature,
+    ) -> Result<bool, Status> {
+        // with gossip every member collects the randomness partial signatures of its groups
+        if signed_partial_signature.task_type == TaskType::Randomness
+            && self
+                .context
+                .read()
+                .await
+                .get_partial_signature_gossip()
+                .is_none()
+        {
+            match self
+                .is_randomness_task_committer(group_epoch, signed_partial_signature)
+                .await?
+            {
+                Some(true) => {}
+                Some(false) => return Err(Status::not_found(NodeError::NotCommitter.to_string())),
+                None => return Ok(false),
+            }
+        }
+
+        let message = match self
+            .resolve_task_message(group_epoch, signed_partial_signature)
+            .await?
+        {
+            Some(message) => message,
+            None => return Ok(false),
+        };
+
+        if signed_partial_signature.message != message {
+            return Err(Status::invalid_argument(
+                NodeError::InvalidTaskMessage.to_string(),
+            ));
+        }
+
+        let SignedPartialSignature {
+            chain_id,
+            group_index,
+            task_type,
+            signature_index,
+            partial_signature,
+            ..
+        } = signed_partial_signature.clone();
+
+        let partial_public_key = self
+            .group_cache
+            .read()
+            .await
+            .get_member(group_index, group_epoch, member)
+            .map_err(|_| Status::not_found(NodeError::MemberNotExisted.to_string()))?
+            .partial_public_key
+            .unwrap();
+
+        let bls_core = SimpleBLSCore {};
+
+        // the pairing check is done against the message of the task, never the one of the sender
+        bls_core
+            .partial_verify(&partial_public_key, &message, &partial_signature)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        match task_type {
+            TaskType::Randomness => {
+                let randomness_result_cache = {
+                    let context = self.context.read().await;
+
+                    match chain_id {
+                        0 => context.get_main_chain().get_randomness_result_cache(),
+                        _ => context
+                            .get_adapter_chain(chain_id)
+                            .unwrap()
                             .get_randomness_result_cache(),
+                    }
+                };
+
+                randomness_result_cache.write().await.add_partial_signature(
+                    signature_index,
+                    member,
+                    partial_signature,
+                )
+            }
+
+            TaskType::GroupRelay => {
+                let group_relay_result_cache = self
+                    .context
+                    .read()
+                    .await
+                    .get_main_chain()
+                    .get_group_relay_result_cache();
+
+                group_relay_result_cache
+                    .write()
+                    .await
+                    .add_partial_signature(signature_index, member, partial_signature)
+            }
+
+            TaskType::GroupRelayConfirmation => {
+                let group_relay_confirmation_result_cache = self
+                    .context
+                    .read()
+                    .await
+                    .get_adapter_chain(chain_id)
+                    .unwrap()
+                    .get_group_relay_confirmation_result_cache();
+
+                group_relay_confirmation_result_cache
+                    .write()
+                    .await
+                    .add_partial_signature(signature_index, member, partial_signature)
+            }
+        }
+        .map_err(|_| Status::internal(TaskError::CommitterCacheNotExisted.to_string()))?;
+
+        Ok(true)
+    }
+
+    /// Whether the node is a committer or a backup committer of the randomness task of the partial
+    /// signature, rotated by the message of the task the committer has seen on chain. Returns None
+    /// if the committer has not seen the task yet.
+    async fn is_randomness_task_committer(
+        &self,
+        group_epoch: usize,
+        signed_partial_signature: &SignedPartialSignature,
+    ) -> Result<Option<bool>, Status> {
+        let SignedPartialSignature {
+            chain_id,
+            group_index,
+            signature_index,
+            ..
+        } = signed_partial_signature.clone();
+
+        let randomness_tasks_cache = {
+            let context = self.context.read().await;
+
+            match chain_id {
+                0 => context.get_main_chain().get_randomness_tasks_cache(),
+                _ if context.contains_chain(chain_id) => context
+                    .get_adapter_chain(chain_id)
+                    .unwrap()
+                    .get_randomness_tasks_cache(),
+                _ => {
+                    return Err(Status::invalid_argument(
+                        NodeError::InvalidChainId(chain_id).to_string(),
+                    ))
+                }
+            }
+        };
+
+        let task = {
+            let randomness_tasks_cache = randomness_tasks_cache.read().await;
+
+            if !randomness_tasks_cache
+                .contains(signature_index)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+            {
+                return Ok(None);
+            }
+
+            randomness_tasks_cache
+                .get(signature_index)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+        };
+
+        if task.group_index != group_index {
+            return Err(Status::invalid_argument(
+                NodeError::InvalidTaskMessage.to_string(),
+            ));
+        }
+
+        let is_committer = self
+            .group_cache
+            .read()
+            .await
+            .get_task_committers(
+                group_index,
+                group_epoch,
+                signature_index,
+                task.message.as_bytes(),
+            )
+            .map(|(committers, backup_committers)| {
+                committers.contains(&self.id_address)
+                    || backup_committers.contains(&self.id_address)
+            })
+            .unwrap_or(false);
+
+        Ok(Some(is_committer))
+    }
+
+    /// Resolves the message of the task of the partial signature from the task the committer has
+    /// seen on chain, building the result cache of the task from it on the way. Returns None if the
+    /// committer has not seen the task yet.
+    async fn resolve_task_message(
+        &self,
+        group_epoch: usize,
+        signed_partial_signature: &SignedPartialSignature,
+    ) -> Result<Option<Vec<u8>>, Status> {
+        let SignedPartialSignature {
+            chain_id,
+            group_index,
+            task_type,
+            signature_index,
+            ..
+        } = signed_partial_signature.clone();
+
+        let threshold = self
+            .group_cache
+            .read()
+            .await
+            .get_threshold(group_index, group_epoch)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        if chain_id != 0 && !self.context.read().await.contains_chain(chain_id) {
+            return Err(Status::invalid_argument(
+                NodeError::InvalidChainId(chain_id).to_string(),
+            ));
+        }
+
+        let invalid_task_message =
+            || Status::invalid_argument(NodeError::InvalidTaskMessage.to_string());
+
+        match task_type {
+            TaskType::Randomness => {
+                let (randomness_tasks_cache, randomness_result_cache) = {
+                    let context = self.context.read().await;
+
+                    match chain_id {
+                        0 => (
+                            context.get_main_chain().get_randomness_tasks_cache(),
+                            context.get_main_chain().get_randomness_result_cache(),
+                        ),
                         _ => {
-                            if !self.context.read().await.contains_chain(chain_id) {
-                                return Err(Status::invalid_argument(
-                                    NodeError::InvalidChainId(chain_id).to_string(),
-                                ));
-                            }
-                            self.context
-                                .read()
-                                .await
-                                .get_adapter_chain(req.chain_id as usize)
-                                .unwrap()
-                                .get_randomness_result_cache()
+                            let adapter_chain = context.get_adapter_chain(chain_id).unwrap();
+                            (
+                                adapter_chain.get_randomness_tasks_cache(),
+                                adapter_chain.get_randomness_result_cache(),
+                            )
+                        }
+                    }
+                };
+
+                if !randomness_result_cache
+                    .read()
+                    .await
+                    .contains(signature_index)
+                {
+                    let task = {
+                        let randomness_tasks_cache = randomness_tasks_cache.read().await;
+
+                        if !randomness_tasks_cache
+                            .contains(signature_index)
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                        {
+                            return Ok(None);
                         }
+
+                        randomness_tasks_cache
+                            .get(signature_index)
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
                     };
 
-                    if !randomness_result_cache
-                        .read()
-                        .await
-                        .contains(req.signature_index as usize)
-                    {
-                        return Err(Status::invalid_argument(
-                            TaskError::CommitterCacheNotExisted.to_string(),
-                        ));
-                        // because we can't assure reliability of requested partial signature to original message,
-                        // we refuse to accept other node's request if the committer has not build this committer cache first.
-                    }
+                    let mut randomness_result_cache = randomness_result_cache.write().await;
 
-                    let committer_cache_message = randomness_result_cache
-                        .read()
-                        .await
-                        .get(req.signature_index as usize)
-                        .unwrap()
-                        .result_cache
-                        .message
-                        .to_string();
-
-                    let req_message = String::from_utf8(req.message)
-                        .map_err(|e| Status::internal(e.to_string()))?;
-
-                    if req_message != committer_cache_message {
-                        return Err(Status::invalid_argument(
-                            NodeError::InvalidTaskMessage.to_string(),
-                        ));
+                    if !randomness_result_cache.contains(signature_index) {
+                        randomness_result_cache
+                            .add(group_index, signature_index, task.message, threshold)
+                            .map_err(|e| Status::internal(e.to_string()))?;
                     }
+                }
 
-                    randomness_result_cache
-                        .write()
-                        .await
-                        .add_partial_signature(
-                            req.signature_index as usize,
-                            req_id_address,
-                            req.partial_signature,
-                        )
-                        .map_err(|_| {
-                            Status::internal(TaskError::CommitterCacheNotExisted.to_string())
-                        })?;
+                let randomness_result_cache = randomness_result_cache.read().await;
+
+                let committer_cache = &randomness_result_cache
+                    .get(signature_index)
+                    .unwrap()
+                    .result_cache;
+
+                if committer_cache.group_index != group_index {
+                    return Err(invalid_task_message());
                 }
 
-                TaskType::GroupRelay => {
-                    let group_relay_result_cache = self
-                        .context
-                        .read()
-                        .await
-                        .get_main_chain()
-                        .get_group_relay_result_cache();
+                Ok(Some(committer_cache.message.as_bytes().to_vec()))
+            }
 
-                    if !group_relay_result_cache
-                        .read()
-                        .await
-                        .contains(req.signature_index as usize)
-                    {
-                        return Err(Status::invalid_argument(
-                            TaskError::CommitterCacheNotExisted.to_string(),
-                        ));
-                        // because we can't assure reliability of requested partial signature to original message,
-                        // we refuse to accept other node's request if the committer has not build this committer cache first.
+            TaskType::GroupRelay => {
+                if chain_id != 0 {
+                    return Err(Status::invalid_argument(
+                        NodeError::InvalidChainId(chain_id).to_string(),
+                    ));
+                }
+
+                let (group_relay_tasks_cache, group_relay_result_cache, chain_identity) = {
+                    let context = self.context.read().await;
+
+                    (
+                        context.get_main_chain().get_group_relay_tasks_cache(),
+                        context.get_main_chain().get_group_relay_result_cache(),
+                        context.get_main_chain().get_chain_identity(),
+                    )
+                };
+
+                if !group_relay_result_cache
+                    .read()
+                    .await
+                    .contains(signature_index)
+                {
+                    let task = {
+                        let group_relay_tasks_cache = group_relay_tasks_cache.read().await;
+
+                        if !group_relay_tasks_cache
+                            .contains(signature_index)
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                        {
+                            return Ok(None);
+                        }
+
+                        group_relay_tasks_cache
+                            .get(signature_index)
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                    };
+
+                    // the relayed group doesn't sign its own relay
+                    if task.relayed_group_index == group_index {
+                        return Err(invalid_task_message());
                     }
 
-                    let relayed_group = group_relay_result_cache
+                    let main_chain_adapter_client = chain_identity
                         .read()
                         .await
-                        .get(req.signature_index as usize)
-                        .unwrap()
-                        .result_cache
-                        .relayed_group
-                        .clone();
-
-                    let relayed_group_as_bytes = bincode::serialize(&relayed_group).unwrap();
-
-                    if req.message != relayed_group_as_bytes {
-                        return Err(Status::invalid_argument(
-                            NodeError::InvalidTaskMessage.to_string(),
-                        ));
-                    }
+                        .build_adapter_client(self.id_address);
 
-                    group_relay_result_cache
-                        .write()
+                    let relayed_group = get_group_relay_message(&main_chain_adapter_client, &task)
                         .await
-                        .add_partial_signature(
-                            req.signature_index as usize,
-                            req_id_address,
-                            req.partial_signature,
-                        )
-                        .map_err(|_| {
-                            Status::internal(TaskError::CommitterCacheNotExisted.to_string())
-                        })?;
+                        .map_err(|e| Status::internal(e.to_string()))?
+                        .ok_or_else(invalid_task_message)?;
+
+                    let mut group_relay_result_cache = group_relay_result_cache.write().await;
+
+                    if !group_relay_result_cache.contains(signature_index) {
+                        group_relay_result_cache
+                            .add(group_index, signature_index, relayed_group, threshold)
+                            .map_err(|e| Status::internal(e.to_string()))?;
+                    }
+                }
+
+                let group_relay_result_cache = group_relay_result_cache.read().await;
+
+                let committer_cache = &group_relay_result_cache
+                    .get(signature_index)
+                    .unwrap()
+                    .result_cache;
+
+                if committer_cache.group_index != group_index {
+                    return Err(invalid_task_message());
+                }
+
+                bincode::serialize(&committer_cache.relayed_group)
+                    .map(Some)
+                    .map_err(|e| Status::internal(e.to_string()))
+            }
+
+            TaskType::GroupRelayConfirmation => {
+                if chain_id == 0 {
+                    return Err(Status::invalid_argument(
+                        NodeError::InvalidChainId(chain_id).to_string(),
+                    ));
                 }
-                TaskType::GroupRelayConfirmation => {
-                    if chain_id == 0 || !self.context.read().await.contains_chain(chain_id) {
-                        return Err(Status::invalid_argument(
-                            NodeError::InvalidChainId(chain_id).to_string(),
-                        ));
+
+                let (
+                    group_relay_confirmation_tasks_cache,
+                    group_relay_confirmation_result_cache,
+                    main_chain_identity,
+                    chain_identity,
+                ) = {
+                    let context = self.context.read().await;
+
+                    let adapter_chain = context.get_adapter_chain(chain_id).unwrap();
+
+                    (
+                        adapter_chain.get_group_relay_confirmation_tasks_cache(),
+                        adapter_chain.get_group_relay_confirmation_result_cache(),
+                        context.get_main_chain().get_chain_identity(),
+                        adapter_chain.get_chain_identity(),
+                    )
+                };
+
+                if !group_relay_confirmation_result_cache
+                    .read()
+                    .await
+                    .contains(signature_index)
+                {
+                    let task = {
+                        let group_relay_confirmation_tasks_cache =
+                            group_relay_confirmation_tasks_cache.read().await;
+
+                        if !group_relay_confirmation_tasks_cache
+                            .contains(signature_index)
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                        {
+                            return Ok(None);
+                        }
+
+                        group_relay_confirmation_tasks_cache
+                            .get(signature_index)
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                    };
+
+                    // only the relayed group confirms its relay
+                    if task.relayed_group_index != group_index {
+                        return Err(invalid_task_message());
                     }
 
-                    let group_relay_confirmation_result_cache = self
-                        .context
+                    let main_chain_adapter_client = main_chain_identity
                         .read()
                         .await
-                        .get_adapter_chain(req.chain_id as usize)
-                        .unwrap()
-                        .get_group_relay_confirmation_result_cache();
+                        .build_adapter_client(self.id_address);
 
-                    if !group_relay_confirmation_result_cache
+                    let adapter_client = chain_identity
                         .read()
                         .await
-                        .contains(req.signature_index as usize)
-                    {
-                        return Err(Status::invalid_argument(
-                            TaskError::CommitterCacheNotExisted.to_string(),
-                        ));
-                        // because we can't assure reliability of requested partial signature to original message,
-                        // we refuse to accept other node's request if the committer has not build this committer cache first.
-                    }
+                        .build_adapter_client(self.id_address);
 
-                    let group_relay_confirmation = group_relay_confirmation_result_cache
-                        .read()
-                        .await
-                        .get(req.signature_index as usize)
-                        .unwrap()
-                        .result_cache
-                        .group_relay_confirmation
-                        .clone();
-
-                    let group_relay_confirmation_as_bytes =
-                        bincode::serialize(&group_relay_confirmation).unwrap();
-
-                    if req.message != group_relay_confirmation_as_bytes {
-                        return Err(Status::invalid_argument(
-                            NodeError::InvalidTaskMessage.to_string(),
-                        ));
+                    let group_relay_confirmation = get_group_relay_confirmation_message(
+                        &main_chain_adapter_client,
+                        &adapter_client,
+                        &task,
+                    )
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?;
+
+                    let mut group_relay_confirmation_result_cache =
+                        group_relay_confirmation_result_cache.write().await;
+
+                    if !group_relay_confirmation_result_cache.contains(signature_index) {
+                        group_relay_confirmation_result_cache
+                            .add(
+                                group_index,
+                                signature_index,
+                                group_relay_confirmation,
+                                threshold,
+                            )
+                            .map_err(|e| Status::internal(e.to_string()))?;
                     }
+                }
 
-                    group_relay_confirmation_result_cache
-                        .write()
-                        .await
-                        .add_partial_signature(
-                            req.signature_index as usize,
-                            req_id_address,
-                            req.partial_signature,
-                        )
-                        .map_err(|_| {
-                            Status::internal(TaskError::CommitterCacheNotExisted.to_string())
-                        })?;
+                let group_relay_confirmation_result_cache =
+                    group_relay_confirmation_result_cache.read().await;
+
+                let committer_cache = &group_relay_confirmation_result_cache
+                    .get(signature_index)
+                    .unwrap()
+                    .result_cache;
+
+                if committer_cache.group_index != group_index {
+                    return Err(invalid_task_message());
                 }
+
+                bincode::serialize(&committer_cache.group_relay_confirmation)
+                    .map(Some)
+                    .map_err(|e| Status::internal(e.to_string()))
             }
+        }
+    }
+
+    async fn start_retrying_pending(self) {
+        loop {
+            tokio::time::sleep(Duration::from_millis(
+                DEFAULT_PENDING_PARTIAL_SIGNATURES_RETRY_INTERVAL_MILLIS,
+            ))
+            .await;
+
+            self.retry_pending_partial_signatures().await;
+        }
+    }
+
+    /// Saves the partial signatures held for their tasks once the tasks show up.
+    async fn retry_pending_partial_signatures(&self) {
+        let pending = self
+            .pending_partial_signatures
+            .write()
+            .await
+            .take(Instant::now());
+
+        for (member, (group_epoch, signed_partial_signature), received_at) in pending {
+            match self
+                .save_partial_signature(member, group_epoch, &signed_partial_signature)
+                .await
+            {
+                Ok(true) => self.gossip(member, signed_partial_signature).await,
+                Ok(false) => {
+                    self.pending_partial_signatures.write().await.push(
+                        member,
+                        (group_epoch, signed_partial_signature),
+                        received_at,
+                    );
+                }
+                Err(status) => debug!(
+                    "pending partial signature {} from {:?} is rejected: {:?}",
+                    signed_partial_signature.signature_index, member, status
+                ),
+            }
+        }
+    }
+
+    /// Handles the partial signature and counts the result.
+    async fn commit_partial_signature_with_metrics(
+        &self,
+        remote_ip: Option<IpAddr>,
+        req: CommitPartialSignatureRequest,
+    ) -> Result<Response<CommitPartialSignatureReply>, Status> {
+        let chain_id = req.chain_id.to_string();
+
+        let task_type = task_type_label(&TaskType::from(req.task_type));
+
+        let res = self.handle_partial_signature(remote_ip, req).await;
+
+        match &res {
+            Ok(_) => PARTIAL_SIGNATURES_RECEIVED
+                .with_label_values(&[&chain_id, task_type])
+                .inc(),
+            Err(status) => PARTIAL_SIGNATURES_REJECTED
+                .with_label_values(&[&chain_id, task_type, &format!("{:?}", status.code())])
+                .inc(),
+        }
+
+        res
+    }
+
+    /// Handles the partial signature and acknowledges it with a result identifying the task.
+    async fn acknowledge_partial_signature(
+        &self,
+        remote_ip: Option<IpAddr>,
+        req: CommitPartialSignatureRequest,
+    ) -> CommitPartialSignatureResult {
+        let (chain_id, task_type, signature_index) =
+            (req.chain_id, req.task_type, req.signature_index);
+
+        let (result, code, message) = match self
+            .commit_partial_signature_with_metrics(remote_ip, req)
+            .await
+        {
+            Ok(reply) => (reply.into_inner().result, Code::Ok, String::new()),
+            Err(status) => (false, status.code(), status.message().to_string()),
+        };
+
+        CommitPartialSignatureResult {
+            chain_id,
+            task_type,
+            signature_index,
+            result,
+            code: code as i32,
+            message,
+        }
+    }
+
+    async fn handle_peer_record(
+        &self,
+        remote_ip: Option<IpAddr>,
+        req: AnnouncePeerRecordRequest,
+    ) -> Result<Response<AnnouncePeerRecordReply>, Status> {
+        self.check_source_rate_limit(remote_ip).await?;
 
-            return Ok(Response::new(CommitPartialSignatureReply { result: true }));
+        let peer_record: PeerRecord = bincode::deserialize(&req.peer_record)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        if !peer_record.verify() || peer_record.is_expired(now_timestamp()) {
+            return Err(Status::invalid_argument(
+                NodeError::InvalidPeerRecord.to_string(),
+            ));
+        }
+
+        // only the endpoints of the members of the groups the node is in are kept
+        if !self
+            .group_cache
+            .read()
+            .await
+            .is_member_of_any_group(peer_record.id_address)
+        {
+            return Err(Status::not_found(NodeError::MemberNotExisted.to_string()));
         }
 
-        Err(Status::not_found(NodeError::MemberNotExisted.to_string()))
+        self.group_cache
+            .write()
+            .await
+            .save_peer_record(peer_record)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        let own_peer_record = self
+            .group_cache
+            .read()
+            .await
+            .get_peer_record(self.id_address)
+            .map(|peer_record| bincode::serialize(peer_record).unwrap())
+            .unwrap_or_default();
+
+        Ok(Response::new(AnnouncePeerRecordReply {
+            peer_record: own_peer_record,
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl<
+        N: NodeInfoFetcher + Sync + Send + 'static,
+        G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
+        I: ChainIdentity
+            + ControllerClientBuilder
+            + CoordinatorClientBuilder
+            + AdapterClientBuilder
+            + ChainProviderBuilder
+            + Sync
+            + Send
+            + 'static,
+    > CommitterService for BLSCommitterServiceServer<N, G, T, I>
+{
+    async fn commit_partial_signature(
+        &self,
+        request: Request<CommitPartialSignatureRequest>,
+    ) -> Result<Response<CommitPartialSignatureReply>, Status> {
+        let remote_ip = request.remote_addr().map(|addr| addr.ip());
+
+        self.commit_partial_signature_with_metrics(remote_ip, request.into_inner())
+            .await
+    }
+
+    async fn commit_partial_signatures(
+        &self,
+        request: Request<CommitPartialSignaturesRequest>,
+    ) -> Result<Response<CommitPartialSignaturesReply>, Status> {
+        let remote_ip = request.remote_addr().map(|addr| addr.ip());
+
+        let partial_signatures = request.into_inner().partial_signatures;
+
+        if partial_signatures.len() > MAX_PARTIAL_SIGNATURES_PER_BATCH {
+            return Err(Status::invalid_argument(format!(
+                "at most {} partial signatures are accepted in a batch",
+                MAX_PARTIAL_SIGNATURES_PER_BATCH
+            )));
+        }
+
+        let mut results = Vec::with_capacity(partial_signatures.len());
+
+        // every partial signature counts against the rate limits on its own
+        for req in partial_signatures {
+            results.push(self.acknowledge_partial_signature(remote_ip, req).await);
+        }
+
+        Ok(Response::new(CommitPartialSignaturesReply { results }))
+    }
+
+    type StreamPartialSignaturesStream =
+        Pin<Box<dyn Stream<Item = Result<CommitPartialSignatureResult, Status>> + Send + 'static>>;
+
+    async fn stream_partial_signatures(
+        &self,
+        request: Request<Streaming<CommitPartialSignatureRequest>>,
+    ) -> Result<Response<Self::StreamPartialSignaturesStream>, Status> {
+        let remote_ip = request.remote_addr().map(|addr| addr.ip());
+
+        let mut requests = request.into_inner();
+
+        let (mut sender, receiver) = mpsc::channel(PARTIAL_SIGNATURE_STREAM_BUFFER);
+
+        let server = self.clone();
+
+        tokio::spawn(async move {
+            while let Some(req) = requests.next().await {
+                let result = match req {
+                    Ok(req) => Ok(server.acknowledge_partial_signature(remote_ip, req).await),
+                    Err(status) => Err(status),
+                };
+
+                // the stream ends once the client goes away or sends an error
+                let is_err = result.is_err();
+
+                if sender.send(result).await.is_err() || is_err {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(receiver)))
+    }
+
+    async fn announce_peer_record(
+        &self,
+        request: Request<AnnouncePeerRecordRequest>,
+    ) -> Result<Response<AnnouncePeerRecordReply>, Status> {
+        let remote_ip = request.remote_addr().map(|addr| addr.ip());
+
+        self.handle_peer_record(remote_ip, request.into_inner())
+            .await
     }
 }
 
@@ -301,7 +1083,13 @@ pub async fn start_committer_server_with_shutdown<
     F: Future<Output = ()>,
     N: NodeInfoFetcher + Sync + Send + 'static,
     G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-    T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater
+        + Sync
+        + Send
+        + 'static,
     I: ChainIdentity
         + ControllerClientBuilder
         + CoordinatorClientBuilder
@@ -328,20 +1116,31 @@ pub async fn start_committer_server_with_shutdown<
 
     let group_cache = context.read().await.get_main_chain().get_group_cache();
 
-    Server::builder()
-        .add_service(CommitterServiceServer::with_interceptor(
-            BLSCommitterServiceServer::new(id_address, group_cache, context),
-            intercept,
-        ))
+    let server = BLSCommitterServiceServer::new(id_address, group_cache, context);
+
+    let pending_partial_signatures_handle = tokio::spawn(server.clone().start_retrying_pending());
+
+    let res = Server::builder()
+        .add_service(CommitterServiceServer::with_interceptor(server, intercept))
         .serve_with_shutdown(addr, shutdown_signal)
-        .await?;
+        .await;
+
+    pending_partial_signatures_handle.abort();
+
+    res?;
     Ok(())
 }
 
 pub async fn start_committer_server<
     N: NodeInfoFetcher + Sync + Send + 'static,
     G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-    T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater
+        + Sync
+        + Send
+        + 'static,
     I: ChainIdentity
         + ControllerClientBuilder
         + CoordinatorClientBuilder
@@ -367,18 +1166,587 @@ pub async fn start_committer_server<
 
     let group_cache = context.read().await.get_main_chain().get_group_cache();
 
-    Server::builder()
-        .add_service(CommitterServiceServer::with_interceptor(
-            BLSCommitterServiceServer::new(id_address, group_cache, context),
-            intercept,
-        ))
+    let server = BLSCommitterServiceServer::new(id_address, group_cache, context);
+
+    let pending_partial_signatures_handle = tokio::spawn(server.clone().start_retrying_pending());
+
+    let res = Server::builder()
+        .add_service(CommitterServiceServer::with_interceptor(server, intercept))
         .serve(addr)
-        .await?;
+        .await;
+
+    pending_partial_signatures_handle.abort();
+
+    res?;
     Ok(())
 }
 
 fn intercept(req: Request<()>) -> Result<Request<()>, Status> {
-    // println!("Intercepting request: {:?}", req);
-
     Ok(req)
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::{
+        committer_stub::{
+            committer_service_client::CommitterServiceClient,
+            committer_service_server::{CommitterService, CommitterServiceServer},
+            CommitPartialSignatureRequest, CommitPartialSignaturesRequest,
+        },
+        BLSCommitterServiceServer, MAX_PARTIAL_SIGNATURES_PER_BATCH,
+    };
+    use crate::node::{
+        algorithm::bls::{BLSCore, SimpleBLSCore},
+        committer::{client::GeneralCommitterClient, CommitterService as _},
+        context::{
+            chain::{types::GeneralMainChain, ChainFetcher, MainChainFetcher},
+            types::GeneralContext,
+            ContextFetcher,
+        },
+    };
+    use arpa_node_core::{
+        DKGStatus, Group, Member, MockChainIdentity, RandomnessTask, SignedPartialSignature,
+        TaskType,
+    };
+    use arpa_node_dal::{
+        cache::{GroupInfo, InMemoryBLSTasksQueue, InMemoryGroupInfoCache, InMemoryNodeInfoCache},
+        BLSTasksUpdater, SignatureResultCacheFetcher,
+    };
+    use ethers::{
+        core::rand,
+        signers::{LocalWallet, Signer},
+        types::Address,
+    };
+    use futures::{stream, TryStreamExt};
+    use std::{collections::BTreeMap, sync::Arc};
+    use threshold_bls::{
+        curve::bls12381::{Scalar, G1},
+        poly::{Idx, Poly},
+        sig::Share,
+    };
+    use tokio::{net::TcpListener, sync::RwLock};
+    use tonic::{transport::Server, Code, Request};
+
+    pub(crate) const GROUP_INDEX: usize = 1;
+
+    pub(crate) type TestCommitterServer = BLSCommitterServiceServer<
+        InMemoryNodeInfoCache,
+        InMemoryGroupInfoCache,
+        InMemoryBLSTasksQueue<RandomnessTask>,
+        MockChainIdentity,
+    >;
+
+    pub(crate) struct TestMember {
+        pub(crate) wallet: LocalWallet,
+        pub(crate) share: Share<Scalar>,
+    }
+
+    /// Deals the key shares of a ready group whose members are all its committers.
+    pub(crate) fn build_group(size: usize, threshold: usize) -> (Group, Vec<TestMember>) {
+        let private = Poly::<Scalar>::new(threshold - 1);
+        let public = private.commit::<G1>();
+
+        let mut members = BTreeMap::new();
+        let mut test_members = vec![];
+
+        for index in 0..size {
+            let wallet = LocalWallet::new(&mut rand::thread_rng());
+            let eval = private.eval(index as Idx);
+
+            members.insert(
+                wallet.address(),
+                Member {
+                    index,
+                    id_address: wallet.address(),
+                    partial_public_key: Some(public.eval(index as Idx).value),
+                },
+            );
+
+            test_members.push(TestMember {
+                wallet,
+                share: Share {
+                    index: eval.index,
+                    private: eval.value,
+                },
+            });
+        }
+
+        let group = Group {
+            index: GROUP_INDEX,
+            epoch: 1,
+            size,
+            threshold,
+            state: true,
+            public_key: Some(public.public_key().clone()),
+            committers: members.keys().copied().collect(),
+            members,
+        };
+
+        (group, test_members)
+    }
+
+    /// Builds the committer server of the first member of the group, which has seen the tasks.
+    pub(crate) async fn build_committer(
+        group: &Group,
+        members: &[TestMember],
+        tasks: Vec<RandomnessTask>,
+    ) -> TestCommitterServer {
+        let id_address = members[0].wallet.address();
+
+        let group_cache = InMemoryGroupInfoCache::rebuild(
+            vec![GroupInfo::rebuild(
+                Some(members[0].share.clone()),
+                group.clone(),
+                DKGStatus::None,
+                0,
+                0,
+                None,
+            )],
+            vec![],
+        );
+
+        let mut randomness_tasks_cache = InMemoryBLSTasksQueue::<RandomnessTask>::new();
+
+        for task in tasks {
+            randomness_tasks_cache.add(task).await.unwrap();
+        }
+
+        let main_chain = GeneralMainChain::new(
+            0,
+            "main chain".to_string(),
+            6,
+            MockChainIdentity::new(0, 0, id_address, String::new()),
+            InMemoryNodeInfoCache::new(id_address),
+            group_cache,
+            randomness_tasks_cache,
+        );
+
+        let group_cache = main_chain.get_group_cache();
+
+        BLSCommitterServiceServer::new(
+            id_address,
+            group_cache,
+            Arc::new(RwLock::new(GeneralContext::new(main_chain))),
+        )
+    }
+
+    pub(crate) fn build_randomness_task(index: usize, message: &str) -> RandomnessTask {
+        RandomnessTask {
+            index,
+            message: message.to_string(),
+            group_index: GROUP_INDEX,
+            assignment_block_height: 0,
+        }
+    }
+
+    /// Signs the partial signature of the member over the message of a randomness task.
+    pub(crate) async fn sign_randomness_partial_signature(
+        member: &TestMember,
+        task_index: usize,
+        message: &str,
+    ) -> SignedPartialSignature {
+        let partial_signature = SimpleBLSCore {}
+            .partial_sign(&member.share, message.as_bytes())
+            .unwrap();
+
+        SignedPartialSignature::sign(
+            &member.wallet,
+            0,
+            GROUP_INDEX,
+            TaskType::Randomness,
+            task_index,
+            message.as_bytes().to_vec(),
+            partial_signature,
+        )
+        .await
+        .unwrap()
+    }
+
+    pub(crate) fn build_request(
+        id_address: Address,
+        partial_signature: SignedPartialSignature,
+    ) -> CommitPartialSignatureRequest {
+        GeneralCommitterClient::new(id_address, String::new()).build_request(partial_signature)
+    }
+
+    /// The members whose partial signatures of the randomness task are saved by the committer.
+    pub(crate) async fn saved_signers(
+        server: &TestCommitterServer,
+        task_index: usize,
+    ) -> Vec<Address> {
+        let randomness_result_cache = server
+            .context
+            .read()
+            .await
+            .get_main_chain()
+            .get_randomness_result_cache();
+
+        let randomness_result_cache = randomness_result_cache.read().await;
+
+        let mut signers = randomness_result_cache
+            .get(task_index)
+            .map(|cache| {
+                cache
+                    .result_cache
+                    .partial_signatures
+                    .keys()
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        signers.sort_unstable();
+
+        signers
+    }
+
+    pub(crate) async fn add_randomness_task(server: &TestCommitterServer, task: RandomnessTask) {
+        let randomness_tasks_cache = server
+            .context
+            .read()
+            .await
+            .get_main_chain()
+            .get_randomness_tasks_cache();
+
+        randomness_tasks_cache
+            .write()
+            .await
+            .add(task)
+            .await
+            .unwrap();
+    }
+
+    /// The message the committer has cached for the randomness task, if any.
+    pub(crate) async fn cached_message(
+        server: &TestCommitterServer,
+        task_index: usize,
+    ) -> Option<String> {
+        let randomness_result_cache = server
+            .context
+            .read()
+            .await
+            .get_main_chain()
+            .get_randomness_result_cache();
+
+        let randomness_result_cache = randomness_result_cache.read().await;
+
+        randomness_result_cache
+            .get(task_index)
+            .map(|cache| cache.result_cache.message.clone())
+    }
+
+    /// Serves the committer on a local port, returns its endpoint.
+    async fn serve(server: TestCommitterServer) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+        let endpoint = listener.local_addr().unwrap().to_string();
+
+        let incoming = Box::pin(stream::unfold(listener, |listener| async move {
+            let connection = listener.accept().await.map(|(connection, _)| connection);
+            Some((connection, listener))
+        }));
+
+        tokio::spawn(
+            Server::builder()
+                .add_service(CommitterServiceServer::new(server))
+                .serve_with_incoming(incoming),
+        );
+
+        endpoint
+    }
+
+    #[tokio::test]
+    async fn test_commit_partial_signatures_acknowledges_each() {
+        let (group, members) = build_group(3, 2);
+
+        let server =
+            build_committer(&group, &members, vec![build_randomness_task(0, "0x01")]).await;
+
+        let (member_1, member_2) = (members[1].wallet.address(), members[2].wallet.address());
+
+        let valid = sign_randomness_partial_signature(&members[1], 0, "0x01").await;
+
+        // signed with the chain key of another member
+        let mis_signed = build_request(
+            member_2,
+            sign_randomness_partial_signature(&members[1], 0, "0x01").await,
+        );
+
+        // signed with the chain key of the member but the key share of another one
+        let other_share = sign_randomness_partial_signature(&members[1], 0, "0x01").await;
+        let wrong_share = SignedPartialSignature::sign(
+            &members[2].wallet,
+            other_share.chain_id,
+            other_share.group_index,
+            other_share.task_type,
+            other_share.signature_index,
+            other_share.message,
+            other_share.partial_signature,
+        )
+        .await
+        .unwrap();
+
+        // the committer has not seen the task yet
+        let unknown_task = sign_randomness_partial_signature(&members[2], 1, "0x02").await;
+
+        let results = server
+            .commit_partial_signatures(Request::new(CommitPartialSignaturesRequest {
+                partial_signatures: vec![
+                    build_request(member_1, valid.clone()),
+                    mis_signed,
+                    build_request(member_2, wrong_share),
+                    build_request(member_1, valid),
+                    build_request(member_2, unknown_task),
+                ],
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .results;
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| (
+                    result.signature_index,
+                    result.result,
+                    Code::from_i32(result.code)
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, true, Code::Ok),
+                (0, false, Code::Unauthenticated),
+                (0, false, Code::Internal),
+                (0, false, Code::AlreadyExists),
+                (1, false, Code::Ok),
+            ]
+        );
+
+        assert_eq!(saved_signers(&server, 0).await, vec![member_1]);
+    }
+
+    #[tokio::test]
+    async fn test_commit_partial_signatures_rejects_oversized_batch() {
+        let (group, members) = build_group(3, 2);
+
+        let server =
+            build_committer(&group, &members, vec![build_randomness_task(0, "0x01")]).await;
+
+        let valid = build_request(
+            members[1].wallet.address(),
+            sign_randomness_partial_signature(&members[1], 0, "0x01").await,
+        );
+
+        let status = server
+            .commit_partial_signatures(Request::new(CommitPartialSignaturesRequest {
+                partial_signatures: vec![valid; MAX_PARTIAL_SIGNATURES_PER_BATCH + 1],
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        // none of the batch is handled
+        assert!(saved_signers(&server, 0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stream_partial_signatures_acknowledges_each() {
+        let (group, members) = build_group(3, 2);
+
+        let server =
+            build_committer(&group, &members, vec![build_randomness_task(0, "0x01")]).await;
+
+        let endpoint = serve(server.clone()).await;
+
+        let (member_1, member_2) = (members[1].wallet.address(), members[2].wallet.address());
+
+        let valid = sign_randomness_partial_signature(&members[1], 0, "0x01").await;
+
+        let requests = vec![
+            build_request(member_1, valid.clone()),
+            build_request(member_1, valid.clone()),
+            build_request(member_2, valid),
+            build_request(
+                member_2,
+                sign_randomness_partial_signature(&members[2], 0, "0x01").await,
+            ),
+        ];
+
+        let mut committer_client = CommitterServiceClient::connect(format!("http://{}", endpoint))
+            .await
+            .unwrap();
+
+        let results = committer_client
+            .stream_partial_signatures(Request::new(stream::iter(requests)))
+            .await
+            .unwrap()
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| (result.result, Code::from_i32(result.code)))
+                .collect::<Vec<_>>(),
+            vec![
+                (true, Code::Ok),
+                (false, Code::AlreadyExists),
+                (false, Code::Unauthenticated),
+                (true, Code::Ok),
+            ]
+        );
+
+        let mut expected = vec![member_1, member_2];
+        expected.sort_unstable();
+
+        assert_eq!(saved_signers(&server, 0).await, expected);
+    }
+
+    #[tokio::test]
+    async fn test_client_streams_partial_signatures_beyond_batch() {
+        let (group, members) = build_group(3, 2);
+
+        let server =
+            build_committer(&group, &members, vec![build_randomness_task(0, "0x01")]).await;
+
+        let endpoint = serve(server.clone()).await;
+
+        let valid = sign_randomness_partial_signature(&members[1], 0, "0x01").await;
+
+        let client = GeneralCommitterClient::new(members[1].wallet.address(), endpoint);
+
+        // more than a batch takes, the copies are rejected one by one rather than the whole batch
+        let results = client
+            .commit_partial_signatures(vec![valid; MAX_PARTIAL_SIGNATURES_PER_BATCH + 1])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), MAX_PARTIAL_SIGNATURES_PER_BATCH + 1);
+        assert!(matches!(results[0], Ok(true)));
+        assert!(results[1..].iter().all(|result| result.is_err()));
+
+        assert_eq!(
+            saved_signers(&server, 0).await,
+            vec![members[1].wallet.address()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_partial_signature_of_another_message() {
+        let (group, members) = build_group(3, 2);
+
+        let server =
+            build_committer(&group, &members, vec![build_randomness_task(0, "0x01")]).await;
+
+        let member_1 = members[1].wallet.address();
+
+        // a well-formed partial signature, but over another message than the one of the task
+        let forged = sign_randomness_partial_signature(&members[1], 0, "0x02").await;
+
+        let status = server
+            .commit_partial_signature(Request::new(build_request(member_1, forged)))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(saved_signers(&server, 0).await.is_empty());
+
+        // the member can still commit its partial signature over the message of the task
+        let valid = sign_randomness_partial_signature(&members[1], 0, "0x01").await;
+
+        let reply = server
+            .commit_partial_signature(Request::new(build_request(member_1, valid)))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(reply.result);
+        assert_eq!(saved_signers(&server, 0).await, vec![member_1]);
+    }
+
+    #[tokio::test]
+    async fn test_hold_partial_signature_until_task_shows_up() {
+        let (group, members) = build_group(3, 2);
+
+        let server = build_committer(&group, &members, vec![]).await;
+
+        let member_1 = members[1].wallet.address();
+
+        let early = sign_randomness_partial_signature(&members[1], 0, "0x01").await;
+
+        let reply = server
+            .commit_partial_signature(Request::new(build_request(member_1, early.clone())))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(!reply.result);
+        assert!(saved_signers(&server, 0).await.is_empty());
+
+        // held partial signatures are not accepted again
+        let status = server
+            .commit_partial_signature(Request::new(build_request(member_1, early)))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::AlreadyExists);
+
+        // nothing changes as long as the task is not seen
+        server.retry_pending_partial_signatures().await;
+        assert!(saved_signers(&server, 0).await.is_empty());
+
+        add_randomness_task(&server, build_randomness_task(0, "0x01")).await;
+
+        server.retry_pending_partial_signatures().await;
+
+        assert_eq!(saved_signers(&server, 0).await, vec![member_1]);
+        assert_eq!(cached_message(&server, 0).await, Some("0x01".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_early_partial_signature_cannot_poison_task_message() {
+        let (group, members) = build_group(3, 2);
+
+        let server = build_committer(&group, &members, vec![]).await;
+
+        let (member_1, member_2) = (members[1].wallet.address(), members[2].wallet.address());
+
+        // the first partial signature of the task carries a forged message
+        let forged = sign_randomness_partial_signature(&members[1], 0, "0x02").await;
+
+        let reply = server
+            .commit_partial_signature(Request::new(build_request(member_1, forged)))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(!reply.result);
+        assert_eq!(cached_message(&server, 0).await, None);
+
+        add_randomness_task(&server, build_randomness_task(0, "0x01")).await;
+
+        // the forged one is dropped once it is checked against the task
+        server.retry_pending_partial_signatures().await;
+
+        assert!(saved_signers(&server, 0).await.is_empty());
+        assert_eq!(cached_message(&server, 0).await, Some("0x01".to_string()));
+
+        // later members are verified against the message of the task
+        let valid = sign_randomness_partial_signature(&members[2], 0, "0x01").await;
+
+        let reply = server
+            .commit_partial_signature(Request::new(build_request(member_2, valid)))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(reply.result);
+        assert_eq!(saved_signers(&server, 0).await, vec![member_2]);
+
+        server.retry_pending_partial_signatures().await;
+        assert_eq!(saved_signers(&server, 0).await, vec![member_2]);
+    }
+}
@@ -6,7 +6,7 @@ use crate::node::{
         group_relay_signature_aggregation::GroupRelaySignatureAggregationListener,
         new_group_relay_confirmation_task::NewGroupRelayConfirmationTaskListener,
         new_group_relay_task::NewGroupRelayTaskListener,
-        new_randomness_task::NewRandomnessTaskListener,
+        new_randomness_task::NewRandomnessTaskListener, peer_record::PeerRecordListener,
         post_commit_grouping::PostCommitGroupingListener, post_grouping::PostGroupingListener,
         pre_grouping::PreGroupingListener,
         randomness_signature_aggregation::RandomnessSignatureAggregationListener,
@@ -14,9 +14,9 @@ use crate::node::{
         ready_to_handle_group_relay_task::ReadyToHandleGroupRelayTaskListener,
         ready_to_handle_randomness_task::ReadyToHandleRandomnessTaskListener, Listener,
     },
-    scheduler::TaskScheduler,
+    scheduler::FixedTaskScheduler,
     subscriber::{
-        block::BlockSubscriber,
+        block::BlockSubscriber, chain_reorg::ChainReorgSubscriber,
         group_relay_confirmation_signature_aggregation::GroupRelayConfirmationSignatureAggregationSubscriber,
         group_relay_signature_aggregation::GroupRelaySignatureAggregationSubscriber,
         in_grouping::InGroupingSubscriber, post_grouping::PostGroupingSubscriber,
@@ -33,7 +33,7 @@ use arpa_node_contract_client::{
 };
 use arpa_node_core::{
     ChainIdentity, GeneralChainIdentity, GroupRelayConfirmationTask, GroupRelayTask,
-    MockChainIdentity, RandomnessTask,
+    MockChainIdentity, RandomnessTask, TaskType,
 };
 use arpa_node_dal::{
     cache::{
@@ -41,11 +41,13 @@ use arpa_node_dal::{
         InMemoryBlockInfoCache, InMemoryGroupInfoCache, InMemoryNodeInfoCache,
         InMemorySignatureResultCache, RandomnessResultCache,
     },
-    {BLSTasksFetcher, BLSTasksUpdater, GroupInfoFetcher, GroupInfoUpdater, NodeInfoFetcher},
+    {
+        BLSTasksFetcher, BLSTasksUpdater, GroupInfoFetcher, GroupInfoUpdater, LogCheckpointFetcher,
+        LogCheckpointUpdater, NodeInfoFetcher,
+    },
 };
 use arpa_node_sqlite_db::{BLSTasksDBClient, GroupInfoDBClient, NodeInfoDBClient};
 use async_trait::async_trait;
-use log::error;
 use std::{marker::PhantomData, sync::Arc};
 use tokio::sync::RwLock;
 
@@ -57,7 +59,10 @@ use super::{
 pub struct GeneralAdapterChain<
     N: NodeInfoFetcher,
     G: GroupInfoFetcher + GroupInfoUpdater,
-    T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater,
     I: ChainIdentity + ControllerClientBuilder + CoordinatorClientBuilder + AdapterClientBuilder,
 > {
     id: usize,
@@ -79,7 +84,13 @@ pub struct GeneralAdapterChain<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -127,7 +138,13 @@ impl<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -144,20 +161,26 @@ impl<
         InMemorySignatureResultCache<GroupRelayConfirmationResultCache>;
 
     async fn init_block_listeners(&self, context: &Self::Context) {
-        let p_block = BlockListener::new(
-            self.id(),
-            self.get_chain_identity(),
-            context.get_event_queue(),
-        );
+        let chain_id = self.id();
+
+        let chain_identity = self.get_chain_identity();
+
+        let block_cache = self.get_block_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_block.start().await {
-                    error!("{:?}", e);
-                };
+            .add_supervised_task(format!("block listener of chain {}", chain_id), move || {
+                BlockListener::new(
+                    chain_id,
+                    chain_identity.clone(),
+                    block_cache.clone(),
+                    event_queue.clone(),
+                )
+                .start()
             });
     }
 
@@ -170,61 +193,100 @@ impl<
             .get_id_address()
             .unwrap();
 
-        let p_new_randomness_task = NewRandomnessTaskListener::new(
-            self.id(),
-            id_address,
-            self.get_chain_identity(),
-            self.get_randomness_tasks_cache(),
-            context.get_event_queue(),
-        );
+        let chain_id = self.id();
+
+        let chain_identity = self.get_chain_identity();
+
+        let randomness_tasks_cache = self.get_randomness_tasks_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_new_randomness_task.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!("new_randomness_task listener of chain {}", chain_id),
+                move || {
+                    NewRandomnessTaskListener::new(
+                        chain_id,
+                        id_address,
+                        chain_identity.clone(),
+                        randomness_tasks_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_ready_to_handle_randomness_task = ReadyToHandleRandomnessTaskListener::new(
-            self.id(),
-            id_address,
-            self.get_chain_identity(),
-            self.get_block_cache(),
-            context.get_main_chain().get_group_cache(),
-            self.get_randomness_tasks_cache(),
-            context.get_event_queue(),
-        );
+        let chain_identity = self.get_chain_identity();
+
+        let block_cache = self.get_block_cache();
+
+        let group_cache = context.get_main_chain().get_group_cache();
+
+        let randomness_tasks_cache = self.get_randomness_tasks_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_ready_to_handle_randomness_task.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!(
+                    "ready_to_handle_randomness_task listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    ReadyToHandleRandomnessTaskListener::new(
+                        chain_id,
+                        id_address,
+                        chain_identity.clone(),
+                        block_cache.clone(),
+                        group_cache.clone(),
+                        randomness_tasks_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_randomness_signature_aggregation = RandomnessSignatureAggregationListener::new(
-            self.id(),
-            id_address,
-            context.get_main_chain().get_group_cache(),
-            self.get_randomness_result_cache(),
-            context.get_event_queue(),
-        );
+        let block_cache = self.get_block_cache();
+
+        let group_cache = context.get_main_chain().get_group_cache();
+
+        let randomness_tasks_cache = self.get_randomness_tasks_cache();
+
+        let randomness_result_cache = self.get_randomness_result_cache();
+
+        let event_queue = context.get_event_queue();
+
+        let partial_signature_gossip = context.get_partial_signature_gossip();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_randomness_signature_aggregation.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!(
+                    "randomness_signature_aggregation listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    RandomnessSignatureAggregationListener::new(
+                        chain_id,
+                        id_address,
+                        block_cache.clone(),
+                        group_cache.clone(),
+                        randomness_tasks_cache.clone(),
+                        randomness_result_cache.clone(),
+                        event_queue.clone(),
+                        partial_signature_gossip,
+                    )
+                    .start()
+                },
+            );
     }
 
     async fn init_group_relay_confirmation_listeners(&self, context: &Self::Context) {
@@ -236,67 +298,93 @@ impl<
             .get_id_address()
             .unwrap();
 
-        let p_new_group_relay_confirmation_task = NewGroupRelayConfirmationTaskListener::new(
-            self.id(),
-            id_address,
-            self.get_chain_identity(),
-            self.get_group_relay_confirmation_tasks_cache(),
-            context.get_event_queue(),
-        );
+        let chain_id = self.id();
+
+        let chain_identity = self.get_chain_identity();
+
+        let group_relay_confirmation_tasks_cache = self.get_group_relay_confirmation_tasks_cache();
+
+        // log checkpoints of the chain are kept along with its randomness tasks
+        let log_checkpoint = self.get_randomness_tasks_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_new_group_relay_confirmation_task.start().await {
-                    error!("{:?}", e);
-                };
-            });
-
-        let p_ready_to_handle_group_relay_confirmation_task =
-            ReadyToHandleGroupRelayConfirmationTaskListener::new(
-                self.id(),
-                self.get_block_cache(),
-                context.get_main_chain().get_group_cache(),
-                self.get_group_relay_confirmation_tasks_cache(),
-                context.get_event_queue(),
+            .add_supervised_task(
+                format!(
+                    "new_group_relay_confirmation_task listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    NewGroupRelayConfirmationTaskListener::new(
+                        chain_id,
+                        id_address,
+                        chain_identity.clone(),
+                        group_relay_confirmation_tasks_cache.clone(),
+                        log_checkpoint.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
             );
 
+        let block_cache = self.get_block_cache();
+
+        let group_cache = context.get_main_chain().get_group_cache();
+
+        let group_relay_confirmation_tasks_cache = self.get_group_relay_confirmation_tasks_cache();
+
+        let event_queue = context.get_event_queue();
+
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_ready_to_handle_group_relay_confirmation_task
+            .add_supervised_task(
+                format!(
+                    "ready_to_handle_group_relay_confirmation_task listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    ReadyToHandleGroupRelayConfirmationTaskListener::new(
+                        chain_id,
+                        block_cache.clone(),
+                        group_cache.clone(),
+                        group_relay_confirmation_tasks_cache.clone(),
+                        event_queue.clone(),
+                    )
                     .start()
-                    .await
-                {
-                    error!("{:?}", e);
-                };
-            });
-
-        let p_group_relay_confirmation_signature_aggregation =
-            GroupRelayConfirmationSignatureAggregationListener::new(
-                self.id(),
-                id_address,
-                context.get_main_chain().get_group_cache(),
-                self.get_group_relay_confirmation_result_cache(),
-                context.get_event_queue(),
+                },
             );
 
+        let group_cache = context.get_main_chain().get_group_cache();
+        let group_relay_confirmation_result_cache =
+            self.get_group_relay_confirmation_result_cache();
+        let event_queue = context.get_event_queue();
+
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_group_relay_confirmation_signature_aggregation
+            .add_supervised_task(
+                format!(
+                    "group_relay_confirmation_signature_aggregation listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    GroupRelayConfirmationSignatureAggregationListener::new(
+                        chain_id,
+                        id_address,
+                        group_cache.clone(),
+                        group_relay_confirmation_result_cache.clone(),
+                        event_queue.clone(),
+                    )
                     .start()
-                    .await
-                {
-                    error!("{:?}", e);
-                };
-            });
+                },
+            );
     }
 
     async fn init_block_subscribers(&self, context: &Self::Context) {
@@ -304,6 +392,24 @@ impl<
             BlockSubscriber::new(self.id(), self.get_block_cache(), context.get_event_queue());
 
         s_block.subscribe().await;
+
+        let s_randomness_reorg = ChainReorgSubscriber::new(
+            self.id(),
+            TaskType::Randomness,
+            self.get_randomness_tasks_cache(),
+            context.get_event_queue(),
+        );
+
+        s_randomness_reorg.subscribe().await;
+
+        let s_group_relay_confirmation_reorg = ChainReorgSubscriber::new(
+            self.id(),
+            TaskType::GroupRelayConfirmation,
+            self.get_group_relay_confirmation_tasks_cache(),
+            context.get_event_queue(),
+        );
+
+        s_group_relay_confirmation_reorg.subscribe().await;
     }
 
     async fn init_randomness_subscribers(&self, context: &Self::Context) {
@@ -318,6 +424,8 @@ impl<
         let s_ready_to_handle_randomness_task = ReadyToHandleRandomnessTaskSubscriber::new(
             self.id(),
             id_address,
+            context.get_main_chain().get_chain_identity(),
+            context.get_partial_signature_gossip(),
             context.get_main_chain().get_group_cache(),
             self.get_randomness_result_cache(),
             context.get_event_queue(),
@@ -399,13 +507,17 @@ impl<
 impl<
         N: NodeInfoFetcher,
         G: GroupInfoFetcher + GroupInfoUpdater,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater,
         I: ChainIdentity + ControllerClientBuilder + CoordinatorClientBuilder + AdapterClientBuilder,
     > GeneralAdapterChain<N, G, T, I>
 {
     pub fn new(
         id: usize,
         description: String,
+        block_confirmations: usize,
         chain_identity: I,
         randomness_tasks_cache: T,
     ) -> Self {
@@ -415,7 +527,9 @@ impl<
             id,
             description,
             chain_identity,
-            block_cache: Arc::new(RwLock::new(InMemoryBlockInfoCache::new())),
+            block_cache: Arc::new(RwLock::new(InMemoryBlockInfoCache::new(
+                block_confirmations,
+            ))),
             randomness_tasks_cache: Arc::new(RwLock::new(randomness_tasks_cache)),
             committer_randomness_result_cache: Arc::new(RwLock::new(
                 InMemorySignatureResultCache::<RandomnessResultCache>::new(),
@@ -434,7 +548,10 @@ impl<
 pub struct GeneralMainChain<
     N: NodeInfoFetcher,
     G: GroupInfoFetcher + GroupInfoUpdater,
-    T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater,
     I: ChainIdentity + ControllerClientBuilder + CoordinatorClientBuilder + AdapterClientBuilder,
 > {
     id: usize,
@@ -462,6 +579,7 @@ impl
     pub fn new(
         id: usize,
         description: String,
+        block_confirmations: usize,
         chain_identity: MockChainIdentity,
         node_cache: InMemoryNodeInfoCache,
         group_cache: InMemoryGroupInfoCache,
@@ -471,7 +589,9 @@ impl
             id,
             description,
             chain_identity: Arc::new(RwLock::new(chain_identity)),
-            block_cache: Arc::new(RwLock::new(InMemoryBlockInfoCache::new())),
+            block_cache: Arc::new(RwLock::new(InMemoryBlockInfoCache::new(
+                block_confirmations,
+            ))),
             randomness_tasks_cache: Arc::new(RwLock::new(randomness_tasks_cache)),
             committer_randomness_result_cache: Arc::new(RwLock::new(
                 InMemorySignatureResultCache::<RandomnessResultCache>::new(),
@@ -499,6 +619,7 @@ impl
     pub fn new(
         id: usize,
         description: String,
+        block_confirmations: usize,
         chain_identity: GeneralChainIdentity,
         node_cache: NodeInfoDBClient,
         group_cache: GroupInfoDBClient,
@@ -508,7 +629,9 @@ impl
             id,
             description,
             chain_identity: Arc::new(RwLock::new(chain_identity)),
-            block_cache: Arc::new(RwLock::new(InMemoryBlockInfoCache::new())),
+            block_cache: Arc::new(RwLock::new(InMemoryBlockInfoCache::new(
+                block_confirmations,
+            ))),
             randomness_tasks_cache: Arc::new(RwLock::new(randomness_tasks_cache)),
             committer_randomness_result_cache: Arc::new(RwLock::new(
                 InMemorySignatureResultCache::<RandomnessResultCache>::new(),
@@ -529,7 +652,13 @@ impl
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -575,7 +704,13 @@ impl<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -595,185 +730,308 @@ impl<
     type GroupRelayResultCaches = InMemorySignatureResultCache<GroupRelayResultCache>;
 
     async fn init_block_listeners(&self, context: &Self::Context) {
-        let p_block = BlockListener::new(
-            self.id(),
-            self.get_chain_identity(),
-            context.get_event_queue(),
-        );
+        let chain_id = self.id();
+
+        let chain_identity = self.get_chain_identity();
+
+        let block_cache = self.get_block_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_block.start().await {
-                    error!("{:?}", e);
-                };
+            .add_supervised_task(format!("block listener of chain {}", chain_id), move || {
+                BlockListener::new(
+                    chain_id,
+                    chain_identity.clone(),
+                    block_cache.clone(),
+                    event_queue.clone(),
+                )
+                .start()
             });
     }
 
     async fn init_dkg_listeners(&self, context: &Self::Context) {
-        let p_pre_grouping = PreGroupingListener::new(
-            self.get_chain_identity(),
-            self.get_group_cache(),
-            context.get_event_queue(),
-        );
+        let chain_identity = self.get_chain_identity();
+
+        let group_cache = self.get_group_cache();
+
+        // log checkpoints of the chain are kept along with its randomness tasks
+        let log_checkpoint = self.get_randomness_tasks_cache();
+
+        let event_queue = context.get_event_queue();
+
+        let chain_id = self.id();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_pre_grouping.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!("pre_grouping listener of chain {}", chain_id),
+                move || {
+                    PreGroupingListener::new(
+                        chain_identity.clone(),
+                        group_cache.clone(),
+                        log_checkpoint.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_post_commit_grouping = PostCommitGroupingListener::new(
-            self.get_chain_identity(),
-            self.get_group_cache(),
-            context.get_event_queue(),
-        );
+        let chain_identity = self.get_chain_identity();
+
+        let group_cache = self.get_group_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_post_commit_grouping.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!("post_commit_grouping listener of chain {}", chain_id),
+                move || {
+                    PostCommitGroupingListener::new(
+                        chain_identity.clone(),
+                        group_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_post_grouping = PostGroupingListener::new(
-            self.get_block_cache(),
-            self.get_group_cache(),
-            context.get_event_queue(),
-        );
+        let block_cache = self.get_block_cache();
+
+        let group_cache = self.get_group_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_post_grouping.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!("post_grouping listener of chain {}", chain_id),
+                move || {
+                    PostGroupingListener::new(
+                        block_cache.clone(),
+                        group_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
+
+        let chain_identity = self.get_chain_identity();
+
+        let node_cache = self.get_node_cache();
+
+        let group_cache = self.get_group_cache();
+
+        context
+            .get_fixed_task_handler()
+            .write()
+            .await
+            .add_supervised_task(
+                format!("peer_record listener of chain {}", chain_id),
+                move || {
+                    PeerRecordListener::new(
+                        chain_identity.clone(),
+                        node_cache.clone(),
+                        group_cache.clone(),
+                    )
+                    .start()
+                },
+            );
     }
 
     async fn init_randomness_listeners(&self, context: &Self::Context) {
         let id_address = self.get_node_cache().read().await.get_id_address().unwrap();
 
-        let p_new_randomness_task = NewRandomnessTaskListener::new(
-            self.id(),
-            id_address,
-            self.get_chain_identity(),
-            self.get_randomness_tasks_cache(),
-            context.get_event_queue(),
-        );
+        let chain_id = self.id();
+
+        let chain_identity = self.get_chain_identity();
+
+        let randomness_tasks_cache = self.get_randomness_tasks_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_new_randomness_task.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!("new_randomness_task listener of chain {}", chain_id),
+                move || {
+                    NewRandomnessTaskListener::new(
+                        chain_id,
+                        id_address,
+                        chain_identity.clone(),
+                        randomness_tasks_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_ready_to_handle_randomness_task = ReadyToHandleRandomnessTaskListener::new(
-            self.id(),
-            id_address,
-            self.get_chain_identity(),
-            self.get_block_cache(),
-            self.get_group_cache(),
-            self.get_randomness_tasks_cache(),
-            context.get_event_queue(),
-        );
+        let chain_identity = self.get_chain_identity();
+
+        let block_cache = self.get_block_cache();
+
+        let group_cache = self.get_group_cache();
+
+        let randomness_tasks_cache = self.get_randomness_tasks_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_ready_to_handle_randomness_task.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!(
+                    "ready_to_handle_randomness_task listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    ReadyToHandleRandomnessTaskListener::new(
+                        chain_id,
+                        id_address,
+                        chain_identity.clone(),
+                        block_cache.clone(),
+                        group_cache.clone(),
+                        randomness_tasks_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_randomness_signature_aggregation = RandomnessSignatureAggregationListener::new(
-            self.id(),
-            id_address,
-            self.get_group_cache(),
-            self.get_randomness_result_cache(),
-            context.get_event_queue(),
-        );
+        let block_cache = self.get_block_cache();
+
+        let group_cache = self.get_group_cache();
+
+        let randomness_tasks_cache = self.get_randomness_tasks_cache();
+
+        let randomness_result_cache = self.get_randomness_result_cache();
+
+        let event_queue = context.get_event_queue();
+
+        let partial_signature_gossip = context.get_partial_signature_gossip();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_randomness_signature_aggregation.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!(
+                    "randomness_signature_aggregation listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    RandomnessSignatureAggregationListener::new(
+                        chain_id,
+                        id_address,
+                        block_cache.clone(),
+                        group_cache.clone(),
+                        randomness_tasks_cache.clone(),
+                        randomness_result_cache.clone(),
+                        event_queue.clone(),
+                        partial_signature_gossip,
+                    )
+                    .start()
+                },
+            );
     }
 
     async fn init_group_relay_listeners(&self, context: &Self::Context) {
         let id_address = self.get_node_cache().read().await.get_id_address().unwrap();
 
-        let p_new_group_relay_task = NewGroupRelayTaskListener::new(
-            self.get_chain_identity(),
-            self.get_group_relay_tasks_cache(),
-            context.get_event_queue(),
-        );
+        let chain_identity = self.get_chain_identity();
+
+        let group_relay_tasks_cache = self.get_group_relay_tasks_cache();
+
+        // log checkpoints of the chain are kept along with its randomness tasks
+        let log_checkpoint = self.get_randomness_tasks_cache();
+
+        let event_queue = context.get_event_queue();
+
+        let chain_id = self.id();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_new_group_relay_task.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!("new_group_relay_task listener of chain {}", chain_id),
+                move || {
+                    NewGroupRelayTaskListener::new(
+                        chain_identity.clone(),
+                        group_relay_tasks_cache.clone(),
+                        log_checkpoint.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_ready_to_handle_group_relay_task = ReadyToHandleGroupRelayTaskListener::new(
-            self.get_block_cache(),
-            self.get_group_cache(),
-            self.get_group_relay_tasks_cache(),
-            context.get_event_queue(),
-        );
+        let block_cache = self.get_block_cache();
+
+        let group_cache = self.get_group_cache();
+
+        let group_relay_tasks_cache = self.get_group_relay_tasks_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_ready_to_handle_group_relay_task.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!(
+                    "ready_to_handle_group_relay_task listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    ReadyToHandleGroupRelayTaskListener::new(
+                        block_cache.clone(),
+                        group_cache.clone(),
+                        group_relay_tasks_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
 
-        let p_group_relay_signature_aggregation = GroupRelaySignatureAggregationListener::new(
-            id_address,
-            self.get_group_cache(),
-            self.get_group_relay_result_cache(),
-            context.get_event_queue(),
-        );
+        let group_cache = self.get_group_cache();
+
+        let group_relay_result_cache = self.get_group_relay_result_cache();
+
+        let event_queue = context.get_event_queue();
 
         context
             .get_fixed_task_handler()
             .write()
             .await
-            .add_task(async move {
-                if let Err(e) = p_group_relay_signature_aggregation.start().await {
-                    error!("{:?}", e);
-                };
-            });
+            .add_supervised_task(
+                format!(
+                    "group_relay_signature_aggregation listener of chain {}",
+                    chain_id
+                ),
+                move || {
+                    GroupRelaySignatureAggregationListener::new(
+                        id_address,
+                        group_cache.clone(),
+                        group_relay_result_cache.clone(),
+                        event_queue.clone(),
+                    )
+                    .start()
+                },
+            );
     }
 
     async fn init_block_subscribers(&self, context: &Self::Context) {
@@ -781,6 +1039,24 @@ impl<
             BlockSubscriber::new(self.id(), self.get_block_cache(), context.get_event_queue());
 
         s_block.subscribe().await;
+
+        let s_randomness_reorg = ChainReorgSubscriber::new(
+            self.id(),
+            TaskType::Randomness,
+            self.get_randomness_tasks_cache(),
+            context.get_event_queue(),
+        );
+
+        s_randomness_reorg.subscribe().await;
+
+        let s_group_relay_reorg = ChainReorgSubscriber::new(
+            self.id(),
+            TaskType::GroupRelay,
+            self.get_group_relay_tasks_cache(),
+            context.get_event_queue(),
+        );
+
+        s_group_relay_reorg.subscribe().await;
     }
 
     async fn init_dkg_subscribers(&self, context: &Self::Context) {
@@ -819,6 +1095,8 @@ impl<
         let s_ready_to_handle_randomness_task = ReadyToHandleRandomnessTaskSubscriber::new(
             self.id(),
             id_address,
+            self.get_chain_identity(),
+            context.get_partial_signature_gossip(),
             self.get_group_cache(),
             self.get_randomness_result_cache(),
             context.get_event_queue(),
@@ -854,7 +1132,13 @@ impl<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -899,7 +1183,13 @@ impl<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -932,7 +1222,13 @@ impl<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -979,7 +1275,13 @@ impl<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
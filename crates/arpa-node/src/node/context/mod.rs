@@ -1,7 +1,7 @@
 pub mod chain;
 pub mod types;
 
-use self::types::ContextHandle;
+use self::types::{ContextHandle, GossipConfig};
 
 use crate::node::{
     queue::event_queue::EventQueue,
@@ -22,6 +22,7 @@ pub trait Context {
 
 #[async_trait]
 pub trait TaskWaiter {
+    /// Waits until SIGINT or SIGTERM is received, then shuts the node down gracefully.
     async fn wait_task(&self);
 }
 
@@ -37,8 +38,23 @@ pub(crate) trait ContextFetcher<T: Context> {
     fn get_dynamic_task_handler(&self) -> Arc<RwLock<SimpleDynamicTaskScheduler>>;
 
     fn get_event_queue(&self) -> Arc<RwLock<EventQueue>>;
+
+    fn get_partial_signature_gossip(&self) -> Option<GossipConfig>;
 }
 
 pub(crate) trait CommitterServerStarter<T: Context> {
     fn start_committer_server(&mut self, rpc_endpoint: String, context: Arc<RwLock<T>>);
 }
+
+pub(crate) trait ManagementServerStarter<T: Context> {
+    fn start_management_server(
+        &mut self,
+        rpc_endpoint: String,
+        rpc_token: Option<String>,
+        context: Arc<RwLock<T>>,
+    );
+}
+
+pub(crate) trait MetricsServerStarter {
+    fn start_metrics_server(&mut self, endpoint: String);
+}
@@ -3,41 +3,76 @@ use super::{
         types::{GeneralAdapterChain, GeneralMainChain},
         Chain, ChainFetcher, MainChainFetcher,
     },
-    CommitterServerStarter, Context, ContextFetcher, TaskWaiter,
+    CommitterServerStarter, Context, ContextFetcher, ManagementServerStarter, MetricsServerStarter,
+    TaskWaiter,
 };
 use crate::node::{
     committer::server,
     error::{ConfigError, NodeError, NodeResult},
+    management::server as management_server,
+    metrics::server as metrics_server,
     queue::event_queue::EventQueue,
     scheduler::{
-        dynamic::SimpleDynamicTaskScheduler, fixed::SimpleFixedTaskScheduler, TaskScheduler,
+        dynamic::SimpleDynamicTaskScheduler, fixed::SimpleFixedTaskScheduler, DynamicTaskScheduler,
+        FixedTaskScheduler, TaskScheduler,
     },
 };
 use arpa_node_contract_client::{
     adapter::AdapterClientBuilder, controller::ControllerClientBuilder,
-    coordinator::CoordinatorClientBuilder, provider::ChainProviderBuilder,
+    coordinator::CoordinatorClientBuilder, ethers::provider::ChainProvider,
+    provider::ChainProviderBuilder,
+};
+use arpa_node_core::{
+    ChainIdentity, FailoverConfig, GeneralChainIdentity, NodeSigner, ProviderTransport,
+    RandomnessTask, RemoteSigner, TransactionConfig, COMMITTER_EXCLUSIVE_WINDOW,
 };
-use arpa_node_core::{ChainIdentity, RandomnessTask};
 use arpa_node_dal::{
-    BLSTasksFetcher, BLSTasksUpdater, GroupInfoFetcher, GroupInfoUpdater, NodeInfoFetcher,
+    BLSTasksFetcher, BLSTasksUpdater, GroupInfoFetcher, GroupInfoUpdater, LogCheckpointFetcher,
+    LogCheckpointUpdater, NodeInfoFetcher,
 };
 use async_trait::async_trait;
 use ethers::{
     prelude::k256::ecdsa::SigningKey,
     signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Wallet},
 };
-use log::error;
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, env, sync::Arc};
 use tokio::sync::RwLock;
 
+pub const DEFAULT_SHUTDOWN_GRACE_PERIOD_MILLIS: u64 = 10000;
+
+pub const DEFAULT_BLOCK_CONFIRMATIONS: usize = 6;
+
+pub const DEFAULT_POLLING_INTERVAL_MILLIS: u64 = 1000;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     pub node_rpc_endpoint: String,
+    // Local management service, disabled if not set
+    pub node_management_rpc_endpoint: Option<String>,
+    // Token to guard the write endpoints of the management service
+    pub node_management_rpc_token: Option<String>,
+    // Prometheus metrics exposed at /metrics, disabled if not set
+    pub node_metrics_endpoint: Option<String>,
     pub provider_endpoint: String,
+    // Transport of provider_endpoint: http, ws or ipc, http if not set
+    pub provider_transport: Option<ProviderTransport>,
+    // Polling interval of blocks and logs over http, 1000 if not set
+    pub provider_polling_interval_millis: Option<u64>,
+    // Fallback endpoints and health checks of the main chain
+    pub provider_failover: Option<FailoverConfig>,
+    // Gas caps and resubmission of transactions on the main chain, defaults if not set
+    pub transaction: Option<TransactionConfig>,
     pub controller_address: String,
+    // Blocks to wait before handling a task of the main chain, 6 if not set
+    pub block_confirmations: Option<usize>,
     // Data file for persistence
     pub data_path: Option<String>,
+    // Key to encrypt the data file, derived from the main chain account if not set(deprecated)
+    pub data_encryption: Option<DataEncryption>,
+    // Gossip of randomness partial signatures within the groups, disabled if not set
+    pub partial_signature_gossip: Option<GossipConfig>,
     pub account: Account,
     pub adapters: Vec<Adapter>,
 }
@@ -47,35 +82,93 @@ pub struct Adapter {
     pub id: usize,
     pub name: String,
     pub endpoint: String,
+    // Transport of endpoint: http, ws or ipc, http if not set
+    pub transport: Option<ProviderTransport>,
+    // Polling interval of blocks and logs over http, 1000 if not set
+    pub polling_interval_millis: Option<u64>,
+    // Fallback endpoints and health checks of the adapter chain
+    pub failover: Option<FailoverConfig>,
+    // Gas caps and resubmission of transactions on the adapter chain, defaults if not set
+    pub transaction: Option<TransactionConfig>,
+    pub adapter_address: String,
+    // Blocks to wait before handling a task of the adapter chain, 6 if not set
+    pub block_confirmations: Option<usize>,
+    // Account to send transactions on the adapter chain, can be different from the main chain one
     pub account: Account,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Gossip of randomness partial signatures among all the members of a group. A member pushes its
+/// partial signature to `fanout` random members besides the committers of the task, and forwards
+/// every one it accepts for the first time the same way. A backup committer of the task fulfills
+/// it itself if it is still not fulfilled `backup_committer_delay_blocks` blocks after the task is
+/// assigned, which is never less than the exclusive window of the committers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GossipConfig {
+    pub fanout: usize,
+    pub backup_committer_delay_blocks: usize,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        GossipConfig {
+            fanout: 3,
+            backup_committer_delay_blocks: COMMITTER_EXCLUSIVE_WINDOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hdwallet: Option<HDWallet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub keystore: Option<Keystore>,
     // not recommended
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
+    // The key is kept by the remote signer, preferred over the local ones if set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_signer: Option<RemoteSignerConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteSignerConfig {
+    // JSON-RPC endpoint compatible with Web3Signer eth1 signing
+    pub endpoint: String,
+    pub address: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+pub struct DataEncryption {
+    pub passphrase: Option<String>,
+    // The private key of the keystore is used as the passphrase, preferred over passphrase if set
+    pub keystore: Option<Keystore>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Keystore {
     pub path: String,
     pub password: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HDWallet {
     pub mnemonic: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub passphrase: Option<String>,
 }
 
 pub struct GeneralContext<
     N: NodeInfoFetcher,
     G: GroupInfoFetcher + GroupInfoUpdater,
-    T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater,
     I: ChainIdentity + ControllerClientBuilder + CoordinatorClientBuilder + AdapterClientBuilder,
 > {
     main_chain: GeneralMainChain<N, G, T, I>,
@@ -83,12 +176,22 @@ pub struct GeneralContext<
     eq: Arc<RwLock<EventQueue>>,
     ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
     f_ts: Arc<RwLock<SimpleFixedTaskScheduler>>,
+    management_rpc_endpoint: Option<String>,
+    management_rpc_token: Option<String>,
+    metrics_endpoint: Option<String>,
+    partial_signature_gossip: Option<GossipConfig>,
 }
 
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -106,14 +209,33 @@ impl<
             eq: Arc::new(RwLock::new(EventQueue::new())),
             ts: Arc::new(RwLock::new(SimpleDynamicTaskScheduler::new())),
             f_ts: Arc::new(RwLock::new(SimpleFixedTaskScheduler::new())),
+            management_rpc_endpoint: None,
+            management_rpc_token: None,
+            metrics_endpoint: None,
+            partial_signature_gossip: None,
         }
     }
 
+    pub fn set_management_server(&mut self, rpc_endpoint: String, rpc_token: Option<String>) {
+        self.management_rpc_endpoint = Some(rpc_endpoint);
+        self.management_rpc_token = rpc_token;
+    }
+
+    pub fn set_metrics_server(&mut self, endpoint: String) {
+        self.metrics_endpoint = Some(endpoint);
+    }
+
+    pub fn set_partial_signature_gossip(&mut self, gossip_config: GossipConfig) {
+        self.partial_signature_gossip = Some(gossip_config);
+    }
+
     pub fn add_adapter_chain(
         &mut self,
         adapter_chain: GeneralAdapterChain<N, G, T, I>,
     ) -> NodeResult<()> {
-        if self.adapter_chains.contains_key(&adapter_chain.id()) {
+        if adapter_chain.id() == self.main_chain.id()
+            || self.adapter_chains.contains_key(&adapter_chain.id())
+        {
             return Err(NodeError::RepeatedChainId);
         }
 
@@ -128,7 +250,13 @@ impl<
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -161,22 +289,48 @@ impl<
             .unwrap()
             .to_string();
 
+        let management_rpc_endpoint = self.management_rpc_endpoint.clone();
+
+        let management_rpc_token = self.management_rpc_token.clone();
+
+        let metrics_endpoint = self.metrics_endpoint.clone();
+
         let context = Arc::new(RwLock::new(self));
 
         f_ts.write()
             .await
             .start_committer_server(rpc_endpoint, context.clone());
 
+        if let Some(management_rpc_endpoint) = management_rpc_endpoint {
+            f_ts.write().await.start_management_server(
+                management_rpc_endpoint,
+                management_rpc_token,
+                context.clone(),
+            );
+        }
+
+        if let Some(metrics_endpoint) = metrics_endpoint {
+            f_ts.write().await.start_metrics_server(metrics_endpoint);
+        }
+
         let ts = context.read().await.get_dynamic_task_handler();
 
-        ContextHandle { ts }
+        let eq = context.read().await.get_event_queue();
+
+        ContextHandle { ts, f_ts, eq }
     }
 }
 
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -213,35 +367,111 @@ impl<
     fn get_event_queue(&self) -> Arc<RwLock<EventQueue>> {
         self.eq.clone()
     }
+
+    fn get_partial_signature_gossip(&self) -> Option<GossipConfig> {
+        self.partial_signature_gossip
+    }
 }
 
 pub struct ContextHandle {
     ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
+    f_ts: Arc<RwLock<SimpleFixedTaskScheduler>>,
+    eq: Arc<RwLock<EventQueue>>,
+}
+
+impl ContextHandle {
+    pub async fn shutdown(&self) {
+        info!("shutting down listeners, subscribers and servers...");
+
+        self.f_ts.read().await.shutdown();
+
+        self.f_ts.write().await.join().await;
+
+        self.eq.read().await.shutdown();
+
+        info!("waiting for running tasks to finish...");
+
+        // give in-flight signing, committing and fulfilling tasks a chance to finish
+        if tokio::time::timeout(
+            std::time::Duration::from_millis(DEFAULT_SHUTDOWN_GRACE_PERIOD_MILLIS),
+            async {
+                loop {
+                    self.ts.write().await.reap_finished_tasks();
+
+                    if self.ts.read().await.is_idle() {
+                        break;
+                    }
+
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                }
+            },
+        )
+        .await
+        .is_err()
+        {
+            warn!("there are still running tasks after the grace period, cancelling them.");
+        }
+
+        self.ts.read().await.shutdown();
+
+        info!("shutdown completed.");
+    }
 }
 
 #[async_trait]
 impl TaskWaiter for ContextHandle {
     async fn wait_task(&self) {
-        loop {
-            while !self.ts.read().await.dynamic_tasks.is_empty() {
-                let (task_recv, task_monitor) = self.ts.write().await.dynamic_tasks.pop().unwrap();
+        let shutdown_signal = shutdown_signal();
 
-                let _ = task_recv.await;
+        tokio::pin!(shutdown_signal);
 
-                if let Some(monitor) = task_monitor {
-                    monitor.abort();
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_signal => break,
+                _ = tokio::time::sleep(std::time::Duration::from_millis(1000)) => {
+                    self.ts.write().await.reap_finished_tasks();
                 }
             }
-
-            tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
         }
+
+        self.shutdown().await;
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for SIGINT");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = futures::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("received SIGINT"),
+        _ = terminate => info!("received SIGTERM"),
     }
 }
 
 impl<
         N: NodeInfoFetcher + Sync + Send + 'static,
         G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity
             + ControllerClientBuilder
             + CoordinatorClientBuilder
@@ -265,6 +495,144 @@ impl<
     }
 }
 
+impl<
+        N: NodeInfoFetcher + Sync + Send + 'static,
+        G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
+        I: ChainIdentity
+            + ControllerClientBuilder
+            + CoordinatorClientBuilder
+            + AdapterClientBuilder
+            + ChainProviderBuilder
+            + Sync
+            + Send
+            + 'static,
+    > ManagementServerStarter<GeneralContext<N, G, T, I>> for SimpleFixedTaskScheduler
+{
+    fn start_management_server(
+        &mut self,
+        rpc_endpoint: String,
+        rpc_token: Option<String>,
+        context: Arc<RwLock<GeneralContext<N, G, T, I>>>,
+    ) {
+        self.add_task(async move {
+            if let Err(e) =
+                management_server::start_management_server(rpc_endpoint, rpc_token, context).await
+            {
+                error!("{:?}", e);
+            };
+        });
+    }
+}
+
+impl MetricsServerStarter for SimpleFixedTaskScheduler {
+    fn start_metrics_server(&mut self, endpoint: String) {
+        self.add_task(async move {
+            if let Err(e) = metrics_server::start_metrics_server(endpoint).await {
+                error!("{:?}", e);
+            };
+        });
+    }
+}
+
+pub fn build_management_rpc_token_from_config(
+    rpc_token: Option<String>,
+) -> Result<Option<String>, ConfigError> {
+    match rpc_token {
+        Some(rpc_token) if rpc_token.eq("env") => {
+            Ok(Some(env::var("ARPA_NODE_MANAGEMENT_RPC_TOKEN")?))
+        }
+        rpc_token => Ok(rpc_token),
+    }
+}
+
+pub fn build_data_key(data_encryption: DataEncryption) -> Result<Vec<u8>, ConfigError> {
+    if let Some(mut keystore) = data_encryption.keystore {
+        if keystore.password.eq("env") {
+            keystore.password = env::var("ARPA_NODE_DATA_KEYSTORE_PASSWORD")?;
+        }
+        let wallet = LocalWallet::decrypt_keystore(&keystore.path, &keystore.password)?;
+        return Ok(hex::encode(wallet.signer().to_bytes()).into_bytes());
+    } else if let Some(mut passphrase) = data_encryption.passphrase {
+        if passphrase.eq("env") {
+            passphrase = env::var("ARPA_NODE_DATA_PASSPHRASE")?;
+        }
+        return Ok(passphrase.into_bytes());
+    }
+
+    Err(ConfigError::LackOfDataKey)
+}
+
+/// Builds the key to encrypt the data file, which falls back to the one derived from the account
+/// so that the data file of the nodes configured before is still readable.
+pub async fn build_data_key_from_config(
+    data_encryption: Option<DataEncryption>,
+    signer: &NodeSigner,
+) -> Result<Vec<u8>, ConfigError> {
+    match data_encryption {
+        Some(data_encryption) => build_data_key(data_encryption),
+        None => {
+            warn!("data_encryption is not set, the data file is encrypted by a key derived from the account, which is deprecated. Please set it and rekey the data file by node-cmd-client rekey.");
+            Ok(signer.get_data_key().await?)
+        }
+    }
+}
+
+pub async fn build_signer_from_config(mut account: Account) -> Result<NodeSigner, ConfigError> {
+    if let Some(remote_signer) = account.remote_signer.take() {
+        let address = remote_signer
+            .address
+            .parse()
+            .map_err(|_| ConfigError::BadFormat)?;
+
+        let signer = RemoteSigner::connect(&remote_signer.endpoint, address).await?;
+
+        return Ok(signer.into());
+    }
+
+    Ok(build_wallet_from_config(account)?.into())
+}
+
+/// Builds the identity of the node on the adapter chain, signing transactions for the chain its
+/// provider is connected to.
+pub async fn build_adapter_chain_identity(
+    adapter: &Adapter,
+) -> Result<GeneralChainIdentity, ConfigError> {
+    let adapter_signer = build_signer_from_config(adapter.account.clone()).await?;
+
+    let adapter_address = adapter
+        .adapter_address
+        .parse()
+        .map_err(|_| ConfigError::BadFormat)?;
+
+    let mut adapter_chain_identity = GeneralChainIdentity::new(
+        adapter.id,
+        adapter.id,
+        adapter_signer,
+        adapter.endpoint.clone(),
+        adapter.transport.unwrap_or_default(),
+        adapter
+            .polling_interval_millis
+            .unwrap_or(DEFAULT_POLLING_INTERVAL_MILLIS),
+        adapter.failover.clone().unwrap_or_default(),
+        adapter.transaction.unwrap_or_default(),
+        adapter_address,
+    );
+
+    let chain_id = ChainProvider::new(&adapter_chain_identity)
+        .get_chain_id()
+        .await?;
+    adapter_chain_identity.set_chain_id(chain_id);
+
+    Ok(adapter_chain_identity)
+}
+
 pub fn build_wallet_from_config(account: Account) -> Result<Wallet<SigningKey>, ConfigError> {
     if account.hdwallet.is_some() {
         let mut hd = account.hdwallet.unwrap();
@@ -276,8 +644,11 @@ pub fn build_wallet_from_config(account: Account) -> Result<Wallet<SigningKey>,
         if hd.path.is_some() {
             wallet = wallet.derivation_path(&hd.path.unwrap()).unwrap();
         }
-        if hd.passphrase.is_some() {
-            wallet = wallet.password(&hd.passphrase.unwrap());
+        if let Some(mut passphrase) = hd.passphrase {
+            if passphrase.eq("env") {
+                passphrase = env::var("ARPA_NODE_HD_ACCOUNT_PASSPHRASE")?;
+            }
+            wallet = wallet.password(&passphrase);
         }
         return Ok(wallet.index(hd.index).unwrap().build()?);
     } else if account.keystore.is_some() {
@@ -1,13 +1,14 @@
-use ethers::providers::Http as HttpProvider;
+use arpa_node_core::NodeSigner;
 use ethers::{
+    contract::AbiError,
     prelude::{signer::SignerMiddlewareError, ContractError, ProviderError},
     providers::Provider,
-    signers::LocalWallet,
+    types::H256,
 };
 use rustc_hex::FromHexError;
 use thiserror::Error;
 
-use crate::ethers::WalletSigner;
+use crate::ethers::{transport::Transport, WalletSigner};
 
 pub type ContractClientResult<A> = Result<A, ContractClientError>;
 
@@ -22,7 +23,9 @@ pub enum ContractClientError {
     #[error(transparent)]
     ContractError(#[from] ContractError<WalletSigner>),
     #[error(transparent)]
-    SignerError(#[from] SignerMiddlewareError<Provider<HttpProvider>, LocalWallet>),
+    AbiError(#[from] AbiError),
+    #[error(transparent)]
+    SignerError(#[from] SignerMiddlewareError<Provider<Transport>, NodeSigner>),
     #[error(transparent)]
     AddressParseError(#[from] FromHexError),
     #[error("can't fetch new block, please check provider")]
@@ -35,8 +38,14 @@ pub enum ContractClientError {
     FetchingGroupRelayTaskError,
     #[error("can't fetch group relay confirmation task, please check provider")]
     FetchingGroupRelayConfirmationTaskError,
+    #[error("transaction {0:?} is still pending, please check the gas caps in config")]
+    TransactionTimeoutError(H256),
+    #[error("transaction {0:?} is reverted")]
+    TransactionRevertedError(H256),
     #[error("there is no task yet")]
     NoTaskAvailable,
+    #[error("{0} is not supported by the on-chain contracts yet")]
+    UnsupportedTransaction(&'static str),
     #[error(transparent)]
     HandlingLogSubscriptionError(#[from] anyhow::Error),
 }
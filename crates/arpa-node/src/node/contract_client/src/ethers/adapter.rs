@@ -5,7 +5,7 @@ use crate::{
 };
 
 use self::adapter_stub::Adapter;
-use super::WalletSigner;
+use super::{transaction::TransactionManager, watch_logs, WalletSigner};
 use arpa_node_core::{
     ChainIdentity, GeneralChainIdentity, Group, GroupRelayConfirmationTask,
     GroupRelayConfirmationTaskState, RandomnessTask,
@@ -13,18 +13,39 @@ use arpa_node_core::{
 use async_trait::async_trait;
 use ethers::prelude::*;
 use ethers::utils::keccak256;
-use std::{collections::HashMap, convert::TryFrom, future::Future, sync::Arc, time::Duration};
+use ethers::{
+    abi::{parse_abi, Tokenize},
+    contract::BaseContract,
+};
+use lazy_static::lazy_static;
+use std::{collections::HashMap, future::Future, sync::Arc};
 
 #[allow(clippy::useless_conversion)]
 pub mod adapter_stub {
     include!("../../../../../contract_stub/adapter.rs");
 }
 
+lazy_static! {
+    // the functions of the adapter sent by nodes, as the adapter service of the rpc mock declares
+    // them, since the adapter ABI is not generated yet
+    static ref ADAPTER_FUNCTIONS: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function requestRandomness(string message) external",
+            "function fulfillRandomness(uint256 groupIndex, uint256 signatureIndex, bytes signature, address[] partialSigners, bytes[] partialSignatures) external",
+            "function fulfillRelay(uint256 relayerGroupIndex, uint256 taskIndex, bytes signature, bytes groupAsBytes) external",
+            "function cancelInvalidRelayConfirmationTask(uint256 taskIndex) external",
+            "function confirmRelay(uint256 taskIndex, bytes groupRelayConfirmationAsBytes, bytes signature) external",
+            "function setInitialGroup(bytes group) external",
+        ])
+        .unwrap()
+    );
+}
+
 #[allow(dead_code)]
 pub struct AdapterClient {
     main_id_address: Address,
     adapter_address: Address,
-    signer: Arc<WalletSigner>,
+    transaction_manager: Arc<TransactionManager>,
 }
 
 impl AdapterClient {
@@ -33,23 +54,12 @@ impl AdapterClient {
         adapter_address: Address,
         identity: &GeneralChainIdentity,
     ) -> Self {
-        let provider = Provider::<Http>::try_from(identity.get_provider_rpc_endpoint())
-            .unwrap()
-            .interval(Duration::from_millis(10u64));
-
-        // instantiate the client with the wallet
-        let signer = Arc::new(SignerMiddleware::new(
-            provider,
-            identity
-                .get_signer()
-                .clone()
-                .with_chain_id(identity.get_chain_id() as u32),
-        ));
+        let transaction_manager = TransactionManager::get_or_build(identity);
 
         AdapterClient {
             main_id_address,
             adapter_address,
-            signer,
+            transaction_manager,
         }
     }
 }
@@ -67,17 +77,29 @@ type AdapterContract = Adapter<WalletSigner>;
 #[async_trait]
 impl ServiceClient<AdapterContract> for AdapterClient {
     async fn prepare_service_client(&self) -> ContractClientResult<AdapterContract> {
-        let adapter_contract = Adapter::new(self.adapter_address, self.signer.clone());
+        let adapter_contract =
+            Adapter::new(self.adapter_address, self.transaction_manager.signer());
 
         Ok(adapter_contract)
     }
 }
 
+impl AdapterClient {
+    async fn send_call<T: Tokenize>(&self, function: &str, args: T) -> ContractClientResult<()> {
+        self.transaction_manager
+            .send_call(&ADAPTER_FUNCTIONS, self.adapter_address, function, args)
+            .await?;
+
+        Ok(())
+    }
+}
+
 #[allow(unused_variables)]
 #[async_trait]
 impl AdapterTransactions for AdapterClient {
     async fn request_randomness(&self, message: &str) -> ContractClientResult<()> {
-        Ok(())
+        self.send_call("requestRandomness", message.to_string())
+            .await
     }
 
     async fn fulfill_randomness(
@@ -87,7 +109,22 @@ impl AdapterTransactions for AdapterClient {
         signature: Vec<u8>,
         partial_signatures: HashMap<Address, Vec<u8>>,
     ) -> ContractClientResult<()> {
-        Ok(())
+        let (partial_signers, partial_signatures): (Vec<Address>, Vec<Bytes>) = partial_signatures
+            .into_iter()
+            .map(|(member, partial_signature)| (member, Bytes::from(partial_signature)))
+            .unzip();
+
+        self.send_call(
+            "fulfillRandomness",
+            (
+                U256::from(group_index),
+                U256::from(signature_index),
+                Bytes::from(signature),
+                partial_signers,
+                partial_signatures,
+            ),
+        )
+        .await
     }
 
     async fn fulfill_relay(
@@ -97,14 +134,24 @@ impl AdapterTransactions for AdapterClient {
         signature: Vec<u8>,
         group_as_bytes: Vec<u8>,
     ) -> ContractClientResult<()> {
-        Ok(())
+        self.send_call(
+            "fulfillRelay",
+            (
+                U256::from(relayer_group_index),
+                U256::from(task_index),
+                Bytes::from(signature),
+                Bytes::from(group_as_bytes),
+            ),
+        )
+        .await
     }
 
     async fn cancel_invalid_relay_confirmation_task(
         &self,
         task_index: usize,
     ) -> ContractClientResult<()> {
-        Ok(())
+        self.send_call("cancelInvalidRelayConfirmationTask", U256::from(task_index))
+            .await
     }
 
     async fn confirm_relay(
@@ -113,11 +160,27 @@ impl AdapterTransactions for AdapterClient {
         group_relay_confirmation_as_bytes: Vec<u8>,
         signature: Vec<u8>,
     ) -> ContractClientResult<()> {
-        Ok(())
+        self.send_call(
+            "confirmRelay",
+            (
+                U256::from(task_index),
+                Bytes::from(group_relay_confirmation_as_bytes),
+                Bytes::from(signature),
+            ),
+        )
+        .await
     }
 
     async fn set_initial_group(&self, group: Vec<u8>) -> ContractClientResult<()> {
-        Ok(())
+        self.send_call("setInitialGroup", Bytes::from(group)).await
+    }
+
+    async fn claim(
+        &self,
+        reward_address: Address,
+        token_amount: usize,
+    ) -> ContractClientResult<()> {
+        Err(ContractClientError::UnsupportedTransaction("claim"))
     }
 }
 
@@ -158,25 +221,21 @@ impl AdapterLogs for AdapterClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
-        let randomness_task_filter =
-            Filter::new()
-                .from_block(BlockNumber::Latest)
-                .topic0(ValueOrArray::Value(H256::from(keccak256(
-                    "RandomnessTask(address,address,uint256)",
-                ))));
-        let mut stream = self
-            .signer
-            .watch(&randomness_task_filter)
-            .await
-            .map_err(|e| {
-                let e: ContractClientError = e.into();
-                e
-            })?;
-        while let Some(log) = stream.next().await {
-            cb(log.into()).await?;
-        }
+        let randomness_task_filter = Filter::new().topic0(ValueOrArray::Value(H256::from(
+            keccak256("RandomnessTask(address,address,uint256)"),
+        )));
+
+        watch_logs(
+            &self.transaction_manager.signer(),
+            randomness_task_filter,
+            from_block,
+            |log| cb(log.into()),
+        )
+        .await?;
+
         Err(ContractClientError::FetchingRandomnessTaskError)
     }
 
@@ -185,24 +244,22 @@ impl AdapterLogs for AdapterClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
-        let group_relay_confirmation_task_filter = Filter::new()
-            .from_block(BlockNumber::Latest)
-            .topic0(ValueOrArray::Value(H256::from(keccak256(
+        let group_relay_confirmation_task_filter =
+            Filter::new().topic0(ValueOrArray::Value(H256::from(keccak256(
                 "GroupRelayConfirmationTask(address,address,uint256)",
             ))));
-        let mut stream = self
-            .signer
-            .watch(&group_relay_confirmation_task_filter)
-            .await
-            .map_err(|e| {
-                let e: ContractClientError = e.into();
-                e
-            })?;
-        while let Some(log) = stream.next().await {
-            cb(log.into()).await?;
-        }
+
+        watch_logs(
+            &self.transaction_manager.signer(),
+            group_relay_confirmation_task_filter,
+            from_block,
+            |log| cb(log.into()),
+        )
+        .await?;
+
         Err(ContractClientError::FetchingGroupRelayConfirmationTaskError)
     }
 }
@@ -1,7 +1,13 @@
 use arpa_node_core::{ChainIdentity, DKGTask, GeneralChainIdentity, GroupRelayTask, Node};
 use async_trait::async_trait;
-use ethers::{prelude::*, utils::keccak256};
-use std::{convert::TryFrom, future::Future, sync::Arc, time::Duration};
+use ethers::{
+    abi::parse_abi,
+    contract::{parse_log, AbiError, BaseContract},
+    prelude::*,
+    utils::keccak256,
+};
+use lazy_static::lazy_static;
+use std::{future::Future, sync::Arc};
 
 use crate::{
     controller::{
@@ -13,36 +19,73 @@ use crate::{
 
 use self::controller_stub::Controller;
 
-use super::WalletSigner;
+use super::{transaction::TransactionManager, watch_logs, WalletSigner};
 
 #[allow(clippy::useless_conversion)]
 pub mod controller_stub {
     include!("../../../../../contract_stub/controller.rs");
 }
 
+lazy_static! {
+    // the functions of Controller.sol sent by nodes, since the controller ABI is not generated yet
+    static ref CONTROLLER_FUNCTIONS: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function nodeRegister(bytes dkgPublicKey) external",
+            "function commitDkg(uint256 groupIndex, uint256 groupEpoch, bytes publicKey, bytes partialPublicKey, address[] disqualifiedNodes, bytes dkgTranscript) external",
+            "function postProcessDkg(uint256 groupIndex, uint256 groupEpoch) external",
+        ])
+        .unwrap()
+    );
+}
+
+// the event emitted by Controller.sol to let the members of a group start the DKG
+#[derive(Clone, Debug, EthEvent)]
+#[ethevent(name = "DkgTask")]
+pub struct DkgTaskEvent {
+    #[ethevent(indexed)]
+    pub group_index: U256,
+    pub group_epoch: U256,
+    pub size: U256,
+    pub threshold: U256,
+    pub members: Vec<Address>,
+    pub assignment_block_height: U256,
+    pub coordinator_address: Address,
+    pub dkg_protocol: U256,
+}
+
+impl From<DkgTaskEvent> for DKGTask {
+    fn from(event: DkgTaskEvent) -> Self {
+        DKGTask {
+            group_index: event.group_index.as_usize(),
+            epoch: event.group_epoch.as_usize(),
+            size: event.size.as_usize(),
+            threshold: event.threshold.as_usize(),
+            // the member index is the position in the group
+            members: event
+                .members
+                .into_iter()
+                .enumerate()
+                .map(|(index, member)| (member, index))
+                .collect(),
+            assignment_block_height: event.assignment_block_height.as_usize(),
+            coordinator_address: event.coordinator_address,
+            dkg_protocol: event.dkg_protocol.as_u32().into(),
+        }
+    }
+}
+
 pub struct ControllerClient {
     controller_address: Address,
-    signer: Arc<WalletSigner>,
+    transaction_manager: Arc<TransactionManager>,
 }
 
 impl ControllerClient {
     pub fn new(controller_address: Address, identity: &GeneralChainIdentity) -> Self {
-        let provider = Provider::<Http>::try_from(identity.get_provider_rpc_endpoint())
-            .unwrap()
-            .interval(Duration::from_millis(10u64));
-
-        // instantiate the client with the wallet
-        let signer = Arc::new(SignerMiddleware::new(
-            provider,
-            identity
-                .get_signer()
-                .clone()
-                .with_chain_id(identity.get_chain_id() as u32),
-        ));
+        let transaction_manager = TransactionManager::get_or_build(identity);
 
         ControllerClient {
             controller_address,
-            signer,
+            transaction_manager,
         }
     }
 }
@@ -60,19 +103,36 @@ type ControllerContract = Controller<WalletSigner>;
 #[async_trait]
 impl ServiceClient<ControllerContract> for ControllerClient {
     async fn prepare_service_client(&self) -> ContractClientResult<ControllerContract> {
-        let controller_contract = Controller::new(self.controller_address, self.signer.clone());
+        let controller_contract =
+            Controller::new(self.controller_address, self.transaction_manager.signer());
 
         Ok(controller_contract)
     }
 }
 
-#[allow(unused_variables)]
 #[async_trait]
 impl ControllerTransactions for ControllerClient {
     async fn node_register(&self, id_public_key: Vec<u8>) -> ContractClientResult<()> {
+        self.transaction_manager
+            .send_call(
+                &CONTROLLER_FUNCTIONS,
+                self.controller_address,
+                "nodeRegister",
+                Bytes::from(id_public_key),
+            )
+            .await?;
+
         Ok(())
     }
 
+    async fn node_activate(&self) -> ContractClientResult<()> {
+        Err(ContractClientError::UnsupportedTransaction("node_activate"))
+    }
+
+    async fn node_quit(&self) -> ContractClientResult<()> {
+        Err(ContractClientError::UnsupportedTransaction("node_quit"))
+    }
+
     async fn commit_dkg(
         &self,
         group_index: usize,
@@ -80,7 +140,24 @@ impl ControllerTransactions for ControllerClient {
         public_key: Vec<u8>,
         partial_public_key: Vec<u8>,
         disqualified_nodes: Vec<Address>,
+        dkg_transcript: Vec<u8>,
     ) -> ContractClientResult<()> {
+        self.transaction_manager
+            .send_call(
+                &CONTROLLER_FUNCTIONS,
+                self.controller_address,
+                "commitDkg",
+                (
+                    U256::from(group_index),
+                    U256::from(group_epoch),
+                    Bytes::from(public_key),
+                    Bytes::from(partial_public_key),
+                    disqualified_nodes,
+                    Bytes::from(dkg_transcript),
+                ),
+            )
+            .await?;
+
         Ok(())
     }
 
@@ -89,6 +166,15 @@ impl ControllerTransactions for ControllerClient {
         group_index: usize,
         group_epoch: usize,
     ) -> ContractClientResult<()> {
+        self.transaction_manager
+            .send_call(
+                &CONTROLLER_FUNCTIONS,
+                self.controller_address,
+                "postProcessDkg",
+                (U256::from(group_index), U256::from(group_epoch)),
+            )
+            .await?;
+
         Ok(())
     }
 }
@@ -108,21 +194,32 @@ impl ControllerLogs for ControllerClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
-        let dkg_task_filter =
-            Filter::new()
-                .from_block(BlockNumber::Latest)
-                .topic0(ValueOrArray::Value(H256::from(keccak256(
-                    "DkgTask(address,address,uint256)",
-                ))));
-        let mut stream = self.signer.watch(&dkg_task_filter).await.map_err(|e| {
-            let e: ContractClientError = e.into();
-            e
-        })?;
-        while let Some(log) = stream.next().await {
-            cb(log.into()).await?;
-        }
+        let dkg_task_filter = Filter::new()
+            .address(self.controller_address)
+            .topic0(ValueOrArray::Value(DkgTaskEvent::signature()));
+
+        watch_logs(
+            &self.transaction_manager.signer(),
+            dkg_task_filter,
+            from_block,
+            |log| {
+                let task = parse_log::<DkgTaskEvent>(log)
+                    .map_err(AbiError::from)
+                    .map(|event| cb(event.into()));
+
+                async move {
+                    match task {
+                        Ok(task) => task.await,
+                        Err(e) => Err(e.into()),
+                    }
+                }
+            },
+        )
+        .await?;
+
         Err(ContractClientError::FetchingDkgTaskError)
     }
 
@@ -131,25 +228,60 @@ impl ControllerLogs for ControllerClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
-        let group_relay_task_filter =
-            Filter::new()
-                .from_block(BlockNumber::Latest)
-                .topic0(ValueOrArray::Value(H256::from(keccak256(
-                    "GroupRelayTask(address,address,uint256)",
-                ))));
-        let mut stream = self
-            .signer
-            .watch(&group_relay_task_filter)
-            .await
-            .map_err(|e| {
-                let e: ContractClientError = e.into();
-                e
-            })?;
-        while let Some(log) = stream.next().await {
-            cb(log.into()).await?;
-        }
+        let group_relay_task_filter = Filter::new().topic0(ValueOrArray::Value(H256::from(
+            keccak256("GroupRelayTask(address,address,uint256)"),
+        )));
+
+        watch_logs(
+            &self.transaction_manager.signer(),
+            group_relay_task_filter,
+            from_block,
+            |log| cb(log.into()),
+        )
+        .await?;
+
         Err(ContractClientError::FetchingGroupRelayTaskError)
     }
 }
+
+#[cfg(test)]
+pub mod controller_tests {
+    use super::DkgTaskEvent;
+    use arpa_node_core::{DKGProtocol, DKGTask};
+    use ethers::{abi::Token, contract::parse_log, prelude::*};
+
+    #[test]
+    fn test_parse_dkg_task_log() {
+        let members = vec![Address::random(), Address::random(), Address::random()];
+        let coordinator_address = Address::random();
+
+        let log = Log {
+            topics: vec![DkgTaskEvent::signature(), H256::from_low_u64_be(1)],
+            data: ethers::abi::encode(&[
+                Token::Uint(U256::from(2)),
+                Token::Uint(U256::from(3)),
+                Token::Uint(U256::from(3)),
+                Token::Array(members.iter().map(|m| Token::Address(*m)).collect()),
+                Token::Uint(U256::from(100)),
+                Token::Address(coordinator_address),
+                Token::Uint(U256::from(1)),
+            ])
+            .into(),
+            ..Default::default()
+        };
+
+        let task: DKGTask = parse_log::<DkgTaskEvent>(log).unwrap().into();
+
+        assert_eq!(task.group_index, 1);
+        assert_eq!(task.epoch, 2);
+        assert_eq!(task.size, 3);
+        assert_eq!(task.threshold, 3);
+        assert_eq!(task.members.get(&members[2]), Some(&2));
+        assert_eq!(task.assignment_block_height, 100);
+        assert_eq!(task.coordinator_address, coordinator_address);
+        assert!(matches!(task.dkg_protocol, DKGProtocol::Gennaro));
+    }
+}
@@ -7,16 +7,16 @@ use crate::{
 };
 
 use self::coordinator_stub::Coordinator;
-use super::WalletSigner;
-use arpa_node_core::{ChainIdentity, GeneralChainIdentity};
+use super::{transaction::TransactionManager, WalletSigner};
+use arpa_node_core::GeneralChainIdentity;
 use async_trait::async_trait;
 use dkg_core::{
-    primitives::{BundledJustification, BundledResponses, BundledShares},
+    primitives::{BundledExtraction, BundledJustification, BundledResponses, BundledShares},
     BoardPublisher,
 };
 use ethers::prelude::*;
 use log::info;
-use std::{convert::TryFrom, sync::Arc, time::Duration};
+use std::sync::Arc;
 use threshold_bls::curve::bls12381::Curve;
 
 #[allow(clippy::useless_conversion)]
@@ -26,27 +26,16 @@ pub mod coordinator_stub {
 
 pub struct CoordinatorClient {
     coordinator_address: Address,
-    signer: Arc<WalletSigner>,
+    transaction_manager: Arc<TransactionManager>,
 }
 
 impl CoordinatorClient {
     pub fn new(coordinator_address: Address, identity: &GeneralChainIdentity) -> Self {
-        let provider = Provider::<Http>::try_from(identity.get_provider_rpc_endpoint())
-            .unwrap()
-            .interval(Duration::from_millis(10u64));
-
-        // instantiate the client with the wallet
-        let signer = Arc::new(SignerMiddleware::new(
-            provider,
-            identity
-                .get_signer()
-                .clone()
-                .with_chain_id(identity.get_chain_id() as u32),
-        ));
+        let transaction_manager = TransactionManager::get_or_build(identity);
 
         CoordinatorClient {
             coordinator_address,
-            signer,
+            transaction_manager,
         }
     }
 }
@@ -64,7 +53,8 @@ type CoordinatorContract = Coordinator<WalletSigner>;
 #[async_trait]
 impl ServiceClient<CoordinatorContract> for CoordinatorClient {
     async fn prepare_service_client(&self) -> ContractClientResult<CoordinatorContract> {
-        let coordinator_contract = Coordinator::new(self.coordinator_address, self.signer.clone());
+        let coordinator_contract =
+            Coordinator::new(self.coordinator_address, self.transaction_manager.signer());
 
         Ok(coordinator_contract)
     }
@@ -76,19 +66,20 @@ impl CoordinatorTransactions for CoordinatorClient {
         let coordinator_contract =
             ServiceClient::<CoordinatorContract>::prepare_service_client(self).await?;
 
-        coordinator_contract
-            .publish(value.into())
-            .send()
-            .await
-            .map_err(|e| {
-                let e: ContractClientError = e.into();
-                e
-            })?
-            .await
-            .map_err(|e| {
-                let e: ContractClientError = e.into();
-                e
-            })?;
+        let call = coordinator_contract.publish(value.into());
+
+        self.transaction_manager.send_transaction(call.tx).await?;
+
+        Ok(())
+    }
+
+    async fn publish_dealing(&self, value: Vec<u8>) -> ContractClientResult<()> {
+        let coordinator_contract =
+            ServiceClient::<CoordinatorContract>::prepare_service_client(self).await?;
+
+        let call = coordinator_contract.publish_dealing(value.into());
+
+        self.transaction_manager.send_transaction(call.tx).await?;
 
         Ok(())
     }
@@ -147,6 +138,23 @@ impl CoordinatorViews for CoordinatorClient {
         Ok(res)
     }
 
+    async fn get_extractions(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+        let coordinator_contract =
+            ServiceClient::<CoordinatorContract>::prepare_service_client(self).await?;
+
+        let res = coordinator_contract
+            .get_extractions()
+            .call()
+            .await
+            .map(|r| r.iter().map(|b| b.to_vec()).collect::<Vec<Vec<u8>>>())
+            .map_err(|e| {
+                let e: ContractClientError = e.into();
+                e
+            })?;
+
+        Ok(res)
+    }
+
     async fn get_participants(&self) -> ContractClientResult<Vec<Address>> {
         let coordinator_contract =
             ServiceClient::<CoordinatorContract>::prepare_service_client(self).await?;
@@ -208,7 +216,10 @@ impl BoardPublisher<Curve> for CoordinatorClient {
         self.publish(serialized).await.map_err(|e| e.into())
     }
 
-    async fn publish_responses(&mut self, responses: BundledResponses) -> Result<(), Self::Error> {
+    async fn publish_responses(
+        &mut self,
+        responses: BundledResponses<Curve>,
+    ) -> Result<(), Self::Error> {
         info!("called publish_responses");
         let serialized = bincode::serialize(&responses)?;
         self.publish(serialized).await.map_err(|e| e.into())
@@ -221,6 +232,14 @@ impl BoardPublisher<Curve> for CoordinatorClient {
         let serialized = bincode::serialize(&justifications)?;
         self.publish(serialized).await.map_err(|e| e.into())
     }
+
+    async fn publish_extraction(
+        &mut self,
+        extraction: BundledExtraction<Curve>,
+    ) -> Result<(), Self::Error> {
+        let serialized = bincode::serialize(&extraction)?;
+        self.publish(serialized).await.map_err(|e| e.into())
+    }
 }
 
 #[cfg(test)]
@@ -228,7 +247,10 @@ pub mod coordinator_tests {
     use crate::coordinator::CoordinatorTransactions;
 
     use super::{CoordinatorClient, WalletSigner};
-    use arpa_node_core::GeneralChainIdentity;
+    use crate::ethers::transport::Transport;
+    use arpa_node_core::{
+        FailoverConfig, GeneralChainIdentity, NodeSigner, ProviderTransport, TransactionConfig,
+    };
     use ethers::abi::Tokenize;
     use ethers::prelude::*;
     use ethers::signers::coins_bip39::English;
@@ -236,7 +258,7 @@ pub mod coordinator_tests {
     use ethers::utils::AnvilInstance;
     use std::env;
     use std::path::PathBuf;
-    use std::{convert::TryFrom, sync::Arc, time::Duration};
+    use std::{sync::Arc, time::Duration};
     use threshold_bls::schemes::bls12_381::G1Scheme;
 
     include!("../../../../../contract_stub/coordinator.rs");
@@ -261,14 +283,14 @@ pub mod coordinator_tests {
         let wallet: LocalWallet = anvil.keys()[0].clone().into();
 
         // 3. connect to the network
-        let provider = Provider::<Http>::try_from(anvil.endpoint())
-            .unwrap()
-            .interval(Duration::from_millis(10u64));
+        let provider =
+            Provider::new(Transport::new(ProviderTransport::Http, &anvil.endpoint()).unwrap())
+                .interval(Duration::from_millis(10u64));
 
         // 4. instantiate the client with the wallet
         let client = Arc::new(SignerMiddleware::new(
             provider,
-            wallet.with_chain_id(anvil.chain_id()),
+            NodeSigner::from(wallet).with_chain_id(anvil.chain_id()),
         ));
 
         // 5. deploy contract
@@ -309,13 +331,23 @@ pub mod coordinator_tests {
         let public_keys = vec![bincode::serialize(&dkg_public_key).unwrap().into()];
 
         coordinator_contract
-            .initialize(nodes, public_keys)
+            // the Joint-Feldman DKG
+            .initialize(nodes, public_keys, U256::zero())
             .send()
             .await
             .unwrap();
 
-        let main_chain_identity =
-            GeneralChainIdentity::new(0, 0, wallet, anvil.endpoint(), Address::random());
+        let main_chain_identity = GeneralChainIdentity::new(
+            0,
+            anvil.chain_id() as usize,
+            wallet.into(),
+            anvil.endpoint(),
+            ProviderTransport::Http,
+            10,
+            FailoverConfig::default(),
+            TransactionConfig::default(),
+            Address::random(),
+        );
 
         let client = CoordinatorClient::new(coordinator_contract.address(), &main_chain_identity);
 
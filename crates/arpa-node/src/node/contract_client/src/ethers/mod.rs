@@ -2,8 +2,109 @@ pub mod adapter;
 pub mod controller;
 pub mod coordinator;
 pub mod provider;
+pub mod transaction;
+pub mod transport;
 
+use self::transport::Transport;
+use crate::error::{ContractClientError, ContractClientResult};
+use arpa_node_core::{GeneralChainIdentity, NodeSigner};
 use ethers::prelude::*;
-use ethers::providers::Http as HttpProvider;
+use std::{future::Future, pin::Pin, time::Duration};
 
-pub(crate) type WalletSigner = SignerMiddleware<Provider<HttpProvider>, LocalWallet>;
+pub(crate) type WalletSigner = SignerMiddleware<Provider<Transport>, NodeSigner>;
+
+/// Builds the provider of the chain with the transport and endpoints in config, the polling
+/// interval is only used by filters and transactions over http.
+pub(crate) fn build_provider(identity: &GeneralChainIdentity) -> Provider<Transport> {
+    let transport = Transport::with_failover(
+        identity.get_provider_transport(),
+        &identity.get_provider_rpc_endpoints(),
+        identity.get_failover_config(),
+    )
+    .unwrap();
+
+    Provider::new(transport).interval(Duration::from_millis(
+        identity.get_polling_interval_millis(),
+    ))
+}
+
+// blocks queried by every eth_getLogs request when back-filling logs
+pub const LOGS_PAGE_SIZE: usize = 1000;
+
+/// Watches the logs matching the filter, after paging through the ones from `from_block`
+/// to the latest block if it is set.
+pub(crate) async fn watch_logs<
+    C: FnMut(Log) -> F + Send,
+    F: Future<Output = ContractClientResult<()>> + Send,
+>(
+    signer: &WalletSigner,
+    filter: Filter,
+    from_block: Option<usize>,
+    mut cb: C,
+) -> ContractClientResult<()> {
+    // the subscription is made before back-filling so that no log is missed in between,
+    // logs received twice are deduplicated by the listeners
+    let mut stream: Pin<Box<dyn Stream<Item = Log> + Send + '_>> =
+        if signer.provider().as_ref().supports_pubsub() {
+            Box::pin(signer.subscribe_logs(&filter).await.map_err(|e| {
+                let e: ContractClientError = e.into();
+                e
+            })?)
+        } else {
+            Box::pin(
+                signer
+                    .watch(&filter.clone().from_block(BlockNumber::Latest))
+                    .await
+                    .map_err(|e| {
+                        let e: ContractClientError = e.into();
+                        e
+                    })?,
+            )
+        };
+
+    if let Some(from_block) = from_block {
+        let latest_block = signer
+            .get_block_number()
+            .await
+            .map_err(|e| {
+                let e: ContractClientError = e.into();
+                e
+            })?
+            .as_usize();
+
+        let mut page_start = from_block;
+
+        while page_start <= latest_block {
+            let page_end = (page_start + LOGS_PAGE_SIZE - 1).min(latest_block);
+
+            let logs = signer
+                .get_logs(
+                    &filter
+                        .clone()
+                        .from_block(page_start as u64)
+                        .to_block(page_end as u64),
+                )
+                .await
+                .map_err(|e| {
+                    let e: ContractClientError = e.into();
+                    e
+                })?;
+
+            for log in logs {
+                cb(log).await?;
+            }
+
+            page_start = page_end + 1;
+        }
+    }
+
+    while let Some(log) = stream.next().await {
+        // logs of orphaned blocks are rolled back by the block listener
+        if log.removed == Some(true) {
+            continue;
+        }
+        cb(log).await?;
+    }
+
+    Ok(())
+}
@@ -1,26 +1,29 @@
-use arpa_node_core::{ChainIdentity, GeneralChainIdentity};
+use arpa_node_core::{BlockHeader, GeneralChainIdentity};
 use async_trait::async_trait;
 use ethers::prelude::*;
-use ethers::providers::Http as HttpProvider;
-use std::{convert::TryFrom, future::Future, time::Duration};
+use std::future::Future;
 
+use super::{build_provider, transport::Transport};
 use crate::{
     error::{ContractClientError, ContractClientResult},
     provider::{BlockFetcher, ChainProviderBuilder},
 };
 
 pub struct ChainProvider {
-    provider: Provider<HttpProvider>,
+    provider: Provider<Transport>,
 }
 
 impl ChainProvider {
     pub fn new(identity: &GeneralChainIdentity) -> Self {
-        let provider = Provider::<Http>::try_from(identity.get_provider_rpc_endpoint())
-            .unwrap()
-            .interval(Duration::from_millis(10u64));
+        let provider = build_provider(identity);
 
         ChainProvider { provider }
     }
+
+    /// Reads the id of the chain the provider is connected to, which transactions are signed for.
+    pub async fn get_chain_id(&self) -> ContractClientResult<usize> {
+        Ok(self.provider.get_chainid().await?.as_usize())
+    }
 }
 
 impl ChainProviderBuilder for GeneralChainIdentity {
@@ -31,28 +34,52 @@ impl ChainProviderBuilder for GeneralChainIdentity {
     }
 }
 
+fn to_block_header(block: Block<H256>) -> ContractClientResult<BlockHeader> {
+    Ok(BlockHeader {
+        number: block
+            .number
+            .ok_or(ContractClientError::FetchingBlockError)?
+            .as_usize(),
+        hash: block.hash.ok_or(ContractClientError::FetchingBlockError)?,
+        parent_hash: block.parent_hash,
+    })
+}
+
 #[async_trait]
 impl BlockFetcher for ChainProvider {
-    async fn subscribe_new_block_height<
-        C: FnMut(usize) -> F + Send,
+    async fn subscribe_new_block<
+        C: FnMut(BlockHeader) -> F + Send,
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
         mut cb: C,
     ) -> ContractClientResult<()> {
-        let mut stream = self.provider.watch_blocks().await?;
-        while let Some(block_hash) = stream.next().await {
-            let block = self
-                .provider
-                .get_block(block_hash)
-                .await?
-                .ok_or(ContractClientError::FetchingBlockError)?;
-            cb(block
-                .number
-                .ok_or(ContractClientError::FetchingBlockError)?
-                .as_usize())
-            .await?;
+        if self.provider.as_ref().supports_pubsub() {
+            let mut stream = self.provider.subscribe_blocks().await?;
+            while let Some(block) = stream.next().await {
+                cb(to_block_header(block)?).await?;
+            }
+        } else {
+            let mut stream = self.provider.watch_blocks().await?;
+            while let Some(block_hash) = stream.next().await {
+                let block = self
+                    .provider
+                    .get_block(block_hash)
+                    .await?
+                    .ok_or(ContractClientError::FetchingBlockError)?;
+                cb(to_block_header(block)?).await?;
+            }
         }
         Err(ContractClientError::FetchingBlockError)
     }
+
+    async fn get_block_header(&self, block_height: usize) -> ContractClientResult<BlockHeader> {
+        let block = self
+            .provider
+            .get_block(block_height as u64)
+            .await?
+            .ok_or(ContractClientError::FetchingBlockError)?;
+
+        to_block_header(block)
+    }
 }
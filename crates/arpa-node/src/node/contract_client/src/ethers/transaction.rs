@@ -0,0 +1,542 @@
+use super::{build_provider, WalletSigner};
+use crate::error::{ContractClientError, ContractClientResult};
+use arpa_node_core::{ChainIdentity, GeneralChainIdentity, TransactionConfig};
+use ethers::{
+    abi::Tokenize, contract::BaseContract, prelude::*,
+    types::transaction::eip2718::TypedTransaction,
+};
+use lazy_static::lazy_static;
+use log::{info, warn};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+use tokio::sync::Mutex;
+
+lazy_static! {
+    // managers are shared by all the clients sending transactions from the same account,
+    // keyed by the chain id read from the provider and the account
+    static ref TRANSACTION_MANAGERS: std::sync::Mutex<HashMap<(usize, Address), Arc<TransactionManager>>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+const GWEI: u64 = 1_000_000_000;
+
+// gas of a plain transfer, which cancels a pending transaction
+const TRANSFER_GAS: u64 = 21_000;
+
+/// Nonces of an account assigned locally. A nonce given back by a transaction which never made it
+/// to the pool is assigned again before any new one, so that it leaves no gap.
+#[derive(Debug, Default)]
+struct Nonces {
+    // next new nonce, fetched from the pending transaction count when unknown
+    next: Option<U256>,
+    released: BTreeSet<U256>,
+}
+
+impl Nonces {
+    fn take(&mut self) -> Option<U256> {
+        if let Some(nonce) = self.released.pop_first() {
+            return Some(nonce);
+        }
+
+        let nonce = self.next?;
+        self.next = Some(nonce + 1);
+
+        Some(nonce)
+    }
+
+    fn init(&mut self, pending_transaction_count: U256) {
+        self.next.get_or_insert(pending_transaction_count);
+    }
+
+    fn release(&mut self, nonce: U256) {
+        if self.next.is_some_and(|next| nonce < next) {
+            self.released.insert(nonce);
+        }
+    }
+
+    /// Skips the nonces already used on chain, by transactions sent out of the manager.
+    fn skip_to(&mut self, transaction_count: U256) {
+        self.released = self.released.split_off(&transaction_count);
+
+        if let Some(next) = self.next.as_mut() {
+            *next = (*next).max(transaction_count);
+        }
+    }
+}
+
+/// Sends the transactions of an account on a chain. Nonces are assigned locally so that
+/// concurrent transactions don't collide, fees are estimated by EIP-1559 and capped by
+/// config, and a transaction still pending after some blocks is replaced with bumped fees.
+pub struct TransactionManager {
+    signer: Arc<WalletSigner>,
+    config: TransactionConfig,
+    nonces: Mutex<Nonces>,
+}
+
+impl TransactionManager {
+    pub fn new(identity: &GeneralChainIdentity) -> Self {
+        let provider = build_provider(identity);
+
+        // instantiate the client with the wallet, signing for the chain id read from the provider
+        let signer = Arc::new(SignerMiddleware::new(
+            provider,
+            identity
+                .get_signer()
+                .clone()
+                .with_chain_id(identity.get_chain_id() as u32),
+        ));
+
+        TransactionManager {
+            signer,
+            config: identity.get_transaction_config(),
+            nonces: Mutex::new(Nonces::default()),
+        }
+    }
+
+    /// Returns the manager of the account of the identity, which is built on first use.
+    pub fn get_or_build(identity: &GeneralChainIdentity) -> Arc<Self> {
+        TRANSACTION_MANAGERS
+            .lock()
+            .unwrap()
+            .entry((identity.get_chain_id(), identity.get_id_address()))
+            .or_insert_with(|| Arc::new(TransactionManager::new(identity)))
+            .clone()
+    }
+
+    pub fn signer(&self) -> Arc<WalletSigner> {
+        self.signer.clone()
+    }
+
+    /// Sends the transaction with the next nonce of the account and waits for its receipt,
+    /// resubmitting it with bumped fees while it is pending.
+    pub async fn send_transaction(
+        &self,
+        mut tx: TypedTransaction,
+    ) -> ContractClientResult<TransactionReceipt> {
+        tx.set_from(self.signer.address());
+
+        let gas = self.signer.estimate_gas(&tx, None).await.map_err(|e| {
+            let e: ContractClientError = e.into();
+            e
+        })?;
+        tx.set_gas(gas);
+
+        let (mut max_fee_per_gas, mut max_priority_fee_per_gas) = self.estimate_fees().await?;
+        set_fees(&mut tx, max_fee_per_gas, max_priority_fee_per_gas);
+
+        let nonce = self.next_nonce().await?;
+        tx.set_nonce(nonce);
+
+        let mut tx_hashes = vec![];
+
+        match self.submit(&tx).await {
+            Ok(tx_hash) => tx_hashes.push(tx_hash),
+            Err(e) => {
+                // other transactions may hold the next nonces already, so the nonce is given
+                // back instead of fetching the nonces again
+                self.release_nonce(nonce).await;
+                return Err(e);
+            }
+        }
+
+        let first_submitted_block = self.get_block_number().await?;
+        let mut last_submitted_block = first_submitted_block;
+
+        loop {
+            tokio::time::sleep(self.signer.provider().get_interval()).await;
+
+            // any of the submitted transactions can be mined since they share the nonce
+            for tx_hash in tx_hashes.iter() {
+                let receipt = self
+                    .signer
+                    .get_transaction_receipt(*tx_hash)
+                    .await
+                    .map_err(|e| {
+                        let e: ContractClientError = e.into();
+                        e
+                    })?;
+
+                if let Some(receipt) = receipt {
+                    if receipt.status == Some(U64::zero()) {
+                        return Err(ContractClientError::TransactionRevertedError(*tx_hash));
+                    }
+
+                    info!(
+                        "transaction {:?} is mined in block {:?}",
+                        tx_hash, receipt.block_number
+                    );
+
+                    return Ok(receipt);
+                }
+            }
+
+            let block_height = self.get_block_number().await?;
+
+            if block_height >= first_submitted_block + self.config.max_pending_blocks {
+                self.cancel(
+                    &tx,
+                    nonce,
+                    &tx_hashes,
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                )
+                .await;
+
+                return Err(ContractClientError::TransactionTimeoutError(
+                    *tx_hashes.last().unwrap(),
+                ));
+            }
+
+            if block_height < last_submitted_block + self.config.gas_bump_blocks {
+                continue;
+            }
+
+            let (bumped_max_fee_per_gas, bumped_max_priority_fee_per_gas) =
+                self.bump_fees(max_fee_per_gas, max_priority_fee_per_gas);
+
+            // fees have reached the caps, keep waiting for the pending transaction
+            if bumped_max_fee_per_gas == max_fee_per_gas
+                && bumped_max_priority_fee_per_gas == max_priority_fee_per_gas
+            {
+                continue;
+            }
+
+            set_fees(
+                &mut tx,
+                bumped_max_fee_per_gas,
+                bumped_max_priority_fee_per_gas,
+            );
+
+            match self.submit(&tx).await {
+                Ok(tx_hash) => {
+                    warn!(
+                        "transaction {:?} is pending after {} blocks, replaced by {:?} with max fee per gas {}",
+                        tx_hashes.last().unwrap(),
+                        block_height - last_submitted_block,
+                        tx_hash,
+                        bumped_max_fee_per_gas
+                    );
+
+                    tx_hashes.push(tx_hash);
+                    max_fee_per_gas = bumped_max_fee_per_gas;
+                    max_priority_fee_per_gas = bumped_max_priority_fee_per_gas;
+                }
+                // the pending transaction may have been mined in between
+                Err(e) => warn!("failed to replace transaction, Error: {:?}", e),
+            }
+
+            last_submitted_block = block_height;
+        }
+    }
+
+    /// Sends a transaction calling the function of the contract at the address with the args, for
+    /// the contracts whose ABI is not generated yet.
+    pub async fn send_call<T: Tokenize>(
+        &self,
+        contract: &BaseContract,
+        address: Address,
+        function: &str,
+        args: T,
+    ) -> ContractClientResult<TransactionReceipt> {
+        let data = contract.encode(function, args)?;
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(address)
+            .data(data)
+            .into();
+
+        self.send_transaction(tx).await
+    }
+
+    /// Frees the nonce of a transaction which timed out, as the later transactions of the account
+    /// can't be mined before it. The transaction is replaced by an empty transfer if it is still in
+    /// the pool, and the nonce is given back to the next transaction if it has been dropped.
+    async fn cancel(
+        &self,
+        tx: &TypedTransaction,
+        nonce: U256,
+        tx_hashes: &[H256],
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    ) {
+        let mut is_pending = false;
+
+        for tx_hash in tx_hashes.iter() {
+            match self.signer.get_transaction(*tx_hash).await {
+                Ok(Some(_)) => is_pending = true,
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("failed to fetch transaction {:?}, Error: {:?}", tx_hash, e);
+                    is_pending = true;
+                }
+            }
+        }
+
+        if !is_pending {
+            self.release_nonce(nonce).await;
+            return;
+        }
+
+        let mut cancellation = tx.clone();
+        cancellation.set_to(self.signer.address());
+        cancellation.set_value(U256::zero());
+        cancellation.set_data(Bytes::default());
+        cancellation.set_gas(TRANSFER_GAS);
+
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            self.bump_fees(max_fee_per_gas, max_priority_fee_per_gas);
+        set_fees(&mut cancellation, max_fee_per_gas, max_priority_fee_per_gas);
+
+        match self.submit(&cancellation).await {
+            Ok(tx_hash) => warn!(
+                "transaction {:?} is pending after {} blocks, cancelled by {:?}",
+                tx_hashes.last().unwrap(),
+                self.config.max_pending_blocks,
+                tx_hash
+            ),
+            // the pending transaction may have been mined in between
+            Err(e) => warn!(
+                "failed to cancel transaction {:?}, Error: {:?}",
+                tx_hashes.last().unwrap(),
+                e
+            ),
+        }
+    }
+
+    async fn submit(&self, tx: &TypedTransaction) -> ContractClientResult<H256> {
+        let pending_tx = self
+            .signer
+            .send_transaction(tx.clone(), None)
+            .await
+            .map_err(|e| {
+                let e: ContractClientError = e.into();
+                e
+            })?;
+
+        Ok(pending_tx.tx_hash())
+    }
+
+    async fn next_nonce(&self) -> ContractClientResult<U256> {
+        let mut nonces = self.nonces.lock().await;
+
+        if let Some(nonce) = nonces.take() {
+            return Ok(nonce);
+        }
+
+        let pending_transaction_count = self.get_transaction_count(BlockNumber::Pending).await?;
+        nonces.init(pending_transaction_count);
+
+        Ok(nonces.take().unwrap())
+    }
+
+    // a nonce used on chain in the meantime is never given back, and the ones before it are
+    // skipped as well
+    async fn release_nonce(&self, nonce: U256) {
+        match self.get_transaction_count(BlockNumber::Latest).await {
+            Ok(transaction_count) if nonce < transaction_count => {
+                self.nonces.lock().await.skip_to(transaction_count)
+            }
+            Ok(_) => self.nonces.lock().await.release(nonce),
+            Err(e) => {
+                warn!("failed to fetch transaction count, Error: {:?}", e);
+                self.nonces.lock().await.release(nonce)
+            }
+        }
+    }
+
+    async fn get_transaction_count(&self, block: BlockNumber) -> ContractClientResult<U256> {
+        let transaction_count = self
+            .signer
+            .get_transaction_count(self.signer.address(), Some(block.into()))
+            .await
+            .map_err(|e| {
+                let e: ContractClientError = e.into();
+                e
+            })?;
+
+        Ok(transaction_count)
+    }
+
+    async fn get_block_number(&self) -> ContractClientResult<usize> {
+        let block_number = self.signer.get_block_number().await.map_err(|e| {
+            let e: ContractClientError = e.into();
+            e
+        })?;
+
+        Ok(block_number.as_usize())
+    }
+
+    async fn estimate_fees(&self) -> ContractClientResult<(U256, U256)> {
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            self.signer.estimate_eip1559_fees(None).await.map_err(|e| {
+                let e: ContractClientError = e.into();
+                e
+            })?;
+
+        Ok(self.cap_fees(max_fee_per_gas, max_priority_fee_per_gas))
+    }
+
+    fn bump_fees(&self, max_fee_per_gas: U256, max_priority_fee_per_gas: U256) -> (U256, U256) {
+        let bump = |fee: U256| fee * (100 + self.config.gas_bump_percent) / 100 + 1;
+
+        self.cap_fees(bump(max_fee_per_gas), bump(max_priority_fee_per_gas))
+    }
+
+    fn cap_fees(&self, max_fee_per_gas: U256, max_priority_fee_per_gas: U256) -> (U256, U256) {
+        let max_fee_per_gas =
+            max_fee_per_gas.min(U256::from(self.config.max_fee_per_gas_gwei) * GWEI);
+
+        let max_priority_fee_per_gas = max_priority_fee_per_gas
+            .min(U256::from(self.config.max_priority_fee_per_gas_gwei) * GWEI)
+            .min(max_fee_per_gas);
+
+        (max_fee_per_gas, max_priority_fee_per_gas)
+    }
+}
+
+fn set_fees(tx: &mut TypedTransaction, max_fee_per_gas: U256, max_priority_fee_per_gas: U256) {
+    match tx {
+        TypedTransaction::Eip1559(inner) => {
+            inner.max_fee_per_gas = Some(max_fee_per_gas);
+            inner.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        }
+        // legacy transactions pay the max fee
+        _ => {
+            tx.set_gas_price(max_fee_per_gas);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod transaction_tests {
+    use super::{Nonces, TransactionManager};
+    use crate::ethers::provider::ChainProvider;
+    use arpa_node_core::{
+        FailoverConfig, GeneralChainIdentity, ProviderTransport, TransactionConfig,
+    };
+    use ethers::prelude::*;
+    use ethers::types::transaction::eip2718::TypedTransaction;
+    use ethers::utils::Anvil;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_released_nonces_are_reused_first() {
+        let mut nonces = Nonces::default();
+
+        assert_eq!(nonces.take(), None);
+
+        nonces.init(U256::from(3));
+
+        let taken = (0..4).map(|_| nonces.take().unwrap()).collect::<Vec<_>>();
+        assert_eq!(taken, (3..7u64).map(U256::from).collect::<Vec<_>>());
+
+        // the nonces held by other transactions stay taken
+        nonces.release(U256::from(5));
+        nonces.release(U256::from(4));
+        nonces.release(U256::from(9));
+
+        assert_eq!(nonces.take(), Some(U256::from(4)));
+        assert_eq!(nonces.take(), Some(U256::from(5)));
+        assert_eq!(nonces.take(), Some(U256::from(7)));
+
+        nonces.release(U256::from(6));
+        nonces.skip_to(U256::from(10));
+
+        assert_eq!(nonces.take(), Some(U256::from(10)));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_transactions_with_local_nonces() {
+        let anvil = Anvil::new().spawn();
+
+        let wallet: LocalWallet = anvil.keys()[0].clone().into();
+
+        let identity = GeneralChainIdentity::new(
+            0,
+            anvil.chain_id() as usize,
+            wallet.into(),
+            anvil.endpoint(),
+            ProviderTransport::Http,
+            10,
+            FailoverConfig::default(),
+            TransactionConfig::default(),
+            Address::random(),
+        );
+
+        let transaction_manager = Arc::new(TransactionManager::new(&identity));
+
+        let handles = (0..5)
+            .map(|_| {
+                let transaction_manager = transaction_manager.clone();
+                tokio::spawn(async move {
+                    let tx: TypedTransaction = Eip1559TransactionRequest::new()
+                        .to(Address::random())
+                        .value(1u64)
+                        .into();
+                    transaction_manager.send_transaction(tx).await
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut nonces = vec![];
+
+        for handle in handles {
+            let receipt = handle.await.unwrap().unwrap();
+
+            let tx = transaction_manager
+                .signer()
+                .get_transaction(receipt.transaction_hash)
+                .await
+                .unwrap()
+                .unwrap();
+
+            nonces.push(tx.nonce);
+        }
+
+        nonces.sort();
+
+        assert_eq!(nonces, (0..5u64).map(U256::from).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_signs_for_chain_id_read_from_provider() {
+        let anvil = Anvil::new().spawn();
+
+        let wallet: LocalWallet = anvil.keys()[0].clone().into();
+
+        // the main chain identity is configured with the id 0
+        let mut identity = GeneralChainIdentity::new(
+            0,
+            0,
+            wallet.into(),
+            anvil.endpoint(),
+            ProviderTransport::Http,
+            10,
+            FailoverConfig::default(),
+            TransactionConfig::default(),
+            Address::random(),
+        );
+
+        let chain_id = ChainProvider::new(&identity).get_chain_id().await.unwrap();
+        assert_eq!(chain_id, anvil.chain_id() as usize);
+        identity.set_chain_id(chain_id);
+
+        let transaction_manager = TransactionManager::get_or_build(&identity);
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::random())
+            .value(1u64)
+            .into();
+        let receipt = transaction_manager.send_transaction(tx).await.unwrap();
+
+        let tx = transaction_manager
+            .signer()
+            .get_transaction(receipt.transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.chain_id, Some(U256::from(anvil.chain_id())));
+    }
+}
@@ -0,0 +1,434 @@
+use arpa_node_core::{FailoverConfig, FailoverStrategy, ProviderTransport};
+use async_trait::async_trait;
+use ethers::providers::{
+    Http, HttpClientError, Ipc, IpcError, JsonRpcClient, ProviderError, PubsubClient, Ws,
+    WsClientError,
+};
+use ethers::types::{U256, U64};
+use log::{info, warn};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TransportError {
+    #[error(transparent)]
+    HttpError(#[from] HttpClientError),
+    #[error(transparent)]
+    WsError(#[from] WsClientError),
+    #[error(transparent)]
+    IpcError(#[from] IpcError),
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("invalid provider endpoint: {0}")]
+    InvalidEndpoint(String),
+    #[error("subscriptions are not supported over http, please use ws or ipc")]
+    PubsubNotSupported,
+    #[error("the provider is not connected yet")]
+    NotConnected,
+}
+
+impl TransportError {
+    // errors returned by the provider itself mean neither the connection is broken nor the endpoint is down
+    fn is_connection_error(&self) -> bool {
+        !matches!(
+            self,
+            TransportError::HttpError(HttpClientError::JsonRpcError(_))
+                | TransportError::WsError(WsClientError::JsonRpcError(_))
+                | TransportError::IpcError(IpcError::JsonRpcError(_))
+        )
+    }
+}
+
+impl From<TransportError> for ProviderError {
+    fn from(e: TransportError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(e))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Connection {
+    Ws(Ws),
+    Ipc(Ipc),
+}
+
+/// WebSocket or IPC connection which is established on first use and re-established
+/// by the next request once it is broken.
+#[derive(Debug, Clone)]
+pub struct ReconnectingPubsub {
+    transport: ProviderTransport,
+    endpoint: String,
+    connection: Arc<Mutex<Option<Connection>>>,
+}
+
+impl ReconnectingPubsub {
+    fn current_connection(&self) -> Result<Connection, TransportError> {
+        self.connection
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or(TransportError::NotConnected)
+    }
+
+    async fn connect(&self) -> Result<Connection, TransportError> {
+        if let Ok(connection) = self.current_connection() {
+            return Ok(connection);
+        }
+
+        let connection = match self.transport {
+            ProviderTransport::Ipc => Connection::Ipc(Ipc::connect(&self.endpoint).await?),
+            _ => Connection::Ws(Ws::connect(self.endpoint.as_str()).await?),
+        };
+
+        info!("connected to provider {}", self.endpoint);
+
+        *self.connection.lock().unwrap() = Some(connection.clone());
+
+        Ok(connection)
+    }
+
+    async fn request<T: Debug + Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: T,
+    ) -> Result<R, TransportError> {
+        let res = match self.connect().await? {
+            Connection::Ws(ws) => ws
+                .request(method, params)
+                .await
+                .map_err(TransportError::from),
+            Connection::Ipc(ipc) => ipc
+                .request(method, params)
+                .await
+                .map_err(TransportError::from),
+        };
+
+        if let Err(e) = &res {
+            if e.is_connection_error() {
+                warn!(
+                    "connection to provider {} is broken, reconnecting on next request. Error: {:?}",
+                    self.endpoint, e
+                );
+
+                self.connection.lock().unwrap().take();
+            }
+        }
+
+        res
+    }
+
+    fn subscribe(
+        &self,
+        id: U256,
+    ) -> Result<<Ws as PubsubClient>::NotificationStream, TransportError> {
+        match self.current_connection()? {
+            Connection::Ws(ws) => Ok(ws.subscribe(id)?),
+            Connection::Ipc(ipc) => Ok(ipc.subscribe(id)?),
+        }
+    }
+
+    fn unsubscribe(&self, id: U256) -> Result<(), TransportError> {
+        match self.current_connection()? {
+            Connection::Ws(ws) => Ok(ws.unsubscribe(id)?),
+            Connection::Ipc(ipc) => Ok(ipc.unsubscribe(id)?),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum EndpointClient {
+    Http(Http),
+    Pubsub(ReconnectingPubsub),
+}
+
+impl EndpointClient {
+    fn new(transport: ProviderTransport, endpoint: &str) -> Result<Self, TransportError> {
+        match transport {
+            ProviderTransport::Http => Http::from_str(endpoint)
+                .map(EndpointClient::Http)
+                .map_err(|_| TransportError::InvalidEndpoint(endpoint.to_string())),
+            ProviderTransport::Ws | ProviderTransport::Ipc => {
+                Ok(EndpointClient::Pubsub(ReconnectingPubsub {
+                    transport,
+                    endpoint: endpoint.to_string(),
+                    connection: Arc::new(Mutex::new(None)),
+                }))
+            }
+        }
+    }
+
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &Value,
+    ) -> Result<R, TransportError> {
+        match self {
+            EndpointClient::Http(http) => Ok(http.request(method, params).await?),
+            EndpointClient::Pubsub(pubsub) => pubsub.request(method, params).await,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    client: EndpointClient,
+    // the endpoint is only used as a last resort until then after failing to respond
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl Endpoint {
+    fn is_unhealthy(&self) -> bool {
+        self.unhealthy_until.lock().unwrap().is_some()
+    }
+
+    fn is_cooling_down(&self) -> bool {
+        matches!(*self.unhealthy_until.lock().unwrap(), Some(until) if Instant::now() < until)
+    }
+}
+
+/// JSON-RPC transport of a chain selected by `ProviderTransport`, over the primary and fallback
+/// endpoints of the chain. Requests fail over to the next healthy endpoint by `FailoverStrategy`
+/// when an endpoint doesn't respond.
+#[derive(Debug, Clone)]
+pub struct Transport {
+    transport: ProviderTransport,
+    endpoints: Arc<Vec<Endpoint>>,
+    strategy: FailoverStrategy,
+    unhealthy_cooldown: Duration,
+    next_endpoint: Arc<AtomicUsize>,
+    // notifications of a subscription come from the endpoint it is made on
+    subscriptions: Arc<Mutex<HashMap<U256, usize>>>,
+}
+
+impl Transport {
+    pub fn new(transport: ProviderTransport, endpoint: &str) -> Result<Self, TransportError> {
+        Transport::with_failover(transport, &[endpoint], &FailoverConfig::default())
+    }
+
+    pub fn with_failover(
+        transport: ProviderTransport,
+        endpoints: &[&str],
+        failover_config: &FailoverConfig,
+    ) -> Result<Self, TransportError> {
+        let endpoints = endpoints
+            .iter()
+            .map(|url| {
+                Ok(Endpoint {
+                    url: url.to_string(),
+                    client: EndpointClient::new(transport, url)?,
+                    unhealthy_until: Mutex::new(None),
+                })
+            })
+            .collect::<Result<Vec<_>, TransportError>>()?;
+
+        Ok(Transport {
+            transport,
+            endpoints: Arc::new(endpoints),
+            strategy: failover_config.strategy,
+            unhealthy_cooldown: Duration::from_millis(failover_config.unhealthy_cooldown_millis),
+            next_endpoint: Arc::new(AtomicUsize::new(0)),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    pub fn supports_pubsub(&self) -> bool {
+        self.transport != ProviderTransport::Http
+    }
+
+    // endpoints in the order of the strategy, the ones cooling down are moved to the end
+    fn ordered_endpoints(&self) -> Vec<usize> {
+        let len = self.endpoints.len();
+
+        let start = match self.strategy {
+            FailoverStrategy::Priority => 0,
+            FailoverStrategy::RoundRobin => {
+                self.next_endpoint.fetch_add(1, Ordering::Relaxed) % len
+            }
+        };
+
+        let mut order = (0..len).map(|i| (start + i) % len).collect::<Vec<_>>();
+
+        order.sort_by_key(|i| self.endpoints[*i].is_cooling_down());
+
+        order
+    }
+
+    // an unhealthy endpoint is probed once its cooldown elapses before requests are routed to it again
+    async fn is_available(&self, endpoint: &Endpoint) -> bool {
+        if !endpoint.is_unhealthy() || endpoint.is_cooling_down() {
+            return true;
+        }
+
+        match endpoint
+            .client
+            .request::<U64>("eth_blockNumber", &Value::Array(vec![]))
+            .await
+        {
+            Ok(_) => {
+                self.mark_healthy(endpoint);
+                true
+            }
+            Err(e) => {
+                self.mark_unhealthy(endpoint, &e);
+                false
+            }
+        }
+    }
+
+    fn mark_healthy(&self, endpoint: &Endpoint) {
+        if endpoint.unhealthy_until.lock().unwrap().take().is_some() {
+            info!("provider endpoint {} is healthy again", endpoint.url);
+        }
+    }
+
+    fn mark_unhealthy(&self, endpoint: &Endpoint, e: &TransportError) {
+        warn!(
+            "provider endpoint {} is unhealthy, skipping it for {:?}. Error: {:?}",
+            endpoint.url, self.unhealthy_cooldown, e
+        );
+
+        *endpoint.unhealthy_until.lock().unwrap() = Some(Instant::now() + self.unhealthy_cooldown);
+    }
+
+    async fn request_any<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &Value,
+    ) -> Result<(usize, R), TransportError> {
+        let mut last_error = TransportError::NotConnected;
+
+        for i in self.ordered_endpoints() {
+            let endpoint = &self.endpoints[i];
+
+            if !self.is_available(endpoint).await {
+                continue;
+            }
+
+            match endpoint.client.request(method, params).await {
+                Ok(res) => {
+                    self.mark_healthy(endpoint);
+                    return Ok((i, res));
+                }
+                Err(e) if e.is_connection_error() => {
+                    self.mark_unhealthy(endpoint, &e);
+                    last_error = e;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for Transport {
+    type Error = TransportError;
+
+    async fn request<T: Debug + Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: T,
+    ) -> Result<R, TransportError> {
+        let params = serde_json::to_value(params)?;
+
+        if method == "eth_subscribe" {
+            let (i, res) = self.request_any::<Value>(method, &params).await?;
+
+            let id: U256 = serde_json::from_value(res.clone())?;
+
+            self.subscriptions.lock().unwrap().insert(id, i);
+
+            return Ok(serde_json::from_value(res)?);
+        }
+
+        let (_, res) = self.request_any(method, &params).await?;
+
+        Ok(res)
+    }
+}
+
+impl PubsubClient for Transport {
+    type NotificationStream = <Ws as PubsubClient>::NotificationStream;
+
+    fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, TransportError> {
+        let id = id.into();
+
+        let i = self
+            .subscriptions
+            .lock()
+            .unwrap()
+            .get(&id)
+            .copied()
+            .ok_or(TransportError::NotConnected)?;
+
+        match &self.endpoints[i].client {
+            EndpointClient::Http(_) => Err(TransportError::PubsubNotSupported),
+            EndpointClient::Pubsub(pubsub) => pubsub.subscribe(id),
+        }
+    }
+
+    fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), TransportError> {
+        let id = id.into();
+
+        let i = self
+            .subscriptions
+            .lock()
+            .unwrap()
+            .remove(&id)
+            .ok_or(TransportError::NotConnected)?;
+
+        match &self.endpoints[i].client {
+            EndpointClient::Http(_) => Err(TransportError::PubsubNotSupported),
+            EndpointClient::Pubsub(pubsub) => pubsub.unsubscribe(id),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod transport_tests {
+    use super::Transport;
+    use arpa_node_core::{FailoverConfig, FailoverStrategy, ProviderTransport};
+    use ethers::providers::JsonRpcClient;
+    use ethers::types::U64;
+    use ethers::utils::Anvil;
+
+    // nothing listens on the port
+    const UNREACHABLE_ENDPOINT: &str = "http://127.0.0.1:1";
+
+    #[tokio::test]
+    async fn test_failover_to_fallback_endpoint() {
+        let anvil = Anvil::new().spawn();
+
+        let failover_config = FailoverConfig {
+            strategy: FailoverStrategy::Priority,
+            ..Default::default()
+        };
+
+        let transport = Transport::with_failover(
+            ProviderTransport::Http,
+            &[UNREACHABLE_ENDPOINT, &anvil.endpoint()],
+            &failover_config,
+        )
+        .unwrap();
+
+        let block_number: U64 = transport.request("eth_blockNumber", ()).await.unwrap();
+
+        assert_eq!(U64::zero(), block_number);
+
+        // the unreachable endpoint is cooling down and tried last
+        assert!(transport.endpoints[0].is_cooling_down());
+        assert_eq!(vec![1, 0], transport.ordered_endpoints());
+    }
+}
@@ -24,6 +24,10 @@ pub mod controller {
     pub trait ControllerTransactions {
         async fn node_register(&self, id_public_key: Vec<u8>) -> ContractClientResult<()>;
 
+        async fn node_activate(&self) -> ContractClientResult<()>;
+
+        async fn node_quit(&self) -> ContractClientResult<()>;
+
         async fn commit_dkg(
             &self,
             group_index: usize,
@@ -31,6 +35,7 @@ pub mod controller {
             public_key: Vec<u8>,
             partial_public_key: Vec<u8>,
             disqualified_nodes: Vec<Address>,
+            dkg_transcript: Vec<u8>,
         ) -> ContractClientResult<()>;
 
         async fn post_process_dkg(
@@ -45,6 +50,7 @@ pub mod controller {
         async fn get_node(&self, id_address: Address) -> ContractClientResult<Node>;
     }
 
+    /// The logs from `from_block` to the latest block are back-filled before watching new logs if it is set.
     #[async_trait]
     pub trait ControllerLogs {
         async fn subscribe_dkg_task<
@@ -52,6 +58,7 @@ pub mod controller {
             F: Future<Output = ContractClientResult<()>> + Send,
         >(
             &self,
+            from_block: Option<usize>,
             cb: C,
         ) -> ContractClientResult<()>;
 
@@ -60,6 +67,7 @@ pub mod controller {
             F: Future<Output = ContractClientResult<()>> + Send,
         >(
             &self,
+            from_block: Option<usize>,
             cb: C,
         ) -> ContractClientResult<()>;
     }
@@ -94,6 +102,10 @@ pub mod coordinator {
         /// in the shares, responses or justifications mapping. Reverts if the participant
         /// has already published their data for a phase or if the DKG has ended.
         async fn publish(&self, value: Vec<u8>) -> ContractClientResult<()>;
+
+        /// Participant publishes their dealing of the PVSS DKG in its single round, which is
+        /// inserted in the shares mapping.
+        async fn publish_dealing(&self, value: Vec<u8>) -> ContractClientResult<()>;
     }
 
     #[async_trait]
@@ -110,6 +122,9 @@ pub mod coordinator {
         /// Gets the participants' justifications
         async fn get_justifications(&self) -> ContractClientResult<Vec<Vec<u8>>>;
 
+        /// Gets the participants' extractions
+        async fn get_extractions(&self) -> ContractClientResult<Vec<Vec<u8>>>;
+
         /// Gets the participants' ethereum addresses
         async fn get_participants(&self) -> ContractClientResult<Vec<Address>>;
 
@@ -174,6 +189,12 @@ pub mod adapter {
         ) -> ContractClientResult<()>;
 
         async fn set_initial_group(&self, group: Vec<u8>) -> ContractClientResult<()>;
+
+        async fn claim(
+            &self,
+            reward_address: Address,
+            token_amount: usize,
+        ) -> ContractClientResult<()>;
     }
 
     #[async_trait]
@@ -195,6 +216,7 @@ pub mod adapter {
         ) -> ContractClientResult<GroupRelayConfirmationTaskState>;
     }
 
+    /// The logs from `from_block` to the latest block are back-filled before watching new logs if it is set.
     #[async_trait]
     pub trait AdapterLogs {
         async fn subscribe_randomness_task<
@@ -202,6 +224,7 @@ pub mod adapter {
             F: Future<Output = ContractClientResult<()>> + Send,
         >(
             &self,
+            from_block: Option<usize>,
             cb: C,
         ) -> ContractClientResult<()>;
 
@@ -210,6 +233,7 @@ pub mod adapter {
             F: Future<Output = ContractClientResult<()>> + Send,
         >(
             &self,
+            from_block: Option<usize>,
             cb: C,
         ) -> ContractClientResult<()>;
     }
@@ -225,19 +249,22 @@ pub mod provider {
 
     use std::future::Future;
 
+    use arpa_node_core::BlockHeader;
     use async_trait::async_trait;
 
     use crate::error::ContractClientResult;
 
     #[async_trait]
     pub trait BlockFetcher {
-        async fn subscribe_new_block_height<
-            C: FnMut(usize) -> F + Send,
+        async fn subscribe_new_block<
+            C: FnMut(BlockHeader) -> F + Send,
             F: Future<Output = ContractClientResult<()>> + Send,
         >(
             &self,
             cb: C,
         ) -> ContractClientResult<()>;
+
+        async fn get_block_header(&self, block_height: usize) -> ContractClientResult<BlockHeader>;
     }
 
     pub trait ChainProviderBuilder {
@@ -7,10 +7,11 @@ use self::adapter_stub::{
     views_client::ViewsClient as AdapterViewsClient, GetGroupRequest, GroupReply, Member,
 };
 use self::adapter_stub::{
-    CancelInvalidRelayConfirmationTaskRequest, ConfirmRelayRequest, FulfillRandomnessRequest,
-    FulfillRelayRequest, GetGroupRelayCacheRequest, GetGroupRelayConfirmationTaskStateRequest,
-    GetSignatureTaskCompletionStateRequest, GroupRelayConfirmationTaskReply, MineRequest,
-    RequestRandomnessRequest, SetInitialGroupRequest, SignatureTaskReply,
+    CancelInvalidRelayConfirmationTaskRequest, ClaimRequest, ConfirmRelayRequest,
+    FulfillRandomnessRequest, FulfillRelayRequest, GetGroupRelayCacheRequest,
+    GetGroupRelayConfirmationTaskStateRequest, GetSignatureTaskCompletionStateRequest,
+    GroupRelayConfirmationTaskReply, MineRequest, RequestRandomnessRequest, SetInitialGroupRequest,
+    SignatureTaskReply,
 };
 use arpa_node_core::{
     address_to_string, ChainIdentity, Group, GroupRelayConfirmationTask,
@@ -100,8 +101,10 @@ impl AdapterLogs for MockAdapterClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        _from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
+        // tasks are pulled from the mock chain, there is nothing to back-fill
         loop {
             let task_res = self.emit_signature_task().await;
             match task_res {
@@ -120,8 +123,10 @@ impl AdapterLogs for MockAdapterClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        _from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
+        // tasks are pulled from the mock chain, there is nothing to back-fill
         loop {
             let task_res = self.emit_group_relay_confirmation_task().await;
             match task_res {
@@ -265,6 +270,27 @@ impl AdapterTransactions for MockAdapterClient {
             .map(|r| r.into_inner())
             .map_err(|status| status.into())
     }
+
+    async fn claim(
+        &self,
+        reward_address: Address,
+        token_amount: usize,
+    ) -> ContractClientResult<()> {
+        let request = Request::new(ClaimRequest {
+            id_address: address_to_string(self.id_address),
+            reward_address: address_to_string(reward_address),
+            token_amount: token_amount as u32,
+        });
+
+        let mut transactions_client =
+            ServiceClient::<TransactionsClient>::prepare_service_client(self).await?;
+
+        transactions_client
+            .claim(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
 }
 
 #[async_trait]
@@ -448,7 +474,6 @@ impl From<Member> for ModelMember {
         ModelMember {
             index: member.index as usize,
             id_address: member.id_address.parse().unwrap(),
-            rpc_endpint: None,
             partial_public_key,
         }
     }
@@ -9,7 +9,7 @@ use crate::ServiceClient;
 use self::controller_stub::{
     transactions_client::TransactionsClient as ControllerTransactionsClient,
     views_client::ViewsClient as ControllerViewsClient, CommitDkgRequest, GetNodeRequest, Member,
-    NodeRegisterRequest, NodeReply, PostProcessDkgRequest,
+    NodeActivateRequest, NodeQuitRequest, NodeRegisterRequest, NodeReply, PostProcessDkgRequest,
 };
 use self::controller_stub::{DkgTaskReply, GroupRelayTaskReply, MineRequest};
 use arpa_node_core::{
@@ -96,8 +96,10 @@ impl ControllerLogs for MockControllerClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        _from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
+        // tasks are pulled from the mock chain, there is nothing to back-fill
         loop {
             let task_res = self.emit_dkg_task().await;
             match task_res {
@@ -115,8 +117,10 @@ impl ControllerLogs for MockControllerClient {
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
+        _from_block: Option<usize>,
         mut cb: C,
     ) -> ContractClientResult<()> {
+        // tasks are pulled from the mock chain, there is nothing to back-fill
         loop {
             let task_res = self.emit_group_relay_task().await;
             match task_res {
@@ -149,6 +153,36 @@ impl ControllerTransactions for MockControllerClient {
             .map_err(|status| status.into())
     }
 
+    async fn node_activate(&self) -> ContractClientResult<()> {
+        let request = Request::new(NodeActivateRequest {
+            id_address: address_to_string(self.id_address),
+        });
+
+        let mut transactions_client =
+            ServiceClient::<TransactionsClient>::prepare_service_client(self).await?;
+
+        transactions_client
+            .node_activate(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
+
+    async fn node_quit(&self) -> ContractClientResult<()> {
+        let request = Request::new(NodeQuitRequest {
+            id_address: address_to_string(self.id_address),
+        });
+
+        let mut transactions_client =
+            ServiceClient::<TransactionsClient>::prepare_service_client(self).await?;
+
+        transactions_client
+            .node_quit(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
+
     async fn commit_dkg(
         &self,
         group_index: usize,
@@ -156,6 +190,7 @@ impl ControllerTransactions for MockControllerClient {
         public_key: Vec<u8>,
         partial_public_key: Vec<u8>,
         disqualified_nodes: Vec<Address>,
+        dkg_transcript: Vec<u8>,
     ) -> ContractClientResult<()> {
         let disqualified_nodes = disqualified_nodes
             .into_iter()
@@ -169,6 +204,7 @@ impl ControllerTransactions for MockControllerClient {
             public_key,
             partial_public_key,
             disqualified_nodes,
+            dkg_transcript,
         });
 
         let mut transactions_client =
@@ -237,6 +273,7 @@ impl ControllerMockHelper for MockControllerClient {
                     members,
                     assignment_block_height,
                     coordinator_address,
+                    dkg_protocol,
                 } = r.into_inner();
 
                 let members = members
@@ -252,6 +289,7 @@ impl ControllerMockHelper for MockControllerClient {
                     members,
                     assignment_block_height: assignment_block_height as usize,
                     coordinator_address: coordinator_address.parse().unwrap(),
+                    dkg_protocol: dkg_protocol.into(),
                 }
             })
             .map_err(|status| match status.code() {
@@ -333,7 +371,6 @@ impl From<Member> for ModelMember {
         ModelMember {
             index: member.index as usize,
             id_address: member.id_address.parse().unwrap(),
-            rpc_endpint: None,
             partial_public_key,
         }
     }
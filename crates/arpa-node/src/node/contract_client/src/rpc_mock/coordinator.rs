@@ -10,7 +10,7 @@ use self::coordinator_stub::{BlsKeysReply, PublishRequest};
 use arpa_node_core::{address_to_string, ChainIdentity, MockChainIdentity};
 use async_trait::async_trait;
 use dkg_core::{
-    primitives::{BundledJustification, BundledResponses, BundledShares},
+    primitives::{BundledExtraction, BundledJustification, BundledResponses, BundledShares},
     BoardPublisher,
 };
 use ethers::types::Address;
@@ -96,6 +96,24 @@ impl CoordinatorTransactions for MockCoordinatorClient {
             .map(|r| r.into_inner())
             .map_err(|status| status.into())
     }
+
+    async fn publish_dealing(&self, value: Vec<u8>) -> ContractClientResult<()> {
+        let mut request = Request::new(PublishRequest {
+            id_address: address_to_string(self.id_address),
+            value,
+        });
+
+        self.set_metadata(&mut request);
+
+        let mut transactions_client =
+            ServiceClient::<TransactionsClient>::prepare_service_client(self).await?;
+
+        transactions_client
+            .publish_dealing(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
 }
 
 #[async_trait]
@@ -142,6 +160,20 @@ impl CoordinatorViews for MockCoordinatorClient {
             .map_err(|status| status.into())
     }
 
+    async fn get_extractions(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+        let mut request: Request<()> = Request::new(());
+
+        self.set_metadata(&mut request);
+
+        let mut views_client = ServiceClient::<ViewsClient>::prepare_service_client(self).await?;
+
+        views_client
+            .get_extractions(request)
+            .await
+            .map(|r| r.into_inner().extractions)
+            .map_err(|status| status.into())
+    }
+
     async fn get_participants(&self) -> ContractClientResult<Vec<Address>> {
         let mut request: Request<()> = Request::new(());
 
@@ -207,7 +239,10 @@ impl BoardPublisher<Curve> for MockCoordinatorClient {
         self.publish(serialized).await.map_err(|e| e.into())
     }
 
-    async fn publish_responses(&mut self, responses: BundledResponses) -> Result<(), Self::Error> {
+    async fn publish_responses(
+        &mut self,
+        responses: BundledResponses<Curve>,
+    ) -> Result<(), Self::Error> {
         info!("called publish_responses");
         let serialized = bincode::serialize(&responses)?;
         self.publish(serialized).await.map_err(|e| e.into())
@@ -220,4 +255,12 @@ impl BoardPublisher<Curve> for MockCoordinatorClient {
         let serialized = bincode::serialize(&justifications)?;
         self.publish(serialized).await.map_err(|e| e.into())
     }
+
+    async fn publish_extraction(
+        &mut self,
+        extraction: BundledExtraction<Curve>,
+    ) -> Result<(), Self::Error> {
+        let serialized = bincode::serialize(&extraction)?;
+        self.publish(serialized).await.map_err(|e| e.into())
+    }
 }
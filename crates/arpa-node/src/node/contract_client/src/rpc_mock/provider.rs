@@ -5,8 +5,9 @@ use crate::{
     error::ContractClientResult,
     provider::{BlockFetcher, ChainProviderBuilder},
 };
-use arpa_node_core::{ChainIdentity, MockChainIdentity, PALCEHOLDER_ADDRESS};
+use arpa_node_core::{BlockHeader, ChainIdentity, MockChainIdentity, PALCEHOLDER_ADDRESS};
 use async_trait::async_trait;
+use ethers_core::types::H256;
 
 impl ChainProviderBuilder for MockChainIdentity {
     type Service = MockAdapterClient;
@@ -19,10 +20,19 @@ impl ChainProviderBuilder for MockChainIdentity {
     }
 }
 
+// the mock chain never reorganizes, so block hashes are derived from heights
+fn mock_block_header(block_height: usize) -> BlockHeader {
+    BlockHeader {
+        number: block_height,
+        hash: H256::from_low_u64_be(block_height as u64),
+        parent_hash: H256::from_low_u64_be(block_height.saturating_sub(1) as u64),
+    }
+}
+
 #[async_trait]
 impl BlockFetcher for MockAdapterClient {
-    async fn subscribe_new_block_height<
-        C: FnMut(usize) -> F + Send,
+    async fn subscribe_new_block<
+        C: FnMut(BlockHeader) -> F + Send,
         F: Future<Output = ContractClientResult<()>> + Send,
     >(
         &self,
@@ -30,8 +40,12 @@ impl BlockFetcher for MockAdapterClient {
     ) -> ContractClientResult<()> {
         loop {
             let block_height = self.mine(1).await?;
-            cb(block_height).await?;
+            cb(mock_block_header(block_height)).await?;
             tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
         }
     }
+
+    async fn get_block_header(&self, block_height: usize) -> ContractClientResult<BlockHeader> {
+        Ok(mock_block_header(block_height))
+    }
 }
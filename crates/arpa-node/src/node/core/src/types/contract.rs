@@ -9,6 +9,20 @@ pub const GROUP_MAX_CAPACITY: usize = 10;
 
 pub const RANDOMNESS_TASK_EXCLUSIVE_WINDOW: usize = 30;
 
+// blocks after the assignment of a randomness task in which only its committers can fulfill it,
+// as the adapter enforces
+pub const COMMITTER_EXCLUSIVE_WINDOW: usize = 10;
+
+// recent block hashes kept to detect chain reorganizations
+pub const MAX_TRACKED_BLOCKS: usize = 256;
+
+// lifetime of the peer record of the node, which is re-announced once half of it has passed
+pub const PEER_RECORD_TTL_SECS: u64 = 24 * 60 * 60;
+
+// how long a signed partial signature is accepted by committers, also bounds the clock drift between nodes
+pub const PARTIAL_SIGNATURE_VALIDITY_SECS: u64 = 60;
+
+#[derive(Debug, Clone)]
 pub struct Node {
     pub id_address: String,
     pub id_public_key: Vec<u8>,
@@ -1,10 +1,14 @@
 use ethers_core::types::Address;
 
+mod signer;
 mod types;
-use ethers_signers::LocalWallet;
+use ethers_signers::Signer;
+pub use signer::*;
 pub use types::*;
 
 pub trait ChainIdentity {
+    type Signer: Signer + Clone;
+
     fn get_id(&self) -> usize;
 
     fn get_chain_id(&self) -> usize;
@@ -15,5 +19,5 @@ pub trait ChainIdentity {
 
     fn get_contract_address(&self) -> Address;
 
-    fn get_signer(&self) -> &LocalWallet;
+    fn get_signer(&self) -> &Self::Signer;
 }
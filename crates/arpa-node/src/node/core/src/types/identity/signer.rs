@@ -0,0 +1,363 @@
+use async_trait::async_trait;
+use ethers_core::{
+    types::{
+        transaction::{
+            eip2718::{TypedTransaction, TypedTransactionError},
+            eip712::Eip712,
+        },
+        Address, Bytes, Signature, SignatureError,
+    },
+    utils::{keccak256, rlp::Rlp},
+};
+use ethers_signers::{LocalWallet, Signer, WalletError};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+// signed by the account to derive the key of its local data when the key is kept by a remote signer
+const DATA_KEY_MESSAGE: &str = "arpa node data key";
+
+#[derive(Debug, Error)]
+pub enum RemoteSignerError {
+    #[error(transparent)]
+    RequestError(#[from] reqwest::Error),
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("remote signer error {0}: {1}")]
+    JsonRpcError(i64, String),
+    #[error(transparent)]
+    DecodingTransactionError(#[from] TypedTransactionError),
+    #[error(transparent)]
+    SignatureError(#[from] SignatureError),
+    #[error("account {0:?} is not managed by the remote signer")]
+    UnknownAccount(Address),
+    #[error("the signature is not made by account {0:?}")]
+    InvalidSignature(Address),
+    #[error("typed data signing is not supported by the remote signer")]
+    TypedDataNotSupported,
+}
+
+/// Client of a remote signer speaking the Web3Signer eth1 JSON-RPC (`eth_accounts`, `eth_sign`
+/// and `eth_signTransaction`), so that the key of the account never leaves the signer.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    endpoint: String,
+    address: Address,
+    chain_id: u64,
+    client: reqwest::Client,
+}
+
+impl RemoteSigner {
+    /// Connects to the remote signer and checks that it manages the account.
+    pub async fn connect(endpoint: &str, address: Address) -> Result<Self, RemoteSignerError> {
+        let signer = RemoteSigner {
+            endpoint: endpoint.to_string(),
+            address,
+            chain_id: 1,
+            client: reqwest::Client::new(),
+        };
+
+        let accounts: Vec<Address> = signer.request("eth_accounts", json!([])).await?;
+
+        if !accounts.contains(&address) {
+            return Err(RemoteSignerError::UnknownAccount(address));
+        }
+
+        Ok(signer)
+    }
+
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<R, RemoteSignerError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let mut res: Value = self
+            .client
+            .post(&self.endpoint)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = res.get("error") {
+            return Err(RemoteSignerError::JsonRpcError(
+                error["code"].as_i64().unwrap_or_default(),
+                error["message"].as_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        Ok(serde_json::from_value(res["result"].take())?)
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    type Error = RemoteSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        let message = Bytes::from(message.as_ref().to_vec());
+
+        let signature: Bytes = self
+            .request("eth_sign", json!([self.address, message]))
+            .await?;
+
+        Ok(Signature::try_from(signature.as_ref())?)
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let mut tx = tx.clone();
+        tx.set_from(self.address);
+        tx.set_chain_id(self.chain_id);
+
+        let signed_tx: Bytes = self.request("eth_signTransaction", json!([tx])).await?;
+
+        let (signed_tx, signature) = TypedTransaction::decode_signed(&Rlp::new(&signed_tx))?;
+
+        // the remote signer could have changed the transaction
+        if signature.recover(tx.sighash())? != self.address || signed_tx.sighash() != tx.sighash() {
+            return Err(RemoteSignerError::InvalidSignature(self.address));
+        }
+
+        Ok(signature)
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        _payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        Err(RemoteSignerError::TypedDataNotSupported)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NodeSignerError {
+    #[error(transparent)]
+    LocalSignerError(#[from] WalletError),
+    #[error(transparent)]
+    RemoteSignerError(#[from] RemoteSignerError),
+}
+
+/// Signer of the chain account of the node, holding the key locally or asking a remote signer.
+#[derive(Debug, Clone)]
+pub enum NodeSigner {
+    Local(LocalWallet),
+    Remote(RemoteSigner),
+}
+
+impl NodeSigner {
+    /// Returns the key to encrypt the local data of the account. It is the private key of a local
+    /// account, and the hash of a signature of a remote one, which relies on the remote signer
+    /// signing deterministically by RFC 6979.
+    pub async fn get_data_key(&self) -> Result<Vec<u8>, NodeSignerError> {
+        match self {
+            NodeSigner::Local(wallet) => Ok(wallet.signer().to_bytes().to_vec()),
+            NodeSigner::Remote(signer) => {
+                let signature = signer.sign_message(DATA_KEY_MESSAGE).await?;
+                Ok(keccak256(signature.to_vec()).to_vec())
+            }
+        }
+    }
+}
+
+impl From<LocalWallet> for NodeSigner {
+    fn from(wallet: LocalWallet) -> Self {
+        NodeSigner::Local(wallet)
+    }
+}
+
+impl From<RemoteSigner> for NodeSigner {
+    fn from(signer: RemoteSigner) -> Self {
+        NodeSigner::Remote(signer)
+    }
+}
+
+#[async_trait]
+impl Signer for NodeSigner {
+    type Error = NodeSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            NodeSigner::Local(wallet) => Ok(wallet.sign_message(message).await?),
+            NodeSigner::Remote(signer) => Ok(signer.sign_message(message).await?),
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        match self {
+            NodeSigner::Local(wallet) => Ok(wallet.sign_transaction(tx).await?),
+            NodeSigner::Remote(signer) => Ok(signer.sign_transaction(tx).await?),
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            NodeSigner::Local(wallet) => Ok(wallet.sign_typed_data(payload).await?),
+            NodeSigner::Remote(signer) => Ok(signer.sign_typed_data(payload).await?),
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            NodeSigner::Local(wallet) => wallet.address(),
+            NodeSigner::Remote(signer) => signer.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            NodeSigner::Local(wallet) => wallet.chain_id(),
+            NodeSigner::Remote(signer) => signer.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            NodeSigner::Local(wallet) => NodeSigner::Local(wallet.with_chain_id(chain_id)),
+            NodeSigner::Remote(signer) => NodeSigner::Remote(signer.with_chain_id(chain_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod signer_tests {
+    use super::{NodeSigner, RemoteSigner, RemoteSignerError};
+    use ethers_core::types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest,
+    };
+    use ethers_signers::{LocalWallet, Signer};
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server,
+    };
+    use serde_json::{json, Value};
+    use std::{convert::Infallible, net::SocketAddr};
+
+    const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    // stand-in of a Web3Signer instance holding the key of a local wallet
+    async fn sign(wallet: LocalWallet, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+        let req: Value = serde_json::from_slice(&body).unwrap();
+
+        let result = match req["method"].as_str().unwrap() {
+            "eth_accounts" => json!([wallet.address()]),
+            "eth_sign" => {
+                let message: Bytes = serde_json::from_value(req["params"][1].clone()).unwrap();
+                let signature = wallet.sign_message(message).await.unwrap();
+                json!(Bytes::from(signature.to_vec()))
+            }
+            "eth_signTransaction" => {
+                let tx: TypedTransaction =
+                    serde_json::from_value(req["params"][0].clone()).unwrap();
+                let wallet = wallet.with_chain_id(tx.chain_id().unwrap().as_u64());
+                let signature = wallet.sign_transaction(&tx).await.unwrap();
+                json!(tx.rlp_signed(&signature))
+            }
+            method => {
+                let res = json!({
+                    "jsonrpc": "2.0",
+                    "id": req["id"],
+                    "error": { "code": -32601, "message": format!("method {} not found", method) },
+                });
+                return Ok(Response::new(Body::from(res.to_string())));
+            }
+        };
+
+        let res = json!({ "jsonrpc": "2.0", "id": req["id"], "result": result });
+
+        Ok(Response::new(Body::from(res.to_string())))
+    }
+
+    fn start_signer(wallet: LocalWallet) -> String {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+
+        let make_service = make_service_fn(move |_| {
+            let wallet = wallet.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| sign(wallet.clone(), req))) }
+        });
+
+        let server = Server::bind(&addr).serve(make_service);
+
+        let endpoint = format!("http://{}", server.local_addr());
+
+        tokio::spawn(server);
+
+        endpoint
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_signs_as_local_wallet() {
+        let wallet: LocalWallet = PRIVATE_KEY.parse().unwrap();
+
+        let endpoint = start_signer(wallet.clone());
+
+        let res = RemoteSigner::connect(&endpoint, Address::random()).await;
+        assert!(matches!(res, Err(RemoteSignerError::UnknownAccount(_))));
+
+        let signer: NodeSigner = RemoteSigner::connect(&endpoint, wallet.address())
+            .await
+            .unwrap()
+            .with_chain_id(5u64)
+            .into();
+
+        let message = "hello";
+        assert_eq!(
+            wallet.sign_message(message).await.unwrap(),
+            signer.sign_message(message).await.unwrap()
+        );
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::random())
+            .value(1u64)
+            .nonce(0u64)
+            .gas(21000u64)
+            .max_fee_per_gas(100u64)
+            .max_priority_fee_per_gas(1u64)
+            .into();
+
+        let signature = signer.sign_transaction(&tx).await.unwrap();
+        let mut signed_tx = tx.clone();
+        signed_tx.set_chain_id(5u64);
+        assert_eq!(
+            wallet.address(),
+            signature.recover(signed_tx.sighash()).unwrap()
+        );
+
+        // the data key is stable as long as the remote signer signs deterministically
+        assert_eq!(
+            signer.get_data_key().await.unwrap(),
+            signer.get_data_key().await.unwrap()
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use ethers_core::{rand, types::Address};
 use ethers_signers::{LocalWallet, Signer};
+use serde::{Deserialize, Serialize};
 
-use super::ChainIdentity;
+use super::{ChainIdentity, NodeSigner};
 
 #[derive(Clone)]
 pub struct MockChainIdentity {
@@ -33,6 +34,8 @@ impl MockChainIdentity {
 }
 
 impl ChainIdentity for MockChainIdentity {
+    type Signer = LocalWallet;
+
     fn get_id(&self) -> usize {
         self.id
     }
@@ -58,34 +61,160 @@ impl ChainIdentity for MockChainIdentity {
     }
 }
 
+/// Transport to connect to the provider of a chain. Logs and new blocks are pushed by
+/// subscriptions over WebSocket and IPC, and polled over HTTP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderTransport {
+    Http,
+    Ws,
+    Ipc,
+}
+
+impl Default for ProviderTransport {
+    fn default() -> Self {
+        ProviderTransport::Http
+    }
+}
+
+/// Order in which the endpoints of a chain are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailoverStrategy {
+    // the primary endpoint first, then the fallback ones in order
+    Priority,
+    // requests are spread over all the endpoints
+    RoundRobin,
+}
+
+impl Default for FailoverStrategy {
+    fn default() -> Self {
+        FailoverStrategy::Priority
+    }
+}
+
+/// Fallback endpoints of the provider of a chain. An endpoint failing to respond is skipped for
+/// `unhealthy_cooldown_millis` until it passes a health check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FailoverConfig {
+    pub fallback_endpoints: Vec<String>,
+    pub strategy: FailoverStrategy,
+    pub unhealthy_cooldown_millis: u64,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        FailoverConfig {
+            fallback_endpoints: vec![],
+            strategy: FailoverStrategy::Priority,
+            unhealthy_cooldown_millis: 10000,
+        }
+    }
+}
+
+/// Fee caps and resubmission policy of the transactions sent by an account. A transaction
+/// still pending after `gas_bump_blocks` blocks is replaced with fees bumped by
+/// `gas_bump_percent`, up to the caps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransactionConfig {
+    pub max_fee_per_gas_gwei: u64,
+    pub max_priority_fee_per_gas_gwei: u64,
+    pub gas_bump_blocks: usize,
+    // nodes reject a replacement with fees bumped less than 10%
+    pub gas_bump_percent: u64,
+    pub max_pending_blocks: usize,
+}
+
+impl Default for TransactionConfig {
+    fn default() -> Self {
+        TransactionConfig {
+            max_fee_per_gas_gwei: 500,
+            max_priority_fee_per_gas_gwei: 10,
+            gas_bump_blocks: 3,
+            gas_bump_percent: 12,
+            max_pending_blocks: 60,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct GeneralChainIdentity {
     id: usize,
     chain_id: usize,
-    wallet: LocalWallet,
+    signer: NodeSigner,
     provider_rpc_endpoint: String,
+    provider_transport: ProviderTransport,
+    polling_interval_millis: u64,
+    failover_config: FailoverConfig,
+    transaction_config: TransactionConfig,
     contract_address: Address,
 }
 
 impl GeneralChainIdentity {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: usize,
         chain_id: usize,
-        wallet: LocalWallet,
+        signer: NodeSigner,
         provider_rpc_endpoint: String,
+        provider_transport: ProviderTransport,
+        polling_interval_millis: u64,
+        failover_config: FailoverConfig,
+        transaction_config: TransactionConfig,
         contract_address: Address,
     ) -> Self {
         GeneralChainIdentity {
             id,
             chain_id,
-            wallet,
+            signer,
             provider_rpc_endpoint,
+            provider_transport,
+            polling_interval_millis,
+            failover_config,
+            transaction_config,
             contract_address,
         }
     }
+
+    pub fn get_provider_transport(&self) -> ProviderTransport {
+        self.provider_transport
+    }
+
+    pub fn get_polling_interval_millis(&self) -> u64 {
+        self.polling_interval_millis
+    }
+
+    /// Returns the primary endpoint followed by the fallback ones.
+    pub fn get_provider_rpc_endpoints(&self) -> Vec<&str> {
+        std::iter::once(self.provider_rpc_endpoint.as_str())
+            .chain(
+                self.failover_config
+                    .fallback_endpoints
+                    .iter()
+                    .map(|e| e.as_str()),
+            )
+            .collect()
+    }
+
+    pub fn get_failover_config(&self) -> &FailoverConfig {
+        &self.failover_config
+    }
+
+    pub fn get_transaction_config(&self) -> TransactionConfig {
+        self.transaction_config
+    }
+
+    /// Sets the id of the chain the transactions are signed for, as read from the provider.
+    pub fn set_chain_id(&mut self, chain_id: usize) {
+        self.chain_id = chain_id;
+    }
 }
 
 impl ChainIdentity for GeneralChainIdentity {
+    type Signer = NodeSigner;
+
     fn get_id(&self) -> usize {
         self.id
     }
@@ -95,7 +224,7 @@ impl ChainIdentity for GeneralChainIdentity {
     }
 
     fn get_id_address(&self) -> Address {
-        self.wallet.address()
+        self.signer.address()
     }
 
     fn get_provider_rpc_endpoint(&self) -> &str {
@@ -106,7 +235,7 @@ impl ChainIdentity for GeneralChainIdentity {
         self.contract_address
     }
 
-    fn get_signer(&self) -> &LocalWallet {
-        &self.wallet
+    fn get_signer(&self) -> &NodeSigner {
+        &self.signer
     }
 }
@@ -1,5 +1,9 @@
-use crate::types::contract::ContractGroup;
-use ethers_core::types::{Address, Log};
+use crate::{now_timestamp, types::contract::ContractGroup};
+use ethers_core::{
+    types::{Address, Log, Signature, H256},
+    utils::keccak256,
+};
+use ethers_signers::Signer;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use threshold_bls::curve::bls12381::G1;
@@ -32,6 +36,13 @@ impl Task for GroupRelayConfirmationTask {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub number: usize,
+    pub hash: H256,
+    pub parent_hash: H256,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct RandomnessTask {
     pub index: usize,
@@ -49,6 +60,7 @@ pub struct DKGTask {
     pub members: BTreeMap<Address, usize>,
     pub assignment_block_height: usize,
     pub coordinator_address: Address,
+    pub dkg_protocol: DKGProtocol,
 }
 
 #[derive(Debug, Clone)]
@@ -105,10 +117,139 @@ impl Group {
 pub struct Member {
     pub index: usize,
     pub id_address: Address,
-    pub rpc_endpint: Option<String>,
     pub partial_public_key: Option<G1>,
 }
 
+/// Announces the endpoint a node serves its committer rpc on to the other members of its groups.
+///
+/// The record is signed with the chain key of the node and kept apart from the DKG, so the endpoint
+/// can be changed without regrouping. A record replaces the known one of the node only if it expires later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    pub id_address: Address,
+    pub rpc_endpoint: String,
+    /// Unix timestamp in seconds after which the record is no longer used.
+    pub expires_at: u64,
+    pub signature: Vec<u8>,
+}
+
+impl PeerRecord {
+    pub async fn sign<S: Signer>(
+        signer: &S,
+        rpc_endpoint: String,
+        expires_at: u64,
+    ) -> Result<Self, S::Error> {
+        let id_address = signer.address();
+
+        let message = Self::signing_message(id_address, &rpc_endpoint, expires_at);
+
+        let signature = signer.sign_message(message).await?;
+
+        Ok(PeerRecord {
+            id_address,
+            rpc_endpoint,
+            expires_at,
+            signature: signature.to_vec(),
+        })
+    }
+
+    /// Whether the record is signed by the node it announces.
+    pub fn verify(&self) -> bool {
+        let message = Self::signing_message(self.id_address, &self.rpc_endpoint, self.expires_at);
+
+        Signature::try_from(self.signature.as_slice())
+            .map(|signature| signature.verify(message, self.id_address).is_ok())
+            .unwrap_or(false)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at <= now
+    }
+
+    fn signing_message(id_address: Address, rpc_endpoint: &str, expires_at: u64) -> Vec<u8> {
+        bincode::serialize(&(id_address, rpc_endpoint, expires_at)).unwrap()
+    }
+}
+
+/// A partial signature sent to a committer, signed with the chain key of the sender.
+///
+/// The chain key signature binds the partial signature to the chain, group and task it is
+/// committed for, so a committer can authenticate the sender before the costly pairing check,
+/// and the request can't be replayed on another chain or for another task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedPartialSignature {
+    pub chain_id: usize,
+    pub group_index: usize,
+    pub task_type: TaskType,
+    pub signature_index: usize,
+    pub message: Vec<u8>,
+    pub partial_signature: Vec<u8>,
+    /// Unix timestamp in seconds when the request is signed.
+    pub signed_at: u64,
+    pub signature: Vec<u8>,
+}
+
+impl SignedPartialSignature {
+    pub async fn sign<S: Signer>(
+        signer: &S,
+        chain_id: usize,
+        group_index: usize,
+        task_type: TaskType,
+        signature_index: usize,
+        message: Vec<u8>,
+        partial_signature: Vec<u8>,
+    ) -> Result<Self, S::Error> {
+        let mut signed_partial_signature = SignedPartialSignature {
+            chain_id,
+            group_index,
+            task_type,
+            signature_index,
+            message,
+            partial_signature,
+            signed_at: now_timestamp(),
+            signature: vec![],
+        };
+
+        signed_partial_signature.signature = signer
+            .sign_message(signed_partial_signature.signing_message())
+            .await?
+            .to_vec();
+
+        Ok(signed_partial_signature)
+    }
+
+    /// The address of the chain key which signed the request, if the signature is well-formed.
+    pub fn recover(&self) -> Option<Address> {
+        Signature::try_from(self.signature.as_slice())
+            .ok()
+            .and_then(|signature| signature.recover(self.signing_message()).ok())
+    }
+
+    /// Whether the request is signed within `validity_secs` from now in either direction,
+    /// tolerating clock drift between the nodes.
+    pub fn is_fresh(&self, now: u64, validity_secs: u64) -> bool {
+        self.signed_at.abs_diff(now) <= validity_secs
+    }
+
+    /// Identifies the signed statement, two requests with the same digest are replays of each other.
+    pub fn digest(&self) -> H256 {
+        H256::from(keccak256(self.signing_message()))
+    }
+
+    fn signing_message(&self) -> Vec<u8> {
+        bincode::serialize(&(
+            self.chain_id,
+            self.group_index,
+            self.task_type.to_i32(),
+            self.signature_index,
+            keccak256(&self.message),
+            keccak256(&self.partial_signature),
+            self.signed_at,
+        ))
+        .unwrap()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupRelayConfirmation {
     pub group: ContractGroup,
@@ -166,6 +307,7 @@ impl From<i32> for GroupRelayConfirmationTaskState {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskType {
     Randomness,
     GroupRelay,
@@ -192,6 +334,25 @@ impl From<i32> for TaskType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogType {
+    DKGTask,
+    RandomnessTask,
+    GroupRelayTask,
+    GroupRelayConfirmationTask,
+}
+
+impl LogType {
+    pub fn to_i32(&self) -> i32 {
+        match self {
+            LogType::DKGTask => 0,
+            LogType::RandomnessTask => 1,
+            LogType::GroupRelayTask => 2,
+            LogType::GroupRelayConfirmationTask => 3,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
 pub enum DKGStatus {
     None,
@@ -222,6 +383,44 @@ impl From<usize> for DKGStatus {
     }
 }
 
+/// The DKG protocol a group runs for a DKG task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DKGProtocol {
+    // the Joint-Feldman DKG, whose public key can be biased by rushing dealers
+    JointFeldman,
+    // the DKG of Gennaro et al., which extracts the public key once the qualified dealers are fixed
+    Gennaro,
+    // the non-interactive DKG, in which every dealer publishes a publicly verifiable dealing once
+    Pvss,
+}
+
+impl DKGProtocol {
+    pub fn to_u32(self) -> u32 {
+        match self {
+            DKGProtocol::JointFeldman => 0,
+            DKGProtocol::Gennaro => 1,
+            DKGProtocol::Pvss => 2,
+        }
+    }
+}
+
+impl From<u32> for DKGProtocol {
+    fn from(p: u32) -> Self {
+        match p {
+            1 => DKGProtocol::Gennaro,
+            2 => DKGProtocol::Pvss,
+            _ => DKGProtocol::JointFeldman,
+        }
+    }
+}
+
+impl Default for DKGProtocol {
+    fn default() -> Self {
+        DKGProtocol::JointFeldman
+    }
+}
+
 impl From<Log> for RandomnessTask {
     fn from(_: Log) -> Self {
         todo!()
@@ -234,14 +433,100 @@ impl From<Log> for GroupRelayConfirmationTask {
     }
 }
 
-impl From<Log> for DKGTask {
+impl From<Log> for GroupRelayTask {
     fn from(_: Log) -> Self {
         todo!()
     }
 }
 
-impl From<Log> for GroupRelayTask {
-    fn from(_: Log) -> Self {
-        todo!()
+#[cfg(test)]
+pub mod node_tests {
+    use crate::{PeerRecord, SignedPartialSignature, TaskType};
+    use ethers_core::rand;
+    use ethers_signers::{LocalWallet, Signer};
+
+    #[tokio::test]
+    async fn test_sign_and_verify_peer_record() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+
+        let record = PeerRecord::sign(&wallet, "127.0.0.1:50061".to_string(), 100)
+            .await
+            .unwrap();
+
+        assert_eq!(wallet.address(), record.id_address);
+        assert!(record.verify());
+        assert!(!record.is_expired(99));
+        assert!(record.is_expired(100));
+    }
+
+    #[tokio::test]
+    async fn test_tampered_peer_record_does_not_verify() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+
+        let record = PeerRecord::sign(&wallet, "127.0.0.1:50061".to_string(), 100)
+            .await
+            .unwrap();
+
+        let mut redirected = record.clone();
+        redirected.rpc_endpoint = "127.0.0.1:50062".to_string();
+        assert!(!redirected.verify());
+
+        let mut extended = record.clone();
+        extended.expires_at = 200;
+        assert!(!extended.verify());
+
+        let mut impersonated = record;
+        impersonated.id_address = LocalWallet::new(&mut rand::thread_rng()).address();
+        assert!(!impersonated.verify());
+    }
+
+    async fn sign_partial_signature(wallet: &LocalWallet) -> SignedPartialSignature {
+        SignedPartialSignature::sign(
+            wallet,
+            1,
+            0,
+            TaskType::Randomness,
+            7,
+            b"message".to_vec(),
+            b"partial signature".to_vec(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_recover_partial_signature() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+
+        let signed = sign_partial_signature(&wallet).await;
+
+        assert_eq!(Some(wallet.address()), signed.recover());
+        assert!(signed.is_fresh(signed.signed_at + 60, 60));
+        assert!(!signed.is_fresh(signed.signed_at + 61, 60));
+        assert!(!signed.is_fresh(signed.signed_at - 61, 60));
+    }
+
+    #[tokio::test]
+    async fn test_partial_signature_is_bound_to_chain_and_task() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+
+        let signed = sign_partial_signature(&wallet).await;
+
+        let mut other_chain = signed.clone();
+        other_chain.chain_id = 2;
+        assert_ne!(Some(wallet.address()), other_chain.recover());
+        assert_ne!(signed.digest(), other_chain.digest());
+
+        let mut other_task = signed.clone();
+        other_task.task_type = TaskType::GroupRelayConfirmation;
+        assert_ne!(Some(wallet.address()), other_task.recover());
+
+        let mut other_message = signed.clone();
+        other_message.message = b"another message".to_vec();
+        assert_ne!(Some(wallet.address()), other_message.recover());
+
+        let mut refreshed = signed;
+        refreshed.signed_at += 1;
+        assert_ne!(Some(wallet.address()), refreshed.recover());
     }
 }
@@ -1,4 +1,4 @@
-use chrono::Local;
+use chrono::{Local, Utc};
 use ethers_core::types::Address;
 
 pub fn format_now_date() -> String {
@@ -6,6 +6,10 @@ pub fn format_now_date() -> String {
     Local::now().format(fmt).to_string()
 }
 
+pub fn now_timestamp() -> u64 {
+    Utc::now().timestamp() as u64
+}
+
 pub fn address_to_string(address: Address) -> String {
     format!("{:?}", address)
 }
@@ -1,8 +1,9 @@
 //! SeaORM Entity. Generated by sea-orm-codegen 0.9.2
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "group_info")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -19,6 +20,7 @@ pub struct Model {
     pub dkg_status: i32,
     pub self_member_index: i32,
     pub dkg_start_block_height: i32,
+    pub dkg_transcript: Option<Vec<u8>>,
     pub create_at: String,
     pub update_at: String,
 }
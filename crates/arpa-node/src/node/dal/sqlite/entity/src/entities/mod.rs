@@ -3,5 +3,7 @@
 pub mod prelude;
 
 pub mod group_info;
+pub mod log_checkpoint;
 pub mod node_info;
+pub mod peer_record;
 pub mod randomness_task;
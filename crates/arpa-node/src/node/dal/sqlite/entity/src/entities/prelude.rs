@@ -1,5 +1,7 @@
 //! SeaORM Entity. Generated by sea-orm-codegen 0.9.2
 
 pub use super::group_info::Entity as GroupInfo;
+pub use super::log_checkpoint::Entity as LogCheckpoint;
 pub use super::node_info::Entity as NodeInfo;
+pub use super::peer_record::Entity as PeerRecord;
 pub use super::randomness_task::Entity as RandomnessTask;
@@ -7,6 +7,7 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
+    pub chain_id: i32,
     pub index: i32,
     pub group_index: i32,
     pub assignment_block_height: i32,
@@ -4,6 +4,10 @@ mod m20220920_000001_create_node_info_table;
 mod m20220920_000002_create_group_info_table;
 mod m20220920_000003_create_randomness_task_table;
 mod m20220920_000004_create_randomness_task_index;
+mod m20221018_000005_add_chain_id_to_randomness_task;
+mod m20221101_000006_create_log_checkpoint_table;
+mod m20221205_000007_add_dkg_transcript_to_group_info;
+mod m20221212_000008_create_peer_record_table;
 
 pub struct Migrator;
 
@@ -15,6 +19,10 @@ impl MigratorTrait for Migrator {
             Box::new(m20220920_000002_create_group_info_table::Migration),
             Box::new(m20220920_000003_create_randomness_task_table::Migration),
             Box::new(m20220920_000004_create_randomness_task_index::Migration),
+            Box::new(m20221018_000005_add_chain_id_to_randomness_task::Migration),
+            Box::new(m20221101_000006_create_log_checkpoint_table::Migration),
+            Box::new(m20221205_000007_add_dkg_transcript_to_group_info::Migration),
+            Box::new(m20221212_000008_create_peer_record_table::Migration),
         ]
     }
 }
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220920_000003_create_randomness_task_table::RandomnessTask;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // existing tasks all come from the main chain
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RandomnessTask::Table)
+                    .add_column(
+                        ColumnDef::new(RandomnessTaskChain::ChainId)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("index").to_owned())
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(RandomnessTask::Table)
+                    .name("chain_id_index")
+                    .col(RandomnessTaskChain::ChainId)
+                    .col(RandomnessTask::Index)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("chain_id_index").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RandomnessTask::Table)
+                    .drop_column(RandomnessTaskChain::ChainId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(RandomnessTask::Table)
+                    .name("index")
+                    .col(RandomnessTask::Index)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum RandomnessTaskChain {
+    ChainId,
+}
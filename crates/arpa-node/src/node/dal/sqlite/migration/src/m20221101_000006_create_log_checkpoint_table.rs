@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LogCheckpoint::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LogCheckpoint::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LogCheckpoint::ChainId).integer().not_null())
+                    .col(ColumnDef::new(LogCheckpoint::LogType).integer().not_null())
+                    .col(
+                        ColumnDef::new(LogCheckpoint::BlockHeight)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LogCheckpoint::CreateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LogCheckpoint::UpdateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(LogCheckpoint::Table)
+                    .name("chain_id_log_type")
+                    .col(LogCheckpoint::ChainId)
+                    .col(LogCheckpoint::LogType)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("chain_id_log_type").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(LogCheckpoint::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum LogCheckpoint {
+    Table,
+    Id,
+    ChainId,
+    LogType,
+    BlockHeight,
+    CreateAt,
+    UpdateAt,
+}
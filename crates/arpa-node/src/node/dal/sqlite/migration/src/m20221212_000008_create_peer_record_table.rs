@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PeerRecord::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PeerRecord::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PeerRecord::IdAddress).text().not_null())
+                    .col(ColumnDef::new(PeerRecord::RpcEndpoint).text().not_null())
+                    .col(
+                        ColumnDef::new(PeerRecord::ExpiresAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PeerRecord::Signature)
+                            .blob(BlobSize::Medium)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PeerRecord::CreateAt).date_time().not_null())
+                    .col(ColumnDef::new(PeerRecord::UpdateAt).date_time().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(PeerRecord::Table)
+                    .name("peer_record_id_address")
+                    .col(PeerRecord::IdAddress)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("peer_record_id_address").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PeerRecord::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum PeerRecord {
+    Table,
+    Id,
+    IdAddress,
+    RpcEndpoint,
+    ExpiresAt,
+    Signature,
+    CreateAt,
+    UpdateAt,
+}
@@ -1,6 +1,8 @@
 use arpa_node_core::{format_now_date, DKGStatus};
 use entity::{group_info, node_info, randomness_task};
-use sea_orm::{ActiveModelTrait, DbBackend, DbConn, DbErr, FromQueryResult, Set, Statement};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DbBackend, DbConn, DbErr, FromQueryResult, Set, Statement,
+};
 
 pub struct NodeMutation;
 
@@ -86,6 +88,33 @@ impl GroupMutation {
         .await
     }
 
+    /// Inserts a group record of another database, e.g. of a backup.
+    pub async fn restore_group_info(
+        db: &DbConn,
+        model: group_info::Model,
+    ) -> Result<group_info::ActiveModel, DbErr> {
+        group_info::ActiveModel {
+            index: Set(model.index),
+            epoch: Set(model.epoch),
+            size: Set(model.size),
+            threshold: Set(model.threshold),
+            state: Set(model.state),
+            public_key: Set(model.public_key),
+            members: Set(model.members),
+            committers: Set(model.committers),
+            share: Set(model.share),
+            dkg_status: Set(model.dkg_status),
+            self_member_index: Set(model.self_member_index),
+            dkg_start_block_height: Set(model.dkg_start_block_height),
+            dkg_transcript: Set(model.dkg_transcript),
+            create_at: Set(model.create_at),
+            update_at: Set(format_now_date()),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+    }
+
     pub async fn update_dkg_status(
         db: &DbConn,
         model: group_info::Model,
@@ -107,6 +136,7 @@ impl GroupMutation {
         public_key: Vec<u8>,
         share: Vec<u8>,
         members: String,
+        dkg_transcript: Vec<u8>,
     ) -> Result<group_info::Model, DbErr> {
         let mut group_info: group_info::ActiveModel = model.into();
 
@@ -114,6 +144,7 @@ impl GroupMutation {
         group_info.public_key = Set(Some(public_key));
         group_info.share = Set(Some(share));
         group_info.members = Set(members);
+        group_info.dkg_transcript = Set(Some(dkg_transcript));
 
         group_info.update_at = Set(format_now_date());
 
@@ -141,12 +172,14 @@ pub struct RandomnessTaskMutation;
 impl RandomnessTaskMutation {
     pub async fn add_task(
         db: &DbConn,
+        chain_id: i32,
         index: i32,
         group_index: i32,
         assignment_block_height: i32,
         message: String,
     ) -> Result<randomness_task::ActiveModel, DbErr> {
         randomness_task::ActiveModel {
+            chain_id: Set(chain_id),
             index: Set(index),
             group_index: Set(group_index),
             assignment_block_height: Set(assignment_block_height),
@@ -162,15 +195,99 @@ impl RandomnessTaskMutation {
 
     pub async fn fetch_available_tasks(
         db: &DbConn,
-        group_index: i32,
+        chain_id: i32,
+        group_indices: &[i32],
         assignment_block_height: i32,
+        confirmed_block_height: i32,
     ) -> Result<Vec<randomness_task::Model>, DbErr> {
+        let mut values: Vec<sea_orm::Value> = vec![
+            chain_id.into(),
+            assignment_block_height.into(),
+            confirmed_block_height.into(),
+        ];
+
+        let group_index_placeholders = group_indices
+            .iter()
+            .enumerate()
+            .map(|(i, group_index)| {
+                values.push((*group_index).into());
+                format!("${}", i + 4)
+            })
+            .collect::<Vec<_>>();
+
+        let group_index_condition = if group_index_placeholders.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "group_index in ({}) or ",
+                group_index_placeholders.join(",")
+            )
+        };
+
         randomness_task::Model::find_by_statement(Statement::from_sql_and_values(
                 DbBackend::Sqlite,
-                r#"update randomness_task set state = 1 where state = 0 and (group_index = $1 or assignment_block_height < $2) 
+                &format!(r#"update randomness_task set state = 1 where chain_id = $1 and state = 0 and assignment_block_height <= $3 and ({}assignment_block_height < $2) 
+                returning *"#, group_index_condition),
+                values,
+            ))
+            .all(db).await
+    }
+
+    pub async fn rollback_tasks(
+        db: &DbConn,
+        chain_id: i32,
+        fork_block_height: i32,
+    ) -> Result<Vec<randomness_task::Model>, DbErr> {
+        randomness_task::Model::find_by_statement(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                r#"delete from randomness_task where chain_id = $1 and state = 0 and assignment_block_height >= $2 
                 returning *"#,
-                vec![group_index.into(),assignment_block_height.into()],
+                vec![chain_id.into(),fork_block_height.into()],
             ))
             .all(db).await
     }
 }
+
+pub struct LogCheckpointMutation;
+
+impl LogCheckpointMutation {
+    pub async fn save_block_height(
+        db: &DbConn,
+        chain_id: i32,
+        log_type: i32,
+        block_height: i32,
+    ) -> Result<(), DbErr> {
+        db.execute(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                r#"insert into log_checkpoint(chain_id, log_type, block_height, create_at, update_at) values($1, $2, $3, $4, $4) 
+                on conflict(chain_id, log_type) do update set block_height = max(block_height, excluded.block_height), update_at = excluded.update_at"#,
+                vec![chain_id.into(), log_type.into(), block_height.into(), format_now_date().into()],
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub struct PeerRecordMutation;
+
+impl PeerRecordMutation {
+    pub async fn save_peer_record(
+        db: &DbConn,
+        id_address: String,
+        rpc_endpoint: String,
+        expires_at: i64,
+        signature: Vec<u8>,
+    ) -> Result<(), DbErr> {
+        db.execute(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                r#"insert into peer_record(id_address, rpc_endpoint, expires_at, signature, create_at, update_at) values($1, $2, $3, $4, $5, $5) 
+                on conflict(id_address) do update set rpc_endpoint = excluded.rpc_endpoint, expires_at = excluded.expires_at, signature = excluded.signature, update_at = excluded.update_at 
+                where excluded.expires_at > peer_record.expires_at"#,
+                vec![id_address.into(), rpc_endpoint.into(), expires_at.into(), signature.into(), format_now_date().into()],
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
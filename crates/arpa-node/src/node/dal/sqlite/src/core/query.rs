@@ -1,7 +1,8 @@
 use entity::{
-    group_info,
+    group_info, log_checkpoint,
     node_info::{self, Entity as NodeInfo},
-    prelude::{GroupInfo, RandomnessTask},
+    peer_record,
+    prelude::{GroupInfo, LogCheckpoint, PeerRecord, RandomnessTask},
     randomness_task,
 };
 use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
@@ -20,10 +21,10 @@ impl NodeQuery {
 pub struct GroupQuery;
 
 impl GroupQuery {
-    pub async fn find_current_group_info(db: &DbConn) -> Result<Option<group_info::Model>, DbErr> {
+    pub async fn find_group_infos(db: &DbConn) -> Result<Vec<group_info::Model>, DbErr> {
         GroupInfo::find()
-            .order_by_desc(group_info::Column::Id)
-            .one(db)
+            .order_by_asc(group_info::Column::Id)
+            .all(db)
             .await
     }
 }
@@ -33,11 +34,52 @@ pub struct RandomnessTaskQuery;
 impl RandomnessTaskQuery {
     pub async fn select_by_index(
         db: &DbConn,
+        chain_id: i32,
         index: i32,
     ) -> Result<Option<randomness_task::Model>, DbErr> {
         RandomnessTask::find()
+            .filter(randomness_task::Column::ChainId.eq(chain_id))
             .filter(randomness_task::Column::Index.eq(index))
             .one(db)
             .await
     }
+    pub async fn select_by_state(
+        db: &DbConn,
+        chain_id: i32,
+        state: i32,
+    ) -> Result<Vec<randomness_task::Model>, DbErr> {
+        RandomnessTask::find()
+            .filter(randomness_task::Column::ChainId.eq(chain_id))
+            .filter(randomness_task::Column::State.eq(state))
+            .order_by_asc(randomness_task::Column::Index)
+            .all(db)
+            .await
+    }
+}
+
+pub struct LogCheckpointQuery;
+
+impl LogCheckpointQuery {
+    pub async fn select_by_log_type(
+        db: &DbConn,
+        chain_id: i32,
+        log_type: i32,
+    ) -> Result<Option<log_checkpoint::Model>, DbErr> {
+        LogCheckpoint::find()
+            .filter(log_checkpoint::Column::ChainId.eq(chain_id))
+            .filter(log_checkpoint::Column::LogType.eq(log_type))
+            .one(db)
+            .await
+    }
+}
+
+pub struct PeerRecordQuery;
+
+impl PeerRecordQuery {
+    pub async fn find_peer_records(db: &DbConn) -> Result<Vec<peer_record::Model>, DbErr> {
+        PeerRecord::find()
+            .order_by_asc(peer_record::Column::Id)
+            .all(db)
+            .await
+    }
 }
@@ -1,23 +1,29 @@
 pub mod core;
 use crate::core::GroupMutation;
 use crate::core::GroupQuery;
+use crate::core::LogCheckpointMutation;
+use crate::core::LogCheckpointQuery;
 use crate::core::NodeMutation;
 use crate::core::NodeQuery;
+use crate::core::PeerRecordMutation;
+use crate::core::PeerRecordQuery;
 use crate::core::RandomnessTaskMutation;
 use crate::core::RandomnessTaskQuery;
 use arpa_node_core::Group;
 use arpa_node_core::Member;
+use arpa_node_core::PeerRecord;
 use arpa_node_core::RANDOMNESS_TASK_EXCLUSIVE_WINDOW;
-use arpa_node_core::{address_to_string, format_now_date, RandomnessTask, Task};
-use arpa_node_dal::cache::InMemoryGroupInfoCache;
+use arpa_node_core::{address_to_string, format_now_date, LogType, RandomnessTask, Task};
 use arpa_node_dal::cache::InMemoryNodeInfoCache;
+use arpa_node_dal::cache::{GroupInfo, InMemoryGroupInfoCache};
 use arpa_node_dal::error::DataAccessResult;
 use arpa_node_dal::error::GroupError;
 use arpa_node_dal::error::RandomnessTaskError;
 use arpa_node_dal::NodeInfoUpdater;
 use arpa_node_dal::{
-    error::DataAccessError, BLSTasksFetcher, BLSTasksUpdater, DKGOutput, GroupInfoFetcher,
-    GroupInfoUpdater, NodeInfoFetcher,
+    error::DataAccessError, BLSTasksFetcher, BLSTasksUpdater, DKGOutput, DKGTranscript,
+    GroupInfoFetcher, GroupInfoUpdater, LogCheckpointFetcher, LogCheckpointUpdater,
+    NodeInfoFetcher,
 };
 use async_trait::async_trait;
 use entity::group_info;
@@ -31,6 +37,7 @@ use sea_orm::DbConn;
 use sea_orm::QueryResult;
 use sea_orm::Statement;
 use sea_orm::{ConnectOptions, DatabaseConnection, DbErr};
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::{marker::PhantomData, sync::Arc, time::Duration};
 mod test_helper;
@@ -45,6 +52,8 @@ pub type DBResult<A> = Result<A, DBError>;
 pub enum DBError {
     #[error("there is no node record yet, please run node with new-run mode")]
     NoNodeRecord,
+    #[error("the data key should be valid utf-8")]
+    InvalidDataKey,
     #[error(transparent)]
     DbError(#[from] DbErr),
     #[error(transparent)]
@@ -57,16 +66,23 @@ impl From<DBError> for DataAccessError {
     }
 }
 
+/// Secrets of the node to restore it from when the database is lost, i.e. the DKG key pair
+/// and the records of the groups it currently holds together with their shares.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataBackup {
+    pub id_address: String,
+    pub dkg_private_key: Vec<u8>,
+    pub dkg_public_key: Vec<u8>,
+    pub groups: Vec<group_info::Model>,
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct SqliteDB {
     connection: Arc<DatabaseConnection>,
 }
 
 impl SqliteDB {
-    pub async fn build(
-        db_path: &str,
-        signing_key: &[u8],
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    pub async fn build(db_path: &str, data_key: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
         let mut opt = ConnectOptions::new(format!("sqlite://{}?mode=rwc", db_path));
         opt.max_connections(100)
             .min_connections(5)
@@ -74,7 +90,7 @@ impl SqliteDB {
             .idle_timeout(Duration::from_secs(8))
             .max_lifetime(Duration::from_secs(8))
             .sqlx_logging(true)
-            .sqlcipher_key(String::from_utf8(signing_key.to_vec())?);
+            .sqlcipher_key(quote_data_key(data_key)?);
 
         let connection = sea_orm::Database::connect(opt).await?;
 
@@ -104,18 +120,107 @@ impl SqliteDB {
     pub fn get_group_info_client(&self) -> GroupInfoDBClient {
         GroupInfoDBClient {
             db_client: Arc::new(self.clone()),
-            group_info_cache: None,
-            group_info_cache_model: None,
+            group_info_cache: InMemoryGroupInfoCache::new(),
+            group_info_cache_models: BTreeMap::new(),
         }
     }
 
-    pub fn get_bls_tasks_client<T: Task>(&self) -> BLSTasksDBClient<T> {
+    /// Tasks of different chains are stored in the same table and isolated by chain id.
+    pub fn get_bls_tasks_client<T: Task>(&self, chain_id: usize) -> BLSTasksDBClient<T> {
         BLSTasksDBClient {
             db_client: Arc::new(self.clone()),
+            chain_id,
             bls_tasks: PhantomData,
         }
     }
 
+    /// Checkpoints the write-ahead log into the database file and closes all connections.
+    pub async fn close(&self) -> DBResult<()> {
+        self.connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                "PRAGMA wal_checkpoint(TRUNCATE);".to_owned(),
+            ))
+            .await?;
+
+        self.connection.get_sqlite_connection_pool().close().await;
+
+        Ok(())
+    }
+
+    /// Re-encrypts the database with the new key and closes all connections,
+    /// the database has to be built again with the new key to be used.
+    pub async fn rekey(self, data_key: &[u8]) -> DBResult<()> {
+        let data_key = quote_data_key(data_key)?;
+
+        // pages in the write-ahead log are encrypted with the old key
+        self.connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                "PRAGMA wal_checkpoint(TRUNCATE);".to_owned(),
+            ))
+            .await?;
+
+        self.connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                format!("PRAGMA rekey = {};", data_key),
+            ))
+            .await?;
+
+        // other connections of the pool are still keyed by the old key
+        self.connection.get_sqlite_connection_pool().close().await;
+
+        Ok(())
+    }
+
+    pub async fn export_backup(&self) -> DBResult<DataBackup> {
+        let node_info = NodeQuery::find_current_node_info(&self.connection)
+            .await?
+            .ok_or(DBError::NoNodeRecord)?;
+
+        let mut group_info_client = self.get_group_info_client();
+
+        let groups = match group_info_client.refresh_current_group_info().await {
+            Ok(()) => group_info_client
+                .group_info_cache_models
+                .into_values()
+                .collect(),
+            Err(DBError::GroupError(GroupError::NoGroupTask)) => vec![],
+            Err(e) => return Err(e),
+        };
+
+        Ok(DataBackup {
+            id_address: node_info.id_address,
+            dkg_private_key: node_info.dkg_private_key,
+            dkg_public_key: node_info.dkg_public_key,
+            groups,
+        })
+    }
+
+    /// Restores the DKG key pair and the groups of the backup into the current node record,
+    /// which is created by running node with new-run mode.
+    pub async fn import_backup(&self, backup: DataBackup) -> DBResult<()> {
+        let node_info = NodeQuery::find_current_node_info(&self.connection)
+            .await?
+            .ok_or(DBError::NoNodeRecord)?;
+
+        NodeMutation::update_node_dkg_key_pair(
+            &self.connection,
+            node_info,
+            backup.dkg_private_key,
+            backup.dkg_public_key,
+        )
+        .await?;
+
+        // later rows win over the existing ones of the same group and epoch
+        for group_info in backup.groups {
+            GroupMutation::restore_group_info(&self.connection, group_info).await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn integrity_check(&self) -> DBResult<String> {
         let query_res: Option<QueryResult> = self
             .connection
@@ -132,6 +237,13 @@ impl SqliteDB {
     }
 }
 
+// the key is set by pragma statements, quoted to be taken as a passphrase of any characters
+fn quote_data_key(data_key: &[u8]) -> DBResult<String> {
+    let data_key = str::from_utf8(data_key).map_err(|_| DBError::InvalidDataKey)?;
+
+    Ok(format!("'{}'", data_key.replace('\'', "''")))
+}
+
 #[derive(Debug)]
 pub struct NodeInfoDBClient {
     db_client: Arc<SqliteDB>,
@@ -190,55 +302,98 @@ impl NodeInfoDBClient {
 #[derive(Debug)]
 pub struct GroupInfoDBClient {
     db_client: Arc<SqliteDB>,
-    group_info_cache_model: Option<group_info::Model>,
-    group_info_cache: Option<InMemoryGroupInfoCache>,
+    group_info_cache_models: BTreeMap<(usize, usize), group_info::Model>,
+    group_info_cache: InMemoryGroupInfoCache,
 }
 
 impl GroupInfoDBClient {
+    /// Reloads the groups the node currently holds, i.e. the latest and the latest committed epoch of each group.
     pub async fn refresh_current_group_info(&mut self) -> DBResult<()> {
         let conn = &self.db_client.connection;
-        let group_info = GroupQuery::find_current_group_info(conn)
+        let group_info_models = GroupQuery::find_group_infos(conn).await?;
+
+        if group_info_models.is_empty() {
+            return Err(GroupError::NoGroupTask.into());
+        }
+
+        let mut groups = vec![];
+
+        let mut models = BTreeMap::new();
+
+        for group_info in group_info_models {
+            let group = Group {
+                index: group_info.index as usize,
+                epoch: group_info.epoch as usize,
+                size: group_info.size as usize,
+                threshold: group_info.threshold as usize,
+                state: group_info.state == 1,
+                public_key: group_info
+                    .public_key
+                    .as_ref()
+                    .map(|bytes| bincode::deserialize(bytes).unwrap()),
+                members: serde_json::from_str(&group_info.members).unwrap(),
+                committers: group_info
+                    .committers
+                    .as_ref()
+                    .map_or(vec![], |str| serde_json::from_str(str).unwrap()),
+            };
+
+            groups.push(GroupInfo::rebuild(
+                group_info
+                    .share
+                    .as_ref()
+                    .map(|bytes| bincode::deserialize(bytes).unwrap()),
+                group,
+                (group_info.dkg_status as usize).into(),
+                group_info.self_member_index as usize,
+                group_info.dkg_start_block_height as usize,
+                group_info
+                    .dkg_transcript
+                    .as_ref()
+                    .map(|bytes| bincode::deserialize(bytes).unwrap()),
+            ));
+
+            // rows are in insertion order, the last one wins if an epoch is saved more than once
+            models.insert(
+                (group_info.index as usize, group_info.epoch as usize),
+                group_info,
+            );
+        }
+
+        let peer_records = PeerRecordQuery::find_peer_records(conn)
             .await?
-            .ok_or(GroupError::NoGroupTask)?;
-
-        let group = Group {
-            index: group_info.index as usize,
-            epoch: group_info.epoch as usize,
-            size: group_info.size as usize,
-            threshold: group_info.threshold as usize,
-            state: group_info.state == 1,
-            public_key: group_info
-                .public_key
-                .as_ref()
-                .map(|bytes| bincode::deserialize(bytes).unwrap()),
-            members: serde_json::from_str(&group_info.members).unwrap(),
-            committers: group_info
-                .committers
-                .as_ref()
-                .map_or(vec![], |str| serde_json::from_str(str).unwrap()),
-        };
+            .into_iter()
+            .map(|peer_record| PeerRecord {
+                id_address: peer_record.id_address.parse().unwrap(),
+                rpc_endpoint: peer_record.rpc_endpoint,
+                expires_at: peer_record.expires_at as u64,
+                signature: peer_record.signature,
+            })
+            .collect();
 
-        self.group_info_cache = Some(InMemoryGroupInfoCache::rebuild(
-            group_info
-                .share
-                .as_ref()
-                .map(|bytes| bincode::deserialize(bytes).unwrap()),
-            group,
-            (group_info.dkg_status as usize).into(),
-            group_info.self_member_index as usize,
-            group_info.dkg_start_block_height as usize,
-        ));
+        self.group_info_cache = InMemoryGroupInfoCache::rebuild(groups, peer_records);
+
+        models.retain(|(index, epoch), _| {
+            self.group_info_cache.get_group_info(*index, *epoch).is_ok()
+        });
 
-        self.group_info_cache_model = Some(group_info);
+        self.group_info_cache_models = models;
 
         Ok(())
     }
 
-    fn only_has_group_task(&self) -> DBResult<()> {
-        self.group_info_cache
-            .as_ref()
-            .map(|_| ())
-            .ok_or_else(|| GroupError::NoGroupTask.into())
+    fn get_group_info_model(
+        &self,
+        index: usize,
+        epoch: usize,
+    ) -> DataAccessResult<group_info::Model> {
+        self.group_info_cache.get_group_info(index, epoch)?;
+
+        Ok(self
+            .group_info_cache_models
+            .get(&(index, epoch))
+            .unwrap()
+            .to_owned())
     }
 
     pub fn get_connection(&self) -> &DbConn {
@@ -249,6 +404,7 @@ impl GroupInfoDBClient {
 #[derive(Debug)]
 pub struct BLSTasksDBClient<T: Task> {
     db_client: Arc<SqliteDB>,
+    chain_id: usize,
     bls_tasks: PhantomData<T>,
 }
 
@@ -322,116 +478,103 @@ impl NodeInfoUpdater for NodeInfoDBClient {
 }
 
 impl GroupInfoFetcher for GroupInfoDBClient {
-    fn get_index(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_index()
+    fn get_indices(&self) -> Vec<usize> {
+        self.group_info_cache.get_indices()
     }
 
-    fn get_epoch(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_epoch()
+    fn get_ready_indices(&self) -> Vec<usize> {
+        self.group_info_cache.get_ready_indices()
     }
 
-    fn get_size(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_size()
+    fn get_epoch(&self, index: usize) -> DataAccessResult<usize> {
+        self.group_info_cache.get_epoch(index)
     }
 
-    fn get_threshold(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_threshold()
+    fn get_ready_epoch(&self, index: usize) -> DataAccessResult<usize> {
+        self.group_info_cache.get_ready_epoch(index)
     }
 
-    fn get_state(&self) -> DataAccessResult<bool> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_state()
+    fn get_size(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        self.group_info_cache.get_size(index, epoch)
     }
 
-    fn get_self_index(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_self_index()
+    fn get_threshold(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        self.group_info_cache.get_threshold(index, epoch)
     }
 
-    fn get_public_key(&self) -> DataAccessResult<&G1> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_public_key()
+    fn get_state(&self, index: usize, epoch: usize) -> DataAccessResult<bool> {
+        self.group_info_cache.get_state(index, epoch)
     }
 
-    fn get_secret_share(&self) -> DataAccessResult<&threshold_bls::sig::Share<Scalar>> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_secret_share()
+    fn get_self_index(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        self.group_info_cache.get_self_index(index, epoch)
     }
 
-    fn get_members(&self) -> DataAccessResult<&BTreeMap<Address, Member>> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_members()
+    fn get_public_key(&self, index: usize, epoch: usize) -> DataAccessResult<&G1> {
+        self.group_info_cache.get_public_key(index, epoch)
     }
 
-    fn get_member(&self, id_address: Address) -> DataAccessResult<&arpa_node_core::Member> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_member(id_address)
+    fn get_secret_share(
+        &self,
+        index: usize,
+        epoch: usize,
+    ) -> DataAccessResult<&threshold_bls::sig::Share<Scalar>> {
+        self.group_info_cache.get_secret_share(index, epoch)
     }
 
-    fn get_committers(&self) -> DataAccessResult<Vec<Address>> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
-
-        group_info.get_committers()
+    fn get_members(
+        &self,
+        index: usize,
+        epoch: usize,
+    ) -> DataAccessResult<&BTreeMap<Address, Member>> {
+        self.group_info_cache.get_members(index, epoch)
     }
 
-    fn get_dkg_start_block_height(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
-
-        let group_info = self.group_info_cache.as_ref().unwrap();
+    fn get_member(
+        &self,
+        index: usize,
+        epoch: usize,
+        id_address: Address,
+    ) -> DataAccessResult<&arpa_node_core::Member> {
+        self.group_info_cache.get_member(index, epoch, id_address)
+    }
 
-        group_info.get_dkg_start_block_height()
+    fn get_committers(&self, index: usize, epoch: usize) -> DataAccessResult<Vec<Address>> {
+        self.group_info_cache.get_committers(index, epoch)
     }
 
-    fn get_dkg_status(&self) -> DataAccessResult<arpa_node_core::DKGStatus> {
-        self.only_has_group_task()?;
+    fn get_dkg_start_block_height(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        self.group_info_cache
+            .get_dkg_start_block_height(index, epoch)
+    }
 
-        let group_info = self.group_info_cache.as_ref().unwrap();
+    fn get_dkg_status(
+        &self,
+        index: usize,
+        epoch: usize,
+    ) -> DataAccessResult<arpa_node_core::DKGStatus> {
+        self.group_info_cache.get_dkg_status(index, epoch)
+    }
 
-        group_info.get_dkg_status()
+    fn get_dkg_transcript(&self, index: usize, epoch: usize) -> DataAccessResult<&DKGTranscript> {
+        self.group_info_cache.get_dkg_transcript(index, epoch)
     }
 
-    fn is_committer(&self, id_address: Address) -> DataAccessResult<bool> {
-        self.only_has_group_task()?;
+    fn is_committer(
+        &self,
+        index: usize,
+        epoch: usize,
+        id_address: Address,
+    ) -> DataAccessResult<bool> {
+        self.group_info_cache.is_committer(index, epoch, id_address)
+    }
 
-        let group_info = self.group_info_cache.as_ref().unwrap();
+    fn get_peer_record(&self, id_address: Address) -> Option<&PeerRecord> {
+        self.group_info_cache.get_peer_record(id_address)
+    }
 
-        group_info.is_committer(id_address)
+    fn get_peer_records(&self) -> Vec<&PeerRecord> {
+        self.group_info_cache.get_peer_records()
     }
 }
 
@@ -449,7 +592,6 @@ impl GroupInfoUpdater for GroupInfoDBClient {
                 let member = Member {
                     index: *index,
                     id_address: *address,
-                    rpc_endpint: None,
                     partial_public_key: None,
                 };
                 (*address, member)
@@ -483,19 +625,11 @@ impl GroupInfoUpdater for GroupInfoDBClient {
         epoch: usize,
         output: DKGOutput<threshold_bls::schemes::bls12_381::G1Curve>,
     ) -> DataAccessResult<(G1, G1, Vec<Address>)> {
-        self.only_has_group_task()?;
-
-        let self_index = self.group_info_cache.as_ref().unwrap().get_self_index()?;
-
-        let mut group = self.group_info_cache.as_ref().unwrap().get_group().clone();
+        let group_info = self.group_info_cache.get_group_info(index, epoch)?;
 
-        if group.index != index {
-            return Err(GroupError::GroupIndexObsolete(group.index).into());
-        }
+        let self_index = group_info.get_self_index();
 
-        if group.epoch != epoch {
-            return Err(GroupError::GroupEpochObsolete(group.epoch).into());
-        }
+        let mut group = group_info.get_group().clone();
 
         if group.state {
             return Err(GroupError::GroupAlreadyReady.into());
@@ -523,17 +657,6 @@ impl GroupInfoUpdater for GroupInfoDBClient {
         let mut partial_public_key = G1::new();
 
         for (_, member) in group.members.iter_mut() {
-            if let Some(node) = output
-                .qual
-                .nodes
-                .iter()
-                .find(|node| member.index == node.id() as usize)
-            {
-                if let Some(rpc_endpoint) = node.get_rpc_endpoint() {
-                    member.rpc_endpint = Some(rpc_endpoint.to_string());
-                }
-            }
-
             member.partial_public_key = Some(output.public.eval(member.index as u32).value);
 
             if self_index == member.index {
@@ -543,11 +666,12 @@ impl GroupInfoUpdater for GroupInfoDBClient {
 
         GroupMutation::update_dkg_output(
             self.get_connection(),
-            self.group_info_cache_model.to_owned().unwrap(),
+            self.get_group_info_model(index, epoch)?,
             qualified_node_indices.len() as i32,
             bincode::serialize(&public_key).unwrap(),
             bincode::serialize(&output.share).unwrap(),
             serde_json::to_string(&group.members).unwrap(),
+            bincode::serialize(&output.transcript).unwrap(),
         )
         .await
         .map_err(|e| {
@@ -566,19 +690,7 @@ impl GroupInfoUpdater for GroupInfoDBClient {
         epoch: usize,
         dkg_status: arpa_node_core::DKGStatus,
     ) -> DataAccessResult<bool> {
-        self.only_has_group_task()?;
-
-        let current_dkg_status = self.group_info_cache.as_ref().unwrap().get_dkg_status()?;
-
-        let group = self.group_info_cache.as_ref().unwrap().get_group();
-
-        if group.index != index {
-            return Err(GroupError::GroupIndexObsolete(group.index).into());
-        }
-
-        if group.epoch != epoch {
-            return Err(GroupError::GroupEpochObsolete(group.epoch).into());
-        }
+        let current_dkg_status = self.group_info_cache.get_dkg_status(index, epoch)?;
 
         if current_dkg_status == dkg_status {
             return Ok(false);
@@ -586,7 +698,7 @@ impl GroupInfoUpdater for GroupInfoDBClient {
 
         GroupMutation::update_dkg_status(
             self.get_connection(),
-            self.group_info_cache_model.to_owned().unwrap(),
+            self.get_group_info_model(index, epoch)?,
             dkg_status.to_usize() as i32,
         )
         .await
@@ -606,25 +718,13 @@ impl GroupInfoUpdater for GroupInfoDBClient {
         epoch: usize,
         committer_indices: Vec<Address>,
     ) -> DataAccessResult<()> {
-        self.only_has_group_task()?;
-
-        let group = self.group_info_cache.as_ref().unwrap().get_group();
-
-        if group.index != index {
-            return Err(GroupError::GroupIndexObsolete(group.index).into());
-        }
-
-        if group.epoch != epoch {
-            return Err(GroupError::GroupEpochObsolete(group.epoch).into());
-        }
-
-        if group.state {
+        if self.group_info_cache.get_state(index, epoch)? {
             return Err(GroupError::GroupAlreadyReady.into());
         }
 
         GroupMutation::update_committers(
             self.get_connection(),
-            self.group_info_cache_model.to_owned().unwrap(),
+            self.get_group_info_model(index, epoch)?,
             serde_json::to_string(&committer_indices).unwrap(),
         )
         .await
@@ -637,29 +737,49 @@ impl GroupInfoUpdater for GroupInfoDBClient {
 
         Ok(())
     }
+
+    async fn save_peer_record(&mut self, peer_record: PeerRecord) -> DataAccessResult<bool> {
+        // a record expiring no later than the saved one is ignored by the statement
+        PeerRecordMutation::save_peer_record(
+            self.get_connection(),
+            address_to_string(peer_record.id_address),
+            peer_record.rpc_endpoint.clone(),
+            peer_record.expires_at as i64,
+            peer_record.signature.clone(),
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        Ok(self.group_info_cache.keep_later_peer_record(peer_record))
+    }
 }
 
 #[async_trait]
 impl BLSTasksFetcher<RandomnessTask> for BLSTasksDBClient<RandomnessTask> {
     async fn contains(&self, task_index: usize) -> DataAccessResult<bool> {
         let conn = &self.db_client.connection;
-        let task = RandomnessTaskQuery::select_by_index(conn, task_index as i32)
-            .await
-            .map_err(|e| {
-                let e: DBError = e.into();
-                e
-            })?;
+        let task =
+            RandomnessTaskQuery::select_by_index(conn, self.chain_id as i32, task_index as i32)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
         Ok(task.is_some())
     }
 
     async fn get(&self, task_index: usize) -> DataAccessResult<RandomnessTask> {
         let conn = &self.db_client.connection;
-        let task = RandomnessTaskQuery::select_by_index(conn, task_index as i32)
-            .await
-            .map_err(|e| {
-                let e: DBError = e.into();
-                e
-            })?;
+        let task =
+            RandomnessTaskQuery::select_by_index(conn, self.chain_id as i32, task_index as i32)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
 
         task.map(|model| RandomnessTask {
             index: model.index as usize,
@@ -672,14 +792,35 @@ impl BLSTasksFetcher<RandomnessTask> for BLSTasksDBClient<RandomnessTask> {
 
     async fn is_handled(&self, task_index: usize) -> DataAccessResult<bool> {
         let conn = &self.db_client.connection;
-        let task = RandomnessTaskQuery::select_by_index(conn, task_index as i32)
+        let task =
+            RandomnessTaskQuery::select_by_index(conn, self.chain_id as i32, task_index as i32)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(task.is_some() && task.unwrap().state == 1)
+    }
+
+    async fn get_pending_tasks(&self) -> DataAccessResult<Vec<RandomnessTask>> {
+        let conn = &self.db_client.connection;
+        let tasks = RandomnessTaskQuery::select_by_state(conn, self.chain_id as i32, 0)
             .await
             .map_err(|e| {
                 let e: DBError = e.into();
                 e
             })?;
 
-        Ok(task.is_some() && task.unwrap().state == 1)
+        Ok(tasks
+            .into_iter()
+            .map(|model| RandomnessTask {
+                index: model.index as usize,
+                message: model.message,
+                group_index: model.group_index as usize,
+                assignment_block_height: model.assignment_block_height as usize,
+            })
+            .collect())
     }
 }
 
@@ -688,6 +829,7 @@ impl BLSTasksUpdater<RandomnessTask> for BLSTasksDBClient<RandomnessTask> {
     async fn add(&mut self, task: RandomnessTask) -> DataAccessResult<()> {
         RandomnessTaskMutation::add_task(
             self.get_connection(),
+            self.chain_id as i32,
             task.index as i32,
             task.group_index as i32,
             task.assignment_block_height as i32,
@@ -705,12 +847,48 @@ impl BLSTasksUpdater<RandomnessTask> for BLSTasksDBClient<RandomnessTask> {
     async fn check_and_get_available_tasks(
         &mut self,
         current_block_height: usize,
-        current_group_index: usize,
+        confirmed_block_height: usize,
+        current_group_indices: &[usize],
     ) -> DataAccessResult<Vec<RandomnessTask>> {
+        let current_group_indices = current_group_indices
+            .iter()
+            .map(|index| *index as i32)
+            .collect::<Vec<_>>();
+
         RandomnessTaskMutation::fetch_available_tasks(
             self.get_connection(),
-            current_group_index as i32,
+            self.chain_id as i32,
+            &current_group_indices,
             (current_block_height - RANDOMNESS_TASK_EXCLUSIVE_WINDOW) as i32,
+            confirmed_block_height as i32,
+        )
+        .await
+        .map(|models| {
+            models
+                .into_iter()
+                .map(|model| RandomnessTask {
+                    index: model.index as usize,
+                    message: model.message,
+                    group_index: model.group_index as usize,
+                    assignment_block_height: model.assignment_block_height as usize,
+                })
+                .collect::<Vec<_>>()
+        })
+        .map_err(|e| {
+            let e: DBError = e.into();
+            let e: DataAccessError = e.into();
+            e
+        })
+    }
+
+    async fn rollback(
+        &mut self,
+        fork_block_height: usize,
+    ) -> DataAccessResult<Vec<RandomnessTask>> {
+        RandomnessTaskMutation::rollback_tasks(
+            self.get_connection(),
+            self.chain_id as i32,
+            fork_block_height as i32,
         )
         .await
         .map(|models| {
@@ -732,19 +910,62 @@ impl BLSTasksUpdater<RandomnessTask> for BLSTasksDBClient<RandomnessTask> {
     }
 }
 
+#[async_trait]
+impl<T: Task + Sync + Send> LogCheckpointFetcher for BLSTasksDBClient<T> {
+    async fn get_last_processed_block(&self, log_type: LogType) -> DataAccessResult<Option<usize>> {
+        let conn = &self.db_client.connection;
+        let checkpoint =
+            LogCheckpointQuery::select_by_log_type(conn, self.chain_id as i32, log_type.to_i32())
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(checkpoint.map(|model| model.block_height as usize))
+    }
+}
+
+#[async_trait]
+impl<T: Task + Sync + Send> LogCheckpointUpdater for BLSTasksDBClient<T> {
+    async fn set_last_processed_block(
+        &mut self,
+        log_type: LogType,
+        block_height: usize,
+    ) -> DataAccessResult<()> {
+        LogCheckpointMutation::save_block_height(
+            &self.db_client.connection,
+            self.chain_id as i32,
+            log_type.to_i32(),
+            block_height as i32,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub mod sqlite_tests {
     use crate::test_helper;
     use crate::DBError;
     use crate::SqliteDB;
     use arpa_node_core::DKGStatus;
-    use arpa_node_core::DKGTask;
+    use arpa_node_core::LogType;
     use arpa_node_core::RandomnessTask;
+    use arpa_node_core::{DKGProtocol, DKGTask, PeerRecord};
+    use arpa_node_dal::error::DataAccessError;
     use arpa_node_dal::error::GroupError;
     use arpa_node_dal::BLSTasksFetcher;
     use arpa_node_dal::BLSTasksUpdater;
     use arpa_node_dal::GroupInfoFetcher;
     use arpa_node_dal::GroupInfoUpdater;
+    use arpa_node_dal::LogCheckpointFetcher;
+    use arpa_node_dal::LogCheckpointUpdater;
     use arpa_node_dal::NodeInfoFetcher;
     use arpa_node_dal::NodeInfoUpdater;
     use ethers_core::types::Address;
@@ -797,113 +1018,286 @@ pub mod sqlite_tests {
     }
 
     #[tokio::test]
-    async fn test_save_node_info() {
+    async fn test_close_and_rebuild_db() {
         setup();
 
         let db = build_sqlite_db().await.unwrap();
 
-        let mut db = db.get_node_info_client();
+        let mut node_cache = db.get_node_info_client();
 
         let id_address = "0x0000000000000000000000000000000000000001"
             .parse()
             .unwrap();
-        let node_rpc_endpoint = String::from("127.0.0.1");
 
         let rng = &mut rand::thread_rng();
 
         let (private_key, public_key) = G1Scheme::keypair(rng);
 
-        if let Err(e) = db
-            .save_node_info(id_address, node_rpc_endpoint, private_key, public_key)
+        node_cache
+            .save_node_info(
+                id_address,
+                String::from("127.0.0.1"),
+                private_key,
+                public_key,
+            )
             .await
-        {
-            println!("{:?}", e);
-        }
+            .unwrap();
 
-        assert_eq!(id_address, db.get_id_address().unwrap());
-        assert_eq!("127.0.0.1", db.get_node_rpc_endpoint().unwrap());
+        db.close().await.unwrap();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut node_cache = db.get_node_info_client();
+
+        node_cache.refresh_current_node_info().await.unwrap();
+
+        assert_eq!(id_address, node_cache.get_id_address().unwrap());
 
         teardown();
     }
 
     #[tokio::test]
-    async fn test_save_node_rpc_endpoint() {
+    async fn test_rekey() {
         setup();
 
         let db = build_sqlite_db().await.unwrap();
 
-        let mut db = db.get_node_info_client();
+        let mut node_cache = db.get_node_info_client();
 
         let id_address = "0x0000000000000000000000000000000000000001"
             .parse()
             .unwrap();
-        let node_rpc_endpoint = String::from("127.0.0.1");
 
         let rng = &mut rand::thread_rng();
 
         let (private_key, public_key) = G1Scheme::keypair(rng);
 
-        if let Err(e) = db
-            .save_node_info(id_address, node_rpc_endpoint, private_key, public_key)
+        node_cache
+            .save_node_info(
+                id_address,
+                String::from("127.0.0.1"),
+                private_key,
+                public_key,
+            )
             .await
-        {
-            println!("{:?}", e);
-        }
+            .unwrap();
 
-        let node_rpc_endpoint = String::from("192.168.0.1");
+        let new_cipher_key = "new passphrase with 'quotes'";
 
-        if let Err(e) = db.set_node_rpc_endpoint(node_rpc_endpoint).await {
-            println!("{:?}", e);
-        }
+        db.rekey(new_cipher_key.as_bytes()).await.unwrap();
 
-        assert_eq!("192.168.0.1", db.get_node_rpc_endpoint().unwrap());
+        assert!(build_sqlite_db().await.is_err());
+
+        let db = SqliteDB::build(DB_PATH, new_cipher_key.as_bytes())
+            .await
+            .unwrap();
+
+        let mut node_cache = db.get_node_info_client();
+
+        node_cache.refresh_current_node_info().await.unwrap();
+
+        assert_eq!(id_address, node_cache.get_id_address().unwrap());
+        assert_eq!(&private_key, node_cache.get_dkg_private_key().unwrap());
 
         teardown();
     }
 
     #[tokio::test]
-    async fn test_save_node_dkg_key_pair() {
+    async fn test_export_and_import_backup() {
         setup();
 
         let db = build_sqlite_db().await.unwrap();
 
-        let mut db = db.get_node_info_client();
+        let mut node_cache = db.get_node_info_client();
 
         let id_address = "0x0000000000000000000000000000000000000001"
             .parse()
             .unwrap();
-        let node_rpc_endpoint = String::from("127.0.0.1");
 
         let rng = &mut rand::thread_rng();
 
         let (private_key, public_key) = G1Scheme::keypair(rng);
 
-        if let Err(e) = db
-            .save_node_info(id_address, node_rpc_endpoint, private_key, public_key)
+        node_cache
+            .save_node_info(
+                id_address,
+                String::from("127.0.0.1"),
+                private_key,
+                public_key,
+            )
             .await
-        {
-            println!("{:?}", e);
-        }
+            .unwrap();
 
-        let rng = &mut rand::thread_rng();
+        let mut group_cache = db.get_group_info_client();
 
-        let (private_key, public_key) = G1Scheme::keypair(rng);
+        group_cache
+            .save_task_info(0, build_dkg_task(1, 1))
+            .await
+            .unwrap();
 
-        if let Err(e) = db.set_dkg_key_pair(private_key, public_key).await {
-            println!("{:?}", e);
-        }
+        let (mut board, phase0s) = test_helper::setup::<bls12381::Curve, G1Scheme, _>(3, 3, rng);
 
-        assert_eq!(&private_key, db.get_dkg_private_key().unwrap());
-        assert_eq!(&public_key, db.get_dkg_public_key().unwrap());
+        let mut outputs =
+            test_helper::run_dkg::<bls12381::Curve, G1Scheme>(&mut board, phase0s).await;
 
-        teardown();
-    }
+        let output = outputs.remove(0);
 
-    #[tokio::test]
-    async fn test_get_current_group_info_when_no_task() {
-        setup();
+        group_cache.save_output(1, 1, output.clone()).await.unwrap();
 
-        let db = build_sqlite_db().await.unwrap();
+        let backup = db.export_backup().await.unwrap();
+
+        assert_eq!(1, backup.groups.len());
+
+        db.close().await.unwrap();
+
+        teardown();
+
+        // the database is lost, and the node is restarted with new-run mode
+        let db = build_sqlite_db().await.unwrap();
+
+        assert_eq!(DBError::NoNodeRecord, db.export_backup().await.unwrap_err());
+
+        let mut node_cache = db.get_node_info_client();
+
+        let (new_private_key, new_public_key) = G1Scheme::keypair(rng);
+
+        node_cache
+            .save_node_info(
+                id_address,
+                String::from("127.0.0.1"),
+                new_private_key,
+                new_public_key,
+            )
+            .await
+            .unwrap();
+
+        db.import_backup(backup).await.unwrap();
+
+        node_cache.refresh_current_node_info().await.unwrap();
+
+        assert_eq!(&private_key, node_cache.get_dkg_private_key().unwrap());
+        assert_eq!(&public_key, node_cache.get_dkg_public_key().unwrap());
+
+        let mut group_cache = db.get_group_info_client();
+
+        group_cache.refresh_current_group_info().await.unwrap();
+
+        assert_eq!(
+            output.share,
+            group_cache.get_secret_share(1, 1).unwrap().to_owned()
+        );
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_save_node_info() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_node_info_client();
+
+        let id_address = "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+        let node_rpc_endpoint = String::from("127.0.0.1");
+
+        let rng = &mut rand::thread_rng();
+
+        let (private_key, public_key) = G1Scheme::keypair(rng);
+
+        if let Err(e) = db
+            .save_node_info(id_address, node_rpc_endpoint, private_key, public_key)
+            .await
+        {
+            println!("{:?}", e);
+        }
+
+        assert_eq!(id_address, db.get_id_address().unwrap());
+        assert_eq!("127.0.0.1", db.get_node_rpc_endpoint().unwrap());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_save_node_rpc_endpoint() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_node_info_client();
+
+        let id_address = "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+        let node_rpc_endpoint = String::from("127.0.0.1");
+
+        let rng = &mut rand::thread_rng();
+
+        let (private_key, public_key) = G1Scheme::keypair(rng);
+
+        if let Err(e) = db
+            .save_node_info(id_address, node_rpc_endpoint, private_key, public_key)
+            .await
+        {
+            println!("{:?}", e);
+        }
+
+        let node_rpc_endpoint = String::from("192.168.0.1");
+
+        if let Err(e) = db.set_node_rpc_endpoint(node_rpc_endpoint).await {
+            println!("{:?}", e);
+        }
+
+        assert_eq!("192.168.0.1", db.get_node_rpc_endpoint().unwrap());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_save_node_dkg_key_pair() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_node_info_client();
+
+        let id_address = "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+        let node_rpc_endpoint = String::from("127.0.0.1");
+
+        let rng = &mut rand::thread_rng();
+
+        let (private_key, public_key) = G1Scheme::keypair(rng);
+
+        if let Err(e) = db
+            .save_node_info(id_address, node_rpc_endpoint, private_key, public_key)
+            .await
+        {
+            println!("{:?}", e);
+        }
+
+        let rng = &mut rand::thread_rng();
+
+        let (private_key, public_key) = G1Scheme::keypair(rng);
+
+        if let Err(e) = db.set_dkg_key_pair(private_key, public_key).await {
+            println!("{:?}", e);
+        }
+
+        assert_eq!(&private_key, db.get_dkg_private_key().unwrap());
+        assert_eq!(&public_key, db.get_dkg_public_key().unwrap());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_get_current_group_info_when_no_task() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
 
         let mut db = db.get_group_info_client();
 
@@ -947,20 +1341,21 @@ pub mod sqlite_tests {
             coordinator_address: "0x00000000000000000000000000000000000000c1"
                 .parse()
                 .unwrap(),
+            dkg_protocol: DKGProtocol::JointFeldman,
         };
 
         if let Err(e) = db.save_task_info(1, task).await {
             println!("{:?}", e);
         }
 
-        let res = db.group_info_cache.unwrap();
+        let res = &db.group_info_cache;
         println!("{:?}", res);
 
-        assert_eq!(3, res.get_size().unwrap());
-        assert_eq!(3, res.get_threshold().unwrap());
-        assert_eq!(0, res.get_member(member_1).unwrap().index);
-        assert_eq!(1, res.get_member(member_2).unwrap().index);
-        assert_eq!(2, res.get_member(member_3).unwrap().index);
+        assert_eq!(3, res.get_size(1, 1).unwrap());
+        assert_eq!(3, res.get_threshold(1, 1).unwrap());
+        assert_eq!(0, res.get_member(1, 1, member_1).unwrap().index);
+        assert_eq!(1, res.get_member(1, 1, member_2).unwrap().index);
+        assert_eq!(2, res.get_member(1, 1, member_3).unwrap().index);
 
         teardown();
     }
@@ -995,6 +1390,7 @@ pub mod sqlite_tests {
             coordinator_address: "0x00000000000000000000000000000000000000c1"
                 .parse()
                 .unwrap(),
+            dkg_protocol: DKGProtocol::JointFeldman,
         };
 
         if let Err(e) = db.save_task_info(1, task).await {
@@ -1005,10 +1401,10 @@ pub mod sqlite_tests {
             println!("{:?}", e);
         }
 
-        let res = db.group_info_cache.unwrap();
+        let res = &db.group_info_cache;
         println!("{:?}", res);
 
-        assert_eq!(DKGStatus::InPhase, res.get_dkg_status().unwrap());
+        assert_eq!(DKGStatus::InPhase, res.get_dkg_status(1, 1).unwrap());
 
         teardown();
     }
@@ -1043,6 +1439,7 @@ pub mod sqlite_tests {
             coordinator_address: "0x00000000000000000000000000000000000000c1"
                 .parse()
                 .unwrap(),
+            dkg_protocol: DKGProtocol::JointFeldman,
         };
 
         if let Err(e) = db.save_task_info(0, task).await {
@@ -1066,23 +1463,184 @@ pub mod sqlite_tests {
             println!("{:?}", e);
         }
 
-        let res = db.group_info_cache.unwrap();
+        let res = &db.group_info_cache;
         println!("{:?}", res);
 
-        assert_eq!(3, res.get_size().unwrap());
-        assert_eq!(output.share, res.get_secret_share().unwrap().to_owned());
-        assert_eq!(output.public.public_key(), res.get_public_key().unwrap());
+        assert_eq!(3, res.get_size(1, 1).unwrap());
+        assert_eq!(output.share, res.get_secret_share(1, 1).unwrap().to_owned());
+        assert_eq!(&output.transcript, res.get_dkg_transcript(1, 1).unwrap());
+        assert_eq!(
+            output.public.public_key(),
+            res.get_public_key(1, 1).unwrap()
+        );
         assert_eq!(
             Some(output.public.eval(0).value),
-            res.get_member(member_1).unwrap().partial_public_key
+            res.get_member(1, 1, member_1).unwrap().partial_public_key
         );
         assert_eq!(
             Some(output.public.eval(1).value),
-            res.get_member(member_2).unwrap().partial_public_key
+            res.get_member(1, 1, member_2).unwrap().partial_public_key
         );
         assert_eq!(
             Some(output.public.eval(2).value),
-            res.get_member(member_3).unwrap().partial_public_key
+            res.get_member(1, 1, member_3).unwrap().partial_public_key
+        );
+
+        teardown();
+    }
+
+    fn build_dkg_task(group_index: usize, epoch: usize) -> DKGTask {
+        let mut members: BTreeMap<Address, usize> = BTreeMap::new();
+        members.insert(
+            "0x0000000000000000000000000000000000000001"
+                .parse()
+                .unwrap(),
+            0,
+        );
+        members.insert(
+            "0x0000000000000000000000000000000000000002"
+                .parse()
+                .unwrap(),
+            1,
+        );
+        members.insert(
+            "0x0000000000000000000000000000000000000003"
+                .parse()
+                .unwrap(),
+            2,
+        );
+
+        DKGTask {
+            group_index,
+            epoch,
+            size: 3,
+            threshold: 3,
+            members,
+            assignment_block_height: 100,
+            coordinator_address: "0x00000000000000000000000000000000000000c1"
+                .parse()
+                .unwrap(),
+            dkg_protocol: DKGProtocol::JointFeldman,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_committed_epoch_is_kept_until_next_epoch_is_committed() {
+        setup();
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_group_info_client();
+
+        let committer: Address = "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+
+        db.save_task_info(0, build_dkg_task(1, 1)).await.unwrap();
+        db.save_committers(1, 1, vec![committer]).await.unwrap();
+
+        db.save_task_info(0, build_dkg_task(1, 2)).await.unwrap();
+
+        assert_eq!(2, db.get_epoch(1).unwrap());
+        assert_eq!(1, db.get_ready_epoch(1).unwrap());
+        assert_eq!(true, db.is_committer(1, 1, committer).unwrap());
+        assert_eq!(false, db.get_state(1, 2).unwrap());
+
+        db.save_committers(1, 2, vec![committer]).await.unwrap();
+
+        assert_eq!(2, db.get_ready_epoch(1).unwrap());
+        assert!(matches!(
+            db.get_state(1, 1),
+            Err(DataAccessError::GroupError(GroupError::GroupEpochObsolete(
+                2
+            )))
+        ));
+
+        db.refresh_current_group_info().await.unwrap();
+
+        assert_eq!(2, db.get_epoch(1).unwrap());
+        assert_eq!(2, db.get_ready_epoch(1).unwrap());
+        assert!(db.get_state(1, 1).is_err());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_hold_shares_of_multiple_groups() {
+        setup();
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_group_info_client();
+
+        db.save_task_info(0, build_dkg_task(1, 1)).await.unwrap();
+        db.save_task_info(2, build_dkg_task(2, 3)).await.unwrap();
+
+        assert_eq!(vec![1, 2], db.get_indices());
+        assert_eq!(Vec::<usize>::new(), db.get_ready_indices());
+        assert_eq!(0, db.get_self_index(1, 1).unwrap());
+        assert_eq!(2, db.get_self_index(2, 3).unwrap());
+
+        db.save_committers(2, 3, vec![]).await.unwrap();
+
+        assert_eq!(vec![2], db.get_ready_indices());
+        assert_eq!(3, db.get_ready_epoch(2).unwrap());
+        assert!(matches!(
+            db.get_ready_epoch(1),
+            Err(DataAccessError::GroupError(GroupError::GroupNotReady))
+        ));
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_save_peer_record() {
+        setup();
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut group_info_client = db.get_group_info_client();
+
+        group_info_client
+            .save_task_info(0, build_dkg_task(1, 1))
+            .await
+            .unwrap();
+
+        let id_address = Address::random();
+
+        let peer_record = |rpc_endpoint: &str, expires_at: u64| PeerRecord {
+            id_address,
+            rpc_endpoint: rpc_endpoint.to_string(),
+            expires_at,
+            signature: vec![1; 65],
+        };
+
+        assert!(group_info_client
+            .save_peer_record(peer_record("127.0.0.1:50061", 100))
+            .await
+            .unwrap());
+        assert!(group_info_client
+            .save_peer_record(peer_record("127.0.0.1:50062", 200))
+            .await
+            .unwrap());
+        assert!(!group_info_client
+            .save_peer_record(peer_record("127.0.0.1:50063", 150))
+            .await
+            .unwrap());
+
+        assert_eq!(
+            Some("127.0.0.1:50062"),
+            group_info_client.get_peer_endpoint(id_address, 199)
+        );
+        assert_eq!(None, group_info_client.get_peer_endpoint(id_address, 200));
+
+        let mut group_info_client = db.get_group_info_client();
+
+        group_info_client
+            .refresh_current_group_info()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            Some(&peer_record("127.0.0.1:50062", 200)),
+            group_info_client.get_peer_record(id_address)
         );
 
         teardown();
@@ -1094,7 +1652,7 @@ pub mod sqlite_tests {
 
         let db = build_sqlite_db().await.unwrap();
 
-        let mut db = db.get_bls_tasks_client::<RandomnessTask>();
+        let mut db = db.get_bls_tasks_client::<RandomnessTask>(0);
 
         let task = RandomnessTask {
             index: 1,
@@ -1111,10 +1669,16 @@ pub mod sqlite_tests {
         assert_eq!(task, db.get(1).await.unwrap());
         assert_eq!(false, db.is_handled(1).await.unwrap());
 
-        let available_tasks = db.check_and_get_available_tasks(100, 1).await.unwrap();
+        let available_tasks = db
+            .check_and_get_available_tasks(100, 100, &[1])
+            .await
+            .unwrap();
         assert_eq!(0, available_tasks.len());
 
-        let available_tasks = db.check_and_get_available_tasks(100, 2).await.unwrap();
+        let available_tasks = db
+            .check_and_get_available_tasks(100, 100, &[2])
+            .await
+            .unwrap();
         assert_eq!(1, available_tasks.len());
         assert_eq!(1, available_tasks[0].index);
         assert_eq!(String::from("test task"), available_tasks[0].message);
@@ -1125,7 +1689,10 @@ pub mod sqlite_tests {
         assert_eq!(task, db.get(1).await.unwrap());
         assert_eq!(true, db.is_handled(1).await.unwrap());
 
-        let available_tasks = db.check_and_get_available_tasks(100, 2).await.unwrap();
+        let available_tasks = db
+            .check_and_get_available_tasks(100, 100, &[2])
+            .await
+            .unwrap();
         assert_eq!(0, available_tasks.len());
 
         teardown();
@@ -1137,7 +1704,7 @@ pub mod sqlite_tests {
 
         let db = build_sqlite_db().await.unwrap();
 
-        let mut db = db.get_bls_tasks_client::<RandomnessTask>();
+        let mut db = db.get_bls_tasks_client::<RandomnessTask>(0);
 
         let task = RandomnessTask {
             index: 1,
@@ -1154,10 +1721,16 @@ pub mod sqlite_tests {
         assert_eq!(task, db.get(1).await.unwrap());
         assert_eq!(false, db.is_handled(1).await.unwrap());
 
-        let available_tasks = db.check_and_get_available_tasks(130, 1).await.unwrap();
+        let available_tasks = db
+            .check_and_get_available_tasks(130, 130, &[1])
+            .await
+            .unwrap();
         assert_eq!(0, available_tasks.len());
 
-        let available_tasks = db.check_and_get_available_tasks(131, 1).await.unwrap();
+        let available_tasks = db
+            .check_and_get_available_tasks(131, 131, &[1])
+            .await
+            .unwrap();
         assert_eq!(1, available_tasks.len());
         assert_eq!(1, available_tasks[0].index);
         assert_eq!(String::from("test task"), available_tasks[0].message);
@@ -1168,9 +1741,228 @@ pub mod sqlite_tests {
         assert_eq!(task, db.get(1).await.unwrap());
         assert_eq!(true, db.is_handled(1).await.unwrap());
 
-        let available_tasks = db.check_and_get_available_tasks(131, 1).await.unwrap();
+        let available_tasks = db
+            .check_and_get_available_tasks(131, 131, &[1])
+            .await
+            .unwrap();
         assert_eq!(0, available_tasks.len());
 
         teardown();
     }
+
+    #[tokio::test]
+    async fn test_randomness_task_waits_for_confirmations() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_bls_tasks_client::<RandomnessTask>(0);
+
+        let task = RandomnessTask {
+            index: 1,
+            message: String::from("test task"),
+            group_index: 2,
+            assignment_block_height: 100,
+        };
+
+        db.add(task.clone()).await.unwrap();
+
+        let available_tasks = db
+            .check_and_get_available_tasks(105, 99, &[2])
+            .await
+            .unwrap();
+        assert_eq!(0, available_tasks.len());
+        assert_eq!(false, db.is_handled(1).await.unwrap());
+
+        let available_tasks = db
+            .check_and_get_available_tasks(106, 100, &[2])
+            .await
+            .unwrap();
+        assert_eq!(1, available_tasks.len());
+        assert_eq!(task, available_tasks[0]);
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_rollback_orphaned_randomness_tasks() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_bls_tasks_client::<RandomnessTask>(0);
+
+        for (index, assignment_block_height) in [(1, 100), (2, 101), (3, 102)] {
+            let task = RandomnessTask {
+                index,
+                message: format!("test task {}", index),
+                group_index: 2,
+                assignment_block_height,
+            };
+
+            db.add(task).await.unwrap();
+        }
+
+        // the task of block 101 has been handled before the reorganization
+        let available_tasks = db
+            .check_and_get_available_tasks(101, 101, &[2])
+            .await
+            .unwrap();
+        assert_eq!(2, available_tasks.len());
+
+        let orphaned_tasks = db.rollback(101).await.unwrap();
+        assert_eq!(1, orphaned_tasks.len());
+        assert_eq!(3, orphaned_tasks[0].index);
+
+        assert_eq!(true, db.contains(2).await.unwrap());
+        assert_eq!(false, db.contains(3).await.unwrap());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_randomness_tasks_of_different_chains_are_isolated() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut main_chain_db = db.get_bls_tasks_client::<RandomnessTask>(0);
+
+        let mut adapter_chain_db = db.get_bls_tasks_client::<RandomnessTask>(1);
+
+        let main_chain_task = RandomnessTask {
+            index: 1,
+            message: String::from("main chain task"),
+            group_index: 2,
+            assignment_block_height: 100,
+        };
+
+        let adapter_chain_task = RandomnessTask {
+            index: 1,
+            message: String::from("adapter chain task"),
+            group_index: 2,
+            assignment_block_height: 100,
+        };
+
+        main_chain_db.add(main_chain_task.clone()).await.unwrap();
+        adapter_chain_db
+            .add(adapter_chain_task.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(main_chain_task, main_chain_db.get(1).await.unwrap());
+        assert_eq!(adapter_chain_task, adapter_chain_db.get(1).await.unwrap());
+
+        let available_tasks = main_chain_db
+            .check_and_get_available_tasks(100, 100, &[2])
+            .await
+            .unwrap();
+        assert_eq!(1, available_tasks.len());
+        assert_eq!(main_chain_task, available_tasks[0]);
+
+        assert_eq!(true, main_chain_db.is_handled(1).await.unwrap());
+        assert_eq!(false, adapter_chain_db.is_handled(1).await.unwrap());
+        assert_eq!(1, adapter_chain_db.get_pending_tasks().await.unwrap().len());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_get_pending_randomness_tasks() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_bls_tasks_client::<RandomnessTask>(0);
+
+        for (index, group_index) in [(1, 1), (2, 2)] {
+            let task = RandomnessTask {
+                index,
+                message: format!("test task {}", index),
+                group_index,
+                assignment_block_height: 100,
+            };
+
+            if let Err(e) = db.add(task).await {
+                println!("{:?}", e);
+            }
+        }
+
+        let pending_tasks = db.get_pending_tasks().await.unwrap();
+        assert_eq!(2, pending_tasks.len());
+        assert_eq!(1, pending_tasks[0].index);
+        assert_eq!(2, pending_tasks[1].index);
+
+        let available_tasks = db
+            .check_and_get_available_tasks(100, 100, &[2])
+            .await
+            .unwrap();
+        assert_eq!(1, available_tasks.len());
+
+        let pending_tasks = db.get_pending_tasks().await.unwrap();
+        assert_eq!(1, pending_tasks.len());
+        assert_eq!(1, pending_tasks[0].index);
+        assert_eq!(String::from("test task 1"), pending_tasks[0].message);
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_log_checkpoint() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut main_chain_db = db.get_bls_tasks_client::<RandomnessTask>(0);
+
+        let adapter_chain_db = db.get_bls_tasks_client::<RandomnessTask>(1);
+
+        assert_eq!(
+            None,
+            main_chain_db
+                .get_last_processed_block(LogType::RandomnessTask)
+                .await
+                .unwrap()
+        );
+
+        main_chain_db
+            .set_last_processed_block(LogType::RandomnessTask, 100)
+            .await
+            .unwrap();
+
+        main_chain_db
+            .set_last_processed_block(LogType::DKGTask, 50)
+            .await
+            .unwrap();
+
+        // the checkpoint never moves backward
+        main_chain_db
+            .set_last_processed_block(LogType::RandomnessTask, 90)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            Some(100),
+            main_chain_db
+                .get_last_processed_block(LogType::RandomnessTask)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Some(50),
+            main_chain_db
+                .get_last_processed_block(LogType::DKGTask)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            None,
+            adapter_chain_db
+                .get_last_processed_block(LogType::RandomnessTask)
+                .await
+                .unwrap()
+        );
+
+        teardown();
+    }
 }
@@ -1,6 +1,8 @@
 use async_trait::async_trait;
 use dkg_core::board::BoardPublisher;
-use dkg_core::primitives::types::{BundledJustification, BundledResponses, BundledShares};
+use dkg_core::primitives::types::{
+    BundledExtraction, BundledJustification, BundledResponses, BundledShares,
+};
 use dkg_core::primitives::{
     group::{Group, Node},
     joint_feldman,
@@ -14,8 +16,9 @@ use threshold_bls::{poly::Idx, sig::Scheme};
 #[derive(Default)]
 pub struct InMemoryBoard<C: Curve> {
     pub shares: Vec<BundledShares<C>>,
-    pub responses: Vec<BundledResponses>,
+    pub responses: Vec<BundledResponses<C>>,
     pub justifs: Vec<BundledJustification<C>>,
+    pub extractions: Vec<BundledExtraction<C>>,
 }
 
 impl<C: Curve> InMemoryBoard<C> {
@@ -25,6 +28,7 @@ impl<C: Curve> InMemoryBoard<C> {
             shares: vec![],
             responses: vec![],
             justifs: vec![],
+            extractions: vec![],
         }
     }
 }
@@ -41,7 +45,7 @@ impl<C: Curve> BoardPublisher<C> for InMemoryBoard<C> {
         Ok(())
     }
 
-    async fn publish_responses(&mut self, bundle: BundledResponses) -> Result<(), Self::Error>
+    async fn publish_responses(&mut self, bundle: BundledResponses<C>) -> Result<(), Self::Error>
     where
         C: 'async_trait,
     {
@@ -59,6 +63,14 @@ impl<C: Curve> BoardPublisher<C> for InMemoryBoard<C> {
         self.justifs.push(bundle);
         Ok(())
     }
+
+    async fn publish_extraction(&mut self, bundle: BundledExtraction<C>) -> Result<(), Self::Error>
+    where
+        C: 'async_trait,
+    {
+        self.extractions.push(bundle);
+        Ok(())
+    }
 }
 
 #[allow(unused)]
@@ -140,9 +152,7 @@ where
     // Create the Phase 0 for each participant
     let phase0s = keypairs
         .iter()
-        .map(|(private, _)| {
-            joint_feldman::DKG::new(private.clone(), String::from(""), group.clone()).unwrap()
-        })
+        .map(|(private, _)| joint_feldman::DKG::new(private.clone(), group.clone()).unwrap())
         .collect::<Vec<_>>();
 
     // Create the board
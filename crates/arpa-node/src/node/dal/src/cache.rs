@@ -2,17 +2,17 @@ use crate::error::{DataAccessResult, GroupError, NodeInfoError};
 
 use super::{
     BLSTasksFetcher, BLSTasksUpdater, BlockInfoFetcher, BlockInfoUpdater, GroupInfoFetcher,
-    GroupInfoUpdater, NodeInfoFetcher, NodeInfoUpdater, ResultCache, SignatureResultCacheFetcher,
-    SignatureResultCacheUpdater,
+    GroupInfoUpdater, LogCheckpointFetcher, LogCheckpointUpdater, NodeInfoFetcher, NodeInfoUpdater,
+    ResultCache, SignatureResultCacheFetcher, SignatureResultCacheUpdater,
 };
 use arpa_node_core::{
     BLSTask, ContractGroup, DKGStatus, DKGTask, Group, GroupRelayConfirmation,
-    GroupRelayConfirmationTask, GroupRelayTask, Member, RandomnessTask, Task, TaskError,
-    RANDOMNESS_TASK_EXCLUSIVE_WINDOW,
+    GroupRelayConfirmationTask, GroupRelayTask, LogType, Member, PeerRecord, RandomnessTask, Task,
+    TaskError, MAX_TRACKED_BLOCKS, RANDOMNESS_TASK_EXCLUSIVE_WINDOW,
 };
 use async_trait::async_trait;
-use dkg_core::primitives::DKGOutput;
-use ethers_core::types::Address;
+use dkg_core::primitives::{DKGOutput, DKGTranscript};
+use ethers_core::types::{Address, H256};
 use log::info;
 use std::collections::{BTreeMap, HashMap};
 use threshold_bls::group::Element;
@@ -24,11 +24,17 @@ use threshold_bls::{
 #[derive(Default)]
 pub struct InMemoryBlockInfoCache {
     block_height: usize,
+    confirmations: usize,
+    block_hashes: BTreeMap<usize, H256>,
 }
 
 impl InMemoryBlockInfoCache {
-    pub fn new() -> Self {
-        InMemoryBlockInfoCache { block_height: 0 }
+    pub fn new(confirmations: usize) -> Self {
+        InMemoryBlockInfoCache {
+            block_height: 0,
+            confirmations,
+            block_hashes: BTreeMap::new(),
+        }
     }
 }
 
@@ -36,12 +42,33 @@ impl BlockInfoFetcher for InMemoryBlockInfoCache {
     fn get_block_height(&self) -> usize {
         self.block_height
     }
+
+    fn get_confirmed_block_height(&self) -> usize {
+        self.block_height.saturating_sub(self.confirmations)
+    }
+
+    fn get_block_hash(&self, block_height: usize) -> Option<H256> {
+        self.block_hashes.get(&block_height).copied()
+    }
 }
 
 impl BlockInfoUpdater for InMemoryBlockInfoCache {
-    fn set_block_height(&mut self, block_height: usize) {
+    fn add_block(&mut self, block_height: usize, block_hash: H256) {
+        self.block_hashes.split_off(&block_height);
+
+        self.block_hashes.insert(block_height, block_hash);
+
+        self.block_hashes = self
+            .block_hashes
+            .split_off(&block_height.saturating_sub(MAX_TRACKED_BLOCKS - 1));
+
         self.block_height = block_height;
     }
+
+    fn rollback(&mut self, fork_block_height: usize) {
+        // the height is left to the new head, which may have been recorded already
+        self.block_hashes.split_off(&fork_block_height);
+    }
 }
 
 #[derive(Debug)]
@@ -120,47 +147,33 @@ impl NodeInfoFetcher for InMemoryNodeInfoCache {
     }
 }
 
-#[derive(Debug)]
-pub struct InMemoryGroupInfoCache {
+/// One epoch of a group the node belongs to.
+#[derive(Debug, Clone)]
+pub struct GroupInfo {
     pub(crate) share: Option<Share<Scalar>>,
     pub(crate) group: Group,
     pub(crate) dkg_status: DKGStatus,
     pub(crate) self_index: usize,
     pub(crate) dkg_start_block_height: usize,
+    pub(crate) dkg_transcript: Option<DKGTranscript>,
 }
 
-impl Default for InMemoryGroupInfoCache {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl InMemoryGroupInfoCache {
-    pub fn new() -> Self {
-        let group: Group = Group::new();
-
-        InMemoryGroupInfoCache {
-            share: None,
-            group,
-            dkg_status: DKGStatus::None,
-            self_index: 0,
-            dkg_start_block_height: 0,
-        }
-    }
-
+impl GroupInfo {
     pub fn rebuild(
         share: Option<Share<Scalar>>,
         group: Group,
         dkg_status: DKGStatus,
         self_index: usize,
         dkg_start_block_height: usize,
+        dkg_transcript: Option<DKGTranscript>,
     ) -> Self {
-        InMemoryGroupInfoCache {
+        GroupInfo {
             share,
             group,
             dkg_status,
             self_index,
             dkg_start_block_height,
+            dkg_transcript,
         }
     }
 
@@ -168,12 +181,97 @@ impl InMemoryGroupInfoCache {
         &self.group
     }
 
-    fn only_has_group_task(&self) -> DataAccessResult<()> {
-        if self.group.index == 0 {
-            return Err(GroupError::NoGroupTask.into());
+    pub fn get_self_index(&self) -> usize {
+        self.self_index
+    }
+
+    pub fn get_dkg_status(&self) -> DKGStatus {
+        self.dkg_status
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryGroupInfoCache {
+    pub(crate) groups: BTreeMap<(usize, usize), GroupInfo>,
+    pub(crate) peer_records: BTreeMap<Address, PeerRecord>,
+}
+
+impl InMemoryGroupInfoCache {
+    pub fn new() -> Self {
+        InMemoryGroupInfoCache {
+            groups: BTreeMap::new(),
+            peer_records: BTreeMap::new(),
+        }
+    }
+
+    pub fn rebuild(groups: Vec<GroupInfo>, peer_records: Vec<PeerRecord>) -> Self {
+        let mut cache = InMemoryGroupInfoCache::new();
+
+        for peer_record in peer_records {
+            cache.keep_later_peer_record(peer_record);
         }
 
-        Ok(())
+        for group_info in groups {
+            cache
+                .groups
+                .insert((group_info.group.index, group_info.group.epoch), group_info);
+        }
+
+        for index in cache.get_indices() {
+            cache.retire_obsolete_epochs(index);
+        }
+
+        cache
+    }
+
+    pub fn get_group_info(&self, index: usize, epoch: usize) -> DataAccessResult<&GroupInfo> {
+        self.groups.get(&(index, epoch)).ok_or_else(|| {
+            let e = match self.get_epoch(index) {
+                Ok(latest_epoch) => GroupError::GroupEpochObsolete(latest_epoch),
+                Err(_) => GroupError::NoGroupTask,
+            };
+            e.into()
+        })
+    }
+
+    fn get_group_info_mut(
+        &mut self,
+        index: usize,
+        epoch: usize,
+    ) -> DataAccessResult<&mut GroupInfo> {
+        self.get_group_info(index, epoch)?;
+
+        Ok(self.groups.get_mut(&(index, epoch)).unwrap())
+    }
+
+    pub fn get_group(&self, index: usize, epoch: usize) -> DataAccessResult<&Group> {
+        self.get_group_info(index, epoch)
+            .map(|group_info| &group_info.group)
+    }
+
+    /// Keeps the latest epoch and the latest committed epoch of the group, and drops the others.
+    pub fn retire_obsolete_epochs(&mut self, index: usize) {
+        let latest_epoch = self.get_epoch(index).ok();
+
+        let ready_epoch = self.get_ready_epoch(index).ok();
+
+        self.groups.retain(|(group_index, epoch), _| {
+            *group_index != index || Some(*epoch) == latest_epoch || Some(*epoch) == ready_epoch
+        });
+    }
+
+    /// Returns whether the record is kept, i.e. no record of the node expiring later is known.
+    pub fn keep_later_peer_record(&mut self, peer_record: PeerRecord) -> bool {
+        if let Some(known) = self.peer_records.get(&peer_record.id_address) {
+            if known.expires_at >= peer_record.expires_at {
+                return false;
+            }
+        }
+
+        self.peer_records
+            .insert(peer_record.id_address, peer_record);
+
+        true
     }
 }
 
@@ -185,61 +283,56 @@ impl GroupInfoUpdater for InMemoryGroupInfoCache {
         epoch: usize,
         dkg_status: DKGStatus,
     ) -> DataAccessResult<bool> {
-        self.only_has_group_task()?;
-
-        if self.group.index != index {
-            return Err(GroupError::GroupIndexObsolete(self.group.index).into());
-        }
+        let group_info = self.get_group_info_mut(index, epoch)?;
 
-        if self.group.epoch != epoch {
-            return Err(GroupError::GroupEpochObsolete(self.group.epoch).into());
-        }
-
-        if self.dkg_status == dkg_status {
+        if group_info.dkg_status == dkg_status {
             return Ok(false);
         }
 
         info!(
-            "dkg_status transfered from {:?} to {:?}",
-            self.dkg_status, dkg_status
+            "dkg_status of group {} epoch {} transfered from {:?} to {:?}",
+            index, epoch, group_info.dkg_status, dkg_status
         );
 
-        self.dkg_status = dkg_status;
+        group_info.dkg_status = dkg_status;
 
         Ok(true)
     }
 
     async fn save_task_info(&mut self, self_index: usize, task: DKGTask) -> DataAccessResult<()> {
-        self.self_index = self_index;
-
-        self.group.index = task.group_index;
-
-        self.group.epoch = task.epoch;
-
-        self.group.size = task.size;
+        let mut group = Group::new();
 
-        self.group.threshold = task.threshold;
+        group.index = task.group_index;
 
-        self.group.public_key = None;
+        group.epoch = task.epoch;
 
-        self.group.state = false;
+        group.size = task.size;
 
-        self.group.members.clear();
-
-        self.group.committers.clear();
-
-        self.dkg_start_block_height = task.assignment_block_height;
+        group.threshold = task.threshold;
 
         task.members.iter().for_each(|(address, index)| {
             let member = Member {
                 index: *index,
                 id_address: *address,
-                rpc_endpint: None,
                 partial_public_key: None,
             };
-            self.group.members.insert(*address, member);
+            group.members.insert(*address, member);
         });
 
+        self.groups.insert(
+            (task.group_index, task.epoch),
+            GroupInfo {
+                share: None,
+                group,
+                dkg_status: DKGStatus::None,
+                self_index,
+                dkg_start_block_height: task.assignment_block_height,
+                dkg_transcript: None,
+            },
+        );
+
+        self.retire_obsolete_epochs(task.group_index);
+
         Ok(())
     }
 
@@ -249,17 +342,9 @@ impl GroupInfoUpdater for InMemoryGroupInfoCache {
         epoch: usize,
         output: DKGOutput<Curve>,
     ) -> DataAccessResult<(G1, G1, Vec<Address>)> {
-        self.only_has_group_task()?;
-
-        if self.group.index != index {
-            return Err(GroupError::GroupIndexObsolete(self.group.index).into());
-        }
-
-        if self.group.epoch != epoch {
-            return Err(GroupError::GroupEpochObsolete(self.group.epoch).into());
-        }
+        let group_info = self.get_group_info_mut(index, epoch)?;
 
-        if self.group.state {
+        if group_info.group.state {
             return Err(GroupError::GroupAlreadyReady.into());
         }
 
@@ -271,7 +356,7 @@ impl GroupInfoUpdater for InMemoryGroupInfoCache {
             .map(|node| node.id() as usize)
             .collect::<Vec<_>>();
 
-        let disqualified_nodes = self
+        let disqualified_nodes = group_info
             .group
             .members
             .iter()
@@ -283,28 +368,19 @@ impl GroupInfoUpdater for InMemoryGroupInfoCache {
 
         let mut partial_public_key = G1::new();
 
-        self.share = Some(output.share);
-        self.group.size = qualified_node_indices.len();
-        self.group
+        group_info.share = Some(output.share);
+        group_info.dkg_transcript = Some(output.transcript);
+        group_info.group.size = qualified_node_indices.len();
+        group_info
+            .group
             .members
             .retain(|node, _| !disqualified_nodes.contains(node));
-        self.group.public_key = Some(public_key);
-
-        for (_, member) in self.group.members.iter_mut() {
-            if let Some(node) = output
-                .qual
-                .nodes
-                .iter()
-                .find(|node| member.index == node.id() as usize)
-            {
-                if let Some(rpc_endpoint) = node.get_rpc_endpoint() {
-                    member.rpc_endpint = Some(rpc_endpoint.to_string());
-                }
-            }
+        group_info.group.public_key = Some(public_key);
 
+        for (_, member) in group_info.group.members.iter_mut() {
             member.partial_public_key = Some(output.public.eval(member.index as u32).value);
 
-            if self.self_index == member.index {
+            if group_info.self_index == member.index {
                 partial_public_key = member.partial_public_key.unwrap();
             }
         }
@@ -318,138 +394,203 @@ impl GroupInfoUpdater for InMemoryGroupInfoCache {
         epoch: usize,
         committer_indices: Vec<Address>,
     ) -> DataAccessResult<()> {
-        self.only_has_group_task()?;
-
-        if self.group.index != index {
-            return Err(GroupError::GroupIndexObsolete(self.group.index).into());
-        }
-
-        if self.group.epoch != epoch {
-            return Err(GroupError::GroupEpochObsolete(self.group.epoch).into());
-        }
+        let group_info = self.get_group_info_mut(index, epoch)?;
 
-        if self.group.state {
+        if group_info.group.state {
             return Err(GroupError::GroupAlreadyReady.into());
         }
 
-        self.group.committers = committer_indices;
+        group_info.group.committers = committer_indices;
 
-        self.group.state = true;
+        group_info.group.state = true;
+
+        self.retire_obsolete_epochs(index);
 
         Ok(())
     }
+
+    async fn save_peer_record(&mut self, peer_record: PeerRecord) -> DataAccessResult<bool> {
+        Ok(self.keep_later_peer_record(peer_record))
+    }
 }
 
 impl GroupInfoFetcher for InMemoryGroupInfoCache {
-    fn get_index(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
-
-        Ok(self.group.index)
-    }
+    fn get_indices(&self) -> Vec<usize> {
+        let mut indices = self
+            .groups
+            .keys()
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>();
 
-    fn get_epoch(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
+        indices.dedup();
 
-        Ok(self.group.epoch)
+        indices
     }
 
-    fn get_size(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
+    fn get_ready_indices(&self) -> Vec<usize> {
+        let mut indices = self
+            .groups
+            .iter()
+            .filter(|(_, group_info)| group_info.group.state)
+            .map(|((index, _), _)| *index)
+            .collect::<Vec<_>>();
 
-        Ok(self.group.size)
-    }
+        indices.dedup();
 
-    fn get_threshold(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
+        indices
+    }
 
-        Ok(self.group.threshold)
+    fn get_epoch(&self, index: usize) -> DataAccessResult<usize> {
+        self.groups
+            .range((index, 0)..=(index, usize::MAX))
+            .next_back()
+            .map(|((_, epoch), _)| *epoch)
+            .ok_or_else(|| GroupError::NoGroupTask.into())
     }
 
-    fn get_state(&self) -> DataAccessResult<bool> {
-        self.only_has_group_task()?;
+    fn get_ready_epoch(&self, index: usize) -> DataAccessResult<usize> {
+        self.groups
+            .range((index, 0)..=(index, usize::MAX))
+            .rev()
+            .find(|(_, group_info)| group_info.group.state)
+            .map(|((_, epoch), _)| *epoch)
+            .ok_or_else(|| GroupError::GroupNotReady.into())
+    }
 
-        Ok(self.group.state)
+    fn get_size(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        Ok(self.get_group(index, epoch)?.size)
     }
 
-    fn get_self_index(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
+    fn get_threshold(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        Ok(self.get_group(index, epoch)?.threshold)
+    }
 
-        Ok(self.self_index)
+    fn get_state(&self, index: usize, epoch: usize) -> DataAccessResult<bool> {
+        Ok(self.get_group(index, epoch)?.state)
     }
 
-    fn get_public_key(&self) -> DataAccessResult<&G1> {
-        self.only_has_group_task()?;
+    fn get_self_index(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        Ok(self.get_group_info(index, epoch)?.self_index)
+    }
 
-        self.group
+    fn get_public_key(&self, index: usize, epoch: usize) -> DataAccessResult<&G1> {
+        self.get_group(index, epoch)?
             .public_key
             .as_ref()
             .ok_or(GroupError::GroupNotExisted)
             .map_err(|e| e.into())
     }
 
-    fn get_secret_share(&self) -> DataAccessResult<&Share<Scalar>> {
-        self.only_has_group_task()?;
-
-        self.share
+    fn get_secret_share(&self, index: usize, epoch: usize) -> DataAccessResult<&Share<Scalar>> {
+        self.get_group_info(index, epoch)?
+            .share
             .as_ref()
             .ok_or(GroupError::GroupNotReady)
             .map_err(|e| e.into())
     }
 
-    fn get_members(&self) -> DataAccessResult<&BTreeMap<Address, Member>> {
-        self.only_has_group_task()?;
-
-        Ok(&self.group.members)
+    fn get_members(
+        &self,
+        index: usize,
+        epoch: usize,
+    ) -> DataAccessResult<&BTreeMap<Address, Member>> {
+        Ok(&self.get_group(index, epoch)?.members)
     }
 
-    fn get_member(&self, id_address: Address) -> DataAccessResult<&Member> {
-        self.only_has_group_task()?;
-
-        self.group
+    fn get_member(
+        &self,
+        index: usize,
+        epoch: usize,
+        id_address: Address,
+    ) -> DataAccessResult<&Member> {
+        self.get_group(index, epoch)?
             .members
             .get(&id_address)
             .ok_or(GroupError::GroupNotExisted)
             .map_err(|e| e.into())
     }
 
-    fn get_committers(&self) -> DataAccessResult<Vec<Address>> {
-        self.only_has_group_task()?;
-
-        Ok(self.group.committers.clone())
+    fn get_committers(&self, index: usize, epoch: usize) -> DataAccessResult<Vec<Address>> {
+        Ok(self.get_group(index, epoch)?.committers.clone())
     }
 
-    fn get_dkg_start_block_height(&self) -> DataAccessResult<usize> {
-        self.only_has_group_task()?;
+    fn get_dkg_start_block_height(&self, index: usize, epoch: usize) -> DataAccessResult<usize> {
+        Ok(self.get_group_info(index, epoch)?.dkg_start_block_height)
+    }
 
-        Ok(self.dkg_start_block_height)
+    fn get_dkg_status(&self, index: usize, epoch: usize) -> DataAccessResult<DKGStatus> {
+        Ok(self.get_group_info(index, epoch)?.dkg_status)
     }
 
-    fn get_dkg_status(&self) -> DataAccessResult<DKGStatus> {
-        self.only_has_group_task()?;
+    fn get_dkg_transcript(&self, index: usize, epoch: usize) -> DataAccessResult<&DKGTranscript> {
+        self.get_group_info(index, epoch)?
+            .dkg_transcript
+            .as_ref()
+            .ok_or(GroupError::GroupNotReady)
+            .map_err(|e| e.into())
+    }
 
-        Ok(self.dkg_status)
+    fn is_committer(
+        &self,
+        index: usize,
+        epoch: usize,
+        id_address: Address,
+    ) -> DataAccessResult<bool> {
+        Ok(self
+            .get_group(index, epoch)?
+            .committers
+            .contains(&id_address))
     }
 
-    fn is_committer(&self, id_address: Address) -> DataAccessResult<bool> {
-        self.only_has_group_task()?;
+    fn get_peer_record(&self, id_address: Address) -> Option<&PeerRecord> {
+        self.peer_records.get(&id_address)
+    }
 
-        Ok(self.group.committers.contains(&id_address))
+    fn get_peer_records(&self) -> Vec<&PeerRecord> {
+        self.peer_records.values().collect()
     }
 }
 
 #[derive(Default)]
 pub struct InMemoryBLSTasksQueue<T: Task> {
     bls_tasks: Vec<BLSTask<T>>,
+    last_processed_blocks: HashMap<i32, usize>,
 }
 
 impl<T: Task> InMemoryBLSTasksQueue<T> {
     pub fn new() -> Self {
         InMemoryBLSTasksQueue {
             bls_tasks: Vec::new(),
+            last_processed_blocks: HashMap::new(),
         }
     }
 }
 
+#[async_trait]
+impl<T: Task + Sync> LogCheckpointFetcher for InMemoryBLSTasksQueue<T> {
+    async fn get_last_processed_block(&self, log_type: LogType) -> DataAccessResult<Option<usize>> {
+        Ok(self.last_processed_blocks.get(&log_type.to_i32()).copied())
+    }
+}
+
+#[async_trait]
+impl<T: Task + Send> LogCheckpointUpdater for InMemoryBLSTasksQueue<T> {
+    async fn set_last_processed_block(
+        &mut self,
+        log_type: LogType,
+        block_height: usize,
+    ) -> DataAccessResult<()> {
+        let last_processed_block = self
+            .last_processed_blocks
+            .entry(log_type.to_i32())
+            .or_insert(block_height);
+
+        *last_processed_block = (*last_processed_block).max(block_height);
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<T: Task + Sync + Clone> BLSTasksFetcher<T> for InMemoryBLSTasksQueue<T> {
     async fn contains(&self, task_index: usize) -> DataAccessResult<bool> {
@@ -461,17 +602,28 @@ impl<T: Task + Sync + Clone> BLSTasksFetcher<T> for InMemoryBLSTasksQueue<T> {
 
     async fn get(&self, task_index: usize) -> DataAccessResult<T> {
         self.bls_tasks
-            .get(task_index)
+            .iter()
+            .find(|task| task.task.index() == task_index)
             .map(|task| task.task.clone())
             .ok_or_else(|| TaskError::TaskNotFound.into())
     }
 
     async fn is_handled(&self, task_index: usize) -> DataAccessResult<bool> {
-        Ok(*self
+        Ok(self
+            .bls_tasks
+            .iter()
+            .find(|task| task.task.index() == task_index)
+            .map(|task| task.state)
+            .unwrap_or(false))
+    }
+
+    async fn get_pending_tasks(&self) -> DataAccessResult<Vec<T>> {
+        Ok(self
             .bls_tasks
-            .get(task_index)
-            .map(|task| &task.state)
-            .unwrap_or(&false))
+            .iter()
+            .filter(|task| !task.state)
+            .map(|task| task.task.clone())
+            .collect())
     }
 }
 
@@ -486,14 +638,16 @@ impl BLSTasksUpdater<RandomnessTask> for InMemoryBLSTasksQueue<RandomnessTask> {
     async fn check_and_get_available_tasks(
         &mut self,
         current_block_height: usize,
-        current_group_index: usize,
+        confirmed_block_height: usize,
+        current_group_indices: &[usize],
     ) -> DataAccessResult<Vec<RandomnessTask>> {
         let available_tasks = self
             .bls_tasks
             .iter_mut()
             .filter(|task| !task.state)
+            .filter(|task| task.task.assignment_block_height <= confirmed_block_height)
             .filter(|task| {
-                task.task.group_index == current_group_index
+                current_group_indices.contains(&task.task.group_index)
                     || current_block_height
                         > task.task.assignment_block_height + RANDOMNESS_TASK_EXCLUSIVE_WINDOW
             })
@@ -505,6 +659,20 @@ impl BLSTasksUpdater<RandomnessTask> for InMemoryBLSTasksQueue<RandomnessTask> {
 
         Ok(available_tasks)
     }
+
+    async fn rollback(
+        &mut self,
+        fork_block_height: usize,
+    ) -> DataAccessResult<Vec<RandomnessTask>> {
+        let (orphaned_tasks, bls_tasks): (Vec<_>, Vec<_>) =
+            self.bls_tasks.drain(..).partition(|task| {
+                !task.state && task.task.assignment_block_height >= fork_block_height
+            });
+
+        self.bls_tasks = bls_tasks;
+
+        Ok(orphaned_tasks.into_iter().map(|task| task.task).collect())
+    }
 }
 
 #[async_trait]
@@ -518,13 +686,19 @@ impl BLSTasksUpdater<GroupRelayTask> for InMemoryBLSTasksQueue<GroupRelayTask> {
     async fn check_and_get_available_tasks(
         &mut self,
         _: usize,
-        current_group_index: usize,
+        confirmed_block_height: usize,
+        current_group_indices: &[usize],
     ) -> DataAccessResult<Vec<GroupRelayTask>> {
         let available_tasks = self
             .bls_tasks
             .iter_mut()
             .filter(|task| !task.state)
-            .filter(|task| task.task.relayed_group_index != current_group_index)
+            .filter(|task| task.task.assignment_block_height <= confirmed_block_height)
+            .filter(|task| {
+                current_group_indices
+                    .iter()
+                    .any(|index| *index != task.task.relayed_group_index)
+            })
             .map(|task| {
                 task.state = true;
                 task.task.clone()
@@ -533,6 +707,20 @@ impl BLSTasksUpdater<GroupRelayTask> for InMemoryBLSTasksQueue<GroupRelayTask> {
 
         Ok(available_tasks)
     }
+
+    async fn rollback(
+        &mut self,
+        fork_block_height: usize,
+    ) -> DataAccessResult<Vec<GroupRelayTask>> {
+        let (orphaned_tasks, bls_tasks): (Vec<_>, Vec<_>) =
+            self.bls_tasks.drain(..).partition(|task| {
+                !task.state && task.task.assignment_block_height >= fork_block_height
+            });
+
+        self.bls_tasks = bls_tasks;
+
+        Ok(orphaned_tasks.into_iter().map(|task| task.task).collect())
+    }
 }
 
 #[async_trait]
@@ -548,13 +736,15 @@ impl BLSTasksUpdater<GroupRelayConfirmationTask>
     async fn check_and_get_available_tasks(
         &mut self,
         _: usize,
-        current_group_index: usize,
+        confirmed_block_height: usize,
+        current_group_indices: &[usize],
     ) -> DataAccessResult<Vec<GroupRelayConfirmationTask>> {
         let available_tasks = self
             .bls_tasks
             .iter_mut()
             .filter(|task| !task.state)
-            .filter(|task| task.task.relayed_group_index == current_group_index)
+            .filter(|task| task.task.assignment_block_height <= confirmed_block_height)
+            .filter(|task| current_group_indices.contains(&task.task.relayed_group_index))
             .map(|task| {
                 task.state = true;
                 task.task.clone()
@@ -563,6 +753,20 @@ impl BLSTasksUpdater<GroupRelayConfirmationTask>
 
         Ok(available_tasks)
     }
+
+    async fn rollback(
+        &mut self,
+        fork_block_height: usize,
+    ) -> DataAccessResult<Vec<GroupRelayConfirmationTask>> {
+        let (orphaned_tasks, bls_tasks): (Vec<_>, Vec<_>) =
+            self.bls_tasks.drain(..).partition(|task| {
+                !task.state && task.task.assignment_block_height >= fork_block_height
+            });
+
+        self.bls_tasks = bls_tasks;
+
+        Ok(orphaned_tasks.into_iter().map(|task| task.task).collect())
+    }
 }
 
 #[derive(Default)]
@@ -644,6 +848,10 @@ impl<T: ResultCache> SignatureResultCacheFetcher<T> for InMemorySignatureResultC
     fn get(&self, signature_index: usize) -> Option<&BLSResultCache<T>> {
         self.signature_result_caches.get(&signature_index)
     }
+
+    fn get_all(&self) -> Vec<&BLSResultCache<T>> {
+        self.signature_result_caches.values().collect()
+    }
 }
 
 impl SignatureResultCacheUpdater<RandomnessResultCache>
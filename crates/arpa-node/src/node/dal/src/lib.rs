@@ -3,12 +3,13 @@ pub mod error;
 
 use std::collections::BTreeMap;
 
-use arpa_node_core::{DKGStatus, DKGTask, Member, Task};
+use arpa_node_core::{DKGStatus, DKGTask, LogType, Member, PeerRecord, Task};
 use async_trait::async_trait;
 use cache::BLSResultCache;
-pub use dkg_core::primitives::DKGOutput;
+use dkg_core::primitives::task_committers;
+pub use dkg_core::primitives::{DKGOutput, DKGTranscript};
 use error::DataAccessResult;
-use ethers_core::types::Address;
+use ethers_core::types::{Address, H256};
 use threshold_bls::{
     curve::bls12381::{Curve, Scalar, G1},
     sig::Share,
@@ -16,10 +17,20 @@ use threshold_bls::{
 
 pub trait BlockInfoFetcher {
     fn get_block_height(&self) -> usize;
+
+    /// The latest block height with enough confirmations, tasks emitted after it are not handled yet.
+    fn get_confirmed_block_height(&self) -> usize;
+
+    /// Hash of a recent block on the canonical chain, if it is still tracked.
+    fn get_block_hash(&self, block_height: usize) -> Option<H256>;
 }
 
 pub trait BlockInfoUpdater {
-    fn set_block_height(&mut self, block_height: usize);
+    /// Records the new head, replacing the tracked blocks at and above its height.
+    fn add_block(&mut self, block_height: usize, block_hash: H256);
+
+    /// Forgets the tracked blocks orphaned by a chain reorganization.
+    fn rollback(&mut self, fork_block_height: usize);
 }
 
 #[async_trait]
@@ -43,6 +54,8 @@ pub trait NodeInfoFetcher {
     fn get_dkg_public_key(&self) -> DataAccessResult<&G1>;
 }
 
+/// Groups are keyed by group index and epoch. A node can belong to several groups at the same time,
+/// and keeps the last committed epoch of a group until a newer one is committed.
 #[async_trait]
 pub trait GroupInfoUpdater {
     async fn save_task_info(&mut self, self_index: usize, task: DKGTask) -> DataAccessResult<()>;
@@ -61,42 +74,124 @@ pub trait GroupInfoUpdater {
         dkg_status: DKGStatus,
     ) -> DataAccessResult<bool>;
 
+    /// Marks the epoch as ready and retires the earlier epochs of the group.
     async fn save_committers(
         &mut self,
         index: usize,
         epoch: usize,
         committer_indices: Vec<Address>,
     ) -> DataAccessResult<()>;
+
+    /// Keeps the peer record unless a record of the same node expiring later is known,
+    /// returns whether it is kept. The signature is checked by the caller.
+    async fn save_peer_record(&mut self, peer_record: PeerRecord) -> DataAccessResult<bool>;
 }
 
 pub trait GroupInfoFetcher {
-    fn get_index(&self) -> DataAccessResult<usize>;
+    /// Indices of all the groups the node holds an epoch of, in ascending order.
+    fn get_indices(&self) -> Vec<usize>;
+
+    /// Indices of the groups with a committed epoch, in ascending order.
+    fn get_ready_indices(&self) -> Vec<usize>;
+
+    /// The latest epoch of the group, which can still be in DKG.
+    fn get_epoch(&self, index: usize) -> DataAccessResult<usize>;
+
+    /// The latest committed epoch of the group, whose share is used for signing.
+    fn get_ready_epoch(&self, index: usize) -> DataAccessResult<usize>;
 
-    fn get_epoch(&self) -> DataAccessResult<usize>;
+    fn get_size(&self, index: usize, epoch: usize) -> DataAccessResult<usize>;
 
-    fn get_size(&self) -> DataAccessResult<usize>;
+    fn get_threshold(&self, index: usize, epoch: usize) -> DataAccessResult<usize>;
 
-    fn get_threshold(&self) -> DataAccessResult<usize>;
+    fn get_state(&self, index: usize, epoch: usize) -> DataAccessResult<bool>;
 
-    fn get_state(&self) -> DataAccessResult<bool>;
+    fn get_self_index(&self, index: usize, epoch: usize) -> DataAccessResult<usize>;
 
-    fn get_self_index(&self) -> DataAccessResult<usize>;
+    fn get_public_key(&self, index: usize, epoch: usize) -> DataAccessResult<&G1>;
 
-    fn get_public_key(&self) -> DataAccessResult<&G1>;
+    fn get_secret_share(&self, index: usize, epoch: usize) -> DataAccessResult<&Share<Scalar>>;
 
-    fn get_secret_share(&self) -> DataAccessResult<&Share<Scalar>>;
+    fn get_members(
+        &self,
+        index: usize,
+        epoch: usize,
+    ) -> DataAccessResult<&BTreeMap<Address, Member>>;
 
-    fn get_members(&self) -> DataAccessResult<&BTreeMap<Address, Member>>;
+    fn get_member(
+        &self,
+        index: usize,
+        epoch: usize,
+        id_address: Address,
+    ) -> DataAccessResult<&Member>;
 
-    fn get_member(&self, id_address: Address) -> DataAccessResult<&Member>;
+    fn get_committers(&self, index: usize, epoch: usize) -> DataAccessResult<Vec<Address>>;
 
-    fn get_committers(&self) -> DataAccessResult<Vec<Address>>;
+    fn get_dkg_start_block_height(&self, index: usize, epoch: usize) -> DataAccessResult<usize>;
 
-    fn get_dkg_start_block_height(&self) -> DataAccessResult<usize>;
+    fn get_dkg_status(&self, index: usize, epoch: usize) -> DataAccessResult<DKGStatus>;
 
-    fn get_dkg_status(&self) -> DataAccessResult<DKGStatus>;
+    /// The statuses and valid complaints of the DKG of the epoch, once it has an output.
+    fn get_dkg_transcript(&self, index: usize, epoch: usize) -> DataAccessResult<&DKGTranscript>;
+
+    fn is_committer(
+        &self,
+        index: usize,
+        epoch: usize,
+        id_address: Address,
+    ) -> DataAccessResult<bool>;
 
-    fn is_committer(&self, id_address: Address) -> DataAccessResult<bool>;
+    /// The committers of a randomness task of the group, followed by its backup committers.
+    /// They rotate over the members with every task, as many of each as the committers chosen
+    /// for the group in DKG.
+    fn get_task_committers(
+        &self,
+        index: usize,
+        epoch: usize,
+        task_index: usize,
+        message: &[u8],
+    ) -> DataAccessResult<(Vec<Address>, Vec<Address>)> {
+        let members = self
+            .get_members(index, epoch)?
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+
+        let count = self.get_committers(index, epoch)?.len();
+
+        Ok(task_committers(task_index, message, &members, count))
+    }
+
+    /// Whether the address is a member of the latest or the latest committed epoch of any group.
+    fn is_member_of_any_group(&self, id_address: Address) -> bool {
+        self.get_indices().into_iter().any(|index| {
+            [self.get_ready_epoch(index).ok(), self.get_epoch(index).ok()]
+                .iter()
+                .flatten()
+                .any(|epoch| self.get_member(index, *epoch, id_address).is_ok())
+        })
+    }
+
+    /// The latest peer record announced by the node, which can have expired.
+    fn get_peer_record(&self, id_address: Address) -> Option<&PeerRecord>;
+
+    fn get_peer_records(&self) -> Vec<&PeerRecord>;
+
+    /// The endpoint the node currently serves its committer rpc on, if it has announced one.
+    fn get_peer_endpoint(&self, id_address: Address, now: u64) -> Option<&str> {
+        self.get_peer_record(id_address)
+            .filter(|record| !record.is_expired(now))
+            .map(|record| record.rpc_endpoint.as_str())
+    }
+
+    /// Whether the address is a committer of the latest committed epoch of any group.
+    fn is_committer_of_any_ready_group(&self, id_address: Address) -> bool {
+        self.get_ready_indices().into_iter().any(|index| {
+            self.get_ready_epoch(index)
+                .and_then(|epoch| self.is_committer(index, epoch, id_address))
+                .unwrap_or(false)
+        })
+    }
 }
 
 #[async_trait]
@@ -106,23 +201,50 @@ pub trait BLSTasksFetcher<T> {
     async fn get(&self, task_index: usize) -> DataAccessResult<T>;
 
     async fn is_handled(&self, task_index: usize) -> DataAccessResult<bool>;
+
+    async fn get_pending_tasks(&self) -> DataAccessResult<Vec<T>>;
 }
 
 #[async_trait]
 pub trait BLSTasksUpdater<T: Task> {
     async fn add(&mut self, task: T) -> DataAccessResult<()>;
 
+    /// Marks and returns the tasks that can be handled by any of the given groups the node is in.
+    /// Tasks emitted after the confirmed block height are kept until they get enough confirmations.
     async fn check_and_get_available_tasks(
         &mut self,
         current_block_height: usize,
-        current_group_index: usize,
+        confirmed_block_height: usize,
+        current_group_indices: &[usize],
     ) -> DataAccessResult<Vec<T>>;
+
+    /// Removes and returns the pending tasks emitted at or above the fork block height.
+    async fn rollback(&mut self, fork_block_height: usize) -> DataAccessResult<Vec<T>>;
+}
+
+/// Keeps the last block of which the logs of each type have been processed on the chain,
+/// so that the logs emitted while the node was down can be back-filled.
+#[async_trait]
+pub trait LogCheckpointFetcher {
+    async fn get_last_processed_block(&self, log_type: LogType) -> DataAccessResult<Option<usize>>;
+}
+
+#[async_trait]
+pub trait LogCheckpointUpdater {
+    /// Moves the checkpoint forward, a lower block height than the saved one is ignored.
+    async fn set_last_processed_block(
+        &mut self,
+        log_type: LogType,
+        block_height: usize,
+    ) -> DataAccessResult<()>;
 }
 
 pub trait SignatureResultCacheFetcher<T: ResultCache> {
     fn contains(&self, signature_index: usize) -> bool;
 
     fn get(&self, signature_index: usize) -> Option<&BLSResultCache<T>>;
+
+    fn get_all(&self) -> Vec<&BLSResultCache<T>>;
 }
 
 pub trait SignatureResultCacheUpdater<T: ResultCache> {
@@ -1,4 +1,5 @@
 use arpa_node_contract_client::error::ContractClientError;
+use arpa_node_core::{NodeSignerError, RemoteSignerError};
 use arpa_node_dal::error::DataAccessError;
 use arpa_node_sqlite_db::DBError;
 use dkg_core::{primitives::DKGError, NodeError as DKGNodeError};
@@ -74,11 +75,29 @@ pub enum NodeError {
 
     #[error("you are not contained in the group")]
     MemberNotExisted,
+
+    #[error("the peer record is not signed by the node it announces or has expired")]
+    InvalidPeerRecord,
+
+    #[error("could not sign the peer record: {0}")]
+    PeerRecordSigningError(String),
+
+    #[error("the partial signature is not signed by the sender or is out of date")]
+    UnauthenticatedPartialSignature,
+
+    #[error("the partial signature has already been committed")]
+    ReplayedPartialSignature,
+
+    #[error("too many requests from {0}")]
+    RateLimited(String),
+
+    #[error("could not sign the partial signature: {0}")]
+    PartialSignatureSigningError(String),
 }
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
-    #[error("please provide at least a hdwallet, keystore or plain private key(not recommended)")]
+    #[error("please provide at least a remote signer, hdwallet, keystore or plain private key(not recommended)")]
     LackOfAccount,
     #[error("bad format")]
     BadFormat,
@@ -86,4 +105,12 @@ pub enum ConfigError {
     EnvVarNotExisted(#[from] VarError),
     #[error(transparent)]
     BuildingAccountError(#[from] WalletError),
+    #[error(transparent)]
+    RemoteSignerError(#[from] RemoteSignerError),
+    #[error("please provide a passphrase or keystore to encrypt the data file")]
+    LackOfDataKey,
+    #[error(transparent)]
+    SignerError(#[from] NodeSignerError),
+    #[error("could not read the chain id from the provider: {0}")]
+    ChainIdUnavailable(#[from] ContractClientError),
 }
@@ -0,0 +1,23 @@
+use super::Event;
+
+#[derive(Clone)]
+pub struct ChainReorg {
+    pub chain_id: usize,
+    /// Height of the first orphaned block.
+    pub fork_block_height: usize,
+}
+
+impl ChainReorg {
+    pub fn new(chain_id: usize, fork_block_height: usize) -> Self {
+        ChainReorg {
+            chain_id,
+            fork_block_height,
+        }
+    }
+}
+
+impl Event for ChainReorg {
+    fn chain_id(&self) -> usize {
+        self.chain_id
+    }
+}
@@ -1,4 +1,4 @@
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct DKGPhase {
@@ -12,11 +12,7 @@ impl DKGPhase {
 }
 
 impl Event for DKGPhase {
-    fn topic(&self) -> Topic {
-        Topic::DKGPhase
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
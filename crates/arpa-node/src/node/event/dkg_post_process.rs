@@ -1,4 +1,4 @@
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct DKGPostProcess {
@@ -16,11 +16,7 @@ impl DKGPostProcess {
 }
 
 impl Event for DKGPostProcess {
-    fn topic(&self) -> Topic {
-        Topic::DKGPostProcess
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
@@ -1,6 +1,6 @@
 use arpa_node_core::Group;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct DKGSuccess {
@@ -14,11 +14,7 @@ impl DKGSuccess {
 }
 
 impl Event for DKGSuccess {
-    fn topic(&self) -> Topic {
-        Topic::DKGSuccess
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
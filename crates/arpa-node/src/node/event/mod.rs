@@ -1,3 +1,4 @@
+pub mod chain_reorg;
 pub mod dkg_phase;
 pub mod dkg_post_process;
 pub mod dkg_success;
@@ -13,13 +14,18 @@ pub mod ready_to_handle_group_relay_confirmation_task;
 pub mod ready_to_handle_group_relay_task;
 pub mod ready_to_handle_randomness_task;
 pub mod run_dkg;
-pub mod types;
 
-use std::any::Any;
+pub trait Event: Clone + Send + Sync + 'static {
+    /// Events of different chains are delivered through separate channels.
+    /// Events that only happen on the main chain return 0.
+    fn chain_id(&self) -> usize;
+}
+
+/// Type name without module path and generic parameters, used as metric label.
+pub(crate) fn short_type_name<T: ?Sized>() -> &'static str {
+    let type_name = std::any::type_name::<T>();
 
-use self::types::Topic;
-pub trait Event {
-    fn topic(&self) -> Topic;
+    let type_name = type_name.split('<').next().unwrap_or(type_name);
 
-    fn as_any(&self) -> &dyn Any;
+    type_name.rsplit("::").next().unwrap_or(type_name)
 }
@@ -1,26 +1,33 @@
-use super::{types::Topic, Event};
+use super::Event;
+use ethers::types::H256;
 
 #[derive(Clone)]
 pub struct NewBlock {
     pub chain_id: usize,
     pub block_height: usize,
+    pub block_hash: H256,
+    /// Height of the first block orphaned by the new head, if the chain was reorganized.
+    pub fork_block_height: Option<usize>,
 }
 
 impl NewBlock {
-    pub fn new(chain_id: usize, block_height: usize) -> Self {
+    pub fn new(
+        chain_id: usize,
+        block_height: usize,
+        block_hash: H256,
+        fork_block_height: Option<usize>,
+    ) -> Self {
         NewBlock {
             chain_id,
             block_height,
+            block_hash,
+            fork_block_height,
         }
     }
 }
 
 impl Event for NewBlock {
-    fn topic(&self) -> Topic {
-        Topic::NewBlock(self.chain_id)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        self.chain_id
     }
 }
@@ -1,4 +1,4 @@
-use super::{types::Topic, Event};
+use super::Event;
 use arpa_node_core::DKGTask;
 
 #[derive(Clone)]
@@ -17,11 +17,7 @@ impl NewDKGTask {
 }
 
 impl Event for NewDKGTask {
-    fn topic(&self) -> Topic {
-        Topic::NewDKGTask
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
@@ -1,6 +1,6 @@
 use arpa_node_core::GroupRelayConfirmationTask;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct NewGroupRelayConfirmationTask {
@@ -18,11 +18,7 @@ impl NewGroupRelayConfirmationTask {
 }
 
 impl Event for NewGroupRelayConfirmationTask {
-    fn topic(&self) -> Topic {
-        Topic::NewGroupRelayConfirmationTask(self.chain_id)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        self.chain_id
     }
 }
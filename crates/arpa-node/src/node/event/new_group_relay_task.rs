@@ -1,6 +1,6 @@
 use arpa_node_core::GroupRelayTask;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct NewGroupRelayTask {
@@ -14,11 +14,7 @@ impl NewGroupRelayTask {
 }
 
 impl Event for NewGroupRelayTask {
-    fn topic(&self) -> Topic {
-        Topic::NewGroupRelayTask
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
@@ -1,6 +1,6 @@
 use arpa_node_core::RandomnessTask;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct NewRandomnessTask {
@@ -18,11 +18,7 @@ impl NewRandomnessTask {
 }
 
 impl Event for NewRandomnessTask {
-    fn topic(&self) -> Topic {
-        Topic::NewRandomnessTask(self.chain_id)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        self.chain_id
     }
 }
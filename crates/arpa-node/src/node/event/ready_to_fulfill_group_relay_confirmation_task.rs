@@ -1,6 +1,6 @@
 use arpa_node_dal::cache::GroupRelayConfirmationResultCache;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct ReadyToFulfillGroupRelayConfirmationTask {
@@ -15,11 +15,7 @@ impl ReadyToFulfillGroupRelayConfirmationTask {
 }
 
 impl Event for ReadyToFulfillGroupRelayConfirmationTask {
-    fn topic(&self) -> Topic {
-        Topic::ReadyToFulfillGroupRelayConfirmationTask(self.chain_id)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        self.chain_id
     }
 }
@@ -1,6 +1,6 @@
 use arpa_node_dal::cache::GroupRelayResultCache;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct ReadyToFulfillGroupRelayTask {
@@ -14,11 +14,7 @@ impl ReadyToFulfillGroupRelayTask {
 }
 
 impl Event for ReadyToFulfillGroupRelayTask {
-    fn topic(&self) -> Topic {
-        Topic::ReadyToFulfillGroupRelayTask
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
@@ -1,6 +1,6 @@
 use arpa_node_dal::cache::RandomnessResultCache;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct ReadyToFulfillRandomnessTask {
@@ -15,11 +15,7 @@ impl ReadyToFulfillRandomnessTask {
 }
 
 impl Event for ReadyToFulfillRandomnessTask {
-    fn topic(&self) -> Topic {
-        Topic::ReadyToFulfillRandomnessTask(self.chain_id)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        self.chain_id
     }
 }
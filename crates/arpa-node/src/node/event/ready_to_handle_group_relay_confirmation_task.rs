@@ -1,25 +1,31 @@
 use arpa_node_core::GroupRelayConfirmationTask;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct ReadyToHandleGroupRelayConfirmationTask {
     pub chain_id: usize,
+    /// The group whose share signs the tasks.
+    pub group_index: usize,
     pub tasks: Vec<GroupRelayConfirmationTask>,
 }
 
 impl ReadyToHandleGroupRelayConfirmationTask {
-    pub fn new(chain_id: usize, tasks: Vec<GroupRelayConfirmationTask>) -> Self {
-        ReadyToHandleGroupRelayConfirmationTask { chain_id, tasks }
+    pub fn new(
+        chain_id: usize,
+        group_index: usize,
+        tasks: Vec<GroupRelayConfirmationTask>,
+    ) -> Self {
+        ReadyToHandleGroupRelayConfirmationTask {
+            chain_id,
+            group_index,
+            tasks,
+        }
     }
 }
 
 impl Event for ReadyToHandleGroupRelayConfirmationTask {
-    fn topic(&self) -> Topic {
-        Topic::ReadyToHandleGroupRelayConfirmationTask(self.chain_id)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        self.chain_id
     }
 }
@@ -1,24 +1,22 @@
 use arpa_node_core::GroupRelayTask;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct ReadyToHandleGroupRelayTask {
+    /// The group whose share signs the tasks.
+    pub group_index: usize,
     pub tasks: Vec<GroupRelayTask>,
 }
 
 impl ReadyToHandleGroupRelayTask {
-    pub fn new(tasks: Vec<GroupRelayTask>) -> Self {
-        ReadyToHandleGroupRelayTask { tasks }
+    pub fn new(group_index: usize, tasks: Vec<GroupRelayTask>) -> Self {
+        ReadyToHandleGroupRelayTask { group_index, tasks }
     }
 }
 
 impl Event for ReadyToHandleGroupRelayTask {
-    fn topic(&self) -> Topic {
-        Topic::ReadyToHandleGroupRelayTask
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
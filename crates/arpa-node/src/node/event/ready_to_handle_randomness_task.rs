@@ -1,25 +1,27 @@
 use arpa_node_core::RandomnessTask;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct ReadyToHandleRandomnessTask {
     pub chain_id: usize,
+    /// The group whose share signs the tasks.
+    pub group_index: usize,
     pub tasks: Vec<RandomnessTask>,
 }
 
 impl ReadyToHandleRandomnessTask {
-    pub fn new(chain_id: usize, tasks: Vec<RandomnessTask>) -> Self {
-        ReadyToHandleRandomnessTask { chain_id, tasks }
+    pub fn new(chain_id: usize, group_index: usize, tasks: Vec<RandomnessTask>) -> Self {
+        ReadyToHandleRandomnessTask {
+            chain_id,
+            group_index,
+            tasks,
+        }
     }
 }
 
 impl Event for ReadyToHandleRandomnessTask {
-    fn topic(&self) -> Topic {
-        Topic::ReadyToHandleRandomnessTask(self.chain_id)
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        self.chain_id
     }
 }
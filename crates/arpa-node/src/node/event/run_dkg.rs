@@ -1,6 +1,6 @@
 use arpa_node_core::DKGTask;
 
-use super::{types::Topic, Event};
+use super::Event;
 
 #[derive(Clone)]
 pub struct RunDKG {
@@ -14,11 +14,7 @@ impl RunDKG {
 }
 
 impl Event for RunDKG {
-    fn topic(&self) -> Topic {
-        Topic::RunDKG
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn chain_id(&self) -> usize {
+        0
     }
 }
@@ -1,40 +1,90 @@
 use super::Listener;
 use crate::node::{
     error::{NodeError, NodeResult},
-    event::new_block::NewBlock,
+    event::{chain_reorg::ChainReorg, new_block::NewBlock},
+    metrics::{inc_rpc_retries, BLOCK_HEIGHT, CHAIN_REORGS},
     queue::{event_queue::EventQueue, EventPublisher},
 };
-use arpa_node_contract_client::provider::{BlockFetcher, ChainProviderBuilder};
-use arpa_node_core::ChainIdentity;
+use arpa_node_contract_client::{
+    error::ContractClientResult,
+    provider::{BlockFetcher, ChainProviderBuilder},
+};
+use arpa_node_core::{BlockHeader, ChainIdentity};
+use arpa_node_dal::BlockInfoFetcher;
 use async_trait::async_trait;
-use log::error;
+use log::{error, warn};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio_retry::{strategy::FixedInterval, RetryIf};
 
-pub struct BlockListener<I: ChainIdentity + ChainProviderBuilder> {
+pub struct BlockListener<B: BlockInfoFetcher, I: ChainIdentity + ChainProviderBuilder> {
     chain_id: usize,
     chain_identity: Arc<RwLock<I>>,
+    block_cache: Arc<RwLock<B>>,
     eq: Arc<RwLock<EventQueue>>,
 }
 
-impl<I: ChainIdentity + ChainProviderBuilder> BlockListener<I> {
+impl<B: BlockInfoFetcher, I: ChainIdentity + ChainProviderBuilder> BlockListener<B, I> {
     pub fn new(
         chain_id: usize,
         chain_identity: Arc<RwLock<I>>,
+        block_cache: Arc<RwLock<B>>,
         eq: Arc<RwLock<EventQueue>>,
     ) -> Self {
         BlockListener {
             chain_id,
             chain_identity,
+            block_cache,
             eq,
         }
     }
 }
 
+/// Walks back the tracked blocks until one is still on the canonical chain,
+/// and returns the height of the first orphaned block if there is any.
+async fn find_fork_block_height<P: BlockFetcher + Sync, B: BlockInfoFetcher + Sync>(
+    provider: &P,
+    block_cache: &B,
+    block: &BlockHeader,
+) -> ContractClientResult<Option<usize>> {
+    let mut fork_block_height = None;
+
+    // tracked blocks above the new head are gone
+    if block_cache.get_block_height() > block.number {
+        fork_block_height = Some(block.number + 1);
+    }
+
+    let mut block_height = block.number;
+
+    let mut canonical_hash = block.hash;
+
+    loop {
+        match block_cache.get_block_hash(block_height) {
+            Some(tracked_hash) if tracked_hash == canonical_hash => break,
+            Some(_) => fork_block_height = Some(block_height),
+            None if block_height < block.number => break,
+            None => {}
+        }
+
+        if block_height == 0 {
+            break;
+        }
+
+        block_height -= 1;
+
+        canonical_hash = if block_height + 1 == block.number {
+            block.parent_hash
+        } else {
+            provider.get_block_header(block_height).await?.hash
+        };
+    }
+
+    Ok(fork_block_height)
+}
+
 #[async_trait]
-impl<I: ChainIdentity + ChainProviderBuilder + Sync + Send> EventPublisher<NewBlock>
-    for BlockListener<I>
+impl<B: BlockInfoFetcher + Sync + Send, I: ChainIdentity + ChainProviderBuilder + Sync + Send>
+    EventPublisher<NewBlock> for BlockListener<B, I>
 {
     async fn publish(&self, event: NewBlock) {
         self.eq.read().await.publish(event).await;
@@ -42,8 +92,10 @@ impl<I: ChainIdentity + ChainProviderBuilder + Sync + Send> EventPublisher<NewBl
 }
 
 #[async_trait]
-impl<I: ChainIdentity + ChainProviderBuilder + Sync + Send + 'static> Listener
-    for BlockListener<I>
+impl<
+        B: BlockInfoFetcher + Sync + Send + 'static,
+        I: ChainIdentity + ChainProviderBuilder + Sync + Send + 'static,
+    > Listener for BlockListener<B, I>
 {
     async fn start(mut self) -> NodeResult<()> {
         let client = self.chain_identity.read().await.build_chain_provider();
@@ -54,19 +106,50 @@ impl<I: ChainIdentity + ChainProviderBuilder + Sync + Send + 'static> Listener
             retry_strategy.clone(),
             || async {
                 let chain_id = self.chain_id;
+                let block_cache = self.block_cache.clone();
                 let eq = self.eq.clone();
+                let client = &client;
                 client
-                    .subscribe_new_block_height(move |block_height: usize| {
+                    .subscribe_new_block(move |block: BlockHeader| {
+                        let block_cache = block_cache.clone();
                         let eq = eq.clone();
                         async move {
+                            BLOCK_HEIGHT
+                                .with_label_values(&[&chain_id.to_string()])
+                                .set(block.number as i64);
+
+                            let fork_block_height =
+                                find_fork_block_height(client, &*block_cache.read().await, &block)
+                                    .await?;
+
+                            if let Some(fork_block_height) = fork_block_height {
+                                warn!(
+                                    "chain {} reorganized from block {}, new head: {}",
+                                    chain_id, fork_block_height, block.number
+                                );
+
+                                CHAIN_REORGS
+                                    .with_label_values(&[&chain_id.to_string()])
+                                    .inc();
+                            }
+
                             eq.read()
                                 .await
-                                .publish(NewBlock {
+                                .publish(NewBlock::new(
                                     chain_id,
-                                    block_height,
-                                })
+                                    block.number,
+                                    block.hash,
+                                    fork_block_height,
+                                ))
                                 .await;
 
+                            if let Some(fork_block_height) = fork_block_height {
+                                eq.read()
+                                    .await
+                                    .publish(ChainReorg::new(chain_id, fork_block_height))
+                                    .await;
+                            }
+
                             Ok(())
                         }
                     })
@@ -76,6 +159,7 @@ impl<I: ChainIdentity + ChainProviderBuilder + Sync + Send + 'static> Listener
             },
             |e: &NodeError| {
                 error!("listener is interrupted. Retry... Error: {:?}, ", e);
+                inc_rpc_retries(self.chain_id, "subscribe_new_block");
                 true
             },
         )
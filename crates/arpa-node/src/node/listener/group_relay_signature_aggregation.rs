@@ -58,9 +58,13 @@ impl<
 {
     async fn start(mut self) -> NodeResult<()> {
         loop {
-            let is_committer = self.group_cache.read().await.is_committer(self.id_address);
+            let is_committer = self
+                .group_cache
+                .read()
+                .await
+                .is_committer_of_any_ready_group(self.id_address);
 
-            if let Ok(true) = is_committer {
+            if is_committer {
                 let ready_signatures = self
                     .group_relay_signature_cache
                     .write()
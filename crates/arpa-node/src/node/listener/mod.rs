@@ -4,6 +4,7 @@ pub mod group_relay_signature_aggregation;
 pub mod new_group_relay_confirmation_task;
 pub mod new_group_relay_task;
 pub mod new_randomness_task;
+pub mod peer_record;
 pub mod post_commit_grouping;
 pub mod post_grouping;
 pub mod pre_grouping;
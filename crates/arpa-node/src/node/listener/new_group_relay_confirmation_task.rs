@@ -2,11 +2,12 @@ use super::Listener;
 use crate::node::{
     error::{NodeError, NodeResult},
     event::new_group_relay_confirmation_task::NewGroupRelayConfirmationTask,
+    metrics::inc_rpc_retries,
     queue::{event_queue::EventQueue, EventPublisher},
 };
 use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterLogs};
-use arpa_node_core::{ChainIdentity, GroupRelayConfirmationTask};
-use arpa_node_dal::{BLSTasksFetcher, BLSTasksUpdater};
+use arpa_node_core::{ChainIdentity, GroupRelayConfirmationTask, LogType};
+use arpa_node_dal::{BLSTasksFetcher, BLSTasksUpdater, LogCheckpointFetcher, LogCheckpointUpdater};
 use async_trait::async_trait;
 use ethers::types::Address;
 use log::{error, info};
@@ -17,24 +18,28 @@ use tokio_retry::{strategy::FixedInterval, RetryIf};
 pub struct NewGroupRelayConfirmationTaskListener<
     I: ChainIdentity + AdapterClientBuilder,
     Q: BLSTasksUpdater<GroupRelayConfirmationTask> + BLSTasksFetcher<GroupRelayConfirmationTask>,
+    C: LogCheckpointFetcher + LogCheckpointUpdater,
 > {
     chain_id: usize,
     id_address: Address,
     chain_identity: Arc<RwLock<I>>,
     group_relay_confirmation_tasks_cache: Arc<RwLock<Q>>,
+    log_checkpoint: Arc<RwLock<C>>,
     eq: Arc<RwLock<EventQueue>>,
 }
 
 impl<
         I: ChainIdentity + AdapterClientBuilder,
         Q: BLSTasksUpdater<GroupRelayConfirmationTask> + BLSTasksFetcher<GroupRelayConfirmationTask>,
-    > NewGroupRelayConfirmationTaskListener<I, Q>
+        C: LogCheckpointFetcher + LogCheckpointUpdater,
+    > NewGroupRelayConfirmationTaskListener<I, Q, C>
 {
     pub fn new(
         chain_id: usize,
         id_address: Address,
         chain_identity: Arc<RwLock<I>>,
         group_relay_confirmation_tasks_cache: Arc<RwLock<Q>>,
+        log_checkpoint: Arc<RwLock<C>>,
         eq: Arc<RwLock<EventQueue>>,
     ) -> Self {
         NewGroupRelayConfirmationTaskListener {
@@ -42,6 +47,7 @@ impl<
             id_address,
             chain_identity,
             group_relay_confirmation_tasks_cache,
+            log_checkpoint,
             eq,
         }
     }
@@ -54,8 +60,9 @@ impl<
             + BLSTasksFetcher<GroupRelayConfirmationTask>
             + Sync
             + Send,
+        C: LogCheckpointFetcher + LogCheckpointUpdater + Sync + Send,
     > EventPublisher<NewGroupRelayConfirmationTask>
-    for NewGroupRelayConfirmationTaskListener<I, Q>
+    for NewGroupRelayConfirmationTaskListener<I, Q, C>
 {
     async fn publish(&self, event: NewGroupRelayConfirmationTask) {
         self.eq.read().await.publish(event).await;
@@ -70,7 +77,8 @@ impl<
             + Sync
             + Send
             + 'static,
-    > Listener for NewGroupRelayConfirmationTaskListener<I, Q>
+        C: LogCheckpointFetcher + LogCheckpointUpdater + Sync + Send + 'static,
+    > Listener for NewGroupRelayConfirmationTaskListener<I, Q, C>
 {
     async fn start(mut self) -> NodeResult<()> {
         let client = self
@@ -87,50 +95,78 @@ impl<
                 let chain_id = self.chain_id;
                 let group_relay_confirmation_tasks_cache =
                     self.group_relay_confirmation_tasks_cache.clone();
+                let log_checkpoint = self.log_checkpoint.clone();
                 let eq = self.eq.clone();
 
+                // back-fills the logs emitted while the node was down or the listener was interrupted
+                let from_block = self
+                    .log_checkpoint
+                    .read()
+                    .await
+                    .get_last_processed_block(LogType::GroupRelayConfirmationTask)
+                    .await?;
+
                 client
-                    .subscribe_group_relay_confirmation_task(move |group_relay_confirmation_task| {
-                        let group_relay_confirmation_tasks_cache =
-                            group_relay_confirmation_tasks_cache.clone();
-                        let eq = eq.clone();
-
-                        async move {
-                            let contained_res = group_relay_confirmation_tasks_cache
-                                .read()
-                                .await
-                                .contains(group_relay_confirmation_task.index)
-                                .await;
-                            if let Ok(false) = contained_res {
-                                info!(
-                                    "received new group_relay_confirmation task. {:?}",
-                                    group_relay_confirmation_task
-                                );
-
-                                group_relay_confirmation_tasks_cache
+                    .subscribe_group_relay_confirmation_task(
+                        from_block,
+                        move |group_relay_confirmation_task| {
+                            let group_relay_confirmation_tasks_cache =
+                                group_relay_confirmation_tasks_cache.clone();
+                            let log_checkpoint = log_checkpoint.clone();
+                            let eq = eq.clone();
+
+                            async move {
+                                let block_height =
+                                    group_relay_confirmation_task.assignment_block_height;
+
+                                let contained_res = group_relay_confirmation_tasks_cache
+                                    .read()
+                                    .await
+                                    .contains(group_relay_confirmation_task.index)
+                                    .await;
+                                if let Ok(false) = contained_res {
+                                    info!(
+                                        "received new group_relay_confirmation task. {:?}",
+                                        group_relay_confirmation_task
+                                    );
+
+                                    group_relay_confirmation_tasks_cache
+                                        .write()
+                                        .await
+                                        .add(group_relay_confirmation_task.clone())
+                                        .await
+                                        .map_err(anyhow::Error::from)?;
+
+                                    eq.read()
+                                        .await
+                                        .publish(NewGroupRelayConfirmationTask::new(
+                                            chain_id,
+                                            group_relay_confirmation_task,
+                                        ))
+                                        .await;
+                                }
+
+                                log_checkpoint
                                     .write()
                                     .await
-                                    .add(group_relay_confirmation_task.clone())
+                                    .set_last_processed_block(
+                                        LogType::GroupRelayConfirmationTask,
+                                        block_height,
+                                    )
                                     .await
                                     .map_err(anyhow::Error::from)?;
 
-                                eq.read()
-                                    .await
-                                    .publish(NewGroupRelayConfirmationTask::new(
-                                        chain_id,
-                                        group_relay_confirmation_task,
-                                    ))
-                                    .await;
+                                Ok(())
                             }
-                            Ok(())
-                        }
-                    })
+                        },
+                    )
                     .await?;
 
                 Ok(())
             },
             |e: &NodeError| {
                 error!("listener is interrupted. Retry... Error: {:?}, ", e);
+                inc_rpc_retries(self.chain_id, "new_group_relay_confirmation_task");
                 true
             },
         )
@@ -2,11 +2,12 @@ use super::Listener;
 use crate::node::{
     error::{NodeError, NodeResult},
     event::new_group_relay_task::NewGroupRelayTask,
+    metrics::inc_rpc_retries,
     queue::{event_queue::EventQueue, EventPublisher},
 };
 use arpa_node_contract_client::controller::{ControllerClientBuilder, ControllerLogs};
-use arpa_node_core::{ChainIdentity, GroupRelayTask};
-use arpa_node_dal::{BLSTasksFetcher, BLSTasksUpdater};
+use arpa_node_core::{ChainIdentity, GroupRelayTask, LogType};
+use arpa_node_dal::{BLSTasksFetcher, BLSTasksUpdater, LogCheckpointFetcher, LogCheckpointUpdater};
 use async_trait::async_trait;
 use log::{error, info};
 use std::sync::Arc;
@@ -16,25 +17,30 @@ use tokio_retry::{strategy::FixedInterval, RetryIf};
 pub struct NewGroupRelayTaskListener<
     I: ChainIdentity + ControllerClientBuilder,
     Q: BLSTasksUpdater<GroupRelayTask> + BLSTasksFetcher<GroupRelayTask>,
+    C: LogCheckpointFetcher + LogCheckpointUpdater,
 > {
     main_chain_identity: Arc<RwLock<I>>,
     group_relay_tasks_cache: Arc<RwLock<Q>>,
+    log_checkpoint: Arc<RwLock<C>>,
     eq: Arc<RwLock<EventQueue>>,
 }
 
 impl<
         I: ChainIdentity + ControllerClientBuilder,
         Q: BLSTasksUpdater<GroupRelayTask> + BLSTasksFetcher<GroupRelayTask>,
-    > NewGroupRelayTaskListener<I, Q>
+        C: LogCheckpointFetcher + LogCheckpointUpdater,
+    > NewGroupRelayTaskListener<I, Q, C>
 {
     pub fn new(
         main_chain_identity: Arc<RwLock<I>>,
         group_relay_tasks_cache: Arc<RwLock<Q>>,
+        log_checkpoint: Arc<RwLock<C>>,
         eq: Arc<RwLock<EventQueue>>,
     ) -> Self {
         NewGroupRelayTaskListener {
             main_chain_identity,
             group_relay_tasks_cache,
+            log_checkpoint,
             eq,
         }
     }
@@ -44,7 +50,8 @@ impl<
 impl<
         I: ChainIdentity + ControllerClientBuilder + Sync + Send,
         Q: BLSTasksUpdater<GroupRelayTask> + BLSTasksFetcher<GroupRelayTask> + Sync + Send,
-    > EventPublisher<NewGroupRelayTask> for NewGroupRelayTaskListener<I, Q>
+        C: LogCheckpointFetcher + LogCheckpointUpdater + Sync + Send,
+    > EventPublisher<NewGroupRelayTask> for NewGroupRelayTaskListener<I, Q, C>
 {
     async fn publish(&self, event: NewGroupRelayTask) {
         self.eq.read().await.publish(event).await;
@@ -55,7 +62,8 @@ impl<
 impl<
         I: ChainIdentity + ControllerClientBuilder + Sync + Send,
         Q: BLSTasksUpdater<GroupRelayTask> + BLSTasksFetcher<GroupRelayTask> + Sync + Send + 'static,
-    > Listener for NewGroupRelayTaskListener<I, Q>
+        C: LogCheckpointFetcher + LogCheckpointUpdater + Sync + Send + 'static,
+    > Listener for NewGroupRelayTaskListener<I, Q, C>
 {
     async fn start(mut self) -> NodeResult<()> {
         let client = self
@@ -70,14 +78,26 @@ impl<
             retry_strategy.clone(),
             || async {
                 let group_relay_tasks_cache = self.group_relay_tasks_cache.clone();
+                let log_checkpoint = self.log_checkpoint.clone();
                 let eq = self.eq.clone();
 
+                // back-fills the logs emitted while the node was down or the listener was interrupted
+                let from_block = self
+                    .log_checkpoint
+                    .read()
+                    .await
+                    .get_last_processed_block(LogType::GroupRelayTask)
+                    .await?;
+
                 client
-                    .subscribe_group_relay_task(move |group_relay_task| {
+                    .subscribe_group_relay_task(from_block, move |group_relay_task| {
                         let group_relay_tasks_cache = group_relay_tasks_cache.clone();
+                        let log_checkpoint = log_checkpoint.clone();
                         let eq = eq.clone();
 
                         async move {
+                            let block_height = group_relay_task.assignment_block_height;
+
                             let contained_res = group_relay_tasks_cache
                                 .read()
                                 .await
@@ -98,6 +118,14 @@ impl<
                                     .publish(NewGroupRelayTask::new(group_relay_task))
                                     .await;
                             }
+
+                            log_checkpoint
+                                .write()
+                                .await
+                                .set_last_processed_block(LogType::GroupRelayTask, block_height)
+                                .await
+                                .map_err(anyhow::Error::from)?;
+
                             Ok(())
                         }
                     })
@@ -107,6 +135,7 @@ impl<
             },
             |e: &NodeError| {
                 error!("listener is interrupted. Retry... Error: {:?}, ", e);
+                inc_rpc_retries(0, "new_group_relay_task");
                 true
             },
         )
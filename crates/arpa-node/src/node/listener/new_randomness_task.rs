@@ -2,11 +2,12 @@ use super::Listener;
 use crate::node::{
     error::{NodeError, NodeResult},
     event::new_randomness_task::NewRandomnessTask,
+    metrics::inc_rpc_retries,
     queue::{event_queue::EventQueue, EventPublisher},
 };
 use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterLogs};
-use arpa_node_core::{ChainIdentity, RandomnessTask};
-use arpa_node_dal::{BLSTasksFetcher, BLSTasksUpdater};
+use arpa_node_core::{ChainIdentity, LogType, RandomnessTask};
+use arpa_node_dal::{BLSTasksFetcher, BLSTasksUpdater, LogCheckpointFetcher, LogCheckpointUpdater};
 use async_trait::async_trait;
 use ethers::types::Address;
 use log::{error, info};
@@ -15,7 +16,10 @@ use tokio::sync::RwLock;
 use tokio_retry::{strategy::FixedInterval, RetryIf};
 
 pub struct NewRandomnessTaskListener<
-    T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater,
     I: ChainIdentity + AdapterClientBuilder,
 > {
     chain_id: usize,
@@ -26,7 +30,10 @@ pub struct NewRandomnessTaskListener<
 }
 
 impl<
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask>,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater,
         I: ChainIdentity + AdapterClientBuilder,
     > NewRandomnessTaskListener<T, I>
 {
@@ -49,7 +56,12 @@ impl<
 
 #[async_trait]
 impl<
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send,
         I: ChainIdentity + AdapterClientBuilder + Sync + Send,
     > EventPublisher<NewRandomnessTask> for NewRandomnessTaskListener<T, I>
 {
@@ -60,7 +72,13 @@ impl<
 
 #[async_trait]
 impl<
-        T: BLSTasksFetcher<RandomnessTask> + BLSTasksUpdater<RandomnessTask> + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
         I: ChainIdentity + AdapterClientBuilder + Sync + Send,
     > Listener for NewRandomnessTaskListener<T, I>
 {
@@ -80,12 +98,22 @@ impl<
                 let randomness_tasks_cache = self.randomness_tasks_cache.clone();
                 let eq = self.eq.clone();
 
+                // back-fills the tasks emitted while the node was down or the listener was interrupted
+                let from_block = self
+                    .randomness_tasks_cache
+                    .read()
+                    .await
+                    .get_last_processed_block(LogType::RandomnessTask)
+                    .await?;
+
                 client
-                    .subscribe_randomness_task(move |randomness_task| {
+                    .subscribe_randomness_task(from_block, move |randomness_task| {
                         let randomness_tasks_cache = randomness_tasks_cache.clone();
                         let eq = eq.clone();
 
                         async move {
+                            let block_height = randomness_task.assignment_block_height;
+
                             let contained_res = randomness_tasks_cache
                                 .read()
                                 .await
@@ -106,6 +134,14 @@ impl<
                                     .publish(NewRandomnessTask::new(chain_id, randomness_task))
                                     .await;
                             }
+
+                            randomness_tasks_cache
+                                .write()
+                                .await
+                                .set_last_processed_block(LogType::RandomnessTask, block_height)
+                                .await
+                                .map_err(anyhow::Error::from)?;
+
                             Ok(())
                         }
                     })
@@ -115,6 +151,7 @@ impl<
             },
             |e: &NodeError| {
                 error!("listener is interrupted. Retry... Error: {:?}, ", e);
+                inc_rpc_retries(self.chain_id, "new_randomness_task");
                 true
             },
         )
@@ -0,0 +1,193 @@
+use super::Listener;
+use crate::node::{
+    committer::{
+        client::GeneralCommitterClient, sign_peer_record, CommitterClient, CommitterService,
+    },
+    error::NodeResult,
+};
+use arpa_node_core::{now_timestamp, ChainIdentity, PEER_RECORD_TTL_SECS};
+use arpa_node_dal::{GroupInfoFetcher, GroupInfoUpdater, NodeInfoFetcher};
+use async_trait::async_trait;
+use ethers::types::Address;
+use log::{info, warn};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::RwLock;
+
+pub const DEFAULT_PEER_RECORD_ANNOUNCE_INTERVAL_MILLIS: u64 = 30000;
+
+/// Keeps the peer record of the node fresh and announces it to the members of its groups,
+/// so that they can still reach the node after its endpoint changes.
+pub struct PeerRecordListener<
+    N: NodeInfoFetcher,
+    G: GroupInfoFetcher + GroupInfoUpdater,
+    I: ChainIdentity,
+> {
+    chain_identity: Arc<RwLock<I>>,
+    node_cache: Arc<RwLock<N>>,
+    group_cache: Arc<RwLock<G>>,
+}
+
+impl<N: NodeInfoFetcher, G: GroupInfoFetcher + GroupInfoUpdater, I: ChainIdentity>
+    PeerRecordListener<N, G, I>
+{
+    pub fn new(
+        chain_identity: Arc<RwLock<I>>,
+        node_cache: Arc<RwLock<N>>,
+        group_cache: Arc<RwLock<G>>,
+    ) -> Self {
+        PeerRecordListener {
+            chain_identity,
+            node_cache,
+            group_cache,
+        }
+    }
+}
+
+impl<
+        N: NodeInfoFetcher + Sync + Send,
+        G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send,
+        I: ChainIdentity + Sync + Send,
+    > PeerRecordListener<N, G, I>
+{
+    /// Signs a new record if the endpoint has changed or half of the lifetime of the record
+    /// has passed, returns whether the record is renewed.
+    async fn refresh_peer_record(&self, id_address: Address) -> NodeResult<bool> {
+        let node_rpc_endpoint = self
+            .node_cache
+            .read()
+            .await
+            .get_node_rpc_endpoint()?
+            .to_string();
+
+        let up_to_date = self
+            .group_cache
+            .read()
+            .await
+            .get_peer_record(id_address)
+            .map_or(false, |peer_record| {
+                peer_record.rpc_endpoint == node_rpc_endpoint
+                    && peer_record.expires_at > now_timestamp() + PEER_RECORD_TTL_SECS / 2
+            });
+
+        if up_to_date {
+            return Ok(false);
+        }
+
+        let peer_record =
+            sign_peer_record(&*self.chain_identity.read().await, node_rpc_endpoint).await?;
+
+        info!(
+            "peer record renewed, endpoint: {}, expires_at: {}",
+            peer_record.rpc_endpoint, peer_record.expires_at
+        );
+
+        self.group_cache
+            .write()
+            .await
+            .save_peer_record(peer_record)
+            .await?;
+
+        Ok(true)
+    }
+
+    /// The other members of the groups the node holds.
+    async fn get_peers(&self, id_address: Address) -> HashSet<Address> {
+        let group_cache = self.group_cache.read().await;
+
+        let mut peers = HashSet::new();
+
+        for index in group_cache.get_indices() {
+            for epoch in [
+                group_cache.get_ready_epoch(index),
+                group_cache.get_epoch(index),
+            ]
+            .iter()
+            .flatten()
+            {
+                if let Ok(members) = group_cache.get_members(index, *epoch) {
+                    peers.extend(members.keys().filter(|member| **member != id_address));
+                }
+            }
+        }
+
+        peers
+    }
+
+    /// Announces the record of the node to the peer, and keeps the record the peer replies with.
+    /// Returns false if the endpoint of the peer is unknown yet.
+    async fn announce(&self, id_address: Address, peer: Address) -> NodeResult<bool> {
+        let (peer_record, endpoint) = {
+            let group_cache = self.group_cache.read().await;
+
+            let peer_record = group_cache.get_peer_record(id_address).cloned();
+
+            let endpoint = group_cache
+                .get_peer_endpoint(peer, now_timestamp())
+                .map(|endpoint| endpoint.to_string());
+
+            (peer_record, endpoint)
+        };
+
+        let (peer_record, endpoint) = match (peer_record, endpoint) {
+            (Some(peer_record), Some(endpoint)) => (peer_record, endpoint),
+            _ => return Ok(false),
+        };
+
+        let reply = GeneralCommitterClient::build(id_address, endpoint)
+            .announce_peer_record(peer_record)
+            .await?;
+
+        if let Some(reply) = reply {
+            if reply.id_address == peer && reply.verify() && !reply.is_expired(now_timestamp()) {
+                self.group_cache
+                    .write()
+                    .await
+                    .save_peer_record(reply)
+                    .await?;
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl<
+        N: NodeInfoFetcher + Sync + Send,
+        G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send,
+        I: ChainIdentity + Sync + Send,
+    > Listener for PeerRecordListener<N, G, I>
+{
+    async fn start(self) -> NodeResult<()> {
+        let id_address = self.node_cache.read().await.get_id_address()?;
+
+        // peers which have got the current record, it is announced again to everyone once renewed
+        let mut announced: HashSet<Address> = HashSet::new();
+
+        loop {
+            if self.refresh_peer_record(id_address).await? {
+                announced.clear();
+            }
+
+            for peer in self.get_peers(id_address).await {
+                if announced.contains(&peer) {
+                    continue;
+                }
+
+                // the peer is retried in the next round if it is not reached
+                match self.announce(id_address, peer).await {
+                    Ok(true) => {
+                        announced.insert(peer);
+                    }
+                    Ok(false) => {}
+                    Err(e) => warn!("failed to announce the peer record to {:?}: {:?}", peer, e),
+                }
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(
+                DEFAULT_PEER_RECORD_ANNOUNCE_INTERVAL_MILLIS,
+            ))
+            .await;
+        }
+    }
+}
@@ -2,6 +2,7 @@ use super::Listener;
 use crate::node::{
     error::{NodeError, NodeResult},
     event::dkg_success::DKGSuccess,
+    metrics::{inc_rpc_retries, observe_dkg_status},
     queue::{event_queue::EventQueue, EventPublisher},
 };
 use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterViews};
@@ -70,15 +71,26 @@ impl<
             if let Err(err) = RetryIf::spawn(
                 retry_strategy.clone(),
                 || async {
-                    let dkg_status = self.group_cache.read().await.get_dkg_status();
+                    let committed_groups = {
+                        let group_cache = self.group_cache.read().await;
 
-                    if let Ok(DKGStatus::CommitSuccess) = dkg_status {
-                        let group_index = self.group_cache.read().await.get_index()?;
+                        group_cache
+                            .get_indices()
+                            .into_iter()
+                            .filter_map(|index| {
+                                let epoch = group_cache.get_epoch(index).ok()?;
 
-                        let group_epoch = self.group_cache.read().await.get_epoch()?;
+                                match group_cache.get_dkg_status(index, epoch) {
+                                    Ok(DKGStatus::CommitSuccess) => Some((index, epoch)),
+                                    _ => None,
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    };
 
+                    for (group_index, group_epoch) in committed_groups {
                         if let Ok(group) = client.get_group(group_index).await {
-                            if group.state {
+                            if group.state && group.epoch == group_epoch {
                                 let res = self
                                     .group_cache
                                     .write()
@@ -91,6 +103,8 @@ impl<
                                     .await?;
 
                                 if res {
+                                    observe_dkg_status(DKGStatus::WaitForPostProcess);
+
                                     self.publish(DKGSuccess { group }).await;
                                 }
                             }
@@ -101,6 +115,7 @@ impl<
                 },
                 |e: &NodeError| {
                     error!("listener is interrupted. Retry... Error: {:?}, ", e);
+                    inc_rpc_retries(0, "post_commit_grouping");
                     true
                 },
             )
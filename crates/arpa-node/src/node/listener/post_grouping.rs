@@ -2,6 +2,7 @@ use super::Listener;
 use crate::node::{
     error::NodeResult,
     event::dkg_post_process::DKGPostProcess,
+    metrics::{observe_dkg_status, DKG_OUTCOMES},
     queue::{event_queue::EventQueue, EventPublisher},
 };
 use arpa_node_core::DKGStatus;
@@ -48,43 +49,56 @@ impl<B: BlockInfoFetcher + Sync + Send, G: GroupInfoFetcher + GroupInfoUpdater +
 {
     async fn start(mut self) -> NodeResult<()> {
         loop {
-            let dkg_status = self.group_cache.read().await.get_dkg_status();
+            let group_keys = {
+                let group_cache = self.group_cache.read().await;
 
-            if let Ok(dkg_status) = dkg_status {
-                match dkg_status {
-                    DKGStatus::None => {}
-                    DKGStatus::InPhase
-                    | DKGStatus::CommitSuccess
-                    | DKGStatus::WaitForPostProcess => {
-                        let dkg_start_block_height =
-                            self.group_cache.read().await.get_dkg_start_block_height()?;
+                group_cache
+                    .get_indices()
+                    .into_iter()
+                    .filter_map(|index| Some((index, group_cache.get_epoch(index).ok()?)))
+                    .collect::<Vec<_>>()
+            };
 
-                        let block_height = self.block_cache.read().await.get_block_height();
+            for (group_index, group_epoch) in group_keys {
+                let dkg_status = self
+                    .group_cache
+                    .read()
+                    .await
+                    .get_dkg_status(group_index, group_epoch);
 
-                        info!("dkg_start_block_height: {},current_block_height: {}, timeuout_dkg_block_height:{}",
-                        dkg_start_block_height,block_height,dkg_start_block_height + DEFAULT_DKG_TIMEOUT_DURATION);
+                if let Ok(
+                    DKGStatus::InPhase | DKGStatus::CommitSuccess | DKGStatus::WaitForPostProcess,
+                ) = dkg_status
+                {
+                    let dkg_start_block_height = self
+                        .group_cache
+                        .read()
+                        .await
+                        .get_dkg_start_block_height(group_index, group_epoch)?;
 
-                        if block_height > dkg_start_block_height + DEFAULT_DKG_TIMEOUT_DURATION {
-                            let group_index =
-                                self.group_cache.read().await.get_index().unwrap_or(0);
+                    let block_height = self.block_cache.read().await.get_block_height();
 
-                            let group_epoch =
-                                self.group_cache.read().await.get_epoch().unwrap_or(0);
+                    info!("group_index: {}, group_epoch: {}, dkg_start_block_height: {},current_block_height: {}, timeuout_dkg_block_height:{}",
+                        group_index,group_epoch,dkg_start_block_height,block_height,dkg_start_block_height + DEFAULT_DKG_TIMEOUT_DURATION);
 
-                            let res = self
-                                .group_cache
-                                .write()
-                                .await
-                                .update_dkg_status(group_index, group_epoch, DKGStatus::None)
-                                .await?;
+                    if block_height > dkg_start_block_height + DEFAULT_DKG_TIMEOUT_DURATION {
+                        let res = self
+                            .group_cache
+                            .write()
+                            .await
+                            .update_dkg_status(group_index, group_epoch, DKGStatus::None)
+                            .await?;
 
-                            if res {
-                                self.publish(DKGPostProcess {
-                                    group_index,
-                                    group_epoch,
-                                })
-                                .await;
-                            }
+                        if res {
+                            observe_dkg_status(DKGStatus::None);
+
+                            DKG_OUTCOMES.with_label_values(&["timeout"]).inc();
+
+                            self.publish(DKGPostProcess {
+                                group_index,
+                                group_epoch,
+                            })
+                            .await;
                         }
                     }
                 }
@@ -2,32 +2,45 @@ use super::Listener;
 use crate::node::{
     error::{NodeError, NodeResult},
     event::new_dkg_task::NewDKGTask,
+    metrics::inc_rpc_retries,
     queue::{event_queue::EventQueue, EventPublisher},
 };
 use arpa_node_contract_client::controller::{ControllerClientBuilder, ControllerLogs};
-use arpa_node_core::ChainIdentity;
-use arpa_node_dal::GroupInfoFetcher;
+use arpa_node_core::{ChainIdentity, LogType};
+use arpa_node_dal::{GroupInfoFetcher, LogCheckpointFetcher, LogCheckpointUpdater};
 use async_trait::async_trait;
 use log::error;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio_retry::{strategy::FixedInterval, RetryIf};
 
-pub struct PreGroupingListener<G: GroupInfoFetcher, I: ChainIdentity + ControllerClientBuilder> {
+pub struct PreGroupingListener<
+    G: GroupInfoFetcher,
+    I: ChainIdentity + ControllerClientBuilder,
+    C: LogCheckpointFetcher + LogCheckpointUpdater,
+> {
     main_chain_identity: Arc<RwLock<I>>,
     group_cache: Arc<RwLock<G>>,
+    log_checkpoint: Arc<RwLock<C>>,
     eq: Arc<RwLock<EventQueue>>,
 }
 
-impl<G: GroupInfoFetcher, I: ChainIdentity + ControllerClientBuilder> PreGroupingListener<G, I> {
+impl<
+        G: GroupInfoFetcher,
+        I: ChainIdentity + ControllerClientBuilder,
+        C: LogCheckpointFetcher + LogCheckpointUpdater,
+    > PreGroupingListener<G, I, C>
+{
     pub fn new(
         main_chain_identity: Arc<RwLock<I>>,
         group_cache: Arc<RwLock<G>>,
+        log_checkpoint: Arc<RwLock<C>>,
         eq: Arc<RwLock<EventQueue>>,
     ) -> Self {
         PreGroupingListener {
             main_chain_identity,
             group_cache,
+            log_checkpoint,
             eq,
         }
     }
@@ -37,7 +50,8 @@ impl<G: GroupInfoFetcher, I: ChainIdentity + ControllerClientBuilder> PreGroupin
 impl<
         G: GroupInfoFetcher + Sync + Send,
         I: ChainIdentity + ControllerClientBuilder + Sync + Send,
-    > EventPublisher<NewDKGTask> for PreGroupingListener<G, I>
+        C: LogCheckpointFetcher + LogCheckpointUpdater + Sync + Send,
+    > EventPublisher<NewDKGTask> for PreGroupingListener<G, I, C>
 {
     async fn publish(&self, event: NewDKGTask) {
         self.eq.read().await.publish(event).await;
@@ -48,7 +62,8 @@ impl<
 impl<
         G: GroupInfoFetcher + Sync + Send + 'static,
         I: ChainIdentity + ControllerClientBuilder + Sync + Send,
-    > Listener for PreGroupingListener<G, I>
+        C: LogCheckpointFetcher + LogCheckpointUpdater + Sync + Send + 'static,
+    > Listener for PreGroupingListener<G, I, C>
 {
     async fn start(mut self) -> NodeResult<()> {
         let client = self
@@ -64,26 +79,39 @@ impl<
             || async {
                 let self_id_address = self.main_chain_identity.read().await.get_id_address();
                 let group_cache = self.group_cache.clone();
+                let log_checkpoint = self.log_checkpoint.clone();
                 let eq = self.eq.clone();
 
+                // back-fills the logs emitted while the node was down or the listener was interrupted
+                let from_block = self
+                    .log_checkpoint
+                    .read()
+                    .await
+                    .get_last_processed_block(LogType::DKGTask)
+                    .await?;
+
                 client
-                    .subscribe_dkg_task(move |dkg_task| {
+                    .subscribe_dkg_task(from_block, move |dkg_task| {
                         let group_cache = group_cache.clone();
+                        let log_checkpoint = log_checkpoint.clone();
                         let eq = eq.clone();
 
                         async move {
+                            let block_height = dkg_task.assignment_block_height;
+
                             if let Some((_, node_index)) = dkg_task
                                 .members
                                 .iter()
                                 .find(|(id_address, _)| **id_address == self_id_address)
                             {
-                                let cache_index = group_cache.read().await.get_index().unwrap_or(0);
-
-                                let cache_epoch = group_cache.read().await.get_epoch().unwrap_or(0);
+                                // the node can be in several groups, only a newer epoch of the same group is a new task
+                                let is_new_task = group_cache
+                                    .read()
+                                    .await
+                                    .get_epoch(dkg_task.group_index)
+                                    .map_or(true, |cache_epoch| cache_epoch < dkg_task.epoch);
 
-                                if cache_index != dkg_task.group_index
-                                    || cache_epoch != dkg_task.epoch
-                                {
+                                if is_new_task {
                                     let self_index = *node_index;
 
                                     eq.read()
@@ -95,6 +123,14 @@ impl<
                                         .await;
                                 }
                             }
+
+                            log_checkpoint
+                                .write()
+                                .await
+                                .set_last_processed_block(LogType::DKGTask, block_height)
+                                .await
+                                .map_err(anyhow::Error::from)?;
+
                             Ok(())
                         }
                     })
@@ -104,6 +140,7 @@ impl<
             },
             |e: &NodeError| {
                 error!("listener is interrupted. Retry... Error: {:?}, ", e);
+                inc_rpc_retries(0, "pre_grouping");
                 true
             },
         )
@@ -1,52 +1,167 @@
 use super::Listener;
 use crate::node::{
+    context::types::GossipConfig,
     error::NodeResult,
     event::ready_to_fulfill_randomness_task::ReadyToFulfillRandomnessTask,
     queue::{event_queue::EventQueue, EventPublisher},
 };
-use arpa_node_dal::{cache::RandomnessResultCache, GroupInfoFetcher, SignatureResultCacheUpdater};
+use arpa_node_core::{RandomnessTask, COMMITTER_EXCLUSIVE_WINDOW};
+use arpa_node_dal::{
+    cache::RandomnessResultCache, BLSTasksFetcher, BlockInfoFetcher, GroupInfoFetcher,
+    SignatureResultCacheUpdater,
+};
 use async_trait::async_trait;
 use ethers::types::Address;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 pub struct RandomnessSignatureAggregationListener<
+    B: BlockInfoFetcher,
     G: GroupInfoFetcher,
+    T: BLSTasksFetcher<RandomnessTask>,
     C: SignatureResultCacheUpdater<RandomnessResultCache>,
 > {
     chain_id: usize,
     id_address: Address,
+    block_cache: Arc<RwLock<B>>,
     group_cache: Arc<RwLock<G>>,
+    randomness_tasks_cache: Arc<RwLock<T>>,
     randomness_signature_cache: Arc<RwLock<C>>,
     eq: Arc<RwLock<EventQueue>>,
+    partial_signature_gossip: Option<GossipConfig>,
+    // signatures of the tasks the node backs the committers up for, held until the block height
+    // their backup delay ends at
+    backup_signatures: Vec<(usize, RandomnessResultCache)>,
 }
 
-impl<G: GroupInfoFetcher, C: SignatureResultCacheUpdater<RandomnessResultCache>>
-    RandomnessSignatureAggregationListener<G, C>
+impl<
+        B: BlockInfoFetcher,
+        G: GroupInfoFetcher,
+        T: BLSTasksFetcher<RandomnessTask>,
+        C: SignatureResultCacheUpdater<RandomnessResultCache>,
+    > RandomnessSignatureAggregationListener<B, G, T, C>
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         chain_id: usize,
         id_address: Address,
+        block_cache: Arc<RwLock<B>>,
         group_cache: Arc<RwLock<G>>,
+        randomness_tasks_cache: Arc<RwLock<T>>,
         randomness_signature_cache: Arc<RwLock<C>>,
         eq: Arc<RwLock<EventQueue>>,
+        partial_signature_gossip: Option<GossipConfig>,
     ) -> Self {
         RandomnessSignatureAggregationListener {
             chain_id,
             id_address,
+            block_cache,
             group_cache,
+            randomness_tasks_cache,
             randomness_signature_cache,
             eq,
+            partial_signature_gossip,
+            backup_signatures: vec![],
+        }
+    }
+}
+
+impl<
+        B: BlockInfoFetcher + Sync + Send,
+        G: GroupInfoFetcher + Sync + Send,
+        T: BLSTasksFetcher<RandomnessTask> + Sync + Send,
+        C: SignatureResultCacheUpdater<RandomnessResultCache> + Sync + Send,
+    > RandomnessSignatureAggregationListener<B, G, T, C>
+{
+    /// Takes the signatures the node is to fulfill at the latest block. The ones it backs the
+    /// committers up for are held until their backup delay ends.
+    async fn take_signatures_to_fulfill(
+        &mut self,
+        backup_committer_delay_blocks: usize,
+    ) -> Vec<RandomnessResultCache> {
+        // committers rotate with every task, and with gossip any member can hold ready
+        // signatures
+        let ready_signatures = self
+            .randomness_signature_cache
+            .write()
+            .await
+            .get_ready_to_commit_signatures();
+
+        let mut tasks = vec![];
+
+        for signature in ready_signatures {
+            let (is_task_committer, is_backup_committer) = {
+                let group_cache = self.group_cache.read().await;
+
+                group_cache
+                    .get_ready_epoch(signature.group_index)
+                    .and_then(|epoch| {
+                        group_cache.get_task_committers(
+                            signature.group_index,
+                            epoch,
+                            signature.randomness_task_index,
+                            signature.message.as_bytes(),
+                        )
+                    })
+                    .map(|(committers, backup_committers)| {
+                        (
+                            committers.contains(&self.id_address),
+                            backup_committers.contains(&self.id_address),
+                        )
+                    })
+                    .unwrap_or((false, false))
+            };
+
+            if is_task_committer {
+                tasks.push(signature);
+            } else if is_backup_committer {
+                // the delay is counted from the assignment of the task, as the window of the
+                // adapter is, or from now if the task is not known
+                let task = self
+                    .randomness_tasks_cache
+                    .read()
+                    .await
+                    .get(signature.randomness_task_index)
+                    .await;
+
+                let assignment_block_height = match task {
+                    Ok(task) => task.assignment_block_height,
+                    Err(_) => self.block_cache.read().await.get_block_height(),
+                };
+
+                // the fulfillment is skipped if the committers have done it meanwhile
+                self.backup_signatures.push((
+                    assignment_block_height + backup_committer_delay_blocks,
+                    signature,
+                ));
+            }
+            // other members which collect the signature by gossip leave it to the
+            // committers and the backup committers of the task, as the adapter only accepts
+            // theirs
         }
+
+        let block_height = self.block_cache.read().await.get_block_height();
+
+        let (due, pending) = std::mem::take(&mut self.backup_signatures)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(deadline, _)| *deadline < block_height);
+
+        self.backup_signatures = pending;
+
+        tasks.extend(due.into_iter().map(|(_, signature)| signature));
+
+        tasks
     }
 }
 
 #[async_trait]
 impl<
+        B: BlockInfoFetcher + Sync + Send,
         G: GroupInfoFetcher + Sync + Send,
+        T: BLSTasksFetcher<RandomnessTask> + Sync + Send,
         C: SignatureResultCacheUpdater<RandomnessResultCache> + Sync + Send,
     > EventPublisher<ReadyToFulfillRandomnessTask>
-    for RandomnessSignatureAggregationListener<G, C>
+    for RandomnessSignatureAggregationListener<B, G, T, C>
 {
     async fn publish(&self, event: ReadyToFulfillRandomnessTask) {
         self.eq.read().await.publish(event).await;
@@ -55,31 +170,195 @@ impl<
 
 #[async_trait]
 impl<
+        B: BlockInfoFetcher + Sync + Send,
         G: GroupInfoFetcher + Sync + Send,
+        T: BLSTasksFetcher<RandomnessTask> + Sync + Send,
         C: SignatureResultCacheUpdater<RandomnessResultCache> + Sync + Send,
-    > Listener for RandomnessSignatureAggregationListener<G, C>
+    > Listener for RandomnessSignatureAggregationListener<B, G, T, C>
 {
     async fn start(mut self) -> NodeResult<()> {
-        loop {
-            let is_committer = self.group_cache.read().await.is_committer(self.id_address);
+        // the adapter rejects backup committers within the exclusive window of the committers,
+        // which backup committers without gossip wait for by default
+        let backup_committer_delay_blocks = self
+            .partial_signature_gossip
+            .map_or(COMMITTER_EXCLUSIVE_WINDOW, |gossip_config| {
+                gossip_config.backup_committer_delay_blocks
+            })
+            .max(COMMITTER_EXCLUSIVE_WINDOW);
 
-            if let Ok(true) = is_committer {
-                let ready_signatures = self
-                    .randomness_signature_cache
-                    .write()
-                    .await
-                    .get_ready_to_commit_signatures();
+        loop {
+            let tasks = self
+                .take_signatures_to_fulfill(backup_committer_delay_blocks)
+                .await;
 
-                if !ready_signatures.is_empty() {
-                    self.publish(ReadyToFulfillRandomnessTask {
-                        chain_id: self.chain_id,
-                        tasks: ready_signatures,
-                    })
-                    .await;
-                }
+            if !tasks.is_empty() {
+                self.publish(ReadyToFulfillRandomnessTask {
+                    chain_id: self.chain_id,
+                    tasks,
+                })
+                .await;
             }
 
             tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
         }
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::RandomnessSignatureAggregationListener;
+    use crate::node::{
+        committer::server::tests::{build_group, build_randomness_task, GROUP_INDEX},
+        queue::event_queue::EventQueue,
+    };
+    use arpa_node_core::{DKGStatus, Group, RandomnessTask, COMMITTER_EXCLUSIVE_WINDOW};
+    use arpa_node_dal::{
+        cache::{
+            GroupInfo, InMemoryBLSTasksQueue, InMemoryBlockInfoCache, InMemoryGroupInfoCache,
+            InMemorySignatureResultCache, RandomnessResultCache,
+        },
+        BLSTasksUpdater, BlockInfoUpdater, GroupInfoFetcher, SignatureResultCacheUpdater,
+    };
+    use ethers::types::{Address, H256};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    const ASSIGNMENT_BLOCK_HEIGHT: usize = 100;
+
+    type TestListener = RandomnessSignatureAggregationListener<
+        InMemoryBlockInfoCache,
+        InMemoryGroupInfoCache,
+        InMemoryBLSTasksQueue<RandomnessTask>,
+        InMemorySignatureResultCache<RandomnessResultCache>,
+    >;
+
+    fn build_group_cache(group: &Group) -> InMemoryGroupInfoCache {
+        InMemoryGroupInfoCache::rebuild(
+            vec![GroupInfo::rebuild(
+                None,
+                group.clone(),
+                DKGStatus::None,
+                0,
+                0,
+                None,
+            )],
+            vec![],
+        )
+    }
+
+    /// Builds the listener of the member holding the signature of the first randomness task,
+    /// assigned at the latest block, with the block cache to move on.
+    async fn build_listener(
+        group: &Group,
+        id_address: Address,
+    ) -> (TestListener, Arc<RwLock<InMemoryBlockInfoCache>>) {
+        let mut task = build_randomness_task(0, "0x01");
+        task.assignment_block_height = ASSIGNMENT_BLOCK_HEIGHT;
+
+        let mut randomness_tasks_cache = InMemoryBLSTasksQueue::<RandomnessTask>::new();
+        randomness_tasks_cache.add(task).await.unwrap();
+
+        let mut randomness_signature_cache =
+            InMemorySignatureResultCache::<RandomnessResultCache>::new();
+        randomness_signature_cache
+            .add(GROUP_INDEX, 0, "0x01".to_string(), 1)
+            .unwrap();
+        randomness_signature_cache
+            .add_partial_signature(0, id_address, vec![])
+            .unwrap();
+
+        let mut block_cache = InMemoryBlockInfoCache::new(0);
+        block_cache.add_block(ASSIGNMENT_BLOCK_HEIGHT, H256::random());
+        let block_cache = Arc::new(RwLock::new(block_cache));
+
+        let listener = RandomnessSignatureAggregationListener::new(
+            0,
+            id_address,
+            block_cache.clone(),
+            Arc::new(RwLock::new(build_group_cache(group))),
+            Arc::new(RwLock::new(randomness_tasks_cache)),
+            Arc::new(RwLock::new(randomness_signature_cache)),
+            Arc::new(RwLock::new(EventQueue::new())),
+            None,
+        );
+
+        (listener, block_cache)
+    }
+
+    #[tokio::test]
+    async fn test_backup_committer_waits_for_the_backup_delay() {
+        let (mut group, _) = build_group(4, 1);
+
+        // a committer and a backup committer of every task, the other two members are neither
+        group.committers.truncate(1);
+
+        let (committers, backup_committers) = build_group_cache(&group)
+            .get_task_committers(GROUP_INDEX, group.epoch, 0, b"0x01")
+            .unwrap();
+
+        let other_member = *group
+            .members
+            .keys()
+            .find(|member| !committers.contains(member) && !backup_committers.contains(member))
+            .unwrap();
+
+        let (mut committer, _) = build_listener(&group, committers[0]).await;
+
+        let (mut backup_committer, backup_block_cache) =
+            build_listener(&group, backup_committers[0]).await;
+
+        let (mut other, other_block_cache) = build_listener(&group, other_member).await;
+
+        assert_eq!(
+            committer
+                .take_signatures_to_fulfill(COMMITTER_EXCLUSIVE_WINDOW)
+                .await
+                .len(),
+            1
+        );
+
+        assert!(backup_committer
+            .take_signatures_to_fulfill(COMMITTER_EXCLUSIVE_WINDOW)
+            .await
+            .is_empty());
+
+        // the delay is counted from the assignment of the task
+        backup_block_cache.write().await.add_block(
+            ASSIGNMENT_BLOCK_HEIGHT + COMMITTER_EXCLUSIVE_WINDOW,
+            H256::random(),
+        );
+
+        assert!(backup_committer
+            .take_signatures_to_fulfill(COMMITTER_EXCLUSIVE_WINDOW)
+            .await
+            .is_empty());
+
+        backup_block_cache.write().await.add_block(
+            ASSIGNMENT_BLOCK_HEIGHT + COMMITTER_EXCLUSIVE_WINDOW + 1,
+            H256::random(),
+        );
+
+        let released = backup_committer
+            .take_signatures_to_fulfill(COMMITTER_EXCLUSIVE_WINDOW)
+            .await;
+
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].randomness_task_index, 0);
+
+        // the signature is released once
+        assert!(backup_committer
+            .take_signatures_to_fulfill(COMMITTER_EXCLUSIVE_WINDOW)
+            .await
+            .is_empty());
+
+        other_block_cache.write().await.add_block(
+            ASSIGNMENT_BLOCK_HEIGHT + COMMITTER_EXCLUSIVE_WINDOW + 1,
+            H256::random(),
+        );
+
+        assert!(other
+            .take_signatures_to_fulfill(COMMITTER_EXCLUSIVE_WINDOW)
+            .await
+            .is_empty());
+    }
+}
@@ -7,7 +7,7 @@ use crate::node::{
 use arpa_node_core::GroupRelayTask;
 use arpa_node_dal::{BLSTasksUpdater, BlockInfoFetcher, GroupInfoFetcher};
 use async_trait::async_trait;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use tokio::sync::RwLock;
 
 pub struct ReadyToHandleGroupRelayTaskListener<
@@ -60,25 +60,40 @@ impl<
 {
     async fn start(mut self) -> NodeResult<()> {
         loop {
-            let is_bls_ready = self.group_cache.read().await.get_state();
-
-            if let Ok(true) = is_bls_ready {
-                let current_group_index = self.group_cache.read().await.get_index()?;
+            let ready_group_indices = self.group_cache.read().await.get_ready_indices();
 
+            if !ready_group_indices.is_empty() {
                 let current_block_height = self.block_cache.read().await.get_block_height();
 
+                let confirmed_block_height =
+                    self.block_cache.read().await.get_confirmed_block_height();
+
                 let available_tasks = self
                     .group_relay_tasks_cache
                     .write()
                     .await
-                    .check_and_get_available_tasks(current_block_height, current_group_index)
+                    .check_and_get_available_tasks(
+                        current_block_height,
+                        confirmed_block_height,
+                        &ready_group_indices,
+                    )
                     .await?;
 
-                if !available_tasks.is_empty() {
-                    self.publish(ReadyToHandleGroupRelayTask {
-                        tasks: available_tasks,
-                    })
-                    .await;
+                let mut tasks_to_process: BTreeMap<usize, Vec<GroupRelayTask>> = BTreeMap::new();
+
+                for task in available_tasks {
+                    // a group never signs the relay of itself
+                    if let Some(group_index) = ready_group_indices
+                        .iter()
+                        .find(|index| **index != task.relayed_group_index)
+                    {
+                        tasks_to_process.entry(*group_index).or_default().push(task);
+                    }
+                }
+
+                for (group_index, tasks) in tasks_to_process {
+                    self.publish(ReadyToHandleGroupRelayTask { group_index, tasks })
+                        .await;
                 }
             }
 
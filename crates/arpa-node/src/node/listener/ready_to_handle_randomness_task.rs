@@ -2,6 +2,7 @@ use super::Listener;
 use crate::node::{
     error::{NodeError, NodeResult},
     event::ready_to_handle_randomness_task::ReadyToHandleRandomnessTask,
+    metrics::inc_rpc_retries,
     queue::{event_queue::EventQueue, EventPublisher},
 };
 use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterViews};
@@ -10,7 +11,7 @@ use arpa_node_dal::{BLSTasksUpdater, BlockInfoFetcher, GroupInfoFetcher};
 use async_trait::async_trait;
 use ethers::types::Address;
 use log::error;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use tokio::sync::RwLock;
 use tokio_retry::{strategy::FixedInterval, RetryIf};
 
@@ -92,37 +93,49 @@ impl<
             if let Err(err) = RetryIf::spawn(
                 retry_strategy.clone(),
                 || async {
-                    let is_bls_ready = self.group_cache.read().await.get_state();
-
-                    if let Ok(true) = is_bls_ready {
-                        let current_group_index = self.group_cache.read().await.get_index()?;
+                    let ready_group_indices = self.group_cache.read().await.get_ready_indices();
 
+                    if !ready_group_indices.is_empty() {
                         let current_block_height = self.block_cache.read().await.get_block_height();
 
+                        let confirmed_block_height =
+                            self.block_cache.read().await.get_confirmed_block_height();
+
                         let available_tasks = self
                             .randomness_tasks_cache
                             .write()
                             .await
                             .check_and_get_available_tasks(
                                 current_block_height,
-                                current_group_index,
+                                confirmed_block_height,
+                                &ready_group_indices,
                             )
                             .await?;
 
-                        let mut tasks_to_process: Vec<RandomnessTask> = vec![];
+                        let mut tasks_to_process: BTreeMap<usize, Vec<RandomnessTask>> =
+                            BTreeMap::new();
 
                         for task in available_tasks {
                             if let Ok(false) =
                                 client.get_signature_task_completion_state(task.index).await
                             {
-                                tasks_to_process.push(task);
+                                // a task over the exclusive window is signed by the first group the node is ready in
+                                let group_index = if ready_group_indices.contains(&task.group_index)
+                                {
+                                    task.group_index
+                                } else {
+                                    ready_group_indices[0]
+                                };
+
+                                tasks_to_process.entry(group_index).or_default().push(task);
                             }
                         }
 
-                        if !tasks_to_process.is_empty() {
+                        for (group_index, tasks) in tasks_to_process {
                             self.publish(ReadyToHandleRandomnessTask {
                                 chain_id: self.chain_id,
-                                tasks: tasks_to_process,
+                                group_index,
+                                tasks,
                             })
                             .await;
                         }
@@ -132,6 +145,7 @@ impl<
                 },
                 |e: &NodeError| {
                     error!("listener is interrupted. Retry... Error: {:?}, ", e);
+                    inc_rpc_retries(self.chain_id, "ready_to_handle_randomness_task");
                     true
                 },
             )
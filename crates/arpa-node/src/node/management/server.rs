@@ -0,0 +1,543 @@
+use self::management_stub::{
+    get_signature_result_caches_request::TaskType as RequestTaskType,
+    group_info_reply::DkgStatus as ReplyDKGStatus,
+    management_service_server::{ManagementService, ManagementServiceServer},
+    ExitDkgRequest, GetRandomnessTasksRequest, GetSignatureResultCachesRequest, GroupInfoReply,
+    GroupInfosReply, Member, NodeInfoReply, PostProcessDkgRequest,
+    RandomnessTask as ReplyRandomnessTask, RandomnessTasksReply, SignatureResultCache,
+    SignatureResultCachesReply,
+};
+use crate::node::{
+    context::{
+        chain::{AdapterChainFetcher, ChainFetcher, MainChainFetcher},
+        types::GeneralContext,
+        ContextFetcher,
+    },
+    error::NodeError,
+    event::dkg_post_process::DKGPostProcess,
+    metrics::{observe_dkg_status, DKG_OUTCOMES},
+    queue::EventPublisher,
+};
+use arpa_node_contract_client::{
+    adapter::AdapterClientBuilder, controller::ControllerClientBuilder,
+    coordinator::CoordinatorClientBuilder, provider::ChainProviderBuilder,
+};
+use arpa_node_core::{
+    address_to_string, now_timestamp, ChainIdentity, DKGStatus, Member as ModelMember,
+    RandomnessTask,
+};
+use arpa_node_dal::{
+    cache::{
+        BLSResultCache, GroupRelayConfirmationResultCache, GroupRelayResultCache,
+        RandomnessResultCache,
+    },
+    BLSTasksFetcher, BLSTasksUpdater, GroupInfoFetcher, GroupInfoUpdater, LogCheckpointFetcher,
+    LogCheckpointUpdater, NodeInfoFetcher, SignatureResultCacheFetcher,
+};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tokio::sync::RwLock;
+use tonic::{transport::Server, Request, Response, Status};
+
+pub mod management_stub {
+    include!("../../../rpc_stub/management.rs");
+}
+
+const MANAGEMENT_TOKEN_METADATA_KEY: &str = "authorization";
+
+pub(crate) struct NodeManagementServiceServer<
+    N: NodeInfoFetcher,
+    G: GroupInfoFetcher + GroupInfoUpdater,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater,
+    I: ChainIdentity
+        + ControllerClientBuilder
+        + CoordinatorClientBuilder
+        + AdapterClientBuilder
+        + ChainProviderBuilder,
+> {
+    rpc_token: Option<String>,
+    context: Arc<RwLock<GeneralContext<N, G, T, I>>>,
+}
+
+impl<
+        N: NodeInfoFetcher,
+        G: GroupInfoFetcher + GroupInfoUpdater,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater,
+        I: ChainIdentity
+            + ControllerClientBuilder
+            + CoordinatorClientBuilder
+            + AdapterClientBuilder
+            + ChainProviderBuilder,
+    > NodeManagementServiceServer<N, G, T, I>
+{
+    pub fn new(
+        rpc_token: Option<String>,
+        context: Arc<RwLock<GeneralContext<N, G, T, I>>>,
+    ) -> Self {
+        NodeManagementServiceServer { rpc_token, context }
+    }
+
+    // write endpoints are refused unless a token is configured and carried by the request
+    fn check_token<R>(&self, req: &Request<R>) -> Result<(), Status> {
+        let rpc_token = self.rpc_token.as_ref().ok_or_else(|| {
+            Status::permission_denied("write endpoints are disabled without a management token")
+        })?;
+
+        let req_token = req
+            .metadata()
+            .get(MANAGEMENT_TOKEN_METADATA_KEY)
+            .ok_or_else(|| Status::unauthenticated("management token is empty"))?
+            .to_str()
+            .map_err(|_| Status::unauthenticated("management token is invalid"))?;
+
+        // compared in constant time so that the token can't be guessed byte by byte from timing
+        if !bool::from(req_token.as_bytes().ct_eq(rpc_token.as_bytes())) {
+            return Err(Status::unauthenticated("management token is invalid"));
+        }
+
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl<
+        N: NodeInfoFetcher + Sync + Send + 'static,
+        G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
+        T: BLSTasksFetcher<RandomnessTask>
+            + BLSTasksUpdater<RandomnessTask>
+            + LogCheckpointFetcher
+            + LogCheckpointUpdater
+            + Sync
+            + Send
+            + 'static,
+        I: ChainIdentity
+            + ControllerClientBuilder
+            + CoordinatorClientBuilder
+            + AdapterClientBuilder
+            + ChainProviderBuilder
+            + Sync
+            + Send
+            + 'static,
+    > ManagementService for NodeManagementServiceServer<N, G, T, I>
+{
+    async fn get_node_info(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<NodeInfoReply>, Status> {
+        let node_cache = self.context.read().await.get_main_chain().get_node_cache();
+
+        let node_cache = node_cache.read().await;
+
+        let id_address = node_cache
+            .get_id_address()
+            .map_err(|e| Status::not_found(e.to_string()))?;
+
+        let node_rpc_endpoint = node_cache
+            .get_node_rpc_endpoint()
+            .map_err(|e| Status::not_found(e.to_string()))?
+            .to_string();
+
+        let dkg_public_key = node_cache
+            .get_dkg_public_key()
+            .map_err(|e| Status::not_found(e.to_string()))?;
+
+        Ok(Response::new(NodeInfoReply {
+            id_address: address_to_string(id_address),
+            node_rpc_endpoint,
+            dkg_public_key: bincode::serialize(dkg_public_key).unwrap(),
+        }))
+    }
+
+    async fn get_group_info(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<GroupInfosReply>, Status> {
+        let context = self.context.read().await;
+
+        let id_address = context
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()
+            .map_err(|e| Status::not_found(e.to_string()))?;
+
+        let group_cache = context.get_main_chain().get_group_cache();
+
+        let group_cache = group_cache.read().await;
+
+        let mut groups = vec![];
+
+        let now = now_timestamp();
+
+        for index in group_cache.get_indices() {
+            // the latest committed epoch is still used for signing while the latest one is in DKG
+            let mut epochs = vec![];
+
+            if let Ok(ready_epoch) = group_cache.get_ready_epoch(index) {
+                epochs.push(ready_epoch);
+            }
+
+            if let Ok(epoch) = group_cache.get_epoch(index) {
+                if !epochs.contains(&epoch) {
+                    epochs.push(epoch);
+                }
+            }
+
+            for epoch in epochs {
+                let public_key = group_cache
+                    .get_public_key(index, epoch)
+                    .map(|public_key| bincode::serialize(public_key).unwrap())
+                    .unwrap_or_default();
+
+                let members = group_cache
+                    .get_members(index, epoch)
+                    .map_err(|e| Status::not_found(e.to_string()))?
+                    .iter()
+                    .map(|(id_address, member)| {
+                        let mut member: Member = member.clone().into();
+
+                        member.rpc_endpoint = group_cache
+                            .get_peer_endpoint(*id_address, now)
+                            .unwrap_or_default()
+                            .to_string();
+
+                        (address_to_string(*id_address), member)
+                    })
+                    .collect();
+
+                let committers = group_cache
+                    .get_committers(index, epoch)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(address_to_string)
+                    .collect();
+
+                let dkg_status: ReplyDKGStatus = group_cache
+                    .get_dkg_status(index, epoch)
+                    .map_err(|e| Status::not_found(e.to_string()))?
+                    .into();
+
+                groups.push(GroupInfoReply {
+                    index: index as u32,
+                    epoch: epoch as u32,
+                    size: group_cache.get_size(index, epoch).unwrap_or(0) as u32,
+                    threshold: group_cache.get_threshold(index, epoch).unwrap_or(0) as u32,
+                    state: group_cache.get_state(index, epoch).unwrap_or(false),
+                    self_index: group_cache.get_self_index(index, epoch).unwrap_or(0) as u32,
+                    public_key,
+                    members,
+                    committers,
+                    is_committer: group_cache
+                        .is_committer(index, epoch, id_address)
+                        .unwrap_or(false),
+                    dkg_status: dkg_status as i32,
+                    dkg_start_block_height: group_cache
+                        .get_dkg_start_block_height(index, epoch)
+                        .unwrap_or(0) as u32,
+                });
+            }
+        }
+
+        Ok(Response::new(GroupInfosReply { groups }))
+    }
+
+    async fn get_randomness_tasks(
+        &self,
+        request: Request<GetRandomnessTasksRequest>,
+    ) -> Result<Response<RandomnessTasksReply>, Status> {
+        let req = request.into_inner();
+
+        let chain_id = req.chain_id as usize;
+
+        let context = self.context.read().await;
+
+        let randomness_tasks_cache = match chain_id {
+            0 => context.get_main_chain().get_randomness_tasks_cache(),
+            _ => context
+                .get_adapter_chain(chain_id)
+                .ok_or_else(|| {
+                    Status::invalid_argument(NodeError::InvalidChainId(chain_id).to_string())
+                })?
+                .get_randomness_tasks_cache(),
+        };
+
+        let tasks = randomness_tasks_cache
+            .read()
+            .await
+            .get_pending_tasks()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .into_iter()
+            .map(|task| task.into())
+            .collect();
+
+        Ok(Response::new(RandomnessTasksReply { tasks }))
+    }
+
+    async fn get_signature_result_caches(
+        &self,
+        request: Request<GetSignatureResultCachesRequest>,
+    ) -> Result<Response<SignatureResultCachesReply>, Status> {
+        let req = request.into_inner();
+
+        let chain_id = req.chain_id as usize;
+
+        let context = self.context.read().await;
+
+        let result_caches = match RequestTaskType::from_i32(req.task_type) {
+            Some(RequestTaskType::Randomness) => {
+                let randomness_result_cache = match chain_id {
+                    0 => context.get_main_chain().get_randomness_result_cache(),
+                    _ => context
+                        .get_adapter_chain(chain_id)
+                        .ok_or_else(|| {
+                            Status::invalid_argument(
+                                NodeError::InvalidChainId(chain_id).to_string(),
+                            )
+                        })?
+                        .get_randomness_result_cache(),
+                };
+
+                randomness_result_cache
+                    .read()
+                    .await
+                    .get_all()
+                    .into_iter()
+                    .map(|result_cache| result_cache.into())
+                    .collect()
+            }
+            Some(RequestTaskType::GroupRelay) => {
+                if chain_id != 0 {
+                    return Err(Status::invalid_argument(
+                        NodeError::InvalidChainId(chain_id).to_string(),
+                    ));
+                }
+
+                let group_relay_result_cache =
+                    context.get_main_chain().get_group_relay_result_cache();
+
+                group_relay_result_cache
+                    .read()
+                    .await
+                    .get_all()
+                    .into_iter()
+                    .map(|result_cache| result_cache.into())
+                    .collect()
+            }
+            Some(RequestTaskType::GroupRelayConfirmation) => {
+                let group_relay_confirmation_result_cache = context
+                    .get_adapter_chain(chain_id)
+                    .ok_or_else(|| {
+                        Status::invalid_argument(NodeError::InvalidChainId(chain_id).to_string())
+                    })?
+                    .get_group_relay_confirmation_result_cache();
+
+                group_relay_confirmation_result_cache
+                    .read()
+                    .await
+                    .get_all()
+                    .into_iter()
+                    .map(|result_cache| result_cache.into())
+                    .collect()
+            }
+            None => return Err(Status::invalid_argument("unknown task type")),
+        };
+
+        Ok(Response::new(SignatureResultCachesReply { result_caches }))
+    }
+
+    async fn post_process_dkg(
+        &self,
+        request: Request<PostProcessDkgRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.check_token(&request)?;
+
+        let group_index = request.into_inner().group_index as usize;
+
+        let context = self.context.read().await;
+
+        let group_cache = context.get_main_chain().get_group_cache();
+
+        let group_epoch = group_cache
+            .read()
+            .await
+            .get_epoch(group_index)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+
+        context
+            .get_event_queue()
+            .read()
+            .await
+            .publish(DKGPostProcess::new(group_index, group_epoch))
+            .await;
+
+        Ok(Response::new(()))
+    }
+
+    async fn exit_dkg(&self, request: Request<ExitDkgRequest>) -> Result<Response<()>, Status> {
+        self.check_token(&request)?;
+
+        let group_index = request.into_inner().group_index as usize;
+
+        let group_cache = self.context.read().await.get_main_chain().get_group_cache();
+
+        let group_epoch = group_cache
+            .read()
+            .await
+            .get_epoch(group_index)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+
+        // the running DKG task watches the status and aborts itself once it is reset
+        let res = group_cache
+            .write()
+            .await
+            .update_dkg_status(group_index, group_epoch, DKGStatus::None)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        if !res {
+            return Err(Status::failed_precondition("there is no DKG in progress"));
+        }
+
+        observe_dkg_status(DKGStatus::None);
+
+        DKG_OUTCOMES.with_label_values(&["exited"]).inc();
+
+        Ok(Response::new(()))
+    }
+}
+
+pub async fn start_management_server<
+    N: NodeInfoFetcher + Sync + Send + 'static,
+    G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static,
+    T: BLSTasksFetcher<RandomnessTask>
+        + BLSTasksUpdater<RandomnessTask>
+        + LogCheckpointFetcher
+        + LogCheckpointUpdater
+        + Sync
+        + Send
+        + 'static,
+    I: ChainIdentity
+        + ControllerClientBuilder
+        + CoordinatorClientBuilder
+        + AdapterClientBuilder
+        + ChainProviderBuilder
+        + Sync
+        + Send
+        + 'static,
+>(
+    endpoint: String,
+    rpc_token: Option<String>,
+    context: Arc<RwLock<GeneralContext<N, G, T, I>>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let addr = endpoint.parse()?;
+
+    Server::builder()
+        .add_service(ManagementServiceServer::new(
+            NodeManagementServiceServer::new(rpc_token, context),
+        ))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
+
+impl From<DKGStatus> for ReplyDKGStatus {
+    fn from(dkg_status: DKGStatus) -> Self {
+        match dkg_status {
+            DKGStatus::None => ReplyDKGStatus::None,
+            DKGStatus::InPhase => ReplyDKGStatus::InPhase,
+            DKGStatus::CommitSuccess => ReplyDKGStatus::CommitSuccess,
+            DKGStatus::WaitForPostProcess => ReplyDKGStatus::WaitForPostProcess,
+        }
+    }
+}
+
+impl From<ModelMember> for Member {
+    fn from(member: ModelMember) -> Self {
+        Member {
+            index: member.index as u32,
+            id_address: address_to_string(member.id_address),
+            // resolved from the peer records
+            rpc_endpoint: String::new(),
+            partial_public_key: member
+                .partial_public_key
+                .map(|partial_public_key| bincode::serialize(&partial_public_key).unwrap())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<RandomnessTask> for ReplyRandomnessTask {
+    fn from(task: RandomnessTask) -> Self {
+        ReplyRandomnessTask {
+            index: task.index as u32,
+            message: task.message,
+            group_index: task.group_index as u32,
+            assignment_block_height: task.assignment_block_height as u32,
+        }
+    }
+}
+
+impl From<&BLSResultCache<RandomnessResultCache>> for SignatureResultCache {
+    fn from(cache: &BLSResultCache<RandomnessResultCache>) -> Self {
+        let result_cache = &cache.result_cache;
+
+        SignatureResultCache {
+            group_index: result_cache.group_index as u32,
+            signature_index: result_cache.randomness_task_index as u32,
+            message: result_cache.message.as_bytes().to_vec(),
+            threshold: result_cache.threshold as u32,
+            partial_signers: result_cache
+                .partial_signatures
+                .keys()
+                .map(|id_address| address_to_string(*id_address))
+                .collect(),
+            committed: cache.state,
+        }
+    }
+}
+
+impl From<&BLSResultCache<GroupRelayResultCache>> for SignatureResultCache {
+    fn from(cache: &BLSResultCache<GroupRelayResultCache>) -> Self {
+        let result_cache = &cache.result_cache;
+
+        SignatureResultCache {
+            group_index: result_cache.group_index as u32,
+            signature_index: result_cache.group_relay_task_index as u32,
+            message: bincode::serialize(&result_cache.relayed_group).unwrap(),
+            threshold: result_cache.threshold as u32,
+            partial_signers: result_cache
+                .partial_signatures
+                .keys()
+                .map(|id_address| address_to_string(*id_address))
+                .collect(),
+            committed: cache.state,
+        }
+    }
+}
+
+impl From<&BLSResultCache<GroupRelayConfirmationResultCache>> for SignatureResultCache {
+    fn from(cache: &BLSResultCache<GroupRelayConfirmationResultCache>) -> Self {
+        let result_cache = &cache.result_cache;
+
+        SignatureResultCache {
+            group_index: result_cache.group_index as u32,
+            signature_index: result_cache.group_relay_confirmation_task_index as u32,
+            message: bincode::serialize(&result_cache.group_relay_confirmation).unwrap(),
+            threshold: result_cache.threshold as u32,
+            partial_signers: result_cache
+                .partial_signatures
+                .keys()
+                .map(|id_address| address_to_string(*id_address))
+                .collect(),
+            committed: cache.state,
+        }
+    }
+}
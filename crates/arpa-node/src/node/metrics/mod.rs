@@ -0,0 +1,146 @@
+pub mod server;
+
+use arpa_node_core::{DKGStatus, TaskType};
+use lazy_static::lazy_static;
+use prometheus::{
+    exponential_buckets, register_histogram_vec, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+};
+
+lazy_static! {
+    pub static ref DKG_STATUS: IntGauge = register_int_gauge!(
+        "arpa_node_dkg_status",
+        "Current DKG status of the node, 0: None, 1: InPhase, 2: CommitSuccess, 3: WaitForPostProcess"
+    )
+    .unwrap();
+    pub static ref DKG_STATUS_TRANSITIONS: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_dkg_status_transitions_total",
+        "Number of DKG status transitions by target status",
+        &["status"]
+    )
+    .unwrap();
+    pub static ref DKG_PHASE_DURATION_SECONDS: HistogramVec = register_histogram_vec!(
+        "arpa_node_dkg_phase_duration_seconds",
+        "Time spent in each DKG phase, from entering the phase to entering the next one",
+        &["phase"],
+        exponential_buckets(1.0, 2.0, 12).unwrap()
+    )
+    .unwrap();
+    pub static ref DKG_OUTCOMES: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_dkg_outcomes_total",
+        "Number of finished DKG runs by outcome",
+        &["outcome"]
+    )
+    .unwrap();
+    pub static ref PARTIAL_SIGNATURES_SENT: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_partial_signatures_sent_total",
+        "Number of partial signatures sent to committers by result",
+        &["chain_id", "task_type", "result"]
+    )
+    .unwrap();
+    pub static ref PARTIAL_SIGNATURES_RECEIVED: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_partial_signatures_received_total",
+        "Number of partial signatures accepted by the committer server",
+        &["chain_id", "task_type"]
+    )
+    .unwrap();
+    pub static ref PARTIAL_SIGNATURES_REJECTED: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_partial_signatures_rejected_total",
+        "Number of partial signatures rejected by the committer server by reason",
+        &["chain_id", "task_type", "reason"]
+    )
+    .unwrap();
+    pub static ref SIGNATURE_AGGREGATION_SECONDS: HistogramVec = register_histogram_vec!(
+        "arpa_node_signature_aggregation_seconds",
+        "Time spent aggregating partial signatures into a threshold signature",
+        &["chain_id", "task_type"],
+        exponential_buckets(0.0005, 2.0, 12).unwrap()
+    )
+    .unwrap();
+    pub static ref FULFILLMENTS: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_fulfillments_total",
+        "Number of fulfillment transactions by result",
+        &["chain_id", "task_type", "result"]
+    )
+    .unwrap();
+    pub static ref RPC_RETRIES: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_rpc_retries_total",
+        "Number of retried RPC calls by operation",
+        &["chain_id", "operation"]
+    )
+    .unwrap();
+    pub static ref EVENTS_PUBLISHED: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_events_published_total",
+        "Number of events published to the event queue",
+        &["event"]
+    )
+    .unwrap();
+    pub static ref EVENTS_PENDING: IntGaugeVec = register_int_gauge_vec!(
+        "arpa_node_events_pending",
+        "Number of published events not yet received by subscribers",
+        &["event"]
+    )
+    .unwrap();
+    pub static ref EVENTS_LAGGED: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_events_lagged_total",
+        "Number of events dropped because a subscriber fell behind",
+        &["event", "subscriber"]
+    )
+    .unwrap();
+    pub static ref EVENT_HANDLING_SECONDS: HistogramVec = register_histogram_vec!(
+        "arpa_node_event_handling_seconds",
+        "Time spent by a subscriber handling an event",
+        &["event", "subscriber"],
+        exponential_buckets(0.0005, 2.0, 16).unwrap()
+    )
+    .unwrap();
+    pub static ref BLOCK_HEIGHT: IntGaugeVec = register_int_gauge_vec!(
+        "arpa_node_block_height",
+        "Latest block height seen by the block listener",
+        &["chain_id"]
+    )
+    .unwrap();
+    pub static ref CHAIN_REORGS: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_chain_reorgs_total",
+        "Number of chain reorganizations detected by the block listener",
+        &["chain_id"]
+    )
+    .unwrap();
+    pub static ref ORPHANED_TASKS: IntCounterVec = register_int_counter_vec!(
+        "arpa_node_orphaned_tasks_total",
+        "Number of pending tasks rolled back because their blocks were orphaned",
+        &["chain_id", "task_type"]
+    )
+    .unwrap();
+}
+
+pub fn dkg_status_label(dkg_status: DKGStatus) -> &'static str {
+    match dkg_status {
+        DKGStatus::None => "none",
+        DKGStatus::InPhase => "in_phase",
+        DKGStatus::CommitSuccess => "commit_success",
+        DKGStatus::WaitForPostProcess => "wait_for_post_process",
+    }
+}
+
+pub fn task_type_label(task_type: &TaskType) -> &'static str {
+    match task_type {
+        TaskType::Randomness => "randomness",
+        TaskType::GroupRelay => "group_relay",
+        TaskType::GroupRelayConfirmation => "group_relay_confirmation",
+    }
+}
+
+pub fn observe_dkg_status(dkg_status: DKGStatus) {
+    DKG_STATUS.set(dkg_status.to_usize() as i64);
+
+    DKG_STATUS_TRANSITIONS
+        .with_label_values(&[dkg_status_label(dkg_status)])
+        .inc();
+}
+
+pub fn inc_rpc_retries(chain_id: usize, operation: &str) {
+    RPC_RETRIES
+        .with_label_values(&[&chain_id.to_string(), operation])
+        .inc();
+}
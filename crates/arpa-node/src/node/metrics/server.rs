@@ -0,0 +1,48 @@
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use log::error;
+use prometheus::{Encoder, TextEncoder};
+use std::convert::Infallible;
+
+pub const METRICS_PATH: &str = "/metrics";
+
+async fn serve_metrics(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET || req.uri().path() != METRICS_PATH {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .unwrap());
+    }
+
+    let encoder = TextEncoder::new();
+
+    let mut buffer = vec![];
+
+    if let Err(e) = encoder.encode(&prometheus::gather(), &mut buffer) {
+        error!("{:?}", e);
+
+        return Ok(Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::empty())
+            .unwrap());
+    }
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, encoder.format_type())
+        .body(Body::from(buffer))
+        .unwrap())
+}
+
+pub async fn start_metrics_server(endpoint: String) -> Result<(), Box<dyn std::error::Error>> {
+    let addr = endpoint.parse()?;
+
+    let make_service =
+        make_service_fn(|_conn| async { Ok::<_, Infallible>(service_fn(serve_metrics)) });
+
+    Server::bind(&addr).serve(make_service).await?;
+    Ok(())
+}
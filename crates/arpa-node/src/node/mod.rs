@@ -15,3 +15,7 @@ pub mod scheduler;
 pub mod algorithm;
 
 pub mod committer;
+
+pub mod management;
+
+pub mod metrics;
@@ -1,43 +1,138 @@
 use super::{EventPublisher, EventSubscriber};
 use crate::node::{
-    event::{types::Topic, Event},
+    event::{short_type_name, Event},
+    metrics::{EVENTS_LAGGED, EVENTS_PENDING, EVENTS_PUBLISHED, EVENT_HANDLING_SECONDS},
     subscriber::Subscriber,
 };
 use async_trait::async_trait;
-use log::error;
-use std::collections::HashMap;
+use log::{debug, error, warn};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+use tokio::sync::broadcast::{self, error::RecvError, Sender};
+use tokio_util::sync::CancellationToken;
+
+pub const DEFAULT_EVENT_CHANNEL_CAPACITY: usize = 1000;
 
-#[derive(Default)]
 pub struct EventQueue {
-    subscribers: HashMap<Topic, Vec<Box<dyn Subscriber + Send + Sync>>>,
+    // one broadcast channel per event type and chain, the value is always a Sender of the keyed event type
+    channels: HashMap<(TypeId, usize), Box<dyn Any + Send + Sync>>,
+    capacity: usize,
+    cancellation_token: CancellationToken,
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventQueue {
     pub fn new() -> Self {
+        EventQueue::with_capacity(DEFAULT_EVENT_CHANNEL_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
         EventQueue {
-            subscribers: HashMap::new(),
+            channels: HashMap::new(),
+            capacity,
+            cancellation_token: CancellationToken::new(),
         }
     }
+
+    /// Stops all subscribers after they finish the event in hand.
+    pub fn shutdown(&self) {
+        self.cancellation_token.cancel();
+    }
+
+    fn get_sender<E: Event>(&self, chain_id: usize) -> Option<&Sender<E>> {
+        self.channels
+            .get(&(TypeId::of::<E>(), chain_id))
+            .and_then(|sender| sender.downcast_ref::<Sender<E>>())
+    }
 }
 
 impl EventSubscriber for EventQueue {
-    fn subscribe(&mut self, topic: Topic, subscriber: Box<dyn Subscriber + Send + Sync>) {
-        self.subscribers.entry(topic).or_insert_with(Vec::new);
+    fn subscribe<S>(&mut self, chain_id: usize, subscriber: S)
+    where
+        S: Subscriber + Send + Sync + 'static,
+    {
+        let capacity = self.capacity;
+
+        let mut receiver = self
+            .channels
+            .entry((TypeId::of::<S::Event>(), chain_id))
+            .or_insert_with(|| Box::new(broadcast::channel::<S::Event>(capacity).0))
+            .downcast_ref::<Sender<S::Event>>()
+            .unwrap()
+            .subscribe();
+
+        let cancellation_token = self.cancellation_token.clone();
+
+        tokio::spawn(async move {
+            let event_name = short_type_name::<S::Event>();
+
+            let subscriber_name = short_type_name::<S>();
 
-        self.subscribers.get_mut(&topic).unwrap().push(subscriber);
+            loop {
+                let event = tokio::select! {
+                    _ = cancellation_token.cancelled() => break,
+                    event = receiver.recv() => event,
+                };
+
+                match event {
+                    Ok(event) => {
+                        EVENTS_PENDING.with_label_values(&[event_name]).dec();
+
+                        debug!("{} received by {}", event_name, subscriber_name);
+
+                        let timer = EVENT_HANDLING_SECONDS
+                            .with_label_values(&[event_name, subscriber_name])
+                            .start_timer();
+
+                        if let Err(e) = subscriber.notify(event).await {
+                            error!("{:?}", e);
+                        }
+
+                        timer.observe_duration();
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(
+                            "{} fell behind and skipped {} {} events",
+                            subscriber_name, skipped, event_name
+                        );
+
+                        EVENTS_PENDING
+                            .with_label_values(&[event_name])
+                            .sub(skipped as i64);
+
+                        EVENTS_LAGGED
+                            .with_label_values(&[event_name, subscriber_name])
+                            .inc_by(skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
     }
 }
 
 #[async_trait]
-impl<E: Event + Clone + Send + Sync + 'static> EventPublisher<E> for EventQueue {
+impl<E: Event> EventPublisher<E> for EventQueue {
     async fn publish(&self, event: E) {
-        let topic = event.topic();
+        // the channel is created by the first subscriber, no one is interested in the event otherwise
+        if let Some(sender) = self.get_sender::<E>(event.chain_id()) {
+            let event_name = short_type_name::<E>();
 
-        if let Some(subscribers) = self.subscribers.get(&topic) {
-            for subscriber in subscribers {
-                if let Err(e) = subscriber.notify(topic, &event).await {
-                    error!("{:?}", e);
-                }
+            // sending never waits for subscribers, so a publisher holding the queue is never held
+            // back by a slow one, which skips the oldest events instead
+            if let Ok(receivers) = sender.send(event) {
+                EVENTS_PUBLISHED.with_label_values(&[event_name]).inc();
+
+                EVENTS_PENDING
+                    .with_label_values(&[event_name])
+                    .add(receivers as i64);
             }
         }
     }
@@ -47,25 +142,59 @@ impl<E: Event + Clone + Send + Sync + 'static> EventPublisher<E> for EventQueue
 pub mod tests {
     use super::EventPublisher;
     use crate::node::{
+        context::types::DEFAULT_POLLING_INTERVAL_MILLIS,
+        error::NodeResult,
         event::new_block::NewBlock,
         listener::block::BlockListener,
-        queue::event_queue::EventQueue,
+        metrics::EVENTS_LAGGED,
+        queue::{event_queue::EventQueue, EventSubscriber},
         subscriber::{block::BlockSubscriber, Subscriber},
     };
-    use arpa_node_core::GeneralChainIdentity;
+    use arpa_node_core::{
+        FailoverConfig, GeneralChainIdentity, ProviderTransport, TransactionConfig,
+    };
     use arpa_node_dal::cache::InMemoryBlockInfoCache;
     use arpa_node_dal::BlockInfoFetcher;
-    use ethers::types::Address;
-    use std::sync::Arc;
+    use async_trait::async_trait;
+    use ethers::{
+        signers::LocalWallet,
+        types::{Address, H256},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
     use tokio::sync::RwLock;
 
+    struct CountingSubscriber {
+        handling_millis: u64,
+        received: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Subscriber for CountingSubscriber {
+        type Event = NewBlock;
+
+        async fn notify(&self, _: NewBlock) -> NodeResult<()> {
+            if self.handling_millis > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(self.handling_millis)).await;
+            }
+
+            self.received.fetch_add(1, Ordering::SeqCst);
+
+            Ok(())
+        }
+
+        async fn subscribe(self) {}
+    }
+
     #[tokio::test]
     async fn test() {
         let eq = Arc::new(RwLock::new(EventQueue::new()));
 
         let chain_id = 1;
 
-        let block_cache = Arc::new(RwLock::new(InMemoryBlockInfoCache::new()));
+        let block_cache = Arc::new(RwLock::new(InMemoryBlockInfoCache::new(0)));
 
         assert_eq!(0, block_cache.clone().read().await.get_block_height());
 
@@ -73,39 +202,112 @@ pub mod tests {
 
         s.subscribe().await;
 
-        let fake_wallet = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
-            .parse()
-            .unwrap();
+        let fake_wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
 
-        let chain_identity =
-            GeneralChainIdentity::new(0, 0, fake_wallet, "".to_string(), Address::random());
+        let chain_identity = GeneralChainIdentity::new(
+            0,
+            0,
+            fake_wallet.into(),
+            "".to_string(),
+            ProviderTransport::Http,
+            DEFAULT_POLLING_INTERVAL_MILLIS,
+            FailoverConfig::default(),
+            TransactionConfig::default(),
+            Address::random(),
+        );
 
         let chain_identity = Arc::new(RwLock::new(chain_identity));
 
-        let p = BlockListener::new(chain_id, chain_identity, eq);
+        let p = BlockListener::new(chain_id, chain_identity, block_cache.clone(), eq);
 
         p.publish(NewBlock {
             chain_id,
             block_height: 1,
+            block_hash: H256::from_low_u64_be(1),
+            fork_block_height: None,
         })
         .await;
 
+        // subscribers receive events in their own tasks
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
         assert_eq!(1, block_cache.clone().read().await.get_block_height());
 
         p.publish(NewBlock {
             chain_id,
             block_height: 10,
+            block_hash: H256::from_low_u64_be(10),
+            fork_block_height: None,
         })
         .await;
 
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
         assert_eq!(10, block_cache.clone().read().await.get_block_height());
 
         p.publish(NewBlock {
             chain_id: 999,
             block_height: 10,
+            block_hash: H256::from_low_u64_be(10),
+            fork_block_height: None,
         })
         .await;
 
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
         assert_eq!(10, block_cache.clone().read().await.get_block_height());
     }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_does_not_block_others() {
+        let mut eq = EventQueue::with_capacity(2);
+
+        let fast_received = Arc::new(AtomicUsize::new(0));
+
+        let slow_received = Arc::new(AtomicUsize::new(0));
+
+        eq.subscribe(
+            1,
+            CountingSubscriber {
+                handling_millis: 0,
+                received: fast_received.clone(),
+            },
+        );
+
+        eq.subscribe(
+            1,
+            CountingSubscriber {
+                handling_millis: 200,
+                received: slow_received.clone(),
+            },
+        );
+
+        for block_height in 0..10 {
+            eq.publish(NewBlock::new(1, block_height, H256::zero(), None))
+                .await;
+
+            tokio::task::yield_now().await;
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+
+        // the fast subscriber keeps up while the slow one skips the events exceeding the buffer
+        assert_eq!(10, fast_received.load(Ordering::SeqCst));
+
+        let slow_received = slow_received.load(Ordering::SeqCst);
+
+        assert!(slow_received < 10);
+
+        // every skipped event is reported
+        let lagged = EVENTS_LAGGED
+            .with_label_values(&["NewBlock", "CountingSubscriber"])
+            .get();
+
+        assert_eq!(10, slow_received as u64 + lagged);
+
+        eq.shutdown();
+    }
 }
@@ -1,17 +1,17 @@
 use async_trait::async_trait;
 
-use super::{
-    event::{types::Topic, Event},
-    subscriber::Subscriber,
-};
+use super::{event::Event, subscriber::Subscriber};
 
 pub mod event_queue;
 
 pub trait EventSubscriber {
-    fn subscribe(&mut self, topic: Topic, subscriber: Box<dyn Subscriber + Send + Sync>);
+    /// Runs the subscriber in its own task, receiving the events of its type on the given chain.
+    fn subscribe<S>(&mut self, chain_id: usize, subscriber: S)
+    where
+        S: Subscriber + Send + Sync + 'static;
 }
 
 #[async_trait]
-pub trait EventPublisher<E: Event + Clone + Send + Sync + 'static> {
+pub trait EventPublisher<E: Event> {
     async fn publish(&self, event: E);
 }
@@ -1,8 +1,9 @@
 use futures::Future;
 use tokio::{
-    sync::{oneshot::channel, oneshot::Receiver},
+    sync::oneshot::{channel, error::TryRecvError, Receiver},
     task::JoinHandle,
 };
+use tokio_util::sync::CancellationToken;
 
 use super::{DynamicTaskScheduler, TaskScheduler};
 
@@ -10,12 +11,14 @@ use super::{DynamicTaskScheduler, TaskScheduler};
 pub struct SimpleDynamicTaskScheduler {
     // TODO access control
     pub dynamic_tasks: Vec<(Receiver<()>, Option<JoinHandle<()>>)>,
+    cancellation_token: CancellationToken,
 }
 
 impl SimpleDynamicTaskScheduler {
     pub fn new() -> Self {
         SimpleDynamicTaskScheduler {
             dynamic_tasks: vec![],
+            cancellation_token: CancellationToken::new(),
         }
     }
 }
@@ -28,13 +31,22 @@ impl TaskScheduler for SimpleDynamicTaskScheduler {
     {
         let (send, recv) = channel::<()>();
 
+        let cancellation_token = self.cancellation_token.clone();
+
         tokio::spawn(async move {
-            future.await;
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = future => {}
+            }
             drop(send);
         });
 
         self.dynamic_tasks.push((recv, None));
     }
+
+    fn get_cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
 }
 
 impl DynamicTaskScheduler for SimpleDynamicTaskScheduler {
@@ -51,6 +63,8 @@ impl DynamicTaskScheduler for SimpleDynamicTaskScheduler {
     {
         let (send, recv) = channel::<()>();
 
+        let cancellation_token = self.cancellation_token.clone();
+
         let task = tokio::spawn(async move {
             future.await;
             drop(send);
@@ -58,18 +72,37 @@ impl DynamicTaskScheduler for SimpleDynamicTaskScheduler {
 
         let task_monitor = tokio::spawn(async move {
             loop {
-                if shutdown_predicate().await {
+                if cancellation_token.is_cancelled() || shutdown_predicate().await {
                     task.abort();
                     return;
                 }
 
-                tokio::time::sleep(std::time::Duration::from_millis(shutdown_check_frequency))
-                    .await;
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => {}
+                    _ = tokio::time::sleep(std::time::Duration::from_millis(shutdown_check_frequency)) => {}
+                }
             }
         });
 
         self.dynamic_tasks.push((recv, Some(task_monitor)));
     }
+
+    fn reap_finished_tasks(&mut self) {
+        self.dynamic_tasks
+            .retain_mut(|(task_recv, task_monitor)| match task_recv.try_recv() {
+                Err(TryRecvError::Empty) => true,
+                _ => {
+                    if let Some(monitor) = task_monitor {
+                        monitor.abort();
+                    }
+                    false
+                }
+            });
+    }
+
+    fn is_idle(&self) -> bool {
+        self.dynamic_tasks.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -1,37 +1,174 @@
+use crate::node::error::NodeResult;
 use async_trait::async_trait;
 use futures::Future;
+use log::{error, info, warn};
+use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
+use tokio_retry::strategy::ExponentialBackoff;
+use tokio_util::sync::CancellationToken;
 
 use super::{FixedTaskScheduler, TaskScheduler};
 
+pub const DEFAULT_RESTART_BACKOFF_BASE_MILLIS: u64 = 1000;
+
+pub const DEFAULT_RESTART_BACKOFF_MAX_MILLIS: u64 = 60000;
+
 #[derive(Default)]
 pub struct SimpleFixedTaskScheduler {
     fixed_tasks: Vec<JoinHandle<()>>,
+    cancellation_token: CancellationToken,
 }
 
 impl SimpleFixedTaskScheduler {
     pub fn new() -> Self {
         SimpleFixedTaskScheduler {
             fixed_tasks: vec![],
+            cancellation_token: CancellationToken::new(),
         }
     }
 }
 
+fn restart_backoff() -> impl Iterator<Item = Duration> {
+    // 2^n * 500ms: 1s, 2s, 4s...
+    ExponentialBackoff::from_millis(2)
+        .factor(DEFAULT_RESTART_BACKOFF_BASE_MILLIS / 2)
+        .max_delay(Duration::from_millis(DEFAULT_RESTART_BACKOFF_MAX_MILLIS))
+}
+
 impl TaskScheduler for SimpleFixedTaskScheduler {
     fn add_task<T>(&mut self, future: T)
     where
         T: Future<Output = ()> + Send + 'static,
         T::Output: Send + 'static,
     {
-        tokio::spawn(future);
+        let cancellation_token = self.cancellation_token.clone();
+
+        let task = tokio::spawn(async move {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = future => {}
+            }
+        });
+
+        self.fixed_tasks.push(task);
+    }
+
+    fn get_cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
     }
 }
 
 #[async_trait]
 impl FixedTaskScheduler for SimpleFixedTaskScheduler {
-    async fn join(mut self) {
-        for fixed_task in self.fixed_tasks.iter_mut() {
+    fn add_supervised_task<F, T>(&mut self, task_name: String, task_factory: F)
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+        T: Future<Output = NodeResult<()>> + Send + 'static,
+    {
+        let cancellation_token = self.cancellation_token.clone();
+
+        let supervisor = tokio::spawn(async move {
+            let mut backoff = restart_backoff();
+
+            loop {
+                let started_at = Instant::now();
+
+                // run the task in its own tokio task so that a panic can be caught here
+                let mut task = tokio::spawn(task_factory());
+
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => {
+                        task.abort();
+                        return;
+                    }
+                    res = &mut task => {
+                        match res {
+                            Ok(Ok(())) => warn!("task {} exited unexpectedly", task_name),
+                            Ok(Err(e)) => error!("task {} failed: {:?}", task_name, e),
+                            Err(e) => error!("task {} panicked: {:?}", task_name, e),
+                        }
+                    }
+                }
+
+                // the task has been running healthily for a while, so start over with a short delay
+                if started_at.elapsed() > Duration::from_millis(DEFAULT_RESTART_BACKOFF_MAX_MILLIS)
+                {
+                    backoff = restart_backoff();
+                }
+
+                let delay = backoff.next().unwrap();
+
+                info!("restarting task {} in {:?}", task_name, delay);
+
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => return,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+            }
+        });
+
+        self.fixed_tasks.push(supervisor);
+    }
+
+    async fn join(&mut self) {
+        for fixed_task in self.fixed_tasks.drain(..) {
             let _ = fixed_task.await;
         }
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::SimpleFixedTaskScheduler;
+    use crate::node::{
+        error::{NodeError, NodeResult},
+        scheduler::{FixedTaskScheduler, TaskScheduler},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[tokio::test]
+    async fn test_restart_failed_task_until_shutdown() {
+        let mut f_ts = SimpleFixedTaskScheduler::new();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let runs_in_task = runs.clone();
+
+        f_ts.add_supervised_task("failing task".to_string(), move || {
+            let runs = runs_in_task.clone();
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                NodeResult::Err(NodeError::GroupNotReady)
+            }
+        });
+
+        // the first restart happens after 1s
+        tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        f_ts.shutdown();
+
+        tokio::time::timeout(std::time::Duration::from_millis(500), f_ts.join())
+            .await
+            .expect("supervised task should stop on shutdown");
+
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_fixed_task_on_shutdown() {
+        let mut f_ts = SimpleFixedTaskScheduler::new();
+
+        f_ts.add_task(futures::future::pending::<()>());
+
+        f_ts.shutdown();
+
+        tokio::time::timeout(std::time::Duration::from_millis(500), f_ts.join())
+            .await
+            .expect("fixed task should stop on shutdown");
+    }
+}
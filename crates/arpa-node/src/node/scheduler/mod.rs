@@ -2,19 +2,34 @@ pub mod dynamic;
 
 pub mod fixed;
 
+use crate::node::error::NodeResult;
 use async_trait::async_trait;
 use futures::Future;
+use tokio_util::sync::CancellationToken;
 
 pub trait TaskScheduler {
     fn add_task<T>(&mut self, future: T)
     where
         T: Future<Output = ()> + Send + 'static,
         T::Output: Send + 'static;
+
+    fn get_cancellation_token(&self) -> CancellationToken;
+
+    fn shutdown(&self) {
+        self.get_cancellation_token().cancel();
+    }
 }
 
 #[async_trait]
 pub trait FixedTaskScheduler: TaskScheduler {
-    async fn join(mut self);
+    /// Keeps the task produced by `task_factory` running until shutdown,
+    /// restarting it with exponential backoff whenever it exits, fails or panics.
+    fn add_supervised_task<F, T>(&mut self, task_name: String, task_factory: F)
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+        T: Future<Output = NodeResult<()>> + Send + 'static;
+
+    async fn join(&mut self);
 }
 
 pub trait DynamicTaskScheduler: TaskScheduler {
@@ -28,4 +43,8 @@ pub trait DynamicTaskScheduler: TaskScheduler {
         T::Output: Send + 'static,
         P: Fn() -> F + Sync + Send + 'static,
         F: Future<Output = bool> + Send + 'static;
+
+    fn reap_finished_tasks(&mut self);
+
+    fn is_idle(&self) -> bool;
 }
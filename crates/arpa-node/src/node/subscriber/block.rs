@@ -1,12 +1,11 @@
 use super::Subscriber;
 use crate::node::{
     error::NodeResult,
-    event::{new_block::NewBlock, types::Topic, Event},
+    event::new_block::NewBlock,
     queue::{event_queue::EventQueue, EventSubscriber},
 };
 use arpa_node_dal::BlockInfoUpdater;
 use async_trait::async_trait;
-use log::debug;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -28,15 +27,23 @@ impl<B: BlockInfoUpdater> BlockSubscriber<B> {
 
 #[async_trait]
 impl<B: BlockInfoUpdater + Sync + Send + 'static> Subscriber for BlockSubscriber<B> {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = NewBlock;
 
-        let &NewBlock { block_height, .. } = payload.as_any().downcast_ref::<NewBlock>().unwrap();
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let NewBlock {
+            block_height,
+            block_hash,
+            fork_block_height,
+            ..
+        } = event;
 
-        self.block_cache
-            .write()
-            .await
-            .set_block_height(block_height);
+        let mut block_cache = self.block_cache.write().await;
+
+        if let Some(fork_block_height) = fork_block_height {
+            block_cache.rollback(fork_block_height);
+        }
+
+        block_cache.add_block(block_height, block_hash);
 
         Ok(())
     }
@@ -46,10 +53,6 @@ impl<B: BlockInfoUpdater + Sync + Send + 'static> Subscriber for BlockSubscriber
 
         let chain_id = self.chain_id;
 
-        let subscriber = Box::new(self);
-
-        eq.write()
-            .await
-            .subscribe(Topic::NewBlock(chain_id), subscriber);
+        eq.write().await.subscribe(chain_id, self);
     }
 }
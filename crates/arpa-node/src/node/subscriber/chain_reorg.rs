@@ -0,0 +1,83 @@
+use super::Subscriber;
+use crate::node::{
+    error::NodeResult,
+    event::chain_reorg::ChainReorg,
+    metrics::{task_type_label, ORPHANED_TASKS},
+    queue::{event_queue::EventQueue, EventSubscriber},
+};
+use arpa_node_core::{Task, TaskType};
+use arpa_node_dal::BLSTasksUpdater;
+use async_trait::async_trait;
+use log::warn;
+use std::{marker::PhantomData, sync::Arc};
+use tokio::sync::RwLock;
+
+pub struct ChainReorgSubscriber<K: Task, Q: BLSTasksUpdater<K>> {
+    pub chain_id: usize,
+    task_type: TaskType,
+    tasks_cache: Arc<RwLock<Q>>,
+    eq: Arc<RwLock<EventQueue>>,
+    k: PhantomData<K>,
+}
+
+impl<K: Task, Q: BLSTasksUpdater<K>> ChainReorgSubscriber<K, Q> {
+    pub fn new(
+        chain_id: usize,
+        task_type: TaskType,
+        tasks_cache: Arc<RwLock<Q>>,
+        eq: Arc<RwLock<EventQueue>>,
+    ) -> Self {
+        ChainReorgSubscriber {
+            chain_id,
+            task_type,
+            tasks_cache,
+            eq,
+            k: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<K: Task + Sync + Send + 'static, Q: BLSTasksUpdater<K> + Sync + Send + 'static> Subscriber
+    for ChainReorgSubscriber<K, Q>
+{
+    type Event = ChainReorg;
+
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let ChainReorg {
+            fork_block_height, ..
+        } = event;
+
+        let orphaned_tasks = self
+            .tasks_cache
+            .write()
+            .await
+            .rollback(fork_block_height)
+            .await?;
+
+        if !orphaned_tasks.is_empty() {
+            // tasks re-emitted on the canonical chain will be received again by the listeners
+            warn!(
+                "rolled back {} unhandled {} tasks of chain {} assigned since orphaned block {}",
+                orphaned_tasks.len(),
+                task_type_label(&self.task_type),
+                self.chain_id,
+                fork_block_height
+            );
+
+            ORPHANED_TASKS
+                .with_label_values(&[&self.chain_id.to_string(), task_type_label(&self.task_type)])
+                .inc_by(orphaned_tasks.len() as u64);
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe(self) {
+        let eq = self.eq.clone();
+
+        let chain_id = self.chain_id;
+
+        eq.write().await.subscribe(chain_id, self);
+    }
+}
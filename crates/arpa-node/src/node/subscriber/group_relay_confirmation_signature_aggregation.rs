@@ -2,19 +2,17 @@ use super::Subscriber;
 use crate::node::{
     algorithm::bls::{BLSCore, SimpleBLSCore},
     error::NodeResult,
-    event::{
-        ready_to_fulfill_group_relay_confirmation_task::ReadyToFulfillGroupRelayConfirmationTask,
-        types::Topic, Event,
-    },
+    event::ready_to_fulfill_group_relay_confirmation_task::ReadyToFulfillGroupRelayConfirmationTask,
+    metrics::{task_type_label, FULFILLMENTS, SIGNATURE_AGGREGATION_SECONDS},
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
 };
 use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterTransactions};
-use arpa_node_core::ChainIdentity;
+use arpa_node_core::{ChainIdentity, TaskType};
 use arpa_node_dal::cache::GroupRelayConfirmationResultCache;
 use async_trait::async_trait;
 use ethers::types::Address;
-use log::{debug, error, info};
+use log::{error, info};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -60,6 +58,7 @@ pub trait FulfillGroupRelayConfirmationHandler {
 }
 
 pub struct GeneralFulfillGroupRelayConfirmationHandler<I: ChainIdentity + AdapterClientBuilder> {
+    chain_id: usize,
     id_address: Address,
     chain_identity: Arc<RwLock<I>>,
 }
@@ -92,9 +91,25 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send> FulfillGroupRelayCon
             Ok(()) => {
                 info!("fulfill group_relay_confirmation successfully! task index: {}, group_index: {}",
                         group_relay_confirmation_task_index, group_index);
+
+                FULFILLMENTS
+                    .with_label_values(&[
+                        &self.chain_id.to_string(),
+                        task_type_label(&TaskType::GroupRelayConfirmation),
+                        "success",
+                    ])
+                    .inc();
             }
             Err(e) => {
                 error!("{:?}", e);
+
+                FULFILLMENTS
+                    .with_label_values(&[
+                        &self.chain_id.to_string(),
+                        task_type_label(&TaskType::GroupRelayConfirmation),
+                        "revert",
+                    ])
+                    .inc();
             }
         }
 
@@ -106,16 +121,13 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send> FulfillGroupRelayCon
 impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
     for GroupRelayConfirmationSignatureAggregationSubscriber<I>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = ReadyToFulfillGroupRelayConfirmationTask;
 
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
         let ReadyToFulfillGroupRelayConfirmationTask {
             tasks: ready_signatures,
             ..
-        } = payload
-            .as_any()
-            .downcast_ref::<ReadyToFulfillGroupRelayConfirmationTask>()
-            .unwrap();
+        } = event;
 
         for signature in ready_signatures {
             let GroupRelayConfirmationResultCache {
@@ -124,15 +136,26 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
                 group_relay_confirmation,
                 threshold,
                 partial_signatures,
-            } = signature.clone();
+            } = signature;
 
             let bls_core = SimpleBLSCore {};
 
+            let aggregation_timer = SIGNATURE_AGGREGATION_SECONDS
+                .with_label_values(&[
+                    &self.chain_id.to_string(),
+                    task_type_label(&TaskType::GroupRelayConfirmation),
+                ])
+                .start_timer();
+
             let signature = bls_core.aggregate(
                 threshold,
                 &partial_signatures.values().cloned().collect::<Vec<_>>(),
             )?;
 
+            aggregation_timer.observe_duration();
+
+            let chain_id = self.chain_id;
+
             let id_address = self.id_address;
 
             let chain_identity = self.chain_identity.clone();
@@ -141,6 +164,7 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
 
             self.ts.write().await.add_task(async move {
                 let handler = GeneralFulfillGroupRelayConfirmationHandler {
+                    chain_id,
                     id_address,
                     chain_identity,
                 };
@@ -167,11 +191,6 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
 
         let chain_id = self.chain_id;
 
-        let subscriber = Box::new(self);
-
-        eq.write().await.subscribe(
-            Topic::ReadyToFulfillGroupRelayConfirmationTask(chain_id),
-            subscriber,
-        );
+        eq.write().await.subscribe(chain_id, self);
     }
 }
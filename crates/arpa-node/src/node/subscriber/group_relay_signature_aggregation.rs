@@ -2,16 +2,17 @@ use super::Subscriber;
 use crate::node::{
     algorithm::bls::{BLSCore, SimpleBLSCore},
     error::NodeResult,
-    event::{ready_to_fulfill_group_relay_task::ReadyToFulfillGroupRelayTask, types::Topic, Event},
+    event::ready_to_fulfill_group_relay_task::ReadyToFulfillGroupRelayTask,
+    metrics::{task_type_label, FULFILLMENTS, SIGNATURE_AGGREGATION_SECONDS},
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
 };
 use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterTransactions};
-use arpa_node_core::ChainIdentity;
+use arpa_node_core::{ChainIdentity, TaskType};
 use arpa_node_dal::cache::GroupRelayResultCache;
 use async_trait::async_trait;
 use ethers::types::Address;
-use log::{debug, error, info};
+use log::{error, info};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -53,6 +54,7 @@ pub trait FulfillGroupRelayHandler {
 }
 
 pub struct GeneralFulfillGroupRelayHandler<I: ChainIdentity + AdapterClientBuilder> {
+    chain_id: usize,
     id_address: Address,
     chain_identity: Arc<RwLock<I>>,
 }
@@ -88,9 +90,25 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send> FulfillGroupRelayHan
                     "fulfill group_relay successfully! task index: {}, group_index: {}",
                     group_relay_task_index, group_index
                 );
+
+                FULFILLMENTS
+                    .with_label_values(&[
+                        &self.chain_id.to_string(),
+                        task_type_label(&TaskType::GroupRelay),
+                        "success",
+                    ])
+                    .inc();
             }
             Err(e) => {
                 error!("{:?}", e);
+
+                FULFILLMENTS
+                    .with_label_values(&[
+                        &self.chain_id.to_string(),
+                        task_type_label(&TaskType::GroupRelay),
+                        "revert",
+                    ])
+                    .inc();
             }
         }
 
@@ -102,16 +120,13 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send> FulfillGroupRelayHan
 impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
     for GroupRelaySignatureAggregationSubscriber<I>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = ReadyToFulfillGroupRelayTask;
 
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
         let ReadyToFulfillGroupRelayTask {
             tasks: ready_signatures,
             ..
-        } = payload
-            .as_any()
-            .downcast_ref::<ReadyToFulfillGroupRelayTask>()
-            .unwrap();
+        } = event;
 
         for signature in ready_signatures {
             let GroupRelayResultCache {
@@ -120,15 +135,26 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
                 relayed_group,
                 threshold,
                 partial_signatures,
-            } = signature.clone();
+            } = signature;
 
             let bls_core = SimpleBLSCore {};
 
+            let aggregation_timer = SIGNATURE_AGGREGATION_SECONDS
+                .with_label_values(&[
+                    &self.chain_id.to_string(),
+                    task_type_label(&TaskType::GroupRelay),
+                ])
+                .start_timer();
+
             let signature = bls_core.aggregate(
                 threshold,
                 &partial_signatures.values().cloned().collect::<Vec<_>>(),
             )?;
 
+            aggregation_timer.observe_duration();
+
+            let chain_id = self.chain_id;
+
             let id_address = self.id_address;
 
             let chain_identity = self.chain_identity.clone();
@@ -137,6 +163,7 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
 
             self.ts.write().await.add_task(async move {
                 let handler = GeneralFulfillGroupRelayHandler {
+                    chain_id,
                     id_address,
                     chain_identity,
                 };
@@ -161,10 +188,6 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
     async fn subscribe(self) {
         let eq = self.eq.clone();
 
-        let subscriber = Box::new(self);
-
-        eq.write()
-            .await
-            .subscribe(Topic::ReadyToFulfillGroupRelayTask, subscriber);
+        eq.write().await.subscribe(0, self);
     }
 }
@@ -1,8 +1,10 @@
 use super::Subscriber;
 use crate::node::{
     algorithm::dkg::{AllPhasesDKGCore, DKGCore},
+    committer::sign_peer_record,
     error::NodeResult,
-    event::{run_dkg::RunDKG, types::Topic, Event},
+    event::run_dkg::RunDKG,
+    metrics::{observe_dkg_status, DKG_OUTCOMES},
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, DynamicTaskScheduler},
 };
@@ -10,10 +12,10 @@ use arpa_node_contract_client::{
     controller::{ControllerClientBuilder, ControllerTransactions},
     coordinator::CoordinatorClientBuilder,
 };
-use arpa_node_core::{ChainIdentity, DKGStatus, DKGTask};
+use arpa_node_core::{now_timestamp, ChainIdentity, DKGStatus, DKGTask};
 use arpa_node_dal::{GroupInfoFetcher, GroupInfoUpdater, NodeInfoFetcher};
 use async_trait::async_trait;
-use log::{debug, error};
+use log::{error, warn};
 use rand::{prelude::ThreadRng, RngCore};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -118,6 +120,9 @@ impl<
             .get_node_rpc_endpoint()?
             .to_string();
 
+        let peer_record =
+            sign_peer_record(&*self.main_chain_identity.read().await, node_rpc_endpoint).await?;
+
         let controller_client = self
             .main_chain_identity
             .read()
@@ -138,10 +143,33 @@ impl<
 
         let mut dkg_core = AllPhasesDKGCore::new(coordinator_client);
 
-        let output = dkg_core
-            .run_dkg(dkg_private_key, node_rpc_endpoint, self.rng)
+        let (output, peer_records) = dkg_core
+            .run_dkg(dkg_private_key, peer_record, task.dkg_protocol, self.rng)
             .await?;
 
+        let now = now_timestamp();
+
+        for peer_record in peer_records {
+            if !peer_record.verify()
+                || peer_record.is_expired(now)
+                || !task.members.contains_key(&peer_record.id_address)
+            {
+                warn!(
+                    "invalid peer record published by {:?}, ignored",
+                    peer_record.id_address
+                );
+                continue;
+            }
+
+            self.group_cache
+                .write()
+                .await
+                .save_peer_record(peer_record)
+                .await?;
+        }
+
+        let dkg_transcript = bincode::serialize(&output.transcript).unwrap();
+
         let (public_key, partial_public_key, disqualified_nodes) = self
             .group_cache
             .write()
@@ -156,6 +184,7 @@ impl<
                 bincode::serialize(&public_key).unwrap(),
                 bincode::serialize(&partial_public_key).unwrap(),
                 disqualified_nodes,
+                dkg_transcript,
             )
             .await?;
 
@@ -170,11 +199,10 @@ impl<
         I: ChainIdentity + ControllerClientBuilder + CoordinatorClientBuilder + Sync + Send + 'static,
     > Subscriber for InGroupingSubscriber<N, G, I>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = RunDKG;
 
-        let RunDKG { dkg_task: task, .. } =
-            payload.as_any().downcast_ref::<RunDKG>().unwrap().clone();
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let RunDKG { dkg_task: task, .. } = event;
 
         static RNG_FN: fn() -> ThreadRng = rand::thread_rng;
 
@@ -199,24 +227,42 @@ impl<
             async move {
                 if let Err(e) = handler.handle(task).await {
                     error!("{:?}", e);
-                } else if let Err(e) = group_cache_for_handler
-                    .write()
-                    .await
-                    .update_dkg_status(task_group_index, task_epoch, DKGStatus::CommitSuccess)
-                    .await
-                {
-                    error!("{:?}", e);
+
+                    DKG_OUTCOMES.with_label_values(&["failure"]).inc();
+                } else {
+                    DKG_OUTCOMES.with_label_values(&["success"]).inc();
+
+                    match group_cache_for_handler
+                        .write()
+                        .await
+                        .update_dkg_status(task_group_index, task_epoch, DKGStatus::CommitSuccess)
+                        .await
+                    {
+                        Ok(true) => observe_dkg_status(DKGStatus::CommitSuccess),
+                        Ok(false) => {}
+                        Err(e) => error!("{:?}", e),
+                    }
                 }
             },
             move || {
                 let group_cache = group_cache_for_handler_shutdown_signal.clone();
                 async move {
-                    let cache_index = group_cache.clone().read().await.get_index().unwrap_or(0);
-
-                    let cache_epoch = group_cache.clone().read().await.get_epoch().unwrap_or(0);
-
-                    cache_index != task_group_index || cache_epoch != task_epoch
-                    //NodeError::GroupIndexObsolete(cache_index)
+                    let cache_epoch = group_cache
+                        .clone()
+                        .read()
+                        .await
+                        .get_epoch(task_group_index)
+                        .ok();
+
+                    // DKG status is reset when it times out or gets exited manually
+                    let dkg_status = group_cache
+                        .clone()
+                        .read()
+                        .await
+                        .get_dkg_status(task_group_index, task_epoch)
+                        .unwrap_or(DKGStatus::None);
+
+                    cache_epoch != Some(task_epoch) || dkg_status == DKGStatus::None
                     //NodeError::GroupEpochObsolete(cache_epoch)
                 }
             },
@@ -229,8 +275,6 @@ impl<
     async fn subscribe(self) {
         let eq = self.eq.clone();
 
-        let subscriber = Box::new(self);
-
-        eq.write().await.subscribe(Topic::RunDKG, subscriber);
+        eq.write().await.subscribe(0, self);
     }
 }
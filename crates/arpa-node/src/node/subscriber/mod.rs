@@ -1,4 +1,5 @@
 pub mod block;
+pub mod chain_reorg;
 pub mod group_relay_confirmation_signature_aggregation;
 pub mod group_relay_signature_aggregation;
 pub mod in_grouping;
@@ -12,14 +13,13 @@ pub mod ready_to_handle_randomness_task;
 
 use async_trait::async_trait;
 
-use crate::node::{
-    error::NodeResult,
-    event::{types::Topic, Event},
-};
+use crate::node::{error::NodeResult, event::Event};
 
 #[async_trait]
 pub trait Subscriber {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()>;
+    type Event: Event;
+
+    async fn notify(&self, event: Self::Event) -> NodeResult<()>;
 
     async fn subscribe(self);
 }
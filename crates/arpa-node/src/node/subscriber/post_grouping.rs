@@ -1,14 +1,14 @@
 use super::Subscriber;
 use crate::node::{
     error::NodeResult,
-    event::{dkg_post_process::DKGPostProcess, types::Topic, Event},
+    event::dkg_post_process::DKGPostProcess,
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
 };
 use arpa_node_contract_client::controller::{ControllerClientBuilder, ControllerTransactions};
 use arpa_node_core::ChainIdentity;
 use async_trait::async_trait;
-use log::{debug, error, info};
+use log::{error, info};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -62,13 +62,13 @@ impl<I: ChainIdentity + ControllerClientBuilder + Sync + Send> DKGPostProcessHan
 impl<I: ChainIdentity + ControllerClientBuilder + Sync + Send + 'static> Subscriber
     for PostGroupingSubscriber<I>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = DKGPostProcess;
 
-        let &DKGPostProcess {
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let DKGPostProcess {
             group_index,
             group_epoch,
-        } = payload.as_any().downcast_ref::<DKGPostProcess>().unwrap();
+        } = event;
 
         let main_chain_identity = self.main_chain_identity.clone();
 
@@ -90,10 +90,6 @@ impl<I: ChainIdentity + ControllerClientBuilder + Sync + Send + 'static> Subscri
     async fn subscribe(self) {
         let eq = self.eq.clone();
 
-        let subscriber = Box::new(self);
-
-        eq.write()
-            .await
-            .subscribe(Topic::DKGPostProcess, subscriber);
+        eq.write().await.subscribe(0, self);
     }
 }
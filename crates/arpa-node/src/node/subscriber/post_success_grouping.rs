@@ -1,12 +1,11 @@
 use super::Subscriber;
 use crate::node::{
     error::NodeResult,
-    event::{dkg_success::DKGSuccess, types::Topic, Event},
+    event::dkg_success::DKGSuccess,
     queue::{event_queue::EventQueue, EventSubscriber},
 };
 use arpa_node_dal::GroupInfoUpdater;
 use async_trait::async_trait;
-use log::debug;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -23,14 +22,10 @@ impl<G: GroupInfoUpdater + Sync + Send> PostSuccessGroupingSubscriber<G> {
 
 #[async_trait]
 impl<G: GroupInfoUpdater + Sync + Send + 'static> Subscriber for PostSuccessGroupingSubscriber<G> {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = DKGSuccess;
 
-        let DKGSuccess { group } = payload
-            .as_any()
-            .downcast_ref::<DKGSuccess>()
-            .unwrap()
-            .clone();
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let DKGSuccess { group } = event;
 
         self.group_cache
             .write()
@@ -44,8 +39,6 @@ impl<G: GroupInfoUpdater + Sync + Send + 'static> Subscriber for PostSuccessGrou
     async fn subscribe(self) {
         let eq = self.eq.clone();
 
-        let subscriber = Box::new(self);
-
-        eq.write().await.subscribe(Topic::DKGSuccess, subscriber);
+        eq.write().await.subscribe(0, self);
     }
 }
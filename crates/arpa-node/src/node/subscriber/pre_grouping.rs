@@ -1,13 +1,14 @@
 use super::Subscriber;
 use crate::node::{
     error::NodeResult,
-    event::{new_dkg_task::NewDKGTask, run_dkg::RunDKG, types::Topic, Event},
+    event::{new_dkg_task::NewDKGTask, run_dkg::RunDKG},
+    metrics::observe_dkg_status,
     queue::{event_queue::EventQueue, EventPublisher, EventSubscriber},
 };
 use arpa_node_core::DKGStatus;
 use arpa_node_dal::{GroupInfoFetcher, GroupInfoUpdater};
 use async_trait::async_trait;
-use log::{debug, info};
+use log::info;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -35,27 +36,26 @@ impl<G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send> EventPublisher<RunDKG
 impl<G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static> Subscriber
     for PreGroupingSubscriber<G>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = NewDKGTask;
 
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
         let NewDKGTask {
             dkg_task,
             self_index,
-        } = payload
-            .as_any()
-            .downcast_ref::<NewDKGTask>()
-            .unwrap()
-            .clone();
-
-        let cache_index = self.group_cache.read().await.get_index().unwrap_or(0);
-
-        let cache_epoch = self.group_cache.read().await.get_epoch().unwrap_or(0);
+        } = event;
 
         let task_group_index = dkg_task.group_index;
 
         let task_epoch = dkg_task.epoch;
 
-        if cache_index != task_group_index || cache_epoch != task_epoch {
+        let cache_epoch = self
+            .group_cache
+            .read()
+            .await
+            .get_epoch(task_group_index)
+            .ok();
+
+        if cache_epoch.map_or(true, |cache_epoch| cache_epoch < task_epoch) {
             self.group_cache
                 .write()
                 .await
@@ -70,6 +70,8 @@ impl<G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static> Subscriber
                 .await?;
 
             if res {
+                observe_dkg_status(DKGStatus::InPhase);
+
                 self.publish(RunDKG { dkg_task }).await;
 
                 info!(
@@ -85,8 +87,6 @@ impl<G: GroupInfoFetcher + GroupInfoUpdater + Sync + Send + 'static> Subscriber
     async fn subscribe(self) {
         let eq = self.eq.clone();
 
-        let subscriber = Box::new(self);
-
-        eq.write().await.subscribe(Topic::NewDKGTask, subscriber);
+        eq.write().await.subscribe(0, self);
     }
 }
@@ -1,19 +1,21 @@
 use super::Subscriber;
 use crate::node::{
     algorithm::bls::{BLSCore, SimpleBLSCore},
-    error::NodeResult,
-    event::{ready_to_fulfill_randomness_task::ReadyToFulfillRandomnessTask, types::Topic, Event},
+    error::{NodeError, NodeResult},
+    event::ready_to_fulfill_randomness_task::ReadyToFulfillRandomnessTask,
+    metrics::{inc_rpc_retries, task_type_label, FULFILLMENTS, SIGNATURE_AGGREGATION_SECONDS},
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
 };
 use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterTransactions, AdapterViews};
-use arpa_node_core::ChainIdentity;
+use arpa_node_core::{ChainIdentity, TaskType};
 use arpa_node_dal::cache::RandomnessResultCache;
 use async_trait::async_trait;
 use ethers::types::Address;
-use log::{debug, error, info};
+use log::{error, info};
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::RwLock;
+use tokio_retry::{strategy::FixedInterval, RetryIf};
 
 pub struct RandomnessSignatureAggregationSubscriber<I: ChainIdentity + AdapterClientBuilder> {
     pub chain_id: usize,
@@ -53,6 +55,7 @@ pub trait FulfillRandomnessHandler {
 }
 
 pub struct GeneralFulfillRandomnessHandler<I: ChainIdentity + AdapterClientBuilder> {
+    chain_id: usize,
     id_address: Address,
     chain_identity: Arc<RwLock<I>>,
 }
@@ -74,30 +77,63 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send> FulfillRandomnessHan
             .await
             .build_adapter_client(self.id_address);
 
-        if !client
-            .get_signature_task_completion_state(randomness_task_index)
-            .await?
-        {
-            match client
-                .fulfill_randomness(
-                    group_index,
-                    randomness_task_index,
-                    signature.clone(),
-                    partial_signatures,
-                )
-                .await
-            {
-                Ok(()) => {
-                    info!("fulfill randomness successfully! signature index: {}, group_index: {}, signature: {}",
-                        randomness_task_index, group_index, hex::encode(signature));
-                }
-                Err(e) => {
-                    error!("{:?}", e);
+        // a reverted fulfillment is retried as long as the task is not fulfilled, by the node or
+        // by another committer, as it can fail for a transient reason
+        let retry_strategy = FixedInterval::from_millis(2000).take(5);
+
+        RetryIf::spawn(
+            retry_strategy,
+            || async {
+                if client
+                    .get_signature_task_completion_state(randomness_task_index)
+                    .await?
+                {
+                    return NodeResult::Ok(());
                 }
-            }
-        }
 
-        Ok(())
+                match client
+                    .fulfill_randomness(
+                        group_index,
+                        randomness_task_index,
+                        signature.clone(),
+                        partial_signatures.clone(),
+                    )
+                    .await
+                {
+                    Ok(()) => {
+                        info!("fulfill randomness successfully! signature index: {}, group_index: {}, signature: {}",
+                            randomness_task_index, group_index, hex::encode(&signature));
+
+                        FULFILLMENTS
+                            .with_label_values(&[
+                                &self.chain_id.to_string(),
+                                task_type_label(&TaskType::Randomness),
+                                "success",
+                            ])
+                            .inc();
+
+                        Ok(())
+                    }
+                    Err(e) => {
+                        FULFILLMENTS
+                            .with_label_values(&[
+                                &self.chain_id.to_string(),
+                                task_type_label(&TaskType::Randomness),
+                                "revert",
+                            ])
+                            .inc();
+
+                        Err(e.into())
+                    }
+                }
+            },
+            |e: &NodeError| {
+                error!("fulfill randomness failed. Retry... Error: {:?}", e);
+                inc_rpc_retries(self.chain_id, "fulfill_randomness");
+                true
+            },
+        )
+        .await
     }
 }
 
@@ -105,16 +141,13 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send> FulfillRandomnessHan
 impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
     for RandomnessSignatureAggregationSubscriber<I>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = ReadyToFulfillRandomnessTask;
 
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
         let ReadyToFulfillRandomnessTask {
             tasks: ready_signatures,
             ..
-        } = payload
-            .as_any()
-            .downcast_ref::<ReadyToFulfillRandomnessTask>()
-            .unwrap();
+        } = event;
 
         for signature in ready_signatures {
             let RandomnessResultCache {
@@ -123,21 +156,33 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
                 message: _,
                 threshold,
                 partial_signatures,
-            } = signature.clone();
+            } = signature;
 
             let bls_core = SimpleBLSCore {};
 
+            let aggregation_timer = SIGNATURE_AGGREGATION_SECONDS
+                .with_label_values(&[
+                    &self.chain_id.to_string(),
+                    task_type_label(&TaskType::Randomness),
+                ])
+                .start_timer();
+
             let signature = bls_core.aggregate(
                 threshold,
                 &partial_signatures.values().cloned().collect::<Vec<_>>(),
             )?;
 
+            aggregation_timer.observe_duration();
+
+            let chain_id = self.chain_id;
+
             let id_address = self.id_address;
 
             let chain_identity = self.chain_identity.clone();
 
             self.ts.write().await.add_task(async move {
                 let handler = GeneralFulfillRandomnessHandler {
+                    chain_id,
                     id_address,
                     chain_identity,
                 };
@@ -164,10 +209,6 @@ impl<I: ChainIdentity + AdapterClientBuilder + Sync + Send + 'static> Subscriber
 
         let chain_id = self.chain_id;
 
-        let subscriber = Box::new(self);
-
-        eq.write()
-            .await
-            .subscribe(Topic::ReadyToFulfillRandomnessTask(chain_id), subscriber);
+        eq.write().await.subscribe(chain_id, self);
     }
 }
@@ -2,30 +2,26 @@ use super::Subscriber;
 use crate::node::{
     algorithm::bls::{BLSCore, SimpleBLSCore},
     committer::{
-        client::GeneralCommitterClient, CommitterClient, CommitterClientHandler, CommitterService,
-    },
-    error::{NodeError, NodeResult},
-    event::{
-        ready_to_handle_group_relay_confirmation_task::ReadyToHandleGroupRelayConfirmationTask,
-        types::Topic, Event,
+        client::GeneralCommitterClient, commit_partial_signatures_with_retry,
+        get_group_relay_confirmation_message, sign_partial_signature, CommitterClientHandler,
     },
+    error::NodeResult,
+    event::ready_to_handle_group_relay_confirmation_task::ReadyToHandleGroupRelayConfirmationTask,
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
 };
-use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterViews};
-use arpa_node_core::ContractGroup;
-use arpa_node_core::Status;
-use arpa_node_core::{ChainIdentity, GroupRelayConfirmation, GroupRelayConfirmationTask, TaskType};
+use arpa_node_contract_client::adapter::AdapterClientBuilder;
+use arpa_node_core::{ChainIdentity, GroupRelayConfirmationTask, TaskType};
 use arpa_node_dal::{
     cache::GroupRelayConfirmationResultCache, GroupInfoFetcher, SignatureResultCacheFetcher,
     SignatureResultCacheUpdater,
 };
 use async_trait::async_trait;
 use ethers::types::Address;
-use log::{debug, error, info};
+use futures::future::join_all;
+use log::{error, info};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tokio_retry::{strategy::FixedInterval, RetryIf};
 
 pub struct ReadyToHandleGroupRelayConfirmationTaskSubscriber<
     G: GroupInfoFetcher,
@@ -84,6 +80,7 @@ pub struct GeneralGroupRelayConfirmationHandler<
     chain_id: usize,
     main_chain_identity: Arc<RwLock<I>>,
     chain_identity: Arc<RwLock<I>>,
+    group_index: usize,
     tasks: Vec<GroupRelayConfirmationTask>,
     group_cache: Arc<RwLock<G>>,
     group_relay_confirmation_signature_cache: Arc<RwLock<C>>,
@@ -133,31 +130,20 @@ impl<
             .await
             .build_adapter_client(main_id_address);
 
-        let committers = self.prepare_committer_clients().await?;
-
-        for task in self.tasks {
-            let relayed_group = controller_client
-                .get_group(task.relayed_group_index)
-                .await?;
-
-            let relayed_group: ContractGroup = relayed_group.into();
-
-            let relayed_group_as_bytes = bincode::serialize(&relayed_group)?;
-
-            let relayed_group_cache = adapter_client
-                .get_group_relay_cache(task.group_relay_cache_index)
-                .await?;
-
-            let relayed_group_cache: ContractGroup = relayed_group_cache.into();
+        let group_epoch = self
+            .group_cache
+            .read()
+            .await
+            .get_ready_epoch(self.group_index)?;
 
-            let relayed_group_cache_as_bytes = bincode::serialize(&relayed_group_cache)?;
+        let committers = self.prepare_committer_clients(self.group_index).await?;
 
-            let status = Status::from(relayed_group_as_bytes == relayed_group_cache_as_bytes);
+        let mut signed_partial_signatures = vec![];
 
-            let group_relay_confirmation = GroupRelayConfirmation {
-                group: relayed_group_cache,
-                status,
-            };
+        for task in self.tasks {
+            let group_relay_confirmation =
+                get_group_relay_confirmation_message(&controller_client, &adapter_client, &task)
+                    .await?;
 
             info!("group_relay_confirmation: {:?}", group_relay_confirmation);
 
@@ -166,79 +152,65 @@ impl<
             let bls_core = SimpleBLSCore {};
 
             let partial_signature = bls_core.partial_sign(
-                self.group_cache.read().await.get_secret_share()?,
+                self.group_cache
+                    .read()
+                    .await
+                    .get_secret_share(self.group_index, group_epoch)?,
                 &group_relay_confirmation_as_bytes,
             )?;
 
-            let threshold = self.group_cache.read().await.get_threshold()?;
-
-            let current_group_index = self.group_cache.read().await.get_index()?;
-
-            if self
+            let threshold = self
                 .group_cache
                 .read()
                 .await
-                .is_committer(main_id_address)?
-            {
-                let contained_res = self
-                    .group_relay_confirmation_signature_cache
-                    .read()
-                    .await
-                    .contains(task.index);
-                if !contained_res {
-                    self.group_relay_confirmation_signature_cache
-                        .write()
-                        .await
-                        .add(
-                            current_group_index,
-                            task.index,
-                            group_relay_confirmation,
-                            threshold,
-                        )?;
-                }
-
-                self.group_relay_confirmation_signature_cache
-                    .write()
-                    .await
-                    .add_partial_signature(
+                .get_threshold(self.group_index, group_epoch)?;
+
+            if self.group_cache.read().await.is_committer(
+                self.group_index,
+                group_epoch,
+                main_id_address,
+            )? {
+                // the committer service may have built the cache already from an early partial
+                let mut group_relay_confirmation_signature_cache =
+                    self.group_relay_confirmation_signature_cache.write().await;
+
+                if !group_relay_confirmation_signature_cache.contains(task.index) {
+                    group_relay_confirmation_signature_cache.add(
+                        self.group_index,
                         task.index,
-                        main_id_address,
-                        partial_signature.clone(),
+                        group_relay_confirmation,
+                        threshold,
                     )?;
-            }
-
-            for committer in committers.iter() {
-                let retry_strategy = FixedInterval::from_millis(2000).take(3);
-
-                let chain_id = self.chain_id;
-
-                if let Err(err) = RetryIf::spawn(
-                    retry_strategy,
-                    || {
-                        committer.clone().commit_partial_signature(
-                            chain_id,
-                            TaskType::GroupRelayConfirmation,
-                            group_relay_confirmation_as_bytes.clone(),
-                            task.index,
-                            partial_signature.clone(),
-                        )
-                    },
-                    |e: &NodeError| {
-                        error!(
-                            "send partial signature to committer {0} failed. Retry... Error: {1:?}",
-                            committer.get_id_address(),
-                            e
-                        );
-                        true
-                    },
-                )
-                .await
-                {
-                    error!("{:?}", err);
                 }
+
+                group_relay_confirmation_signature_cache.add_partial_signature(
+                    task.index,
+                    main_id_address,
+                    partial_signature.clone(),
+                )?;
             }
+
+            // signed with the main chain key, which identifies the member to the committers
+            let signed_partial_signature = sign_partial_signature(
+                &*self.main_chain_identity.read().await,
+                self.chain_id,
+                self.group_index,
+                TaskType::GroupRelayConfirmation,
+                task.index,
+                group_relay_confirmation_as_bytes,
+                partial_signature,
+            )
+            .await?;
+
+            signed_partial_signatures.push(signed_partial_signature);
         }
 
+        // the partial signatures of all the tasks are committed in one batch to every committer
+        join_all(committers.into_iter().map(|committer| {
+            commit_partial_signatures_with_retry(committer, signed_partial_signatures.clone())
+        }))
+        .await;
+
         Ok(())
     }
 }
@@ -254,14 +226,12 @@ impl<
             + 'static,
     > Subscriber for ReadyToHandleGroupRelayConfirmationTaskSubscriber<G, I, C>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = ReadyToHandleGroupRelayConfirmationTask;
 
-        let ReadyToHandleGroupRelayConfirmationTask { tasks, .. } = payload
-            .as_any()
-            .downcast_ref::<ReadyToHandleGroupRelayConfirmationTask>()
-            .unwrap()
-            .clone();
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let ReadyToHandleGroupRelayConfirmationTask {
+            group_index, tasks, ..
+        } = event;
 
         let chain_id = self.chain_identity.read().await.get_id();
 
@@ -279,6 +249,7 @@ impl<
                 chain_id,
                 main_chain_identity,
                 chain_identity,
+                group_index,
                 tasks,
                 group_cache: group_cache_for_handler,
                 group_relay_confirmation_signature_cache:
@@ -298,11 +269,6 @@ impl<
 
         let chain_id = self.chain_id;
 
-        let subscriber = Box::new(self);
-
-        eq.write().await.subscribe(
-            Topic::ReadyToHandleGroupRelayConfirmationTask(chain_id),
-            subscriber,
-        );
+        eq.write().await.subscribe(chain_id, self);
     }
 }
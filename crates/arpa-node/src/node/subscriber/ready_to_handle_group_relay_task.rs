@@ -2,25 +2,26 @@ use super::Subscriber;
 use crate::node::{
     algorithm::bls::{BLSCore, SimpleBLSCore},
     committer::{
-        client::GeneralCommitterClient, CommitterClient, CommitterClientHandler, CommitterService,
+        client::GeneralCommitterClient, commit_partial_signatures_with_retry,
+        get_group_relay_message, sign_partial_signature, CommitterClientHandler,
     },
-    error::{NodeError, NodeResult},
-    event::{ready_to_handle_group_relay_task::ReadyToHandleGroupRelayTask, types::Topic, Event},
+    error::NodeResult,
+    event::ready_to_handle_group_relay_task::ReadyToHandleGroupRelayTask,
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
 };
-use arpa_node_contract_client::adapter::{AdapterClientBuilder, AdapterViews};
-use arpa_node_core::{ChainIdentity, ContractGroup, GroupRelayTask, TaskType};
+use arpa_node_contract_client::adapter::AdapterClientBuilder;
+use arpa_node_core::{ChainIdentity, GroupRelayTask, TaskType};
 use arpa_node_dal::{
     cache::GroupRelayResultCache, GroupInfoFetcher, SignatureResultCacheFetcher,
     SignatureResultCacheUpdater,
 };
 use async_trait::async_trait;
 use ethers::types::Address;
-use log::{debug, error};
+use futures::future::join_all;
+use log::error;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tokio_retry::{strategy::FixedInterval, RetryIf};
 
 pub struct ReadyToHandleGroupRelayTaskSubscriber<
     G: GroupInfoFetcher,
@@ -71,6 +72,7 @@ pub struct GeneralGroupRelayHandler<
         + SignatureResultCacheFetcher<GroupRelayResultCache>,
 > {
     main_chain_identity: Arc<RwLock<I>>,
+    group_index: usize,
     tasks: Vec<GroupRelayTask>,
     group_cache: Arc<RwLock<G>>,
     group_relay_signature_cache: Arc<RwLock<C>>,
@@ -113,90 +115,85 @@ impl<
             .await
             .build_adapter_client(main_id_address);
 
-        let committers = self.prepare_committer_clients().await?;
+        let group_epoch = self
+            .group_cache
+            .read()
+            .await
+            .get_ready_epoch(self.group_index)?;
 
-        for task in self.tasks {
-            let relayed_group = client.get_group(task.relayed_group_index).await?;
+        let committers = self.prepare_committer_clients(self.group_index).await?;
 
-            let relayed_group: ContractGroup = relayed_group.into();
+        let mut signed_partial_signatures = vec![];
 
-            if relayed_group.epoch != task.relayed_group_epoch {
-                continue;
-            }
+        for task in self.tasks {
+            let relayed_group = match get_group_relay_message(&client, &task).await? {
+                Some(relayed_group) => relayed_group,
+                None => continue,
+            };
 
             let relayed_group_as_bytes = bincode::serialize(&relayed_group)?;
 
             let bls_core = SimpleBLSCore {};
 
             let partial_signature = bls_core.partial_sign(
-                self.group_cache.read().await.get_secret_share()?,
+                self.group_cache
+                    .read()
+                    .await
+                    .get_secret_share(self.group_index, group_epoch)?,
                 &relayed_group_as_bytes,
             )?;
 
-            let threshold = self.group_cache.read().await.get_threshold()?;
-
-            let current_group_index = self.group_cache.read().await.get_index()?;
-
-            if self
+            let threshold = self
                 .group_cache
                 .read()
                 .await
-                .is_committer(main_id_address)?
-            {
-                let contained_res = self
-                    .group_relay_signature_cache
-                    .read()
-                    .await
-                    .contains(task.controller_global_epoch);
-                if !contained_res {
-                    self.group_relay_signature_cache.write().await.add(
-                        current_group_index,
+                .get_threshold(self.group_index, group_epoch)?;
+
+            if self.group_cache.read().await.is_committer(
+                self.group_index,
+                group_epoch,
+                main_id_address,
+            )? {
+                // the committer service may have built the cache already from an early partial
+                let mut group_relay_signature_cache =
+                    self.group_relay_signature_cache.write().await;
+
+                if !group_relay_signature_cache.contains(task.controller_global_epoch) {
+                    group_relay_signature_cache.add(
+                        self.group_index,
                         task.controller_global_epoch,
                         relayed_group,
                         threshold,
                     )?;
                 }
 
-                self.group_relay_signature_cache
-                    .write()
-                    .await
-                    .add_partial_signature(
-                        task.controller_global_epoch,
-                        main_id_address,
-                        partial_signature.clone(),
-                    )?;
+                group_relay_signature_cache.add_partial_signature(
+                    task.controller_global_epoch,
+                    main_id_address,
+                    partial_signature.clone(),
+                )?;
             }
 
-            for committer in committers.iter() {
-                let retry_strategy = FixedInterval::from_millis(2000).take(3);
-
-                if let Err(err) = RetryIf::spawn(
-                    retry_strategy,
-                    || {
-                        committer.clone().commit_partial_signature(
-                            0,
-                            TaskType::GroupRelay,
-                            relayed_group_as_bytes.clone(),
-                            task.controller_global_epoch,
-                            partial_signature.clone(),
-                        )
-                    },
-                    |e: &NodeError| {
-                        error!(
-                            "send partial signature to committer {0} failed. Retry... Error: {1:?}",
-                            committer.get_id_address(),
-                            e
-                        );
-                        true
-                    },
-                )
-                .await
-                {
-                    error!("{:?}", err);
-                }
-            }
+            let signed_partial_signature = sign_partial_signature(
+                &*self.main_chain_identity.read().await,
+                0,
+                self.group_index,
+                TaskType::GroupRelay,
+                task.controller_global_epoch,
+                relayed_group_as_bytes,
+                partial_signature,
+            )
+            .await?;
+
+            signed_partial_signatures.push(signed_partial_signature);
         }
 
+        // the partial signatures of all the tasks are committed in one batch to every committer
+        join_all(committers.into_iter().map(|committer| {
+            commit_partial_signatures_with_retry(committer, signed_partial_signatures.clone())
+        }))
+        .await;
+
         Ok(())
     }
 }
@@ -212,14 +209,10 @@ impl<
             + 'static,
     > Subscriber for ReadyToHandleGroupRelayTaskSubscriber<G, I, C>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = ReadyToHandleGroupRelayTask;
 
-        let ReadyToHandleGroupRelayTask { tasks } = payload
-            .as_any()
-            .downcast_ref::<ReadyToHandleGroupRelayTask>()
-            .unwrap()
-            .clone();
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let ReadyToHandleGroupRelayTask { group_index, tasks } = event;
 
         let main_chain_identity = self.main_chain_identity.clone();
 
@@ -230,6 +223,7 @@ impl<
         self.ts.write().await.add_task(async move {
             let handler = GeneralGroupRelayHandler {
                 main_chain_identity,
+                group_index,
                 tasks,
                 group_cache: group_cache_for_handler,
                 group_relay_signature_cache: group_relay_signature_cache_for_handler,
@@ -246,10 +240,6 @@ impl<
     async fn subscribe(self) {
         let eq = self.eq.clone();
 
-        let subscriber = Box::new(self);
-
-        eq.write()
-            .await
-            .subscribe(Topic::ReadyToHandleGroupRelayTask, subscriber);
+        eq.write().await.subscribe(0, self);
     }
 }
@@ -1,34 +1,41 @@
 use crate::node::{
     algorithm::bls::{BLSCore, SimpleBLSCore},
     committer::{
-        client::GeneralCommitterClient, CommitterClient, CommitterClientHandler, CommitterService,
+        client::GeneralCommitterClient,
+        commit_partial_signatures_with_retry,
+        gossip::{gossip_partial_signature, prepare_gossip_clients},
+        sign_partial_signature, CommitterClientHandler,
     },
-    error::{NodeError, NodeResult},
-    event::{ready_to_handle_randomness_task::ReadyToHandleRandomnessTask, types::Topic, Event},
+    context::types::GossipConfig,
+    error::NodeResult,
+    event::ready_to_handle_randomness_task::ReadyToHandleRandomnessTask,
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
 };
-use arpa_node_core::{RandomnessTask, TaskType};
+use arpa_node_core::{ChainIdentity, RandomnessTask, SignedPartialSignature, TaskType};
 use arpa_node_dal::{
     cache::RandomnessResultCache, GroupInfoFetcher, SignatureResultCacheFetcher,
     SignatureResultCacheUpdater,
 };
 use async_trait::async_trait;
 use ethers::types::Address;
-use log::{debug, error};
-use std::sync::Arc;
+use futures::future::join_all;
+use log::error;
+use std::{collections::BTreeMap, sync::Arc};
 use tokio::sync::RwLock;
-use tokio_retry::{strategy::FixedInterval, RetryIf};
 
 use super::Subscriber;
 
 pub struct ReadyToHandleRandomnessTaskSubscriber<
     G: GroupInfoFetcher,
+    I: ChainIdentity,
     C: SignatureResultCacheUpdater<RandomnessResultCache>
         + SignatureResultCacheFetcher<RandomnessResultCache>,
 > {
     pub chain_id: usize,
     id_address: Address,
+    main_chain_identity: Arc<RwLock<I>>,
+    partial_signature_gossip: Option<GossipConfig>,
     group_cache: Arc<RwLock<G>>,
     randomness_signature_cache: Arc<RwLock<C>>,
     eq: Arc<RwLock<EventQueue>>,
@@ -37,13 +44,16 @@ pub struct ReadyToHandleRandomnessTaskSubscriber<
 
 impl<
         G: GroupInfoFetcher,
+        I: ChainIdentity,
         C: SignatureResultCacheUpdater<RandomnessResultCache>
             + SignatureResultCacheFetcher<RandomnessResultCache>,
-    > ReadyToHandleRandomnessTaskSubscriber<G, C>
+    > ReadyToHandleRandomnessTaskSubscriber<G, I, C>
 {
     pub fn new(
         chain_id: usize,
         id_address: Address,
+        main_chain_identity: Arc<RwLock<I>>,
+        partial_signature_gossip: Option<GossipConfig>,
         group_cache: Arc<RwLock<G>>,
         randomness_signature_cache: Arc<RwLock<C>>,
         eq: Arc<RwLock<EventQueue>>,
@@ -52,6 +62,8 @@ impl<
         ReadyToHandleRandomnessTaskSubscriber {
             chain_id,
             id_address,
+            main_chain_identity,
+            partial_signature_gossip,
             group_cache,
             randomness_signature_cache,
             eq,
@@ -67,11 +79,15 @@ pub trait RandomnessHandler {
 
 pub struct GeneralRandomnessHandler<
     G: GroupInfoFetcher,
+    I: ChainIdentity,
     C: SignatureResultCacheUpdater<RandomnessResultCache>
         + SignatureResultCacheFetcher<RandomnessResultCache>,
 > {
     chain_id: usize,
     id_address: Address,
+    main_chain_identity: Arc<RwLock<I>>,
+    partial_signature_gossip: Option<GossipConfig>,
+    group_index: usize,
     tasks: Vec<RandomnessTask>,
     group_cache: Arc<RwLock<G>>,
     randomness_signature_cache: Arc<RwLock<C>>,
@@ -80,11 +96,12 @@ pub struct GeneralRandomnessHandler<
 #[async_trait]
 impl<
         G: GroupInfoFetcher + Sync + Send,
+        I: ChainIdentity + Sync + Send,
         C: SignatureResultCacheUpdater<RandomnessResultCache>
             + SignatureResultCacheFetcher<RandomnessResultCache>
             + Sync
             + Send,
-    > CommitterClientHandler<GeneralCommitterClient, G> for GeneralRandomnessHandler<G, C>
+    > CommitterClientHandler<GeneralCommitterClient, G> for GeneralRandomnessHandler<G, I, C>
 {
     async fn get_id_address(&self) -> Address {
         self.id_address
@@ -98,89 +115,127 @@ impl<
 #[async_trait]
 impl<
         G: GroupInfoFetcher + Sync + Send,
+        I: ChainIdentity + Sync + Send,
         C: SignatureResultCacheUpdater<RandomnessResultCache>
             + SignatureResultCacheFetcher<RandomnessResultCache>
             + Sync
             + Send,
-    > RandomnessHandler for GeneralRandomnessHandler<G, C>
+    > RandomnessHandler for GeneralRandomnessHandler<G, I, C>
 {
     async fn handle(self) -> NodeResult<()> {
-        let committers = self.prepare_committer_clients().await?;
+        let group_epoch = self
+            .group_cache
+            .read()
+            .await
+            .get_ready_epoch(self.group_index)?;
+
+        // the partial signatures go to the committers and backup committers of each task
+        let mut signed_partial_signatures: BTreeMap<Address, Vec<SignedPartialSignature>> =
+            BTreeMap::new();
 
         for task in self.tasks {
             let bls_core = SimpleBLSCore {};
 
             let partial_signature = bls_core.partial_sign(
-                self.group_cache.read().await.get_secret_share()?,
+                self.group_cache
+                    .read()
+                    .await
+                    .get_secret_share(self.group_index, group_epoch)?,
                 task.message.as_bytes(),
             )?;
 
-            let threshold = self.group_cache.read().await.get_threshold()?;
-
-            let current_group_index = self.group_cache.read().await.get_index()?;
-
-            if self
+            let threshold = self
                 .group_cache
                 .read()
                 .await
-                .is_committer(self.id_address)?
-            {
-                let contained_res = self
-                    .randomness_signature_cache
-                    .read()
-                    .await
-                    .contains(task.index);
-                if !contained_res {
-                    self.randomness_signature_cache.write().await.add(
-                        current_group_index,
+                .get_threshold(self.group_index, group_epoch)?;
+
+            let (committers, backup_committers) =
+                self.group_cache.read().await.get_task_committers(
+                    self.group_index,
+                    group_epoch,
+                    task.index,
+                    task.message.as_bytes(),
+                )?;
+
+            let receivers = committers
+                .into_iter()
+                .chain(backup_committers)
+                .collect::<Vec<_>>();
+
+            // with gossip every member collects partial signatures to step in as a backup committer
+            if self.partial_signature_gossip.is_some() || receivers.contains(&self.id_address) {
+                // the committer service may have built the cache already from an early partial
+                let mut randomness_signature_cache = self.randomness_signature_cache.write().await;
+
+                if !randomness_signature_cache.contains(task.index) {
+                    randomness_signature_cache.add(
+                        self.group_index,
                         task.index,
                         task.message.clone(),
                         threshold,
                     )?;
                 }
 
-                self.randomness_signature_cache
-                    .write()
-                    .await
-                    .add_partial_signature(
-                        task.index,
-                        self.id_address,
-                        partial_signature.clone(),
-                    )?;
+                randomness_signature_cache.add_partial_signature(
+                    task.index,
+                    self.id_address,
+                    partial_signature.clone(),
+                )?;
             }
 
-            for committer in committers.iter() {
-                let retry_strategy = FixedInterval::from_millis(2000).take(3);
-
-                let chain_id = self.chain_id;
-
-                if let Err(err) = RetryIf::spawn(
-                    retry_strategy,
-                    || {
-                        committer.clone().commit_partial_signature(
-                            chain_id,
-                            TaskType::Randomness,
-                            task.message.as_bytes().to_vec(),
-                            task.index,
-                            partial_signature.clone(),
-                        )
-                    },
-                    |e: &NodeError| {
-                        error!(
-                            "send partial signature to committer {0} failed. Retry... Error: {1:?}",
-                            committer.get_id_address(),
-                            e
-                        );
-                        true
-                    },
-                )
-                .await
-                {
-                    error!("{:?}", err);
+            // signed with the main chain key, which identifies the member to the committers
+            let signed_partial_signature = sign_partial_signature(
+                &*self.main_chain_identity.read().await,
+                self.chain_id,
+                self.group_index,
+                TaskType::Randomness,
+                task.index,
+                task.message.as_bytes().to_vec(),
+                partial_signature,
+            )
+            .await?;
+
+            if let Some(gossip_config) = self.partial_signature_gossip {
+                // the committers get it from the node directly
+                let peers: Vec<GeneralCommitterClient> = prepare_gossip_clients(
+                    &*self.group_cache.read().await,
+                    self.group_index,
+                    self.id_address,
+                    &receivers,
+                    gossip_config.fanout,
+                );
+
+                tokio::spawn(gossip_partial_signature(
+                    peers,
+                    signed_partial_signature.clone(),
+                ));
+            }
+
+            for receiver in receivers {
+                if receiver != self.id_address {
+                    signed_partial_signatures
+                        .entry(receiver)
+                        .or_default()
+                        .push(signed_partial_signature.clone());
                 }
             }
         }
 
+        let mut commits = vec![];
+
+        for (receiver, partial_signatures) in signed_partial_signatures {
+            if let Some(committer) = self.prepare_member_client(self.group_index, receiver).await {
+                commits.push(commit_partial_signatures_with_retry(
+                    committer,
+                    partial_signatures,
+                ));
+            }
+        }
+
+        // the partial signatures of all the tasks are committed in one batch to every committer
+        join_all(commits).await;
+
         Ok(())
     }
 }
@@ -188,26 +243,29 @@ impl<
 #[async_trait]
 impl<
         G: GroupInfoFetcher + Sync + Send + 'static,
+        I: ChainIdentity + Sync + Send + 'static,
         C: SignatureResultCacheUpdater<RandomnessResultCache>
             + SignatureResultCacheFetcher<RandomnessResultCache>
             + Sync
             + Send
             + 'static,
-    > Subscriber for ReadyToHandleRandomnessTaskSubscriber<G, C>
+    > Subscriber for ReadyToHandleRandomnessTaskSubscriber<G, I, C>
 {
-    async fn notify(&self, topic: Topic, payload: &(dyn Event + Send + Sync)) -> NodeResult<()> {
-        debug!("{:?}", topic);
+    type Event = ReadyToHandleRandomnessTask;
 
-        let ReadyToHandleRandomnessTask { tasks, .. } = payload
-            .as_any()
-            .downcast_ref::<ReadyToHandleRandomnessTask>()
-            .unwrap()
-            .clone();
+    async fn notify(&self, event: Self::Event) -> NodeResult<()> {
+        let ReadyToHandleRandomnessTask {
+            group_index, tasks, ..
+        } = event;
 
         let chain_id = self.chain_id;
 
         let id_address = self.id_address;
 
+        let main_chain_identity = self.main_chain_identity.clone();
+
+        let partial_signature_gossip = self.partial_signature_gossip;
+
         let group_cache_for_handler = self.group_cache.clone();
 
         let randomness_signature_cache_for_handler = self.randomness_signature_cache.clone();
@@ -216,6 +274,9 @@ impl<
             let handler = GeneralRandomnessHandler {
                 chain_id,
                 id_address,
+                main_chain_identity,
+                partial_signature_gossip,
+                group_index,
                 tasks,
                 group_cache: group_cache_for_handler,
                 randomness_signature_cache: randomness_signature_cache_for_handler,
@@ -234,10 +295,6 @@ impl<
 
         let chain_id = self.chain_id;
 
-        let subscriber = Box::new(self);
-
-        eq.write()
-            .await
-            .subscribe(Topic::ReadyToHandleRandomnessTask(chain_id), subscriber);
+        eq.write().await.subscribe(chain_id, self);
     }
 }
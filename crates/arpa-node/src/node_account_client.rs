@@ -1,52 +1,389 @@
-use ethers::signers::LocalWallet;
-use rand_chacha::rand_core::SeedableRng;
+use arpa_node::node::context::types::{
+    build_signer_from_config, Account, Config, HDWallet, Keystore,
+};
+use ethers::core::rand::{rngs::OsRng, CryptoRng, RngCore};
+use ethers::signers::{
+    coins_bip39::{English, Mnemonic},
+    LocalWallet, MnemonicBuilder, Signer,
+};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use serde::Serialize;
+use std::env;
+use std::fs::read_to_string;
 use std::path::PathBuf;
 use structopt::StructOpt;
 
+const KEYSTORE_PASSWORD_ENV: &str = "ARPA_NODE_ACCOUNT_KEYSTORE_PASSWORD";
+const MNEMONIC_ENV: &str = "ARPA_NODE_HD_ACCOUNT_MNEMONIC";
+const PASSPHRASE_ENV: &str = "ARPA_NODE_HD_ACCOUNT_PASSPHRASE";
+const PRIVATE_KEY_ENV: &str = "ARPA_NODE_ACCOUNT_PRIVATE_KEY";
+
 #[derive(StructOpt, Debug)]
-#[structopt(name = "Arpa Node Account Generator")]
+#[structopt(name = "Arpa Node Account Client")]
 pub struct Opt {
-    /// Mode to run.
-    #[structopt(short = "m", long, possible_values = &["plain", "keystore", "hdwallet"])]
-    mode: String,
+    /// Generate keys from the given seed instead of the OS random number generator.
+    /// Anyone knowing the seed can recover the keys, never use it for real accounts.
+    #[structopt(long)]
+    insecure_deterministic: Option<u64>,
+
+    #[structopt(subcommand)]
+    cmd: Command,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum Command {
+    /// Generate a private key, printed together with its address.
+    Plain,
+    /// Generate a private key into an encrypted keystore.
+    /// The password is read from ARPA_NODE_ACCOUNT_KEYSTORE_PASSWORD unless given explicitly.
+    Keystore {
+        /// Set the directory to save the keystore
+        #[structopt(short = "p", long, parse(from_os_str))]
+        path: PathBuf,
+
+        /// Set the file name of the keystore, a random uuid by default
+        #[structopt(short = "n", long)]
+        name: Option<String>,
+
+        #[structopt(short = "w", long)]
+        password: Option<String>,
+    },
+    /// Generate a BIP-39 mnemonic and list the addresses derived from it.
+    Hdwallet {
+        #[structopt(long, possible_values = &["12", "15", "18", "21", "24"], default_value = "12")]
+        words: usize,
+
+        /// BIP-39 passphrase protecting the mnemonic
+        #[structopt(long)]
+        passphrase: Option<String>,
+
+        /// Index of the derivation path to use as the node account
+        #[structopt(short = "i", long, default_value = "0")]
+        index: u32,
+
+        /// Number of derivation paths to list
+        #[structopt(long, default_value = "5")]
+        count: u32,
+    },
+    /// List the addresses derived from an existing mnemonic.
+    /// The mnemonic is read from ARPA_NODE_HD_ACCOUNT_MNEMONIC unless given explicitly.
+    Derive {
+        #[structopt(long)]
+        mnemonic: Option<String>,
+
+        /// BIP-39 passphrase protecting the mnemonic
+        #[structopt(long)]
+        passphrase: Option<String>,
+
+        /// Index of the derivation path to use as the node account
+        #[structopt(short = "i", long, default_value = "0")]
+        index: u32,
 
-    /// Seed
-    #[structopt(short = "s", long)]
-    seed: u64,
+        /// Number of derivation paths to list
+        #[structopt(long, default_value = "5")]
+        count: u32,
+    },
+    /// Encrypt an existing private key into a keystore.
+    /// The key is read from ARPA_NODE_ACCOUNT_PRIVATE_KEY and the password from
+    /// ARPA_NODE_ACCOUNT_KEYSTORE_PASSWORD unless given explicitly.
+    Import {
+        #[structopt(long)]
+        private_key: Option<String>,
 
-    /// Keystore password
-    #[structopt(short = "w", long, required_if("mode", "keystore"))]
-    password: Option<String>,
+        /// Set the directory to save the keystore
+        #[structopt(short = "p", long, parse(from_os_str))]
+        path: PathBuf,
 
-    /// Set the place to save keystore
-    #[structopt(short = "p", long, parse(from_os_str), required_if("mode", "keystore"))]
-    path: Option<PathBuf>,
+        /// Set the file name of the keystore, a random uuid by default
+        #[structopt(short = "n", long)]
+        name: Option<String>,
 
-    /// Set the name of keystore
-    #[structopt(short = "n", long)]
-    name: Option<String>,
+        #[structopt(short = "w", long)]
+        password: Option<String>,
+    },
+    /// Decrypt a keystore and print its private key.
+    /// The password is read from ARPA_NODE_ACCOUNT_KEYSTORE_PASSWORD unless given explicitly.
+    Export {
+        /// Path of the keystore file
+        #[structopt(parse(from_os_str))]
+        keystore: PathBuf,
+
+        #[structopt(short = "w", long)]
+        password: Option<String>,
+    },
+    /// Print the addresses of the main chain and adapter accounts configured in config.yml.
+    Address {
+        /// Set the config path
+        #[structopt(short = "c", long, parse(from_os_str), default_value = "config.yml")]
+        config_path: PathBuf,
+    },
+}
+
+/// Random number generator of the keys, which is the OS one unless a seed is given explicitly.
+enum AccountRng {
+    Os(OsRng),
+    Deterministic(ChaCha20Rng),
+}
+
+impl AccountRng {
+    fn new(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => {
+                eprintln!(
+                    "WARNING: keys are generated deterministically from seed {}, never use them for real accounts!",
+                    seed
+                );
+                AccountRng::Deterministic(ChaCha20Rng::seed_from_u64(seed))
+            }
+            None => AccountRng::Os(OsRng),
+        }
+    }
+}
+
+impl RngCore for AccountRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            AccountRng::Os(rng) => rng.next_u32(),
+            AccountRng::Deterministic(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            AccountRng::Os(rng) => rng.next_u64(),
+            AccountRng::Deterministic(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            AccountRng::Os(rng) => rng.fill_bytes(dest),
+            AccountRng::Deterministic(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ethers::core::rand::Error> {
+        match self {
+            AccountRng::Os(rng) => rng.try_fill_bytes(dest),
+            AccountRng::Deterministic(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+impl CryptoRng for AccountRng {}
+
+#[derive(Serialize)]
+struct AccountSection {
+    account: Account,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opt = Opt::from_args();
-    println!("{:#?}", opt);
 
-    match opt.mode.as_str() {
-        "keystore" => {
-            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(opt.seed);
-            LocalWallet::new_keystore(
-                opt.path.unwrap(),
+    let mut rng = AccountRng::new(opt.insecure_deterministic);
+
+    match opt.cmd {
+        Command::Plain => {
+            let wallet = LocalWallet::new(&mut rng);
+
+            println!("address: {:?}", wallet.address());
+            println!("private key: {}", hex::encode(wallet.signer().to_bytes()));
+
+            print_account_section(Account {
+                hdwallet: None,
+                keystore: None,
+                private_key: Some("env".to_string()),
+                remote_signer: None,
+            })?;
+
+            println!(
+                "# set {} to the private key, or replace env with it",
+                PRIVATE_KEY_ENV
+            );
+        }
+        Command::Keystore {
+            path,
+            name,
+            password,
+        } => {
+            let password = read_secret(password, KEYSTORE_PASSWORD_ENV)?;
+
+            let (wallet, name) =
+                LocalWallet::new_keystore(&path, &mut rng, &password, name.as_deref())?;
+
+            println!("address: {:?}", wallet.address());
+
+            print_keystore_account_section(path.join(name))?;
+        }
+        Command::Hdwallet {
+            words,
+            passphrase,
+            index,
+            count,
+        } => {
+            let mnemonic = Mnemonic::<English>::new_with_count(&mut rng, words)?;
+
+            let phrase = mnemonic.to_phrase()?;
+
+            println!("mnemonic: {}", phrase);
+
+            list_derived_addresses(&phrase, passphrase.as_deref(), count)?;
+
+            print_hdwallet_account_section(index, passphrase.is_some())?;
+        }
+        Command::Derive {
+            mnemonic,
+            passphrase,
+            index,
+            count,
+        } => {
+            let phrase = read_secret(mnemonic, MNEMONIC_ENV)?;
+
+            list_derived_addresses(&phrase, passphrase.as_deref(), count)?;
+
+            print_hdwallet_account_section(index, passphrase.is_some())?;
+        }
+        Command::Import {
+            private_key,
+            path,
+            name,
+            password,
+        } => {
+            let private_key = read_secret(private_key, PRIVATE_KEY_ENV)?;
+
+            let password = read_secret(password, KEYSTORE_PASSWORD_ENV)?;
+
+            let wallet: LocalWallet = private_key.parse()?;
+
+            let name = eth_keystore::encrypt_key(
+                &path,
                 &mut rng,
-                &opt.password.unwrap(),
-                opt.name.as_deref(),
-            )
-            .unwrap();
+                wallet.signer().to_bytes(),
+                &password,
+                name.as_deref(),
+            )?;
+
+            println!("address: {:?}", wallet.address());
+
+            print_keystore_account_section(path.join(name))?;
+        }
+        Command::Export { keystore, password } => {
+            let password = read_secret(password, KEYSTORE_PASSWORD_ENV)?;
+
+            let wallet = LocalWallet::decrypt_keystore(&keystore, &password)?;
+
+            println!("address: {:?}", wallet.address());
+            println!("private key: {}", hex::encode(wallet.signer().to_bytes()));
+        }
+        Command::Address { config_path } => {
+            let config_str = &read_to_string(&config_path).unwrap_or_else(|_| {
+                panic!(
+                    "Error loading configuration file {}, please check the configuration!",
+                    config_path.display()
+                )
+            });
+
+            let config: Config =
+                serde_yaml::from_str(config_str).expect("Error loading configuration file");
+
+            let address = build_signer_from_config(config.account).await?.address();
+
+            println!("main chain: {:?}", address);
+
+            for adapter in config.adapters {
+                let address = build_signer_from_config(adapter.account).await?.address();
+
+                println!("adapter chain {}: {:?}", adapter.id, address);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn read_secret(secret: Option<String>, env_key: &str) -> Result<String, env::VarError> {
+    match secret {
+        Some(secret) => Ok(secret),
+        None => env::var(env_key),
+    }
+}
+
+fn list_derived_addresses(
+    phrase: &str,
+    passphrase: Option<&str>,
+    count: u32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for index in 0..count {
+        let mut builder = MnemonicBuilder::<English>::default().phrase(phrase);
 
-            println!("keystore generated successfully.");
+        if let Some(passphrase) = passphrase {
+            builder = builder.password(passphrase);
         }
-        _ => panic!("not implemented."),
+
+        let wallet = builder.index(index)?.build()?;
+
+        println!("m/44'/60'/0'/0/{}: {:?}", index, wallet.address());
     }
 
     Ok(())
 }
+
+fn print_keystore_account_section(path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    print_account_section(Account {
+        hdwallet: None,
+        keystore: Some(Keystore {
+            path: path.display().to_string(),
+            password: "env".to_string(),
+        }),
+        private_key: None,
+        remote_signer: None,
+    })?;
+
+    println!(
+        "# set {} to the keystore password, or replace env with it",
+        KEYSTORE_PASSWORD_ENV
+    );
+
+    Ok(())
+}
+
+fn print_hdwallet_account_section(
+    index: u32,
+    with_passphrase: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    print_account_section(Account {
+        hdwallet: Some(HDWallet {
+            mnemonic: "env".to_string(),
+            path: None,
+            index,
+            passphrase: with_passphrase.then(|| "env".to_string()),
+        }),
+        keystore: None,
+        private_key: None,
+        remote_signer: None,
+    })?;
+
+    println!(
+        "# set {} to the mnemonic, or replace env with it",
+        MNEMONIC_ENV
+    );
+
+    if with_passphrase {
+        println!(
+            "# set {} to the passphrase, or replace env with it",
+            PASSPHRASE_ENV
+        );
+    }
+
+    Ok(())
+}
+
+// secrets are left as env in the section so that they are not written to config.yml by accident
+fn print_account_section(account: Account) -> Result<(), Box<dyn std::error::Error>> {
+    println!();
+    println!("# account section of config.yml");
+    print!("{}", serde_yaml::to_string(&AccountSection { account })?);
+
+    Ok(())
+}
@@ -1,7 +1,12 @@
-use arpa_node::node::context::chain::types::GeneralMainChain;
-use arpa_node::node::context::types::{build_wallet_from_config, Config, GeneralContext};
+use arpa_node::node::context::chain::types::{GeneralAdapterChain, GeneralMainChain};
+use arpa_node::node::context::types::{
+    build_adapter_chain_identity, build_data_key_from_config,
+    build_management_rpc_token_from_config, build_signer_from_config, Config, GeneralContext,
+    DEFAULT_BLOCK_CONFIRMATIONS, DEFAULT_POLLING_INTERVAL_MILLIS,
+};
 use arpa_node::node::context::{Context, TaskWaiter};
-use arpa_node_contract_client::controller::{ControllerClientBuilder, ControllerTransactions};
+use arpa_node_contract_client::controller::ControllerTransactions;
+use arpa_node_contract_client::ethers::provider::ChainProvider;
 use arpa_node_contract_client::rpc_mock::controller::MockControllerClient;
 use arpa_node_core::format_now_date;
 use arpa_node_core::GeneralChainIdentity;
@@ -123,11 +128,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let data_path = PathBuf::from(config.data_path.unwrap());
 
+    let management_rpc_token =
+        build_management_rpc_token_from_config(config.node_management_rpc_token.take())?;
+
     match opt.mode.as_str() {
         "new-run" => {
-            let wallet = build_wallet_from_config(config.account)?;
+            let signer = build_signer_from_config(config.account).await?;
 
-            let id_address = wallet.address();
+            let id_address = signer.address();
 
             if data_path.exists() {
                 fs::rename(
@@ -140,11 +148,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 info!("Existing data file found. Renamed to the directory of data_path.",);
             }
 
-            let db = SqliteDB::build(
-                data_path.as_os_str().to_str().unwrap(),
-                &wallet.signer().to_bytes(),
-            )
-            .await?;
+            let data_key = build_data_key_from_config(config.data_encryption, &signer).await?;
+
+            let db = SqliteDB::build(data_path.as_os_str().to_str().unwrap(), &data_key).await?;
 
             let rng = &mut rand::thread_rng();
 
@@ -167,19 +173,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             let group_cache = db.get_group_info_client();
 
-            let randomness_tasks_cache = db.get_bls_tasks_client::<RandomnessTask>();
+            let randomness_tasks_cache = db.get_bls_tasks_client::<RandomnessTask>(0);
 
-            let main_chain_identity = GeneralChainIdentity::new(
+            let mut main_chain_identity = GeneralChainIdentity::new(
                 0,
                 0,
-                wallet,
+                signer,
                 config.provider_endpoint.clone(),
+                config.provider_transport.unwrap_or_default(),
+                config
+                    .provider_polling_interval_millis
+                    .unwrap_or(DEFAULT_POLLING_INTERVAL_MILLIS),
+                config.provider_failover.clone().unwrap_or_default(),
+                config.transaction.unwrap_or_default(),
                 config
                     .controller_address
                     .parse()
                     .expect("bad format of controller_address"),
             );
 
+            // transactions are signed for the chain the provider is connected to
+            let chain_id = ChainProvider::new(&main_chain_identity)
+                .get_chain_id()
+                .await?;
+            main_chain_identity.set_chain_id(chain_id);
+
             let main_chain = GeneralMainChain::<
                 NodeInfoDBClient,
                 GroupInfoDBClient,
@@ -188,35 +206,69 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             >::new(
                 0,
                 "main chain".to_string(),
-                main_chain_identity.clone(),
+                config
+                    .block_confirmations
+                    .unwrap_or(DEFAULT_BLOCK_CONFIRMATIONS),
+                main_chain_identity,
                 node_cache,
                 group_cache,
                 randomness_tasks_cache,
             );
 
-            let context = GeneralContext::new(main_chain);
+            let mut context = GeneralContext::new(main_chain);
 
-            let handle = context.deploy().await;
+            if let Some(management_rpc_endpoint) = config.node_management_rpc_endpoint {
+                context.set_management_server(management_rpc_endpoint, management_rpc_token);
+            }
+
+            if let Some(metrics_endpoint) = config.node_metrics_endpoint {
+                context.set_metrics_server(metrics_endpoint);
+            }
 
-            // TODO register node to randcast network, this should be moved to node_cmd_client(triggering manully to avoid accidental operation) in prod
-            let client = main_chain_identity.build_controller_client();
+            if let Some(gossip_config) = config.partial_signature_gossip {
+                context.set_partial_signature_gossip(gossip_config);
+            }
 
-            client
-                .node_register(bincode::serialize(&dkg_public_key).unwrap())
-                .await?;
+            for adapter in config.adapters {
+                let adapter_chain_identity = build_adapter_chain_identity(&adapter).await?;
+
+                let randomness_tasks_cache = db.get_bls_tasks_client::<RandomnessTask>(adapter.id);
+
+                let adapter_chain = GeneralAdapterChain::<
+                    NodeInfoDBClient,
+                    GroupInfoDBClient,
+                    BLSTasksDBClient<RandomnessTask>,
+                    GeneralChainIdentity,
+                >::new(
+                    adapter.id,
+                    adapter.name,
+                    adapter
+                        .block_confirmations
+                        .unwrap_or(DEFAULT_BLOCK_CONFIRMATIONS),
+                    adapter_chain_identity,
+                    randomness_tasks_cache,
+                );
+
+                context.add_adapter_chain(adapter_chain)?;
+            }
+
+            let handle = context.deploy().await;
+
+            // registering is triggered manually by node-cmd-client to avoid accidental operation
+            info!("Node is ready. Please register it to randcast network by node-cmd-client register.");
 
             handle.wait_task().await;
+
+            db.close().await?;
         }
         "re-run" => {
-            let wallet = build_wallet_from_config(config.account)?;
+            let signer = build_signer_from_config(config.account).await?;
 
-            let id_address = wallet.address();
+            let id_address = signer.address();
 
-            let db = SqliteDB::build(
-                data_path.as_os_str().to_str().unwrap(),
-                &wallet.signer().to_bytes(),
-            )
-            .await?;
+            let data_key = build_data_key_from_config(config.data_encryption, &signer).await?;
+
+            let db = SqliteDB::build(data_path.as_os_str().to_str().unwrap(), &data_key).await?;
 
             let mut node_cache = db.get_node_info_client();
 
@@ -235,19 +287,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "It seems there is no existing group record. Please execute in new-run mode.",
             );
 
-            let randomness_tasks_cache = db.get_bls_tasks_client::<RandomnessTask>();
+            let randomness_tasks_cache = db.get_bls_tasks_client::<RandomnessTask>(0);
 
-            let main_chain_identity = GeneralChainIdentity::new(
+            let mut main_chain_identity = GeneralChainIdentity::new(
                 0,
                 0,
-                wallet,
+                signer,
                 config.provider_endpoint.clone(),
+                config.provider_transport.unwrap_or_default(),
+                config
+                    .provider_polling_interval_millis
+                    .unwrap_or(DEFAULT_POLLING_INTERVAL_MILLIS),
+                config.provider_failover.clone().unwrap_or_default(),
+                config.transaction.unwrap_or_default(),
                 config
                     .controller_address
                     .parse()
                     .expect("bad format of controller_address"),
             );
 
+            // transactions are signed for the chain the provider is connected to
+            let chain_id = ChainProvider::new(&main_chain_identity)
+                .get_chain_id()
+                .await?;
+            main_chain_identity.set_chain_id(chain_id);
+
             let main_chain = GeneralMainChain::<
                 NodeInfoDBClient,
                 GroupInfoDBClient,
@@ -256,17 +320,57 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             >::new(
                 0,
                 "main chain".to_string(),
+                config
+                    .block_confirmations
+                    .unwrap_or(DEFAULT_BLOCK_CONFIRMATIONS),
                 main_chain_identity,
                 node_cache,
                 group_cache,
                 randomness_tasks_cache,
             );
 
-            let context = GeneralContext::new(main_chain);
+            let mut context = GeneralContext::new(main_chain);
+
+            if let Some(management_rpc_endpoint) = config.node_management_rpc_endpoint {
+                context.set_management_server(management_rpc_endpoint, management_rpc_token);
+            }
+
+            if let Some(metrics_endpoint) = config.node_metrics_endpoint {
+                context.set_metrics_server(metrics_endpoint);
+            }
+
+            if let Some(gossip_config) = config.partial_signature_gossip {
+                context.set_partial_signature_gossip(gossip_config);
+            }
+
+            for adapter in config.adapters {
+                let adapter_chain_identity = build_adapter_chain_identity(&adapter).await?;
+
+                let randomness_tasks_cache = db.get_bls_tasks_client::<RandomnessTask>(adapter.id);
+
+                let adapter_chain = GeneralAdapterChain::<
+                    NodeInfoDBClient,
+                    GroupInfoDBClient,
+                    BLSTasksDBClient<RandomnessTask>,
+                    GeneralChainIdentity,
+                >::new(
+                    adapter.id,
+                    adapter.name,
+                    adapter
+                        .block_confirmations
+                        .unwrap_or(DEFAULT_BLOCK_CONFIRMATIONS),
+                    adapter_chain_identity,
+                    randomness_tasks_cache,
+                );
+
+                context.add_adapter_chain(adapter_chain)?;
+            }
 
             let handle = context.deploy().await;
 
             handle.wait_task().await;
+
+            db.close().await?;
         }
         "demo" => {
             let id_address = format!("0x000000000000000000000000000000000000000{}", node_id)
@@ -308,34 +412,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             >::new(
                 0,
                 "main chain".to_string(),
+                config
+                    .block_confirmations
+                    .unwrap_or(DEFAULT_BLOCK_CONFIRMATIONS),
                 main_chain_identity,
                 node_cache,
                 group_cache,
                 randomness_tasks_cache,
             );
 
-            let context = GeneralContext::new(main_chain);
+            let mut context = GeneralContext::new(main_chain);
 
-            // suspend handling adapters
-            // for adapter in config.adapters {
-            //     let chain_identity =
-            //         ChainIdentity::new(adapter.id, vec![], adapter.id_address, adapter.endpoint);
+            if let Some(management_rpc_endpoint) = config.node_management_rpc_endpoint {
+                context.set_management_server(management_rpc_endpoint, management_rpc_token);
+            }
 
-            //     let randomness_tasks_cache = InMemoryBLSTasksQueue::<RandomnessTask>::new();
+            if let Some(metrics_endpoint) = config.node_metrics_endpoint {
+                context.set_metrics_server(metrics_endpoint);
+            }
 
-            //     let chain = InMemoryAdapterChain::<
-            //         InMemoryNodeInfoCache,
-            //         InMemoryGroupInfoCache,
-            //         InMemoryBLSTasksQueue<RandomnessTask>,
-            //     >::new(
-            //         adapter.id,
-            //         adapter.name,
-            //         chain_identity,
-            //         randomness_tasks_cache,
-            //     );
+            if let Some(gossip_config) = config.partial_signature_gossip {
+                context.set_partial_signature_gossip(gossip_config);
+            }
 
-            //     context.add_adapter_chain(chain)?;
-            // }
+            for adapter in config.adapters {
+                let adapter_chain_identity =
+                    MockChainIdentity::new(adapter.id, adapter.id, id_address, adapter.endpoint);
+
+                let randomness_tasks_cache = InMemoryBLSTasksQueue::<RandomnessTask>::new();
+
+                let adapter_chain = GeneralAdapterChain::<
+                    InMemoryNodeInfoCache,
+                    InMemoryGroupInfoCache,
+                    InMemoryBLSTasksQueue<RandomnessTask>,
+                    MockChainIdentity,
+                >::new(
+                    adapter.id,
+                    adapter.name,
+                    adapter
+                        .block_confirmations
+                        .unwrap_or(DEFAULT_BLOCK_CONFIRMATIONS),
+                    adapter_chain_identity,
+                    randomness_tasks_cache,
+                );
+
+                context.add_adapter_chain(adapter_chain)?;
+            }
 
             let handle = context.deploy().await;
 
@@ -351,5 +473,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         _ => panic!("unimplemented mode"),
     }
 
+    info!("Node exited.");
+
     Ok(())
 }
@@ -1,91 +1,440 @@
+use arpa_node::node::context::types::{
+    build_adapter_chain_identity, build_data_key, build_data_key_from_config,
+    build_signer_from_config, Config, DataEncryption, Keystore, DEFAULT_POLLING_INTERVAL_MILLIS,
+};
+use arpa_node::node::error::NodeError;
 use arpa_node_contract_client::{
-    adapter::{AdapterTransactions, AdapterViews},
-    rpc_mock::adapter::MockAdapterClient,
+    adapter::{AdapterClientBuilder, AdapterTransactions, AdapterViews},
+    controller::{ControllerClientBuilder, ControllerTransactions, ControllerViews},
+    ethers::provider::ChainProvider,
 };
-use arpa_node_core::ContractGroup;
+use arpa_node_core::RandomnessTask;
+use arpa_node_core::{ChainIdentity, ContractGroup, GeneralChainIdentity, MockChainIdentity};
+use arpa_node_dal::{BLSTasksFetcher, NodeInfoFetcher};
+use arpa_node_sqlite_db::{DataBackup, SqliteDB};
+use ethers::signers::Signer;
 use ethers::types::Address;
+use std::collections::BTreeMap;
 use std::env;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "Arpa Node Cmd Client")]
+pub struct Opt {
+    /// Backend to interact with.
+    /// 1) chain: Call contracts on chain as the account configured in config.yml.
+    /// 2) mock: Call the mock contract servers as the given id address.
+    #[structopt(short = "m", long, possible_values = &["chain", "mock"], default_value = "chain")]
+    mode: String,
+
+    /// Set the id address to act as when calling the mock contract servers
+    #[structopt(short = "a", long, required_if("mode", "mock"))]
+    id_address: Option<Address>,
+
+    /// Set the config path
+    #[structopt(short = "c", long, parse(from_os_str), default_value = "config.yml")]
+    config_path: PathBuf,
+
+    #[structopt(subcommand)]
+    cmd: Command,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum Command {
+    /// Register the node to randcast network with its DKG public key.
+    /// The key is loaded from the node database unless given explicitly.
+    Register {
+        /// Hex encoded DKG public key, required in mock mode
+        #[structopt(long)]
+        dkg_public_key: Option<String>,
+    },
+    /// Activate the node after it quitted or got slashed.
+    Activate,
+    /// Quit randcast network. The node will leave its group in the next grouping.
+    Quit,
+    /// Claim rewards to the reward address.
+    Claim {
+        reward_address: Address,
+
+        token_amount: usize,
+
+        /// Chain where the rewards are accumulated, 0 for main chain
+        #[structopt(long, default_value = "0")]
+        chain_id: usize,
+    },
+    /// Show the node record on the controller.
+    Status {
+        /// Id address of the node to query, defaults to the current account
+        id_address: Option<Address>,
+    },
+    /// Show a group on the main chain or an adapter chain.
+    Group {
+        index: usize,
+
+        #[structopt(long, default_value = "0")]
+        chain_id: usize,
+    },
+    /// Show the completion state of a randomness task, together with the local record in chain mode.
+    Tasks {
+        index: usize,
+
+        #[structopt(long, default_value = "0")]
+        chain_id: usize,
+    },
+    /// Relay the first group of the main chain to an adapter chain, authenticated admin only.
+    SetInitialGroup {
+        #[structopt(long)]
+        chain_id: usize,
+    },
+    /// Re-encrypt the node database with a new passphrase or the key of a keystore.
+    /// Update data_encryption in config.yml accordingly afterwards.
+    Rekey {
+        /// New passphrase, env to read it from ARPA_NODE_DATA_PASSPHRASE
+        #[structopt(long, required_unless("keystore"))]
+        passphrase: Option<String>,
+
+        /// Path of the keystore whose private key is used as the new passphrase
+        #[structopt(long, conflicts_with("passphrase"))]
+        keystore: Option<String>,
+
+        /// Password of the keystore, read from ARPA_NODE_DATA_KEYSTORE_PASSWORD if not set
+        #[structopt(long)]
+        keystore_password: Option<String>,
+    },
+    /// Export the DKG key pair and the shares of the node to an encrypted backup file.
+    /// The password is read from ARPA_NODE_BACKUP_PASSWORD unless given explicitly.
+    ExportBackup {
+        /// Set the directory to save the backup
+        #[structopt(short = "p", long, parse(from_os_str))]
+        path: PathBuf,
+
+        /// Set the file name of the backup, a random uuid by default
+        #[structopt(short = "n", long)]
+        name: Option<String>,
+
+        #[structopt(short = "w", long)]
+        password: Option<String>,
+    },
+    /// Restore the DKG key pair and the shares from a backup file into the node database,
+    /// which should be created by running node-client in new-run mode first.
+    /// The password is read from ARPA_NODE_BACKUP_PASSWORD unless given explicitly.
+    ImportBackup {
+        #[structopt(parse(from_os_str))]
+        backup: PathBuf,
+
+        #[structopt(short = "w", long)]
+        password: Option<String>,
+    },
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut args = env::args();
+    let opt = Opt::from_args();
 
-    args.next();
+    let config_path_str = opt
+        .config_path
+        .clone()
+        .into_os_string()
+        .into_string()
+        .unwrap();
 
-    let id_address = match args.next() {
-        Some(arg) => arg,
-        None => panic!("Didn't get an id_address string"),
-    };
+    let config_str = &read_to_string(opt.config_path).unwrap_or_else(|_| {
+        panic!(
+            "Error loading configuration file {}, please check the configuration!",
+            config_path_str
+        )
+    });
 
-    let contract_rpc_endpoint = match args.next() {
-        Some(arg) => arg,
-        None => panic!("Didn't get an adapter rpc endpoint string"),
-    };
+    let mut config: Config =
+        serde_yaml::from_str(config_str).expect("Error loading configuration file");
+    if config.data_path.is_none() {
+        config.data_path = Some(String::from("data.sqlite"));
+    }
 
-    let contract_type = match args.next() {
-        Some(arg) => arg,
-        None => panic!("Didn't get a contract type string"),
-    };
+    match opt.mode.as_str() {
+        "chain" => {
+            let data_path = PathBuf::from(config.data_path.unwrap());
 
-    let instruction = match args.next() {
-        Some(arg) => arg,
-        None => panic!("Didn't get an instruction string"),
-    };
+            let signer = build_signer_from_config(config.account).await?;
+
+            let db = if data_path.exists() {
+                let data_key = build_data_key_from_config(config.data_encryption, &signer).await?;
 
-    let id_address: Address = id_address.parse().unwrap();
+                Some(SqliteDB::build(data_path.as_os_str().to_str().unwrap(), &data_key).await?)
+            } else {
+                None
+            };
+
+            let mut main_chain_identity = GeneralChainIdentity::new(
+                0,
+                0,
+                signer,
+                config.provider_endpoint,
+                config.provider_transport.unwrap_or_default(),
+                config
+                    .provider_polling_interval_millis
+                    .unwrap_or(DEFAULT_POLLING_INTERVAL_MILLIS),
+                config.provider_failover.unwrap_or_default(),
+                config.transaction.unwrap_or_default(),
+                config
+                    .controller_address
+                    .parse()
+                    .expect("bad format of controller_address"),
+            );
+
+            // transactions are signed for the chain the provider is connected to
+            let chain_id = ChainProvider::new(&main_chain_identity)
+                .get_chain_id()
+                .await?;
+            main_chain_identity.set_chain_id(chain_id);
+
+            let mut adapter_chain_identities = BTreeMap::new();
+
+            for adapter in config.adapters {
+                let adapter_chain_identity = build_adapter_chain_identity(&adapter).await?;
 
-    if contract_type == "1" {
-        // Controller
-        let client = MockAdapterClient::new(contract_rpc_endpoint, id_address);
+                adapter_chain_identities.insert(adapter.id, adapter_chain_identity);
+            }
+
+            execute(opt.cmd, main_chain_identity, adapter_chain_identities, db).await?;
+        }
+        "mock" => {
+            let id_address = opt.id_address.unwrap();
 
-        if instruction == "set_initial_group" {
-            let group = client.get_group(1).await?;
+            let main_chain_identity =
+                MockChainIdentity::new(0, 0, id_address, config.provider_endpoint);
 
-            let group: ContractGroup = group.into();
+            let adapter_chain_identities = config
+                .adapters
+                .into_iter()
+                .map(|adapter| {
+                    (
+                        adapter.id,
+                        MockChainIdentity::new(
+                            adapter.id,
+                            adapter.id,
+                            id_address,
+                            adapter.endpoint,
+                        ),
+                    )
+                })
+                .collect();
 
-            let group_as_bytes = bincode::serialize(&group).unwrap();
+            execute(opt.cmd, main_chain_identity, adapter_chain_identities, None).await?;
+        }
+        _ => panic!("unimplemented mode"),
+    }
 
-            let adapter_rpc_endpoint = match args.next() {
-                Some(arg) => arg,
-                None => panic!("Didn't get a seed string"),
+    Ok(())
+}
+
+async fn execute<I: ChainIdentity + ControllerClientBuilder + AdapterClientBuilder>(
+    cmd: Command,
+    main_chain_identity: I,
+    adapter_chain_identities: BTreeMap<usize, I>,
+    db: Option<SqliteDB>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let id_address = main_chain_identity.get_id_address();
+
+    let adapter_client = |chain_id: usize| {
+        if chain_id == 0 {
+            return Ok(main_chain_identity.build_adapter_client(id_address));
+        }
+        adapter_chain_identities
+            .get(&chain_id)
+            .map(|identity| identity.build_adapter_client(id_address))
+            .ok_or(NodeError::InvalidChainId(chain_id))
+    };
+
+    match cmd {
+        Command::Register { dkg_public_key } => {
+            let id_public_key = match dkg_public_key {
+                Some(dkg_public_key) => hex::decode(dkg_public_key.trim_start_matches("0x"))?,
+                None => {
+                    let db = db.expect(
+                        "It seems there is no existing node database. Please run node-client in new-run mode first.",
+                    );
+
+                    let mut node_cache = db.get_node_info_client();
+
+                    node_cache.refresh_current_node_info().await?;
+
+                    bincode::serialize(node_cache.get_dkg_public_key()?)?
+                }
             };
 
-            let client = MockAdapterClient::new(adapter_rpc_endpoint, id_address);
+            let client = main_chain_identity.build_controller_client();
+
+            client.node_register(id_public_key).await?;
+
+            println!("node {:?} registered successfully", id_address);
+        }
+        Command::Activate => {
+            let client = main_chain_identity.build_controller_client();
+
+            client.node_activate().await?;
+
+            println!("node {:?} activated successfully", id_address);
+        }
+        Command::Quit => {
+            let client = main_chain_identity.build_controller_client();
+
+            client.node_quit().await?;
+
+            println!("node {:?} quitted successfully", id_address);
+        }
+        Command::Claim {
+            reward_address,
+            token_amount,
+            chain_id,
+        } => {
+            let client = adapter_client(chain_id)?;
+
+            client.claim(reward_address, token_amount).await?;
+
+            println!(
+                "claimed {} tokens to {:?} successfully",
+                token_amount, reward_address
+            );
+        }
+        Command::Status {
+            id_address: query_address,
+        } => {
+            let client = main_chain_identity.build_controller_client();
+
+            let node = client.get_node(query_address.unwrap_or(id_address)).await?;
 
-            if let Err(e) = client.set_initial_group(group_as_bytes).await {
-                println!("{:?}", e);
+            println!("{:#?}", node);
+        }
+        Command::Group { index, chain_id } => {
+            let client = adapter_client(chain_id)?;
+
+            let group = client.get_group(index).await?;
+
+            println!("{:#?}", group);
+        }
+        Command::Tasks { index, chain_id } => {
+            let client = adapter_client(chain_id)?;
+
+            let completed = client.get_signature_task_completion_state(index).await?;
+
+            println!(
+                "task {} completed on chain {}: {}",
+                index, chain_id, completed
+            );
+
+            if let Some(db) = db {
+                let randomness_tasks_cache = db.get_bls_tasks_client::<RandomnessTask>(chain_id);
+
+                if randomness_tasks_cache.contains(index).await? {
+                    let task = randomness_tasks_cache.get(index).await?;
+
+                    let handled = randomness_tasks_cache.is_handled(index).await?;
+
+                    println!("{:#?}", task);
+                    println!("task {} handled by this node: {}", task.index, handled);
+                } else {
+                    println!("task {} not found in node database", index);
+                }
             }
+        }
+        Command::SetInitialGroup { chain_id } => {
+            let main_client = main_chain_identity.build_adapter_client(id_address);
+
+            let group: ContractGroup = main_client.get_group(1).await?.into();
+
+            let group_as_bytes = bincode::serialize(&group)?;
+
+            let client = adapter_client(chain_id)?;
+
+            client.set_initial_group(group_as_bytes).await?;
 
             println!("set_initial_group successfully");
-        } else if instruction == "get_group" {
-            let group_index = match args.next() {
-                Some(arg) => arg,
-                None => panic!("Didn't get a seed string"),
-            };
+        }
+        Command::Rekey {
+            passphrase,
+            keystore,
+            keystore_password,
+        } => {
+            let db = db.expect("It seems there is no existing node database.");
 
-            let group = client
-                .get_group(group_index.parse::<usize>().unwrap())
-                .await?;
+            let data_key = build_data_key(DataEncryption {
+                passphrase,
+                keystore: keystore.map(|path| Keystore {
+                    path,
+                    password: keystore_password.unwrap_or_else(|| "env".to_string()),
+                }),
+            })?;
 
-            println!("{:?}", group);
+            db.rekey(&data_key).await?;
+
+            println!(
+                "node database rekeyed successfully, please update data_encryption in config.yml"
+            );
         }
-    } else if contract_type == "2" {
-        // Adapter
-        let client = MockAdapterClient::new(contract_rpc_endpoint, id_address);
-
-        if instruction == "get_group" {
-            let group_index = match args.next() {
-                Some(arg) => arg,
-                None => panic!("Didn't get a seed string"),
-            };
+        Command::ExportBackup {
+            path,
+            name,
+            password,
+        } => {
+            let db = db.expect("It seems there is no existing node database.");
 
-            let group = client
-                .get_group(group_index.parse::<usize>().unwrap())
-                .await?;
+            let password = read_backup_password(password)?;
+
+            let backup = db.export_backup().await?;
+
+            let groups = backup.groups.len();
+
+            let name = eth_keystore::encrypt_key(
+                &path,
+                &mut ethers::core::rand::thread_rng(),
+                bincode::serialize(&backup)?,
+                &password,
+                name.as_deref(),
+            )?;
 
-            println!("{:?}", group);
+            println!(
+                "DKG key pair and {} groups exported to {}",
+                groups,
+                path.join(name).display()
+            );
+        }
+        Command::ImportBackup { backup, password } => {
+            let db = db.expect(
+                "It seems there is no existing node database. Please run node-client in new-run mode first.",
+            );
+
+            let password = read_backup_password(password)?;
+
+            let backup: DataBackup =
+                bincode::deserialize(&eth_keystore::decrypt_key(&backup, &password)?)?;
+
+            if backup.id_address.parse::<Address>()? != id_address {
+                println!(
+                    "WARNING: the backup is made by node {}, while the current account is {:?}",
+                    backup.id_address, id_address
+                );
+            }
+
+            let groups = backup.groups.len();
+
+            db.import_backup(backup).await?;
+
+            println!(
+                "DKG key pair and {} groups imported successfully, please restart node-client in re-run mode",
+                groups
+            );
         }
     }
 
     Ok(())
 }
+
+fn read_backup_password(password: Option<String>) -> Result<String, env::VarError> {
+    match password {
+        Some(password) => Ok(password),
+        None => env::var("ARPA_NODE_BACKUP_PASSWORD"),
+    }
+}
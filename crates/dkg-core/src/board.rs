@@ -2,7 +2,9 @@
 ///
 /// A board is where DKG participants publish their data for the corresponding DKG
 /// phase.
-use super::primitives::types::{BundledJustification, BundledResponses, BundledShares};
+use super::primitives::types::{
+    BundledExtraction, BundledJustification, BundledResponses, BundledShares,
+};
 use async_trait::async_trait;
 use bincode::serialize_into;
 use std::io::Write;
@@ -24,7 +26,10 @@ where
         C: 'async_trait;
 
     /// Publishes the responses to the board
-    async fn publish_responses(&mut self, responses: BundledResponses) -> Result<(), Self::Error>
+    async fn publish_responses(
+        &mut self,
+        responses: BundledResponses<C>,
+    ) -> Result<(), Self::Error>
     where
         C: 'async_trait;
 
@@ -35,6 +40,14 @@ where
     ) -> Result<(), Self::Error>
     where
         C: 'async_trait;
+
+    /// Publishes the extraction to the board
+    async fn publish_extraction(
+        &mut self,
+        extraction: BundledExtraction<C>,
+    ) -> Result<(), Self::Error>
+    where
+        C: 'async_trait;
 }
 
 // Board implementation for all `Write` implementers, leveraging serde/bincode
@@ -54,7 +67,7 @@ where
         serialize_into(self, &shares)
     }
 
-    async fn publish_responses(&mut self, responses: BundledResponses) -> Result<(), Self::Error>
+    async fn publish_responses(&mut self, responses: BundledResponses<C>) -> Result<(), Self::Error>
     where
         C: 'async_trait,
     {
@@ -70,4 +83,14 @@ where
     {
         serialize_into(self, &justifications)
     }
+
+    async fn publish_extraction(
+        &mut self,
+        extraction: BundledExtraction<C>,
+    ) -> Result<(), Self::Error>
+    where
+        C: 'async_trait,
+    {
+        serialize_into(self, &extraction)
+    }
 }
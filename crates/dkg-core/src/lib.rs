@@ -6,6 +6,17 @@
 //! The implementation is a state machine which has Phases 0 to 3. Phase 3 is only reachable if any of the
 //! n parties does not publish its shares in the first phase. If less than t parties participate in any stage,
 //! the DKG fails.
+//!
+//! Alternatively, the `pvss` module provides a publicly verifiable, non-interactive DKG in which
+//! every party publishes a single dealing, so that the group forms in one round and anyone can
+//! reject an invalid dealing without interacting with its dealer.
+//!
+//! The `gennaro` module runs the same phases with Pedersen commitments instead, followed by Phases 4
+//! and 5 in which the distributed public key is extracted once the qualified parties are fixed, so
+//! that no party can bias the distributed public key by getting itself disqualified.
+//!
+//! Every output carries a `DKGTranscript` of the final statuses of the shares and of the valid
+//! complaints, from which each disqualified party is blamed with the reasons of its complaints.
 
 /// Board trait and implementations for publishing data from each DKG phase
 pub mod board;
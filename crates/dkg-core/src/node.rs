@@ -7,8 +7,10 @@ use threshold_bls::group::Curve;
 use super::{
     board::BoardPublisher,
     primitives::{
-        phases::{Phase0, Phase1, Phase2, Phase3},
-        types::{BundledJustification, BundledResponses, BundledShares, DKGOutput},
+        phases::{Phase0, Phase1, Phase2, Phase3, Phase5},
+        types::{
+            BundledExtraction, BundledJustification, BundledResponses, BundledShares, DKGOutput,
+        },
         DKGError,
     },
 };
@@ -27,8 +29,9 @@ pub enum NodeError {
 /// Phase2 can either be successful or require going to Phase 3.
 #[derive(Clone, Debug)]
 pub enum Phase2Result<C: Curve, P: Phase3<C>> {
-    /// The final DKG output
-    Output(DKGOutput<C>),
+    /// The output of the DKG, final unless the protocol extracts the public key
+    /// in further phases
+    Output(P::Output),
     /// Indicates that Phase 2 failed and that the protocol must proceed to Phase 3
     GoToPhase3(P),
 }
@@ -61,12 +64,11 @@ where
 {
     type Next = P::Next;
 
-    async fn run(mut self, board: &mut B, rng: F) -> NodeResult<Self::Next>
+    async fn run(self, board: &mut B, rng: F) -> NodeResult<Self::Next>
     where
         C: 'async_trait,
         F: 'async_trait,
     {
-        self.set_rpc_endpoint();
         let (next, shares) = self.encrypt_shares(rng)?;
         if let Some(sh) = shares {
             board
@@ -97,7 +99,7 @@ where
     where
         C: 'async_trait,
     {
-        let (next, bundle) = self.process_shares(shares, true)?;
+        let (next, bundle) = self.process_shares(shares, true, &mut rand::thread_rng())?;
 
         if let Some(bundle) = bundle {
             board
@@ -111,19 +113,20 @@ where
 }
 
 #[async_trait]
-impl<C, B, P> DKGPhase<C, B, &[BundledResponses]> for P
+impl<C, B, P> DKGPhase<C, B, &[BundledResponses<C>]> for P
 where
     C: Curve,
     B: BoardPublisher<C> + Send,
     P: Phase2<C> + Send,
     P::Next: Send,
+    P::Output: Send,
 {
     type Next = Phase2Result<C, P::Next>;
 
     async fn run(
         self,
         board: &mut B,
-        responses: &'async_trait [BundledResponses],
+        responses: &'async_trait [BundledResponses<C>],
     ) -> NodeResult<Self::Next>
     where
         C: 'async_trait,
@@ -159,8 +162,9 @@ where
     C: Curve,
     B: BoardPublisher<C> + Send,
     P: Phase3<C> + Send,
+    P::Output: Send,
 {
-    type Next = DKGOutput<C>;
+    type Next = P::Output;
 
     async fn run(
         self,
@@ -174,6 +178,27 @@ where
     }
 }
 
+#[async_trait]
+impl<C, B, P> DKGPhase<C, B, &[BundledExtraction<C>]> for P
+where
+    C: Curve,
+    B: BoardPublisher<C> + Send,
+    P: Phase5<C> + Send,
+{
+    type Next = DKGOutput<C>;
+
+    async fn run(
+        self,
+        _: &mut B,
+        extractions: &'async_trait [BundledExtraction<C>],
+    ) -> NodeResult<Self::Next>
+    where
+        C: 'async_trait,
+    {
+        Ok(self.process_extractions(extractions)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -436,9 +461,7 @@ mod tests {
         // Create the Phase 0 for each participant
         let phase0s = keypairs
             .iter()
-            .map(|(private, _)| {
-                joint_feldman::DKG::new(private.clone(), String::from(""), group.clone()).unwrap()
-            })
+            .map(|(private, _)| joint_feldman::DKG::new(private.clone(), group.clone()).unwrap())
             .collect::<Vec<_>>();
 
         // Create the board
@@ -1,6 +1,8 @@
 use crate::primitives::{
     group::Group,
+    nizk::{mul, DLEQProof},
     status::{Status, StatusMatrix},
+    transcript::{Complaint, ComplaintReason},
     types::*,
     DKGError, DKGResult, ShareError,
 };
@@ -8,21 +10,23 @@ use crate::primitives::{
 use rand_core::RngCore;
 use std::collections::HashMap;
 use threshold_bls::{
-    ecies,
+    ecies::{self, EciesCipher},
     group::{Curve, Element},
     poly::{Idx, PrivatePoly, PublicPoly},
 };
 
-pub type ShareInfo<C> = HashMap<Idx, (<C as Curve>::Scalar, String)>;
+pub type ShareInfo<C> = HashMap<Idx, <C as Curve>::Scalar>;
 pub type PublicInfo<C> = HashMap<Idx, PublicPoly<C>>;
+pub type DealInfo<C> = HashMap<Idx, BundledShares<C>>;
 
+/// Decrypts the share and checks it against the dealer's public polynomial.
 pub fn decrypt_and_check_share<C: Curve>(
     private_key: &C::Scalar,
     own_idx: Idx,
     dealer_idx: Idx,
     public: &PublicPoly<C>,
     share: &EncryptedShare<C>,
-) -> Result<(C::Scalar, String), DKGError> {
+) -> Result<C::Scalar, DKGError> {
     let buff = ecies::decrypt::<C>(private_key, &share.secret).map_err(|err| {
         println!("ERROR {:?}", err);
         ShareError::InvalidCiphertext(dealer_idx, err)
@@ -35,28 +39,119 @@ pub fn decrypt_and_check_share<C: Curve>(
         return Err(ShareError::InvalidShare(dealer_idx).into());
     }
 
-    let rpc_endpoint_buff =
-        ecies::decrypt::<C>(private_key, &share.rpc_endpoint_secret).map_err(|err| {
-            println!("ERROR {:?}", err);
-            ShareError::InvalidCiphertext(dealer_idx, err)
-        })?;
+    Ok(clear_share)
+}
+
+/// Checks the plaintext of a cipher dealt to the holder at the given index
+/// against the dealer's public polynomial.
+pub fn plain_share_correct<C: Curve>(idx: Idx, public: &PublicPoly<C>, plaintext: &[u8]) -> bool {
+    bincode::deserialize::<C::Scalar>(plaintext)
+        .map(|share| share_correct::<C>(idx, &share, public))
+        .unwrap_or(false)
+}
+
+/// Collects the bundles of the given dealers whose public polynomial has the
+/// right degree, including our own, against which complaints are checked.
+pub fn collect_deals<C: Curve>(
+    dealers: &Group<C>,
+    share_holders: &Group<C>,
+    bundles: &[BundledShares<C>],
+) -> DealInfo<C> {
+    bundles
+        .iter()
+        .filter(|b| dealers.contains_index(b.dealer_idx))
+        .filter(|b| b.public.degree() == share_holders.threshold - 1)
+        .map(|b| (b.dealer_idx, b.clone()))
+        .collect()
+}
+
+/// Opens the cipher dealt to us by revealing its Diffie-Hellman key, with the
+/// proof that it was computed with our private key.
+pub fn prove_complaint<C: Curve, R: RngCore>(
+    private_key: &C::Scalar,
+    cipher: &EciesCipher<C>,
+    rng: &mut R,
+) -> ComplaintProof<C> {
+    let g = C::Point::one();
+    let public_key = mul::<C>(&g, private_key);
+    let dh_key = mul::<C>(cipher.ephemeral(), private_key);
+    let proof = DLEQProof::prove(
+        private_key,
+        &g,
+        &public_key,
+        cipher.ephemeral(),
+        &dh_key,
+        rng,
+    );
+
+    ComplaintProof { dh_key, proof }
+}
+
+/// Checks that the complaint of a holder against a dealer is well-founded and
+/// returns its reason: either the dealer did not deal a cipher to the holder,
+/// or the proof opens the cipher and its plaintext is not a correct share.
+pub fn verify_complaint<C, F>(
+    dealer_idx: Idx,
+    holder_idx: Idx,
+    holder_key: &C::Point,
+    deals: &DealInfo<C>,
+    proof: Option<&ComplaintProof<C>>,
+    plaintext_correct: F,
+) -> Option<ComplaintReason>
+where
+    C: Curve,
+    F: Fn(Idx, &PublicPoly<C>, &[u8]) -> bool,
+{
+    let deal = deals.get(&dealer_idx).and_then(|bundle| {
+        bundle
+            .shares
+            .iter()
+            .find(|s| s.share_idx == holder_idx)
+            .map(|share| (bundle, share))
+    });
+
+    let (bundle, share) = match deal {
+        Some(deal) => deal,
+        // nothing was dealt to the holder, which everyone can see
+        None => return Some(ComplaintReason::MissingShare),
+    };
 
-    let clear_rpc_endpoint = bincode::deserialize(&rpc_endpoint_buff)?;
+    let proof = proof?;
 
-    Ok((clear_share, clear_rpc_endpoint))
+    let g = C::Point::one();
+    if !proof
+        .proof
+        .verify(&g, holder_key, share.secret.ephemeral(), &proof.dh_key)
+    {
+        return None;
+    }
+
+    match ecies::decrypt_with_dh::<C>(&proof.dh_key, &share.secret) {
+        Ok(plaintext) if plaintext_correct(holder_idx, &bundle.public, &plaintext) => None,
+        Ok(_) => Some(ComplaintReason::InvalidShare),
+        Err(_) => Some(ComplaintReason::InvalidCiphertext),
+    }
 }
 
-/// set_statuses set the status of the given responses on the status matrix.
-pub fn set_statuses<C: Curve>(
+/// set_statuses set the status of the given responses on the status matrix,
+/// and returns the valid complaints of all share holders, ours included.
+///
+/// Complaints which do not prove that the dealer's cipher was bad are
+/// ignored, so that a share holder can not force a dealer to reveal its share.
+pub fn set_statuses<C, F>(
     holder_idx: Idx,
     dealers: &Group<C>,
     holders: &Group<C>,
     statuses: &mut StatusMatrix,
-    responses: &[BundledResponses],
-) {
-    // makes sure the API doesn't take into account our own responses!
-    let not_from_me = responses.iter().filter(|r| r.share_idx != holder_idx);
-    let valid_idx = not_from_me.filter(|r| {
+    deals: &DealInfo<C>,
+    responses: &[BundledResponses<C>],
+    plaintext_correct: F,
+) -> Vec<Complaint>
+where
+    C: Curve,
+    F: Fn(Idx, &PublicPoly<C>, &[u8]) -> bool,
+{
+    let valid_idx = responses.iter().filter(|r| {
         let good_holder = holders.contains_index(r.share_idx);
         let good_dealers = !r
             .responses
@@ -65,13 +160,43 @@ pub fn set_statuses<C: Curve>(
         good_dealers && good_holder
     });
 
+    let mut complaints = Vec::new();
     for bundle in valid_idx {
         let holder_index = bundle.share_idx;
+        // unwrap since the holder index was checked above
+        let holder_key = holders
+            .nodes
+            .iter()
+            .find(|n| n.id() == holder_index)
+            .unwrap()
+            .key();
         for response in bundle.responses.iter() {
             let dealer_index = response.dealer_idx;
-            statuses.set(dealer_index, holder_index, response.status);
+            if !response.status.is_success() {
+                match verify_complaint(
+                    dealer_index,
+                    holder_index,
+                    holder_key,
+                    deals,
+                    response.proof.as_ref(),
+                    &plaintext_correct,
+                ) {
+                    Some(reason) => complaints.push(Complaint {
+                        dealer_idx: dealer_index,
+                        holder_idx: holder_index,
+                        reason,
+                    }),
+                    None => continue,
+                }
+            }
+            // makes sure the API doesn't take into account our own responses!
+            if holder_index != holder_idx {
+                statuses.set(dealer_index, holder_index, response.status);
+            }
         }
     }
+
+    complaints
 }
 
 /// Checks if the commitment to the share corresponds to the public polynomial's
@@ -89,7 +214,6 @@ pub fn create_share_bundle<C: Curve, R: RngCore>(
     dealer_idx: Idx,
     secret: &PrivatePoly<C>,
     public: &PublicPoly<C>,
-    node_rpc_endpoint: &str,
     group: &Group<C>,
     mut rng: R,
 ) -> DKGResult<BundledShares<C>> {
@@ -107,15 +231,10 @@ pub fn create_share_bundle<C: Curve, R: RngCore>(
             // encrypt it
             let cipher = ecies::encrypt::<C, _>(n.key(), &buff, &mut rng);
 
-            let rpc_endpoint_buff = bincode::serialize(node_rpc_endpoint)?;
-
-            let rpc_endpoint_secret = ecies::encrypt::<C, _>(n.key(), &rpc_endpoint_buff, &mut rng);
-
             // save the share
             Ok(EncryptedShare {
                 share_idx: n.id(),
                 secret: cipher,
-                rpc_endpoint_secret,
             })
         })
         .collect::<Result<Vec<_>, DKGError>>()?;
@@ -128,18 +247,36 @@ pub fn create_share_bundle<C: Curve, R: RngCore>(
     })
 }
 
-pub fn compute_bundle_response(
+/// Computes the responses of the share holder, proving each complaint against a
+/// dealer which dealt it a cipher.
+pub fn compute_bundle_response<C: Curve, R: RngCore>(
     my_idx: Idx,
+    my_private: &C::Scalar,
     statuses: &StatusMatrix,
+    deals: &DealInfo<C>,
     publish_all: bool,
-) -> Option<BundledResponses> {
+    rng: &mut R,
+) -> Option<BundledResponses<C>> {
     let responses = statuses
         .get_for_share(my_idx)
         .into_iter()
         .enumerate()
-        .map(|(i, b)| Response {
-            dealer_idx: i as Idx,
-            status: Status::from(b),
+        .map(|(i, b)| {
+            let dealer_idx = i as Idx;
+            let status = Status::from(b);
+            let proof = if status.is_success() {
+                None
+            } else {
+                deals
+                    .get(&dealer_idx)
+                    .and_then(|bundle| bundle.shares.iter().find(|s| s.share_idx == my_idx))
+                    .map(|share| prove_complaint(my_private, &share.secret, rng))
+            };
+            Response {
+                dealer_idx,
+                status,
+                proof,
+            }
         });
 
     let responses = if !publish_all {
@@ -162,7 +299,8 @@ pub fn compute_bundle_response(
 }
 
 /// Processes the shares and returns the private share of the user and a public
-/// polynomial, as well as the status matrix of the protocol.
+/// polynomial, as well as the deals to check the complaints against and the
+/// status matrix of the protocol.
 ///
 /// Depending on which variant of the DKG protocol is used, the status
 /// matrix responses which correspond to our index may be used in the
@@ -172,13 +310,14 @@ pub fn compute_bundle_response(
 /// not good unless you hear otherwise.  - Broadcast only responses which
 /// are complaints: You assume that shares of other nodes are good unless
 /// you hear otherwise.
+#[allow(clippy::type_complexity)]
 pub fn process_shares_get_all<C: Curve>(
     dealers: &Group<C>,
     share_holders: &Group<C>,
     my_idx: Idx,
     my_private: &C::Scalar,
     bundles: &[BundledShares<C>],
-) -> DKGResult<(ShareInfo<C>, PublicInfo<C>, StatusMatrix)> {
+) -> DKGResult<(ShareInfo<C>, PublicInfo<C>, DealInfo<C>, StatusMatrix)> {
     // there are "old_n" dealers and for each dealer, "new_n" share holders
     let mut statuses = StatusMatrix::new(dealers.len(), share_holders.len(), Status::Success);
 
@@ -225,17 +364,16 @@ pub fn process_shares_get_all<C: Curve>(
             .map(|share| (bundle.dealer_idx, share))
             .ok()
         })
-        .fold(
-            ShareInfo::<C>::new(),
-            |mut acc, (didx, (share, rpc_endpoint))| {
-                // println!(" -- got new share from {}", didx);
-                statuses.set(didx, my_idx, Status::Success);
-                acc.insert(didx, (share, rpc_endpoint));
-                acc
-            },
-        );
-
-    Ok((valid_shares, publics, statuses))
+        .fold(ShareInfo::<C>::new(), |mut acc, (didx, share)| {
+            // println!(" -- got new share from {}", didx);
+            statuses.set(didx, my_idx, Status::Success);
+            acc.insert(didx, share);
+            acc
+        });
+
+    let deals = collect_deals(dealers, share_holders, bundles);
+
+    Ok((valid_shares, publics, deals, statuses))
 }
 
 pub fn get_justification<C: Curve>(
@@ -259,6 +397,7 @@ pub fn get_justification<C: Curve>(
                     Some(Justification {
                         share_idx: id,
                         share: secret.eval(id).value,
+                        blinding: None,
                     })
                 } else {
                     None
@@ -303,10 +442,7 @@ pub fn internal_process_justifications<C: Curve>(
                     // justification is valid, we mark it off from our matrix
                     statuses.set(bundle.dealer_idx, justification.share_idx, Status::Success);
                     if holder_idx == justification.share_idx {
-                        valid_shares.insert(
-                            bundle.dealer_idx,
-                            (justification.share.clone(), "".to_string()),
-                        );
+                        valid_shares.insert(bundle.dealer_idx, justification.share.clone());
                     }
                 })
         });
@@ -320,7 +456,7 @@ pub mod tests {
     use rand::thread_rng;
     use threshold_bls::poly::{Eval, Poly, PolyError};
 
-    fn reconstruct<C: Curve>(
+    pub fn reconstruct<C: Curve>(
         thr: usize,
         shares: &[DKGOutput<C>],
     ) -> Result<PrivatePoly<C>, PolyError> {
@@ -359,7 +495,7 @@ pub mod tests {
         s
     }
 
-    pub fn id_resp(r: Vec<BundledResponses>) -> Vec<BundledResponses> {
+    pub fn id_resp<C: Curve>(r: Vec<BundledResponses<C>>) -> Vec<BundledResponses<C>> {
         r
     }
 
@@ -374,17 +510,30 @@ pub mod tests {
         o
     }
 
+    /// Finishes the protocol from the outputs of its Phase 2 or Phase 3, for
+    /// the DKGs which run more phases after the qualified dealers are fixed.
+    pub trait Finish<C: Curve>: Sized {
+        fn finish(outputs: Vec<Self>) -> Vec<DKGOutput<C>>;
+    }
+
+    impl<C: Curve> Finish<C> for DKGOutput<C> {
+        fn finish(outputs: Vec<Self>) -> Vec<DKGOutput<C>> {
+            outputs
+        }
+    }
+
     pub fn invalid_shares<C, P>(
         thr: usize,
         dkgs: Vec<P>,
         map_share: impl Fn(Vec<BundledShares<C>>) -> Vec<BundledShares<C>>,
-        map_resp: impl Fn(Vec<BundledResponses>) -> Vec<BundledResponses>,
+        map_resp: impl Fn(Vec<BundledResponses<C>>) -> Vec<BundledResponses<C>>,
         map_just: impl Fn(Vec<BundledJustification<C>>) -> Vec<BundledJustification<C>>,
         map_out: impl Fn(Vec<DKGOutput<C>>) -> Vec<DKGOutput<C>>,
     ) -> DKGResult<PublicPoly<C>>
     where
         C: Curve,
         P: Phase0<C>,
+        <<P::Next as Phase1<C>>::Next as Phase2<C>>::Output: Finish<C>,
     {
         let n = dkgs.len();
         let mut all_shares = Vec::with_capacity(n);
@@ -405,7 +554,9 @@ pub mod tests {
         let dkgs: Vec<_> = dkgs
             .into_iter()
             .map(|dkg| {
-                let (ndkg, bundle_o) = dkg.process_shares(&all_shares, false).unwrap();
+                let (ndkg, bundle_o) = dkg
+                    .process_shares(&all_shares, false, &mut thread_rng())
+                    .unwrap();
                 if let Some(bundle) = bundle_o {
                     response_bundles.push(bundle);
                 }
@@ -439,6 +590,7 @@ pub mod tests {
             .into_iter()
             .map(|dkg| dkg.process_justifications(&justifications))
             .collect::<Result<Vec<_>, DKGError>>()?;
+        let outputs = Finish::finish(outputs);
 
         let outputs = map_out(outputs);
 
@@ -456,6 +608,7 @@ pub mod tests {
     where
         C: Curve,
         P: Phase0<C>,
+        <<P::Next as Phase1<C>>::Next as Phase2<C>>::Output: Finish<C>,
     {
         let n = dkgs.len();
 
@@ -476,7 +629,10 @@ pub mod tests {
         let response_bundles = Vec::with_capacity(n);
         let dkgs: Vec<_> = dkgs
             .into_iter()
-            .filter_map(|dkg| dkg.process_shares(&all_shares, false).ok())
+            .filter_map(|dkg| {
+                dkg.process_shares(&all_shares, false, &mut thread_rng())
+                    .ok()
+            })
             .map(|(ndkg, bundle_o)| {
                 assert!(
                     bundle_o.is_none(),
@@ -491,6 +647,7 @@ pub mod tests {
             .into_iter()
             .map(|dkg| dkg.process_responses(&response_bundles).expect("wholo"))
             .collect::<Vec<_>>();
+        let outputs = Finish::finish(outputs);
 
         // Reconstruct the threshold private polynomial from all the outputs
         let recovered_private = reconstruct(nthr, &outputs).unwrap();
@@ -508,4 +665,91 @@ pub mod tests {
         }
         (outputs, recovered_public)
     }
+
+    /// Runs the DKG with complaints against an honest dealer which prove
+    /// nothing: one without a proof, one opening a correct share and one
+    /// opening the cipher of another holder. Everyone ignores them and the DKG
+    /// finishes without justifications.
+    pub fn unfounded_complaints<C, P>(
+        nthr: usize,
+        privs: &[C::Scalar],
+        dkgs: Vec<P>,
+    ) -> PublicPoly<C>
+    where
+        C: Curve,
+        P: Phase0<C>,
+        <<P::Next as Phase1<C>>::Next as Phase2<C>>::Output: Finish<C>,
+    {
+        let mut all_shares = Vec::with_capacity(dkgs.len());
+        let dkgs: Vec<_> = dkgs
+            .into_iter()
+            .map(|dkg| {
+                let (ndkg, shares) = dkg.encrypt_shares(thread_rng).unwrap();
+                if let Some(sh) = shares {
+                    all_shares.push(sh);
+                }
+                ndkg
+            })
+            .collect();
+
+        let dkgs: Vec<_> = dkgs
+            .into_iter()
+            .map(|dkg| {
+                let (ndkg, bundle_o) = dkg
+                    .process_shares(&all_shares, false, &mut thread_rng())
+                    .unwrap();
+                assert!(
+                    bundle_o.is_none(),
+                    "honest shares should not get complaints"
+                );
+                ndkg
+            })
+            .collect();
+
+        let cipher = |holder_idx: Idx| {
+            all_shares[0]
+                .shares
+                .iter()
+                .find(|s| s.share_idx == holder_idx)
+                .map(|s| s.secret.clone())
+                .unwrap()
+        };
+        let complaint = |holder_idx: Idx, proof: Option<ComplaintProof<C>>| BundledResponses {
+            share_idx: holder_idx,
+            responses: vec![Response {
+                dealer_idx: all_shares[0].dealer_idx,
+                status: Status::Complaint,
+                proof,
+            }],
+        };
+        let responses = vec![
+            complaint(1, None),
+            complaint(
+                2,
+                Some(prove_complaint(&privs[2], &cipher(2), &mut thread_rng())),
+            ),
+            complaint(
+                3,
+                Some(prove_complaint(&privs[4], &cipher(3), &mut thread_rng())),
+            ),
+        ];
+
+        let outputs = dkgs
+            .into_iter()
+            .map(|dkg| {
+                dkg.process_responses(&responses)
+                    .expect("unfounded complaints should be ignored")
+            })
+            .collect::<Vec<_>>();
+        let outputs = Finish::finish(outputs);
+
+        let recovered_public = reconstruct(nthr, &outputs).unwrap().commit::<C::Point>();
+        for out in outputs.iter() {
+            assert_eq!(out.public.public_key(), recovered_public.public_key());
+            assert_eq!(out.qual.len(), outputs.len());
+            // the unfounded complaints are not part of the transcript
+            assert!(out.transcript.complaints.is_empty());
+        }
+        recovered_public
+    }
 }
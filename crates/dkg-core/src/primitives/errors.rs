@@ -28,6 +28,12 @@ pub enum DKGError {
     #[error("only has {0}/{1} required justifications")]
     NotEnoughJustifications(usize, usize),
 
+    /// NotEnoughExtractions is raised when less than a threshold of share
+    /// holders published a valid extraction of their share, so that the
+    /// distributed public key can not be recovered.
+    #[error("only has {0}/{1} valid extractions")]
+    NotEnoughExtractions(usize, usize),
+
     /// Rejected is raised when the participant is rejected from the final
     /// output
     #[error("this participant is rejected from the qualified set")]
@@ -75,4 +81,20 @@ pub enum ShareError {
     /// second is the degree it should be,i.e. `threshold - 1`.
     #[error("[dealer: {0}] polynomial does not have the correct degree, got: {1}, expected {2}")]
     InvalidPublicPolynomial(Idx, usize, usize),
+    /// InvalidDealer is raised when the dealer of a dealing is not in the group.
+    #[error("[dealer: {0}] dealer is not in the group")]
+    InvalidDealer(Idx),
+    /// InvalidShareCount is raised when a dealing does not hold exactly one
+    /// share for every participant. The fields are the number of shares and
+    /// the size of the group.
+    #[error("[dealer: {0}] dealing has {1} shares for a group of {2}")]
+    InvalidShareCount(Idx, usize, usize),
+    /// MissingShare is raised when a dealing has no share or more than one
+    /// share for the given participant.
+    #[error("[dealer: {0}] dealing has no unique share for participant {1}")]
+    MissingShare(Idx, Idx),
+    /// InvalidEncryptionProof is raised when the encrypted share of the given
+    /// participant is not proven to be the evaluation of the public polynomial.
+    #[error("[dealer: {0}] encryption of the share for participant {1} is not proven correct")]
+    InvalidEncryptionProof(Idx, Idx),
 }
@@ -0,0 +1,844 @@
+//! Implements the Distributed Key Generation protocol from
+//! [Gennaro et al.](https://link.springer.com/article/10.1007/s00145-006-0347-3).
+//!
+//! In the Joint-Feldman DKG, the dealers publish the commitments of their
+//! polynomials in the group generator from the start, so that a dealer seeing
+//! the commitments of the others can bias the distributed public key by
+//! getting itself disqualified on purpose. Here the dealers commit to their
+//! polynomials by Pedersen commitments, which hide the secrets, and the set of
+//! qualified dealers is fixed by the shares, complaints and justifications
+//! first, exactly as in the Joint-Feldman DKG.
+//!
+//! Only then, every share holder extracts the public evaluation of its
+//! final share, with the proof that it is the part of its Pedersen commitment
+//! in the group generator, and the distributed public polynomial is recovered
+//! from any threshold of valid extractions. Withholding an extraction no longer
+//! influences the distributed key, which runs in two more phases than the
+//! Joint-Feldman DKG.
+use super::{
+    common::{collect_deals, compute_bundle_response, set_statuses, DealInfo, PublicInfo},
+    nizk::{mul, sub, DLogPairProof},
+};
+use crate::primitives::{
+    group::Group,
+    phases::{Phase0, Phase1, Phase2, Phase3, Phase4, Phase5},
+    status::{Status, StatusMatrix},
+    transcript::{Complaint, DKGTranscript},
+    types::*,
+    DKGError, DKGResult, ShareError,
+};
+
+use threshold_bls::{
+    ecies,
+    group::{Curve, Element, Point},
+    poly::{Eval, Idx, Poly, PrivatePoly, PublicPoly},
+    sig::Share,
+};
+
+use rand_core::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The seed of the second generator of the Pedersen commitments
+const PEDERSEN_GENERATOR_SEED: &[u8] = b"dkg-core-gennaro-pedersen-generator";
+
+/// The shares and blinding shares received from each dealer
+type PedersenShares<C> = BTreeMap<Idx, (<C as Curve>::Scalar, <C as Curve>::Scalar)>;
+
+/// Returns the second generator of the Pedersen commitments, whose discrete
+/// logarithm in the group generator is unknown to anyone.
+pub fn pedersen_generator<C: Curve>() -> C::Point {
+    let mut h = C::Point::new();
+    h.map(PEDERSEN_GENERATOR_SEED)
+        .expect("could not map the seed of the Pedersen generator to the group");
+    h
+}
+
+/// Commits to the coefficients of the secret polynomial blinded by the
+/// coefficients of the blinding polynomial.
+fn pedersen_commit<C: Curve>(
+    secret: &PrivatePoly<C>,
+    blinding: &PrivatePoly<C>,
+    h: &C::Point,
+) -> PublicPoly<C> {
+    let mut commitment = secret.commit::<C::Point>();
+    let blinding: Vec<C::Scalar> = blinding.clone().into();
+    let blinding = blinding
+        .iter()
+        .map(|coeff| mul::<C>(h, coeff))
+        .collect::<Vec<_>>();
+    commitment.add(&PublicPoly::<C>::from(blinding));
+    commitment
+}
+
+/// Checks if the Pedersen commitment to the share and blinding share
+/// corresponds to the commitment polynomial evaluated at the given point.
+pub fn pedersen_share_correct<C: Curve>(
+    idx: Idx,
+    share: &C::Scalar,
+    blinding: &C::Scalar,
+    commitment: &PublicPoly<C>,
+    h: &C::Point,
+) -> bool {
+    let mut commit = mul::<C>(&C::Point::one(), share);
+    commit.add(&mul::<C>(h, blinding));
+    commitment.eval(idx).value == commit
+}
+
+fn decrypt_and_check_pedersen_share<C: Curve>(
+    private_key: &C::Scalar,
+    own_idx: Idx,
+    dealer_idx: Idx,
+    commitment: &PublicPoly<C>,
+    share: &EncryptedShare<C>,
+    h: &C::Point,
+) -> DKGResult<(C::Scalar, C::Scalar)> {
+    let buff = ecies::decrypt::<C>(private_key, &share.secret)
+        .map_err(|err| ShareError::InvalidCiphertext(dealer_idx, err))?;
+
+    let (clear_share, clear_blinding): (C::Scalar, C::Scalar) = bincode::deserialize(&buff)?;
+
+    if !pedersen_share_correct::<C>(own_idx, &clear_share, &clear_blinding, commitment, h) {
+        return Err(ShareError::InvalidShare(dealer_idx).into());
+    }
+
+    Ok((clear_share, clear_blinding))
+}
+
+/// Checks the plaintext of a cipher dealt to the holder at the given index
+/// against the dealer's Pedersen commitment.
+fn plain_pedersen_share_correct<C: Curve>(
+    idx: Idx,
+    commitment: &PublicPoly<C>,
+    plaintext: &[u8],
+    h: &C::Point,
+) -> bool {
+    bincode::deserialize::<(C::Scalar, C::Scalar)>(plaintext)
+        .map(|(share, blinding)| pedersen_share_correct::<C>(idx, &share, &blinding, commitment, h))
+        .unwrap_or(false)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct GDKGInfo<C: Curve> {
+    private_key: C::Scalar,
+    pub public_key: C::Point,
+    index: Idx,
+    group: Group<C>,
+    secret: PrivatePoly<C>,
+    blinding: PrivatePoly<C>,
+    commitment: PublicPoly<C>,
+}
+
+impl<C: Curve> GDKGInfo<C> {
+    /// Returns the number of nodes participating in the group for this DKG
+    fn n(&self) -> usize {
+        self.group.len()
+    }
+
+    /// Returns the threshold of the group for this DKG
+    fn thr(&self) -> usize {
+        self.group.threshold
+    }
+}
+
+/// GDKG is the struct containing the logic to run the Distributed Key Generation
+/// protocol from [Gennaro et al.](https://link.springer.com/article/10.1007/s00145-006-0347-3).
+///
+/// The protocol runs the phases of the Joint-Feldman DKG with Pedersen
+/// commitments, followed by the extraction of the distributed public key as
+/// described in the module documentation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct GDKG<C: Curve> {
+    /// Metadata about the DKG
+    pub info: GDKGInfo<C>,
+}
+
+impl<C: Curve> GDKG<C> {
+    /// Creates a new DKG instance from the provided private key and group.
+    ///
+    /// The private key must be part of the group, otherwise this will return an error.
+    pub fn new(private_key: C::Scalar, group: Group<C>) -> Result<GDKG<C>, DKGError> {
+        use rand::prelude::*;
+        Self::new_rand(private_key, group, &mut thread_rng())
+    }
+
+    /// Creates a new DKG instance from the provided private key, group and RNG.
+    ///
+    /// The private key must be part of the group, otherwise this will return an error.
+    pub fn new_rand<R: RngCore>(
+        private_key: C::Scalar,
+        group: Group<C>,
+        rng: &mut R,
+    ) -> Result<GDKG<C>, DKGError> {
+        // get the public key
+        let mut public_key = C::Point::one();
+        public_key.mul(&private_key);
+
+        // check if the public key is part of the group
+        let index = group
+            .index(&public_key)
+            .ok_or(DKGError::PublicKeyNotFound)?;
+
+        // Generate a secret and a blinding polynomial and commit to them
+        let secret = PrivatePoly::<C>::new_from(group.threshold - 1, rng);
+        let blinding = PrivatePoly::<C>::new_from(group.threshold - 1, rng);
+        let commitment = pedersen_commit::<C>(&secret, &blinding, &pedersen_generator::<C>());
+
+        let info = GDKGInfo {
+            private_key,
+            public_key,
+            index,
+            group,
+            secret,
+            blinding,
+            commitment,
+        };
+
+        Ok(GDKG { info })
+    }
+}
+
+impl<C: Curve> Phase0<C> for GDKG<C> {
+    type Next = GDKGWaitingShare<C>;
+
+    /// Evaluates the secret and blinding polynomials at the index of each DKG
+    /// participant and encrypts the pair with the corresponding public key.
+    /// Returns the bundled encrypted shares, committed by the Pedersen
+    /// commitments, as well as the next phase of the DKG.
+    fn encrypt_shares<R, F>(
+        self,
+        rng: F,
+    ) -> DKGResult<(GDKGWaitingShare<C>, Option<BundledShares<C>>)>
+    where
+        R: RngCore,
+        F: Fn() -> R,
+    {
+        let mut rng = rng();
+        let info = &self.info;
+
+        let shares = info
+            .group
+            .nodes
+            .iter()
+            .map(|n| {
+                let pair = (
+                    info.secret.eval(n.id()).value,
+                    info.blinding.eval(n.id()).value,
+                );
+                let buff = bincode::serialize(&pair)?;
+
+                Ok(EncryptedShare {
+                    share_idx: n.id(),
+                    secret: ecies::encrypt::<C, _>(n.key(), &buff, &mut rng),
+                })
+            })
+            .collect::<Result<Vec<_>, DKGError>>()?;
+
+        let bundle = BundledShares {
+            dealer_idx: info.index,
+            shares,
+            public: info.commitment.clone(),
+        };
+
+        Ok((GDKGWaitingShare { info: self.info }, Some(bundle)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+/// DKG Stage which waits to receive the shares from the previous phase's participants
+/// as input. After processing the shares, if there were any complaints it will generate
+/// a bundle of responses for the next phase.
+pub struct GDKGWaitingShare<C: Curve> {
+    /// Metadata about the DKG
+    info: GDKGInfo<C>,
+}
+
+impl<C: Curve> Phase1<C> for GDKGWaitingShare<C> {
+    type Next = GDKGWaitingResponse<C>;
+
+    /// Tries to decrypt the provided shares and blinding shares and checks them
+    /// against the Pedersen commitments of their dealers. The shares are kept
+    /// per dealer, since only the ones of the qualified dealers are summed once
+    /// the complaints are settled.
+    ///
+    /// A complaint is returned in the same cases as in the Joint-Feldman DKG.
+    fn process_shares<R: RngCore>(
+        self,
+        bundles: &[BundledShares<C>],
+        publish_all: bool,
+        rng: &mut R,
+    ) -> DKGResult<(GDKGWaitingResponse<C>, Option<BundledResponses<C>>)> {
+        let thr = self.info.thr();
+        let my_idx = self.info.index;
+        let h = pedersen_generator::<C>();
+
+        let mut statuses = StatusMatrix::new(self.info.n(), self.info.n(), Status::Success);
+        // we only explicitly allow correct shares, as in the Joint-Feldman DKG
+        (0..self.info.n())
+            .filter(|&dealer_idx| dealer_idx != my_idx as usize)
+            .for_each(|dealer_idx| {
+                statuses.set(dealer_idx as Idx, my_idx, Status::Complaint);
+            });
+
+        let mut commitments = PublicInfo::<C>::new();
+        let mut shares = PedersenShares::<C>::new();
+        for bundle in bundles
+            .iter()
+            .filter(|b| b.dealer_idx != my_idx)
+            .filter(|b| self.info.group.contains_index(b.dealer_idx))
+            .filter(|b| b.public.degree() == thr - 1)
+        {
+            commitments.insert(bundle.dealer_idx, bundle.public.clone());
+
+            let decrypted = bundle
+                .shares
+                .iter()
+                .find(|s| s.share_idx == my_idx)
+                .and_then(|share| {
+                    decrypt_and_check_pedersen_share(
+                        &self.info.private_key,
+                        my_idx,
+                        bundle.dealer_idx,
+                        &bundle.public,
+                        share,
+                        &h,
+                    )
+                    .ok()
+                });
+
+            if let Some(pair) = decrypted {
+                statuses.set(bundle.dealer_idx, my_idx, Status::Success);
+                shares.insert(bundle.dealer_idx, pair);
+            }
+        }
+
+        // we check with `thr - 1` because we already have our shares
+        if shares.len() < thr - 1 {
+            return Err(DKGError::NotEnoughValidShares(shares.len(), thr));
+        }
+
+        shares.insert(
+            my_idx,
+            (
+                self.info.secret.eval(my_idx).value,
+                self.info.blinding.eval(my_idx).value,
+            ),
+        );
+        commitments.insert(my_idx, self.info.commitment.clone());
+
+        let deals = collect_deals(&self.info.group, &self.info.group, bundles);
+        let bundle = compute_bundle_response(
+            my_idx,
+            &self.info.private_key,
+            &statuses,
+            &deals,
+            publish_all,
+            rng,
+        );
+        let new_dkg = GDKGWaitingResponse {
+            info: self.info,
+            shares,
+            commitments,
+            deals,
+            statuses,
+        };
+
+        Ok((new_dkg, bundle))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+/// DKG Stage which waits to receive the responses from the previous phase's participants
+/// as input. The responses will be processed and justifications may be generated as a byproduct
+/// if there are complaints.
+pub struct GDKGWaitingResponse<C: Curve> {
+    info: GDKGInfo<C>,
+    shares: PedersenShares<C>,
+    commitments: PublicInfo<C>,
+    deals: DealInfo<C>,
+    statuses: StatusMatrix,
+}
+
+impl<C: Curve> Phase2<C> for GDKGWaitingResponse<C> {
+    type Output = GDKGExtraction<C>;
+    type Next = GDKGWaitingJustification<C>;
+
+    #[allow(clippy::type_complexity)]
+    /// Checks if the responses when applied to the status matrix result in a
+    /// matrix with only `Success` elements. If so, every dealer is qualified and
+    /// the protocol moves on to the extraction.
+    ///
+    /// If there are complaints in the Status matrix, then it will return an
+    /// error with the justifications required for Phase 3 of the DKG.
+    fn process_responses(
+        self,
+        responses: &[BundledResponses<C>],
+    ) -> Result<GDKGExtraction<C>, DKGResult<(Self::Next, Option<BundledJustification<C>>)>> {
+        let info = self.info;
+        let mut statuses = self.statuses;
+        let h = pedersen_generator::<C>();
+        let complaints = set_statuses(
+            info.index,
+            &info.group,
+            &info.group,
+            &mut statuses,
+            &self.deals,
+            responses,
+            |idx, commitment, plaintext| {
+                plain_pedersen_share_correct::<C>(idx, commitment, plaintext, &h)
+            },
+        );
+
+        let justifications_required = info.group.nodes.iter().any(|n| !statuses.all_true(n.id()));
+
+        if justifications_required {
+            let bundled_justifications = get_pedersen_justification(&info, &statuses);
+            let dkg = GDKGWaitingJustification {
+                info,
+                shares: self.shares,
+                commitments: self.commitments,
+                statuses,
+                complaints,
+            };
+
+            return Err(Ok((dkg, bundled_justifications)));
+        }
+
+        // everybody is qualified in this case since there is no complaint at all
+        let qual = info.group.clone();
+        Ok(GDKGExtraction::new(
+            info,
+            qual,
+            &self.shares,
+            &self.commitments,
+            DKGTranscript::new(statuses, complaints),
+        ))
+    }
+}
+
+/// Reveals the shares and blinding shares of our deal for which there were
+/// complaints.
+fn get_pedersen_justification<C: Curve>(
+    info: &GDKGInfo<C>,
+    statuses: &StatusMatrix,
+) -> Option<BundledJustification<C>> {
+    if statuses.all_true(info.index) {
+        return None;
+    }
+
+    let justifications = statuses
+        .get_for_dealer(info.index)
+        .iter()
+        .enumerate()
+        .filter_map(|(i, success)| {
+            if !success {
+                // reveal the share and the blinding share
+                let id = i as Idx;
+                Some(Justification {
+                    share_idx: id,
+                    share: info.secret.eval(id).value,
+                    blinding: Some(info.blinding.eval(id).value),
+                })
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Some(BundledJustification {
+        dealer_idx: info.index,
+        justifications,
+        public: info.commitment.clone(),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+/// DKG Stage which waits to receive the justifications from the previous phase's participants
+/// as input to fix the qualified dealers, or an error.
+pub struct GDKGWaitingJustification<C: Curve> {
+    info: GDKGInfo<C>,
+    shares: PedersenShares<C>,
+    commitments: PublicInfo<C>,
+    // guaranteed to be of the right size (n)
+    statuses: StatusMatrix,
+    // the complaints found valid in the responses
+    complaints: Vec<Complaint>,
+}
+
+impl<C> Phase3<C> for GDKGWaitingJustification<C>
+where
+    C: Curve,
+{
+    type Output = GDKGExtraction<C>;
+
+    /// Accept a justification if the following conditions are true:
+    /// - bundle's dealer index is in range
+    /// - the justification reveals the blinding share
+    /// - the pair corresponds to the Pedersen commitment received in the bundled
+    /// shares during first period.
+    /// Return the extraction phase if `len(qual) >= thr`
+    fn process_justifications(
+        mut self,
+        justifs: &[BundledJustification<C>],
+    ) -> Result<GDKGExtraction<C>, DKGError> {
+        let h = pedersen_generator::<C>();
+        let my_idx = self.info.index;
+        let group = &self.info.group;
+
+        for bundle in justifs
+            .iter()
+            .filter(|b| group.contains_index(b.dealer_idx))
+        {
+            // only justifications for commitments that have been broadcasted in
+            // the first phase
+            let commitment = match self.commitments.get(&bundle.dealer_idx) {
+                Some(commitment) => commitment,
+                None => continue,
+            };
+
+            for justification in bundle.justifications.iter() {
+                let blinding = match &justification.blinding {
+                    Some(blinding) => blinding,
+                    None => continue,
+                };
+
+                if !pedersen_share_correct::<C>(
+                    justification.share_idx,
+                    &justification.share,
+                    blinding,
+                    commitment,
+                    &h,
+                ) {
+                    continue;
+                }
+
+                // justification is valid, we mark it off from our matrix
+                self.statuses
+                    .set(bundle.dealer_idx, justification.share_idx, Status::Success);
+                if justification.share_idx == my_idx {
+                    self.shares.insert(
+                        bundle.dealer_idx,
+                        (justification.share.clone(), blinding.clone()),
+                    );
+                }
+            }
+        }
+
+        // QUAL is the set of all entries in the matrix where all bits are set
+        let qual_indices = (0..self.info.n())
+            .filter(|&dealer| self.statuses.all_true(dealer as Idx))
+            .collect::<Vec<_>>();
+
+        let thr = self.info.thr();
+        if qual_indices.len() < thr {
+            // too many unanswered justifications, DKG abort !
+            return Err(DKGError::NotEnoughJustifications(qual_indices.len(), thr));
+        }
+
+        // create a group out of the qualifying nodes
+        let qual_nodes = self
+            .info
+            .group
+            .nodes
+            .iter()
+            .filter(|n| qual_indices.contains(&(n.id() as usize)))
+            .cloned()
+            .collect();
+        let qual = Group::<C>::new(qual_nodes, thr)?;
+
+        Ok(GDKGExtraction::new(
+            self.info,
+            qual,
+            &self.shares,
+            &self.commitments,
+            DKGTranscript::new(self.statuses, self.complaints),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+/// DKG Stage reached once the qualified dealers are fixed. It holds the sums of
+/// the shares, blinding shares and commitments of the qualified dealers, and
+/// extracts the public evaluation of the share for the next phase.
+pub struct GDKGExtraction<C: Curve> {
+    info: GDKGInfo<C>,
+    qual: Group<C>,
+    share: C::Scalar,
+    blinding: C::Scalar,
+    commitment: PublicPoly<C>,
+    transcript: DKGTranscript,
+}
+
+impl<C: Curve> GDKGExtraction<C> {
+    fn new(
+        info: GDKGInfo<C>,
+        qual: Group<C>,
+        shares: &PedersenShares<C>,
+        commitments: &PublicInfo<C>,
+        transcript: DKGTranscript,
+    ) -> Self {
+        let mut share = C::Scalar::zero();
+        let mut blinding = C::Scalar::zero();
+        let mut commitment = PublicPoly::<C>::zero();
+        for node in qual.nodes.iter() {
+            // unwrap since a dealer is only qualified if we hold a valid share
+            // from it, which was checked against its commitment
+            let (s, b) = shares.get(&node.id()).unwrap();
+            share.add(s);
+            blinding.add(b);
+            commitment.add(commitments.get(&node.id()).unwrap());
+        }
+
+        Self {
+            info,
+            qual,
+            share,
+            blinding,
+            commitment,
+            transcript,
+        }
+    }
+
+    /// Returns the group of the qualified dealers
+    pub fn qual(&self) -> &Group<C> {
+        &self.qual
+    }
+}
+
+impl<C: Curve> Phase4<C> for GDKGExtraction<C> {
+    type Next = GDKGWaitingExtraction<C>;
+
+    /// Publishes the share multiplied by the group generator, with the proof
+    /// that the rest of the summed commitment at our index is the blinding
+    /// share multiplied by the Pedersen generator.
+    fn extract<R: RngCore>(
+        self,
+        rng: &mut R,
+    ) -> DKGResult<(GDKGWaitingExtraction<C>, BundledExtraction<C>)> {
+        let g = C::Point::one();
+        let h = pedersen_generator::<C>();
+
+        let public = mul::<C>(&g, &self.share);
+        let blinded = mul::<C>(&h, &self.blinding);
+        let proof =
+            DLogPairProof::prove(&self.share, &self.blinding, &g, &public, &h, &blinded, rng);
+
+        let bundle = BundledExtraction {
+            share_idx: self.info.index,
+            public,
+            proof,
+        };
+
+        let dkg = GDKGWaitingExtraction {
+            index: self.info.index,
+            group: self.info.group,
+            qual: self.qual,
+            share: self.share,
+            commitment: self.commitment,
+            transcript: self.transcript,
+        };
+
+        Ok((dkg, bundle))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+/// DKG Stage which waits to receive the extractions of the share holders as
+/// input to produce either the final DKG Output, or an error.
+pub struct GDKGWaitingExtraction<C: Curve> {
+    index: Idx,
+    group: Group<C>,
+    qual: Group<C>,
+    share: C::Scalar,
+    commitment: PublicPoly<C>,
+    transcript: DKGTranscript,
+}
+
+impl<C: Curve> Phase5<C> for GDKGWaitingExtraction<C> {
+    /// Accepts an extraction if its share holder is part of the group and its
+    /// proof is valid against the summed commitment of the qualified dealers,
+    /// then recovers the distributed public polynomial from a threshold of them.
+    fn process_extractions(
+        self,
+        extractions: &[BundledExtraction<C>],
+    ) -> Result<DKGOutput<C>, DKGError> {
+        let g = C::Point::one();
+        let h = pedersen_generator::<C>();
+
+        let mut evals = BTreeMap::new();
+        for extraction in extractions
+            .iter()
+            .filter(|e| self.group.contains_index(e.share_idx))
+        {
+            if evals.contains_key(&extraction.share_idx) {
+                continue;
+            }
+
+            let blinded = sub::<C>(
+                &self.commitment.eval(extraction.share_idx).value,
+                &extraction.public,
+            );
+            if extraction
+                .proof
+                .verify(&g, &extraction.public, &h, &blinded)
+            {
+                evals.insert(extraction.share_idx, extraction.public.clone());
+            }
+        }
+
+        let thr = self.qual.threshold;
+        if evals.len() < thr {
+            return Err(DKGError::NotEnoughExtractions(evals.len(), thr));
+        }
+
+        let evals = evals
+            .into_iter()
+            .map(|(index, value)| Eval { value, index })
+            .collect();
+        let public = Poly::<C::Point>::full_recover(thr, evals)?;
+
+        Ok(DKGOutput {
+            qual: self.qual,
+            public,
+            share: Share {
+                index: self.index,
+                private: self.share,
+            },
+            transcript: self.transcript,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{
+        common::tests::{
+            check2, full_dkg, id_out, id_resp, invalid2, invalid_shares, setup_group,
+            unfounded_complaints, Finish,
+        },
+        default_threshold,
+    };
+    use rand::thread_rng;
+    use std::fmt::Debug;
+    use threshold_bls::curve::bls12381::Curve as BCurve;
+
+    use serde::{de::DeserializeOwned, Serialize};
+    use static_assertions::assert_impl_all;
+
+    assert_impl_all!(GDKG<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+    assert_impl_all!(GDKGExtraction<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+    assert_impl_all!(GDKGWaitingExtraction<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+    assert_impl_all!(BundledExtraction<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+
+    impl<C: Curve> Finish<C> for GDKGExtraction<C> {
+        fn finish(outputs: Vec<Self>) -> Vec<DKGOutput<C>> {
+            let (dkgs, extractions): (Vec<_>, Vec<_>) = outputs
+                .into_iter()
+                .map(|dkg| dkg.extract(&mut thread_rng()).unwrap())
+                .unzip();
+
+            dkgs.into_iter()
+                .map(|dkg| dkg.process_extractions(&extractions).unwrap())
+                .collect()
+        }
+    }
+
+    fn setup_dkg<C: Curve>(n: usize) -> Vec<GDKG<C>> {
+        let (privs, group) = setup_group::<C>(n, default_threshold(n));
+        privs
+            .into_iter()
+            .map(|p| GDKG::new(p, group.clone()).unwrap())
+            .collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn test_full_dkg() {
+        let n = 5;
+        let thr = default_threshold(n);
+        full_dkg(thr, setup_dkg::<BCurve>(n));
+    }
+
+    #[test]
+    fn test_invalid_shares_dkg() {
+        let n = 5;
+        let thr = default_threshold(n);
+        invalid_shares(
+            thr,
+            setup_dkg::<BCurve>(n),
+            invalid2,
+            id_resp,
+            check2,
+            id_out,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_unfounded_complaints_dkg() {
+        let n = 5;
+        let thr = default_threshold(n);
+        let (privs, group) = setup_group::<BCurve>(n, thr);
+        let dkgs = privs
+            .clone()
+            .into_iter()
+            .map(|p| GDKG::new(p, group.clone()).unwrap())
+            .collect::<Vec<_>>();
+        unfounded_complaints(thr, &privs, dkgs);
+    }
+
+    #[test]
+    fn test_invalid_extractions() {
+        let n = 5;
+        let thr = default_threshold(n);
+
+        let dkgs = setup_dkg::<BCurve>(n);
+        let (dkgs, shares): (Vec<_>, Vec<_>) = dkgs
+            .into_iter()
+            .map(|dkg| dkg.encrypt_shares(thread_rng).unwrap())
+            .map(|(dkg, shares)| (dkg, shares.unwrap()))
+            .unzip();
+        let (dkgs, extractions): (Vec<_>, Vec<_>) = dkgs
+            .into_iter()
+            .map(|dkg| {
+                dkg.process_shares(&shares, false, &mut thread_rng())
+                    .unwrap()
+                    .0
+            })
+            .map(|dkg| dkg.process_responses(&[]).unwrap())
+            .map(|dkg| dkg.extract(&mut thread_rng()).unwrap())
+            .unzip();
+
+        // an extraction shifted by a multiple of the Pedersen generator can't
+        // pass as the extraction of the share
+        let mut forged = extractions[0].clone();
+        forged.public.add(&pedersen_generator::<BCurve>());
+        // only the extractions of the threshold - 1 others are valid
+        let mut invalid = vec![forged];
+        invalid.extend_from_slice(&extractions[1..thr]);
+
+        let dkg = dkgs[0].clone();
+        assert!(matches!(
+            dkg.process_extractions(&invalid),
+            Err(DKGError::NotEnoughExtractions(got, required)) if got == thr - 1 && required == thr
+        ));
+
+        // any threshold of valid extractions recovers the same public polynomial
+        let first = dkgs[0]
+            .clone()
+            .process_extractions(&extractions[..thr])
+            .unwrap();
+        let last = dkgs[1]
+            .clone()
+            .process_extractions(&extractions[n - thr..])
+            .unwrap();
+        assert_eq!(first.public, last.public);
+    }
+}
@@ -7,11 +7,11 @@ use threshold_bls::{group::Curve, poly::Idx};
 /// of the protocol, if sucessful, the index is used to verify the validity of
 /// the share this node holds.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
-pub struct Node<C: Curve>(Idx, C::Point, Option<String>);
+pub struct Node<C: Curve>(Idx, C::Point);
 
 impl<C: Curve> Node<C> {
     pub fn new(index: Idx, public: C::Point) -> Self {
-        Self(index, public, None)
+        Self(index, public)
     }
 }
 
@@ -25,14 +25,6 @@ impl<C: Curve> Node<C> {
     pub fn key(&self) -> &C::Point {
         &self.1
     }
-
-    pub fn set_rpc_endpoint(&mut self, rpc_endpoint: String) {
-        self.2 = Some(rpc_endpoint);
-    }
-
-    pub fn get_rpc_endpoint(&self) -> Option<&String> {
-        self.2.as_ref()
-    }
 }
 
 /// A Group is a collection of Nodes with an associated threshold. A DKG scheme
@@ -113,3 +105,80 @@ where
         Self::new(nodes, thr).expect("threshold should be good here")
     }
 }
+
+/// Chooses the committers of a signature task of a group and their backups. The members, in
+/// ascending order, are rotated to the position derived from the index and the message of the
+/// task, the message being seeded by the last randomness output, so the committers of every task
+/// are different and can't be known in advance. The first `count` rotated members are the
+/// committers of the task and the next `count` its backup committers.
+///
+/// Nodes and the adapter choose the committers of a task with this same function.
+pub fn task_committers<T: Clone>(
+    task_index: usize,
+    message: &[u8],
+    members: &[T],
+    count: usize,
+) -> (Vec<T>, Vec<T>) {
+    if members.is_empty() {
+        return (vec![], vec![]);
+    }
+
+    // FNV-1a, which is simple enough for the adapter to compute on chain
+    let seed = message.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+
+    let start = (seed.wrapping_add(task_index as u64) % members.len() as u64) as usize;
+
+    let count = count.min(members.len());
+
+    let mut committers = members[start..]
+        .iter()
+        .chain(members[..start].iter())
+        .take(count * 2)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let backup_committers = committers.split_off(count);
+
+    (committers, backup_committers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::task_committers;
+
+    #[test]
+    fn test_task_committers() {
+        let members = vec![1, 2, 3, 4, 5];
+
+        let (committers, backup_committers) = task_committers(0, b"message", &members, 2);
+        assert_eq!(committers.len(), 2);
+        assert_eq!(backup_committers.len(), 2);
+        assert!(committers.iter().all(|c| !backup_committers.contains(c)));
+
+        // the next task starts from the next member
+        let (next, _) = task_committers(1, b"message", &members, 2);
+        assert_eq!(next[0], committers[1]);
+        assert_eq!(next[1], backup_committers[0]);
+
+        assert_eq!(
+            (committers, backup_committers),
+            task_committers(0, b"message", &members, 2)
+        );
+
+        // the backups are the remaining members if there are not enough of them
+        let (committers, backup_committers) = task_committers(0, b"message", &members, 3);
+        assert_eq!(committers.len(), 3);
+        assert_eq!(backup_committers.len(), 2);
+
+        let (committers, backup_committers) = task_committers(0, b"message", &members, 6);
+        assert_eq!(committers.len(), 5);
+        assert!(backup_committers.is_empty());
+
+        assert_eq!(
+            task_committers::<usize>(0, b"message", &[], 2),
+            (vec![], vec![])
+        );
+    }
+}
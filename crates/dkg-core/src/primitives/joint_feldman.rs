@@ -6,6 +6,7 @@ use crate::primitives::{
     group::Group,
     phases::{Phase0, Phase1, Phase2, Phase3},
     status::StatusMatrix,
+    transcript::{Complaint, DKGTranscript},
     types::*,
     DKGError, DKGResult,
 };
@@ -29,7 +30,6 @@ pub struct DKGInfo<C: Curve> {
     group: Group<C>,
     secret: Poly<C::Scalar>,
     public: Poly<C::Point>,
-    rpc_endpoint: String,
 }
 
 impl<C: Curve> DKGInfo<C> {
@@ -63,13 +63,9 @@ impl<C: Curve> DKG<C> {
     /// Creates a new DKG instance from the provided private key and group.
     ///
     /// The private key must be part of the group, otherwise this will return an error.
-    pub fn new(
-        private_key: C::Scalar,
-        rpc_endpoint: String,
-        group: Group<C>,
-    ) -> Result<DKG<C>, DKGError> {
+    pub fn new(private_key: C::Scalar, group: Group<C>) -> Result<DKG<C>, DKGError> {
         use rand::prelude::*;
-        Self::new_rand(private_key, rpc_endpoint, group, &mut thread_rng())
+        Self::new_rand(private_key, group, &mut thread_rng())
     }
 
     /// Creates a new DKG instance from the provided private key, group and RNG.
@@ -77,7 +73,6 @@ impl<C: Curve> DKG<C> {
     /// The private key must be part of the group, otherwise this will return an error.
     pub fn new_rand<R: RngCore>(
         private_key: C::Scalar,
-        rpc_endpoint: String,
         group: Group<C>,
         rng: &mut R,
     ) -> Result<DKG<C>, DKGError> {
@@ -101,7 +96,6 @@ impl<C: Curve> DKG<C> {
             group,
             secret,
             public,
-            rpc_endpoint,
         };
 
         Ok(DKG { info })
@@ -111,20 +105,6 @@ impl<C: Curve> DKG<C> {
 impl<C: Curve> Phase0<C> for DKG<C> {
     type Next = DKGWaitingShare<C>;
 
-    fn set_rpc_endpoint(&mut self) {
-        let my_idx = self.info.index;
-
-        let my_node = self
-            .info
-            .group
-            .nodes
-            .iter_mut()
-            .find(|node| node.id() == my_idx)
-            .unwrap();
-
-        my_node.set_rpc_endpoint(self.info.rpc_endpoint.clone());
-    }
-
     /// Evaluates the secret polynomial at the index of each DKG participant and encrypts
     /// the result with the corresponding public key. Returns the bundled encrypted shares
     /// as well as the next phase of the DKG.
@@ -140,7 +120,6 @@ impl<C: Curve> Phase0<C> for DKG<C> {
             self.info.index,
             &self.info.secret,
             &self.info.public,
-            &self.info.rpc_endpoint,
             &self.info.group,
             rng(),
         )?;
@@ -173,15 +152,19 @@ impl<C: Curve> Phase1<C> for DKGWaitingShare<C> {
     /// - invalid encryption
     /// - invalid length of public polynomial
     /// - invalid share w.r.t. public polynomial
-    fn process_shares(
-        mut self,
+    ///
+    /// The complaints for the last two cases open the dealer's cipher to
+    /// everyone, proving that the share is invalid.
+    fn process_shares<R: RngCore>(
+        self,
         bundles: &[BundledShares<C>],
         publish_all: bool,
-    ) -> DKGResult<(DKGWaitingResponse<C>, Option<BundledResponses>)> {
+        rng: &mut R,
+    ) -> DKGResult<(DKGWaitingResponse<C>, Option<BundledResponses<C>>)> {
         // publish_all = false;
         let thr = self.info.thr();
         let my_idx = self.info.index;
-        let (shares, publics, statuses) = process_shares_get_all(
+        let (shares, publics, deals, statuses) = process_shares_get_all(
             &self.info.group,
             &self.info.group,
             my_idx,
@@ -202,23 +185,19 @@ impl<C: Curve> Phase1<C> for DKGWaitingShare<C> {
         let mut fshare = self.info.secret.eval(self.info.index).value;
         // The public key polynomial is the sum of all shared polynomials
         let mut fpub = self.info.public.clone();
-        shares
-            .iter()
-            .for_each(|(&dealer_idx, (share, rpc_endpoint))| {
-                let node = self
-                    .info
-                    .group
-                    .nodes
-                    .iter_mut()
-                    .find(|node| node.id() == dealer_idx)
-                    .unwrap();
-                node.set_rpc_endpoint(rpc_endpoint.to_string());
-
-                fpub.add(publics.get(&dealer_idx).unwrap());
-                fshare.add(share);
-            });
-        let bundle = compute_bundle_response(my_idx, &statuses, publish_all);
-        let new_dkg = DKGWaitingResponse::new(self.info, fshare, fpub, statuses, publics);
+        shares.iter().for_each(|(&dealer_idx, share)| {
+            fpub.add(publics.get(&dealer_idx).unwrap());
+            fshare.add(share);
+        });
+        let bundle = compute_bundle_response(
+            my_idx,
+            &self.info.private_key,
+            &statuses,
+            &deals,
+            publish_all,
+            rng,
+        );
+        let new_dkg = DKGWaitingResponse::new(self.info, fshare, fpub, statuses, publics, deals);
 
         Ok((new_dkg, bundle))
     }
@@ -235,6 +214,7 @@ pub struct DKGWaitingResponse<C: Curve> {
     dist_pub: PublicPoly<C>,
     statuses: StatusMatrix,
     publics: PublicInfo<C>,
+    deals: DealInfo<C>,
 }
 
 impl<C: Curve> DKGWaitingResponse<C> {
@@ -244,6 +224,7 @@ impl<C: Curve> DKGWaitingResponse<C> {
         dist_pub: PublicPoly<C>,
         statuses: StatusMatrix,
         publics: PublicInfo<C>,
+        deals: DealInfo<C>,
     ) -> Self {
         Self {
             info,
@@ -251,30 +232,35 @@ impl<C: Curve> DKGWaitingResponse<C> {
             dist_pub,
             statuses,
             publics,
+            deals,
         }
     }
 }
 
 impl<C: Curve> Phase2<C> for DKGWaitingResponse<C> {
+    type Output = DKGOutput<C>;
     type Next = DKGWaitingJustification<C>;
     #[allow(clippy::type_complexity)]
     /// Checks if the responses when applied to the status matrix result in a
     /// matrix with only `Success` elements. If so, the protocol terminates.
     ///
     /// If there are complaints in the Status matrix, then it will return an
-    /// error with the justifications required for Phase 3 of the DKG.
+    /// error with the justifications required for Phase 3 of the DKG. Only the
+    /// complaints proving that the dealer's cipher was bad are applied.
     fn process_responses(
         self,
-        responses: &[BundledResponses],
+        responses: &[BundledResponses<C>],
     ) -> Result<DKGOutput<C>, DKGResult<(Self::Next, Option<BundledJustification<C>>)>> {
         let info = self.info;
         let mut statuses = self.statuses;
-        set_statuses(
+        let complaints = set_statuses(
             info.index,
             &info.group,
             &info.group,
             &mut statuses,
+            &self.deals,
             responses,
+            plain_share_correct::<C>,
         );
 
         // find out if justifications are required
@@ -290,6 +276,7 @@ impl<C: Curve> Phase2<C> for DKGWaitingResponse<C> {
                 dist_pub: self.dist_pub,
                 statuses: RefCell::new(statuses),
                 publics: self.publics,
+                complaints,
             };
 
             return Err(Ok((dkg, bundled_justifications)));
@@ -307,6 +294,7 @@ impl<C: Curve> Phase2<C> for DKGWaitingResponse<C> {
             qual: info.group,
             public: self.dist_pub,
             share,
+            transcript: DKGTranscript::new(statuses, complaints),
         })
     }
 }
@@ -324,12 +312,15 @@ pub struct DKGWaitingJustification<C: Curve> {
     // guaranteed to be of the right size (n)
     statuses: RefCell<StatusMatrix>,
     publics: HashMap<Idx, PublicPoly<C>>,
+    complaints: Vec<Complaint>,
 }
 
 impl<C> Phase3<C> for DKGWaitingJustification<C>
 where
     C: Curve,
 {
+    type Output = DKGOutput<C>;
+
     /// Accept a justification if the following conditions are true:
     /// - bundle's dealer index is in range
     /// - a justification was required for the given share (no-op)
@@ -352,7 +343,7 @@ where
             justifs,
         );
 
-        for (idx, (share, _)) in &valid_shares {
+        for (idx, share) in &valid_shares {
             add_share.add(share);
             // unwrap since internal_process_justi. gauarantees each share comes
             // from a public polynomial we've seen in the first round.
@@ -392,6 +383,7 @@ where
             qual: group,
             public: add_public,
             share: ds,
+            transcript: DKGTranscript::new(statuses.clone(), self.complaints),
         })
     }
 }
@@ -400,7 +392,10 @@ where
 pub mod tests {
     use super::*;
     use crate::primitives::{
-        common::tests::{check2, full_dkg, id_out, id_resp, invalid2, invalid_shares, setup_group},
+        common::tests::{
+            check2, full_dkg, id_out, id_resp, invalid2, invalid_shares, setup_group,
+            unfounded_complaints,
+        },
         default_threshold,
     };
     use std::fmt::Debug;
@@ -421,7 +416,7 @@ pub mod tests {
         let (privs, group) = setup_group::<C>(n, default_threshold(n));
         privs
             .into_iter()
-            .map(|p| DKG::new(p, String::from(""), group.clone()).unwrap())
+            .map(|p| DKG::new(p, group.clone()).unwrap())
             .collect::<Vec<_>>()
     }
 
@@ -458,4 +453,17 @@ pub mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_unfounded_complaints_dkg() {
+        let n = 5;
+        let thr = default_threshold(n);
+        let (privs, group) = setup_group::<BCurve>(n, thr);
+        let dkgs = privs
+            .clone()
+            .into_iter()
+            .map(|p| DKG::new(p, group.clone()).unwrap())
+            .collect::<Vec<_>>();
+        unfounded_complaints(thr, &privs, dkgs);
+    }
 }
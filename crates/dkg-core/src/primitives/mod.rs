@@ -9,12 +9,25 @@ pub mod types;
 pub use types::*;
 
 /// 2D binary array utilities for tracking successful (or not) participation in the DKG
-pub(crate) mod status;
+pub mod status;
+
+/// Record of the statuses and valid complaints of a DKG, to blame its disqualified dealers
+pub mod transcript;
+pub use transcript::*;
 
 pub mod joint_feldman;
 
 pub mod resharing;
 
+/// Publicly verifiable, non-interactive DKG forming a group in a single round
+pub mod pvss;
+
+/// DKG with Pedersen commitments, whose public key can not be biased by disqualifications
+pub mod gennaro;
+
+/// Zero-knowledge proofs of the DKG messages
+pub mod nizk;
+
 mod common;
 
 mod errors;
@@ -0,0 +1,323 @@
+//! Non-interactive zero-knowledge proofs over the group of a curve, made non-interactive by the
+//! Fiat-Shamir heuristic.
+use rand_core::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::convert::TryInto;
+use threshold_bls::group::{Curve, Element, Scalar};
+
+/// A domain separator of the challenges
+const DOMAIN: &[u8] = b"dkg-core-nizk";
+
+/// Proof of the equality of two discrete logarithms, i.e. that `x = s * g` and `y = s * h` for
+/// the same secret `s`, by Chaum-Pedersen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct DLEQProof<C: Curve> {
+    c: C::Scalar,
+    z: C::Scalar,
+}
+
+impl<C: Curve> DLEQProof<C> {
+    pub fn prove<R: RngCore>(
+        secret: &C::Scalar,
+        g: &C::Point,
+        x: &C::Point,
+        h: &C::Point,
+        y: &C::Point,
+        rng: &mut R,
+    ) -> Self {
+        let w = C::Scalar::rand(rng);
+
+        let a1 = mul::<C>(g, &w);
+        let a2 = mul::<C>(h, &w);
+
+        let c = challenge::<C>(&[g, x, h, y, &a1, &a2]);
+
+        DLEQProof {
+            z: response::<C>(&w, &c, secret),
+            c,
+        }
+    }
+
+    pub fn verify(&self, g: &C::Point, x: &C::Point, h: &C::Point, y: &C::Point) -> bool {
+        let a1 = lincomb::<C>(g, &self.z, x, &self.c);
+        let a2 = lincomb::<C>(h, &self.z, y, &self.c);
+
+        challenge::<C>(&[g, x, h, y, &a1, &a2]) == self.c
+    }
+}
+
+/// Proof of the knowledge of `a` and `b` such that `x = a * g` and `y = b * h`, by two Schnorr
+/// proofs sharing one challenge. Given `x + y` committed by Pedersen to the bases `g` and `h`, it
+/// proves that `x` is the part of the commitment in `g`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct DLogPairProof<C: Curve> {
+    c: C::Scalar,
+    za: C::Scalar,
+    zb: C::Scalar,
+}
+
+impl<C: Curve> DLogPairProof<C> {
+    pub fn prove<R: RngCore>(
+        a: &C::Scalar,
+        b: &C::Scalar,
+        g: &C::Point,
+        x: &C::Point,
+        h: &C::Point,
+        y: &C::Point,
+        rng: &mut R,
+    ) -> Self {
+        let wa = C::Scalar::rand(rng);
+        let wb = C::Scalar::rand(rng);
+
+        let ta = mul::<C>(g, &wa);
+        let tb = mul::<C>(h, &wb);
+
+        let c = challenge::<C>(&[g, x, h, y, &ta, &tb]);
+
+        DLogPairProof {
+            za: response::<C>(&wa, &c, a),
+            zb: response::<C>(&wb, &c, b),
+            c,
+        }
+    }
+
+    pub fn verify(&self, g: &C::Point, x: &C::Point, h: &C::Point, y: &C::Point) -> bool {
+        let ta = lincomb::<C>(g, &self.za, x, &self.c);
+        let tb = lincomb::<C>(h, &self.zb, y, &self.c);
+
+        challenge::<C>(&[g, x, h, y, &ta, &tb]) == self.c
+    }
+}
+
+/// Proof that an ElGamal ciphertext `(r * g, r * y + b * g)` to the public key `y` encrypts a
+/// bit `b`, i.e. 0 or 1, by the disjunction of two Chaum-Pedersen proofs of which one is
+/// simulated (Cramer-Damgård-Schoenmakers).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct BitProof<C: Curve> {
+    c0: C::Scalar,
+    c1: C::Scalar,
+    z0: C::Scalar,
+    z1: C::Scalar,
+}
+
+impl<C: Curve> BitProof<C> {
+    pub fn prove<R: RngCore>(
+        bit: bool,
+        randomness: &C::Scalar,
+        public_key: &C::Point,
+        ephemeral: &C::Point,
+        cipher: &C::Point,
+        rng: &mut R,
+    ) -> Self {
+        let g = C::Point::one();
+
+        let targets = bit_targets::<C>(cipher);
+
+        let (real, fake) = if bit { (1, 0) } else { (0, 1) };
+
+        let mut c = vec![C::Scalar::zero(); 2];
+        let mut z = vec![C::Scalar::zero(); 2];
+        let mut commitments = vec![(C::Point::zero(), C::Point::zero()); 2];
+
+        // simulate the branch of the other bit with a chosen challenge
+        c[fake] = C::Scalar::rand(rng);
+        z[fake] = C::Scalar::rand(rng);
+        commitments[fake] = (
+            lincomb::<C>(&g, &z[fake], ephemeral, &c[fake]),
+            lincomb::<C>(public_key, &z[fake], &targets[fake], &c[fake]),
+        );
+
+        let w = C::Scalar::rand(rng);
+        commitments[real] = (mul::<C>(&g, &w), mul::<C>(public_key, &w));
+
+        let mut c_real = challenge::<C>(&[
+            &g,
+            public_key,
+            ephemeral,
+            cipher,
+            &commitments[0].0,
+            &commitments[0].1,
+            &commitments[1].0,
+            &commitments[1].1,
+        ]);
+        c_real.sub(&c[fake]);
+
+        z[real] = response::<C>(&w, &c_real, randomness);
+        c[real] = c_real;
+
+        let z1 = z.pop().unwrap();
+        let z0 = z.pop().unwrap();
+        let c1 = c.pop().unwrap();
+        let c0 = c.pop().unwrap();
+
+        BitProof { c0, c1, z0, z1 }
+    }
+
+    pub fn verify(&self, public_key: &C::Point, ephemeral: &C::Point, cipher: &C::Point) -> bool {
+        let g = C::Point::one();
+
+        let targets = bit_targets::<C>(cipher);
+
+        let commitments = [(&self.c0, &self.z0), (&self.c1, &self.z1)]
+            .iter()
+            .zip(targets.iter())
+            .map(|((c, z), target)| {
+                (
+                    lincomb::<C>(&g, z, ephemeral, c),
+                    lincomb::<C>(public_key, z, target, c),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut c = self.c0.clone();
+        c.add(&self.c1);
+
+        challenge::<C>(&[
+            &g,
+            public_key,
+            ephemeral,
+            cipher,
+            &commitments[0].0,
+            &commitments[0].1,
+            &commitments[1].0,
+            &commitments[1].1,
+        ]) == c
+    }
+}
+
+/// The ciphertext encrypts the bit `b` if `cipher - b * g` shares the discrete logarithm of the
+/// ephemeral key.
+fn bit_targets<C: Curve>(cipher: &C::Point) -> [C::Point; 2] {
+    [cipher.clone(), sub::<C>(cipher, &C::Point::one())]
+}
+
+/// Hashes the statement and the commitments of a proof to its challenge.
+fn challenge<C: Curve>(points: &[&C::Point]) -> C::Scalar {
+    let mut hasher = Sha512::new();
+    hasher.input(DOMAIN);
+    for point in points {
+        hasher.input(bincode::serialize(point).expect("could not serialize element"));
+    }
+    let digest = hasher.result();
+
+    // reduces the 512 bits of the digest into the field, so that the challenge is nearly uniform
+    let mut word_base = C::Scalar::new();
+    word_base.set_int(1 << 32);
+    word_base.mul(&word_base.clone());
+
+    digest.chunks(8).fold(C::Scalar::zero(), |mut acc, word| {
+        let mut word_scalar = C::Scalar::new();
+        word_scalar.set_int(u64::from_be_bytes(word.try_into().unwrap()));
+        acc.mul(&word_base);
+        acc.add(&word_scalar);
+        acc
+    })
+}
+
+/// z = w - c * secret
+fn response<C: Curve>(w: &C::Scalar, c: &C::Scalar, secret: &C::Scalar) -> C::Scalar {
+    let mut c_secret = c.clone();
+    c_secret.mul(secret);
+    let mut z = w.clone();
+    z.sub(&c_secret);
+    z
+}
+
+pub(crate) fn mul<C: Curve>(point: &C::Point, scalar: &C::Scalar) -> C::Point {
+    let mut res = point.clone();
+    res.mul(scalar);
+    res
+}
+
+pub(crate) fn sub<C: Curve>(a: &C::Point, b: &C::Point) -> C::Point {
+    let mut minus_one = C::Scalar::one();
+    minus_one.negate();
+    let mut res = mul::<C>(b, &minus_one);
+    res.add(a);
+    res
+}
+
+/// a * p + b * q
+fn lincomb<C: Curve>(p: &C::Point, a: &C::Scalar, q: &C::Point, b: &C::Scalar) -> C::Point {
+    let mut res = mul::<C>(p, a);
+    res.add(&mul::<C>(q, b));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use threshold_bls::curve::bls12381::Curve as BCurve;
+
+    type Scalar = <BCurve as Curve>::Scalar;
+    type Point = <BCurve as Curve>::Point;
+
+    #[test]
+    fn test_dleq_proof() {
+        let rng = &mut thread_rng();
+
+        let secret = Scalar::rand(rng);
+        let g = Point::one();
+        let h = Point::rand(rng);
+        let x = mul::<BCurve>(&g, &secret);
+        let y = mul::<BCurve>(&h, &secret);
+
+        let proof = DLEQProof::<BCurve>::prove(&secret, &g, &x, &h, &y, rng);
+        assert!(proof.verify(&g, &x, &h, &y));
+
+        let other = mul::<BCurve>(&h, &Scalar::rand(rng));
+        assert!(!proof.verify(&g, &x, &h, &other));
+    }
+
+    #[test]
+    fn test_dlog_pair_proof() {
+        let rng = &mut thread_rng();
+
+        let (a, b) = (Scalar::rand(rng), Scalar::rand(rng));
+        let g = Point::one();
+        let h = Point::rand(rng);
+        let x = mul::<BCurve>(&g, &a);
+        let y = mul::<BCurve>(&h, &b);
+
+        let proof = DLogPairProof::<BCurve>::prove(&a, &b, &g, &x, &h, &y, rng);
+        assert!(proof.verify(&g, &x, &h, &y));
+
+        // moving a part of the commitment from x to y breaks the proof
+        let shift = mul::<BCurve>(&h, &Scalar::rand(rng));
+        let mut shifted_x = x;
+        shifted_x.add(&shift);
+        let shifted_y = sub::<BCurve>(&y, &shift);
+        assert!(!proof.verify(&g, &shifted_x, &h, &shifted_y));
+    }
+
+    #[test]
+    fn test_bit_proof() {
+        let rng = &mut thread_rng();
+
+        let public_key = mul::<BCurve>(&Point::one(), &Scalar::rand(rng));
+
+        for &bit in &[false, true] {
+            let randomness = Scalar::rand(rng);
+            let ephemeral = mul::<BCurve>(&Point::one(), &randomness);
+            let mut cipher = mul::<BCurve>(&public_key, &randomness);
+            if bit {
+                cipher.add(&Point::one());
+            }
+
+            let proof =
+                BitProof::<BCurve>::prove(bit, &randomness, &public_key, &ephemeral, &cipher, rng);
+            assert!(proof.verify(&public_key, &ephemeral, &cipher));
+
+            // the ciphertext of 2 can't pass as a bit
+            let mut cipher_of_two = mul::<BCurve>(&public_key, &randomness);
+            cipher_of_two.add(&Point::one());
+            cipher_of_two.add(&Point::one());
+            assert!(!proof.verify(&public_key, &ephemeral, &cipher_of_two));
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::primitives::{
-    types::{BundledJustification, BundledResponses, BundledShares, DKGOutput},
+    types::{BundledExtraction, BundledJustification, BundledResponses, BundledShares, DKGOutput},
     DKGError, DKGResult,
 };
 use rand::RngCore;
@@ -15,8 +15,6 @@ use std::fmt::Debug;
 pub trait Phase0<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a> {
     type Next: Phase1<C>;
 
-    fn set_rpc_endpoint(&mut self);
-
     fn encrypt_shares<R, F>(self, rng: F) -> DKGResult<(Self::Next, Option<BundledShares<C>>)>
     where
         R: RngCore,
@@ -29,11 +27,12 @@ pub trait Phase0<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a>
 pub trait Phase1<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a> {
     type Next: Phase2<C>;
 
-    fn process_shares(
+    fn process_shares<R: RngCore>(
         self,
         bundles: &[BundledShares<C>],
         publish_all: bool,
-    ) -> DKGResult<(Self::Next, Option<BundledResponses>)>;
+        rng: &mut R,
+    ) -> DKGResult<(Self::Next, Option<BundledResponses<C>>)>;
 }
 
 /// Phase2 is the trait abstracting the third stage of a distributed key
@@ -41,26 +40,65 @@ pub trait Phase1<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a>
 /// responses, look if they can finish the protocol. If not, dealers look if
 /// they have to produce some justifications.
 ///
-/// The return method of this trait is first the `DKGOutput` if the protocol can
+/// The return method of this trait is first the `Output` if the protocol can
 /// be finished already. If not, the call returns an error which either contains
 /// the next phase and potential justifications or a fatal error that makes this
 /// node unable to continue participating in the protocol.
+///
+/// The `Output` is the `DKGOutput` unless the protocol needs more phases after
+/// the qualified dealers are fixed.
 pub trait Phase2<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a> {
-    type Next: Phase3<C>;
+    type Output;
+    type Next: Phase3<C, Output = Self::Output>;
 
     #[allow(clippy::type_complexity)]
     fn process_responses(
         self,
-        responses: &[BundledResponses],
-    ) -> Result<DKGOutput<C>, DKGResult<(Self::Next, Option<BundledJustification<C>>)>>;
+        responses: &[BundledResponses<C>],
+    ) -> Result<Self::Output, DKGResult<(Self::Next, Option<BundledJustification<C>>)>>;
 }
 
 /// Phase3 is the trait abstracting the final stage of a distributed key
 /// generation protocol. At this stage, the share holders process the potential
 /// justifications, and look if they can finish the protocol.
 pub trait Phase3<C: Curve>: Debug {
+    type Output;
+
     fn process_justifications(
         self,
         justifs: &[BundledJustification<C>],
+    ) -> Result<Self::Output, DKGError>;
+}
+
+/// Phase4 is the trait abstracting the extraction stage of a distributed key
+/// generation with Pedersen commitments. Once the qualified dealers are fixed,
+/// every share holder reveals the public evaluation of its share, with a proof
+/// that it is consistent with the commitments.
+pub trait Phase4<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a> {
+    type Next: Phase5<C>;
+
+    fn extract<R: RngCore>(self, rng: &mut R) -> DKGResult<(Self::Next, BundledExtraction<C>)>;
+}
+
+/// Phase5 is the trait abstracting the final stage of a distributed key
+/// generation with Pedersen commitments. At this stage, every participant
+/// verifies the extractions and recovers the distributed public polynomial
+/// from them.
+pub trait Phase5<C: Curve>: Debug {
+    fn process_extractions(
+        self,
+        extractions: &[BundledExtraction<C>],
     ) -> Result<DKGOutput<C>, DKGError>;
 }
+
+/// SingleRound is the trait abstracting a non-interactive distributed key
+/// generation. Every dealer publishes a publicly verifiable dealing once, and
+/// every participant computes its output from the published dealings, without
+/// complaints or justifications.
+pub trait SingleRound<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a> {
+    type Dealing: Clone + Debug + Serialize + for<'a> Deserialize<'a>;
+
+    fn deal<R: RngCore>(&self, rng: &mut R) -> DKGResult<Self::Dealing>;
+
+    fn process_dealings(self, dealings: &[Self::Dealing]) -> DKGResult<DKGOutput<C>>;
+}
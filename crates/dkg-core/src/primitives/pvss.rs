@@ -0,0 +1,527 @@
+//! Implements a publicly verifiable, non-interactive DKG based on PVSS. Every dealer shares a
+//! random secret once, publishing the commitment of its polynomial together with the shares
+//! encrypted to the share holders and the proofs of their correct encryption, so that anyone,
+//! e.g. the coordinator, can verify a dealing without interacting with the dealer. Each
+//! participant then sums its shares of the valid dealings and the group forms in a single round,
+//! without complaints or justifications.
+//!
+//! A share is encrypted bit by bit by ElGamal in the exponent: a bit `b` is encrypted to the
+//! public key `y` of the share holder as `(r * g, r * y + b * g)`, with a proof that `b` is 0 or
+//! 1. The ciphertexts combined by the powers of 2 encrypt the share itself, which is proven by a
+//! DLEQ proof to be the evaluation of the committed polynomial at the holder's index. The
+//! holder decrypts every bit by its private key. The price of public verifiability is the size
+//! of a dealing, which grows with the bit length of the scalar field for every share holder.
+use super::{
+    common::share_correct,
+    nizk::{mul, sub, BitProof, DLEQProof},
+};
+use crate::primitives::{
+    group::Group,
+    phases::SingleRound,
+    status::{Status, StatusMatrix},
+    transcript::{Complaint, ComplaintReason, DKGTranscript},
+    types::DKGOutput,
+    DKGError, DKGResult, ShareError,
+};
+
+use threshold_bls::{
+    group::{Curve, Element, Scalar},
+    poly::{Idx, PrivatePoly, PublicPoly},
+    sig::Share,
+};
+
+use rand_core::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// EncryptedBit holds the ElGamal encryption of a bit of a share and the
+/// proof that it is 0 or 1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct EncryptedBit<C: Curve> {
+    pub ephemeral: C::Point,
+    pub cipher: C::Point,
+    pub proof: BitProof<C>,
+}
+
+/// PublicShare holds a share encrypted bit by bit to the `share_idx`-th
+/// participant, least significant bit first, and the proof that it is the
+/// evaluation of the dealer's public polynomial at the participant's index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct PublicShare<C: Curve> {
+    /// The index of the participant this share belongs to
+    pub share_idx: Idx,
+    pub bits: Vec<EncryptedBit<C>>,
+    pub proof: DLEQProof<C>,
+}
+
+/// PublicDealing is published once by every dealer and can be verified by
+/// anyone with `verify_dealing`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct PublicDealing<C: Curve> {
+    /// The dealer's index
+    pub dealer_idx: Idx,
+    /// The encrypted shares to every participant of the group
+    pub shares: Vec<PublicShare<C>>,
+    /// The commitment of the secret polynomial created by the dealer
+    pub public: PublicPoly<C>,
+}
+
+/// NIDKG is the state of a participant of the non-interactive DKG, which
+/// deals its shares and processes the dealings of everyone afterwards.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct NIDKG<C: Curve> {
+    private_key: C::Scalar,
+    pub public_key: C::Point,
+    index: Idx,
+    group: Group<C>,
+    secret: PrivatePoly<C>,
+    public: PublicPoly<C>,
+}
+
+impl<C: Curve> NIDKG<C> {
+    /// Creates a new DKG instance from the provided private key and group.
+    ///
+    /// The private key must be part of the group, otherwise this will return an error.
+    pub fn new(private_key: C::Scalar, group: Group<C>) -> DKGResult<NIDKG<C>> {
+        use rand::prelude::*;
+        Self::new_rand(private_key, group, &mut thread_rng())
+    }
+
+    /// Creates a new DKG instance from the provided private key, group and RNG.
+    ///
+    /// The private key must be part of the group, otherwise this will return an error.
+    pub fn new_rand<R: RngCore>(
+        private_key: C::Scalar,
+        group: Group<C>,
+        rng: &mut R,
+    ) -> DKGResult<NIDKG<C>> {
+        let public_key = mul::<C>(&C::Point::one(), &private_key);
+
+        let index = group
+            .index(&public_key)
+            .ok_or(DKGError::PublicKeyNotFound)?;
+
+        let secret = PrivatePoly::<C>::new_from(group.threshold - 1, rng);
+        let public = secret.commit::<C::Point>();
+
+        Ok(NIDKG {
+            private_key,
+            public_key,
+            index,
+            group,
+            secret,
+            public,
+        })
+    }
+}
+
+impl<C: Curve> SingleRound<C> for NIDKG<C> {
+    type Dealing = PublicDealing<C>;
+
+    /// Evaluates the secret polynomial at the index of each participant and
+    /// encrypts the result verifiably to the corresponding public key.
+    fn deal<R: RngCore>(&self, rng: &mut R) -> DKGResult<PublicDealing<C>> {
+        let shares = self
+            .group
+            .nodes
+            .iter()
+            .map(|n| {
+                let share = self.secret.eval(n.id()).value;
+                encrypt_share(n.id(), n.key(), &share, &self.public, rng)
+            })
+            .collect();
+
+        Ok(PublicDealing {
+            dealer_idx: self.index,
+            shares,
+            public: self.public.clone(),
+        })
+    }
+
+    /// Verifies the dealings and sums the shares and public polynomials of the
+    /// valid ones. The first valid dealing of a dealer is taken, and the
+    /// dealers of the valid dealings form the qualified group, which is the
+    /// same for every participant since the verification is public. The
+    /// transcript holds a complaint of every participant against each dealer
+    /// without a valid dealing.
+    ///
+    /// A dealing is not signed by its dealer, so nothing here prevents it from
+    /// claiming another dealer's index. The dealings must come from a board
+    /// which authenticates their publishers and only accepts a dealing at the
+    /// publisher's own index, as the coordinator does when it verifies them.
+    fn process_dealings(self, dealings: &[PublicDealing<C>]) -> DKGResult<DKGOutput<C>> {
+        let thr = self.group.threshold;
+
+        let mut valid_dealings = BTreeMap::new();
+        let mut invalid_dealers = Vec::new();
+        for dealing in dealings {
+            if valid_dealings.contains_key(&dealing.dealer_idx) {
+                continue;
+            }
+            if verify_dealing(&self.group, dealing).is_ok() {
+                valid_dealings.insert(dealing.dealer_idx, dealing);
+            } else {
+                invalid_dealers.push(dealing.dealer_idx);
+            }
+        }
+
+        let n = self.group.len();
+        let mut statuses = StatusMatrix::new(n, n, Status::Success);
+        let mut complaints = Vec::new();
+        for dealer in self.group.nodes.iter().map(|node| node.id()) {
+            if valid_dealings.contains_key(&dealer) {
+                continue;
+            }
+            let reason = if invalid_dealers.contains(&dealer) {
+                ComplaintReason::InvalidDealing
+            } else {
+                ComplaintReason::MissingShare
+            };
+            for holder in self.group.nodes.iter().map(|node| node.id()) {
+                statuses.set(dealer, holder, Status::Complaint);
+                complaints.push(Complaint {
+                    dealer_idx: dealer,
+                    holder_idx: holder,
+                    reason,
+                });
+            }
+        }
+
+        if valid_dealings.len() < thr {
+            return Err(DKGError::NotEnoughValidShares(valid_dealings.len(), thr));
+        }
+
+        let mut share = C::Scalar::zero();
+        let mut public = PublicPoly::<C>::zero();
+        for dealing in valid_dealings.values() {
+            share.add(&decrypt_share(&self.private_key, self.index, dealing)?);
+            public.add(&dealing.public);
+        }
+
+        let qual_nodes = self
+            .group
+            .nodes
+            .into_iter()
+            .filter(|n| valid_dealings.contains_key(&n.id()))
+            .collect();
+        let qual = Group::<C>::new(qual_nodes, thr)?;
+
+        Ok(DKGOutput {
+            qual,
+            public,
+            share: Share {
+                index: self.index,
+                private: share,
+            },
+            transcript: DKGTranscript::new(statuses, complaints),
+        })
+    }
+}
+
+/// Verifies a dealing against the group without any secret, i.e. that the
+/// public polynomial has the degree of the threshold and that every
+/// participant is given exactly one share which is proven to encrypt the
+/// evaluation of the polynomial at its index.
+pub fn verify_dealing<C: Curve>(
+    group: &Group<C>,
+    dealing: &PublicDealing<C>,
+) -> Result<(), ShareError> {
+    let dealer_idx = dealing.dealer_idx;
+
+    if !group.contains_index(dealer_idx) {
+        return Err(ShareError::InvalidDealer(dealer_idx));
+    }
+
+    if dealing.public.degree() != group.threshold - 1 {
+        return Err(ShareError::InvalidPublicPolynomial(
+            dealer_idx,
+            dealing.public.degree(),
+            group.threshold - 1,
+        ));
+    }
+
+    if dealing.shares.len() != group.len() {
+        return Err(ShareError::InvalidShareCount(
+            dealer_idx,
+            dealing.shares.len(),
+            group.len(),
+        ));
+    }
+
+    for node in group.nodes.iter() {
+        let mut shares = dealing.shares.iter().filter(|s| s.share_idx == node.id());
+
+        let share = match (shares.next(), shares.next()) {
+            (Some(share), None) => share,
+            _ => return Err(ShareError::MissingShare(dealer_idx, node.id())),
+        };
+
+        if !verify_share(node.key(), &dealing.public, share) {
+            return Err(ShareError::InvalidEncryptionProof(dealer_idx, node.id()));
+        }
+    }
+
+    Ok(())
+}
+
+fn encrypt_share<C: Curve, R: RngCore>(
+    share_idx: Idx,
+    public_key: &C::Point,
+    share: &C::Scalar,
+    public: &PublicPoly<C>,
+    rng: &mut R,
+) -> PublicShare<C> {
+    let mut randomnesses = vec![];
+    let mut bits = vec![];
+
+    for bit in share.to_bits() {
+        let randomness = C::Scalar::rand(rng);
+
+        let ephemeral = mul::<C>(&C::Point::one(), &randomness);
+
+        let mut cipher = mul::<C>(public_key, &randomness);
+        if bit {
+            cipher.add(&C::Point::one());
+        }
+
+        let proof = BitProof::prove(bit, &randomness, public_key, &ephemeral, &cipher, rng);
+
+        randomnesses.push(randomness);
+        bits.push(EncryptedBit {
+            ephemeral,
+            cipher,
+            proof,
+        });
+    }
+
+    // the combined ciphertext encrypts the share with the combined randomness
+    let randomness = combine::<C, _>(randomnesses);
+    let (ephemeral, cipher) = combine_bits(&bits);
+
+    let proof = DLEQProof::prove(
+        &randomness,
+        &C::Point::one(),
+        &ephemeral,
+        public_key,
+        &sub::<C>(&cipher, &public.eval(share_idx).value),
+        rng,
+    );
+
+    PublicShare {
+        share_idx,
+        bits,
+        proof,
+    }
+}
+
+fn verify_share<C: Curve>(
+    public_key: &C::Point,
+    public: &PublicPoly<C>,
+    share: &PublicShare<C>,
+) -> bool {
+    if share.bits.len() != C::Scalar::zero().to_bits().len() {
+        return false;
+    }
+
+    if !share
+        .bits
+        .iter()
+        .all(|bit| bit.proof.verify(public_key, &bit.ephemeral, &bit.cipher))
+    {
+        return false;
+    }
+
+    let (ephemeral, cipher) = combine_bits(&share.bits);
+
+    share.proof.verify(
+        &C::Point::one(),
+        &ephemeral,
+        public_key,
+        &sub::<C>(&cipher, &public.eval(share.share_idx).value),
+    )
+}
+
+/// Decrypts the share of the participant from a verified dealing.
+fn decrypt_share<C: Curve>(
+    private_key: &C::Scalar,
+    own_idx: Idx,
+    dealing: &PublicDealing<C>,
+) -> Result<C::Scalar, ShareError> {
+    let dealer_idx = dealing.dealer_idx;
+
+    let share = dealing
+        .shares
+        .iter()
+        .find(|s| s.share_idx == own_idx)
+        .ok_or(ShareError::MissingShare(dealer_idx, own_idx))?;
+
+    let bits = share
+        .bits
+        .iter()
+        .map(|bit| {
+            // b * g = cipher - private_key * ephemeral
+            let value = sub::<C>(&bit.cipher, &mul::<C>(&bit.ephemeral, private_key));
+            if value == C::Point::zero() {
+                Ok(C::Scalar::zero())
+            } else if value == C::Point::one() {
+                Ok(C::Scalar::one())
+            } else {
+                Err(ShareError::InvalidEncryptionProof(dealer_idx, own_idx))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let share = combine::<C, _>(bits);
+
+    if !share_correct::<C>(own_idx, &share, &dealing.public) {
+        return Err(ShareError::InvalidShare(dealer_idx));
+    }
+
+    Ok(share)
+}
+
+fn combine_bits<C: Curve>(bits: &[EncryptedBit<C>]) -> (C::Point, C::Point) {
+    (
+        combine::<C, _>(bits.iter().map(|bit| bit.ephemeral.clone()).collect()),
+        combine::<C, _>(bits.iter().map(|bit| bit.cipher.clone()).collect()),
+    )
+}
+
+/// Combines the values of the bits, least significant first, with the powers
+/// of 2 by Horner's rule.
+fn combine<C: Curve, E: Element<RHS = C::Scalar>>(values: Vec<E>) -> E {
+    let mut two = C::Scalar::new();
+    two.set_int(2);
+
+    values.into_iter().rev().fold(E::zero(), |mut acc, value| {
+        acc.mul(&two);
+        acc.add(&value);
+        acc
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::primitives::common::tests::{reconstruct, setup_group};
+    use rand::thread_rng;
+    use std::fmt::Debug;
+    use threshold_bls::curve::bls12381::Curve as BCurve;
+
+    use static_assertions::assert_impl_all;
+
+    assert_impl_all!(NIDKG<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+    assert_impl_all!(PublicDealing<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+
+    // dealings are large, so the groups are kept small
+    fn setup_dkg<C: Curve>(n: usize, thr: usize) -> (Group<C>, Vec<NIDKG<C>>) {
+        let (privs, group) = setup_group::<C>(n, thr);
+        let dkgs = privs
+            .into_iter()
+            .map(|p| NIDKG::new(p, group.clone()).unwrap())
+            .collect::<Vec<_>>();
+        (group, dkgs)
+    }
+
+    fn deal_all<C: Curve>(dkgs: &[NIDKG<C>]) -> Vec<PublicDealing<C>> {
+        dkgs.iter()
+            .map(|dkg| dkg.deal(&mut thread_rng()).unwrap())
+            .collect()
+    }
+
+    fn check_outputs<C: Curve>(thr: usize, outputs: &[DKGOutput<C>]) {
+        let recovered_private = reconstruct(thr, outputs).unwrap();
+        let recovered_public = recovered_private.commit::<C::Point>();
+        for out in outputs.iter() {
+            assert_eq!(out.public.public_key(), recovered_public.public_key());
+        }
+    }
+
+    #[test]
+    fn test_full_dkg() {
+        let (n, thr) = (3, 2);
+        let (group, dkgs) = setup_dkg::<BCurve>(n, thr);
+
+        let dealings = deal_all(&dkgs);
+
+        for dealing in dealings.iter() {
+            verify_dealing(&group, dealing).unwrap();
+        }
+
+        let outputs = dkgs
+            .into_iter()
+            .map(|dkg| dkg.process_dealings(&dealings))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(outputs.iter().all(|out| out.qual.len() == n));
+
+        check_outputs(thr, &outputs);
+    }
+
+    #[test]
+    fn test_invalid_dealings_are_rejected() {
+        let (n, thr) = (3, 2);
+        let (group, dkgs) = setup_dkg::<BCurve>(n, thr);
+
+        let mut dealings = deal_all(&dkgs);
+
+        // a dealing missing the share of a participant
+        let mut incomplete_dealing = dealings[2].clone();
+        incomplete_dealing.shares.remove(1);
+        assert!(matches!(
+            verify_dealing(&group, &incomplete_dealing),
+            Err(ShareError::InvalidShareCount(2, 2, 3))
+        ));
+
+        // encrypts another share to the second participant
+        let other_share = encrypt_share(
+            dealings[0].shares[1].share_idx,
+            group.nodes[1].key(),
+            &<BCurve as Curve>::Scalar::rand(&mut thread_rng()),
+            &dealings[0].public,
+            &mut thread_rng(),
+        );
+        dealings[0].shares[1] = other_share;
+        assert!(matches!(
+            verify_dealing(&group, &dealings[0]),
+            Err(ShareError::InvalidEncryptionProof(0, 1))
+        ));
+
+        let outputs = dkgs
+            .into_iter()
+            .map(|dkg| dkg.process_dealings(&dealings))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // the dealer of the invalid dealing is excluded by everyone
+        for out in outputs.iter() {
+            assert_eq!(n - 1, out.qual.len());
+            assert!(!out.qual.contains_index(0));
+
+            let blames = out.transcript.blames();
+            assert_eq!(blames.len(), 1);
+            assert!(blames[&0]
+                .iter()
+                .all(|c| c.reason == ComplaintReason::InvalidDealing));
+        }
+
+        check_outputs(thr, &outputs);
+    }
+
+    #[test]
+    fn test_not_enough_valid_dealings() {
+        let (n, thr) = (3, 2);
+        let (_, dkgs) = setup_dkg::<BCurve>(n, thr);
+
+        let mut dealings = deal_all(&dkgs);
+        dealings.truncate(thr - 1);
+
+        let res = dkgs[0].clone().process_dealings(&dealings);
+        assert!(matches!(res, Err(DKGError::NotEnoughValidShares(1, 2))));
+    }
+}
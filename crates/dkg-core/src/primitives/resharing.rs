@@ -9,6 +9,7 @@ use crate::primitives::{
     group::Group,
     phases::{Phase0, Phase1, Phase2, Phase3},
     status::{Status, StatusMatrix},
+    transcript::{Complaint, ComplaintReason, DKGTranscript},
     types::*,
     DKGError, DKGResult,
 };
@@ -135,8 +136,6 @@ impl<C: Curve> RDKG<C> {
 impl<C: Curve> Phase0<C> for RDKG<C> {
     type Next = RDKGWaitingShare<C>;
 
-    fn set_rpc_endpoint(&mut self) {}
-
     fn encrypt_shares<R, F>(
         self,
         rng: F,
@@ -155,7 +154,6 @@ impl<C: Curve> Phase0<C> for RDKG<C> {
             info.prev_index.unwrap(),
             &secret,
             &public,
-            "",
             &info.new_group,
             rng(),
         )?;
@@ -182,11 +180,12 @@ pub struct RDKGWaitingShare<C: Curve> {
 impl<C: Curve> Phase1<C> for RDKGWaitingShare<C> {
     type Next = RDKGWaitingResponse<C>;
     #[allow(unused_assignments)]
-    fn process_shares(
+    fn process_shares<R: RngCore>(
         self,
         bundles: &[BundledShares<C>],
         mut publish_all: bool,
-    ) -> DKGResult<(RDKGWaitingResponse<C>, Option<BundledResponses>)> {
+        rng: &mut R,
+    ) -> DKGResult<(RDKGWaitingResponse<C>, Option<BundledResponses<C>>)> {
         publish_all = false;
         if !self.info.is_share_holder() {
             return Ok((
@@ -199,13 +198,15 @@ impl<C: Curve> Phase1<C> for RDKGWaitingShare<C> {
                     info: self.info,
                     shares: ShareInfo::<C>::new(),
                     publics: PublicInfo::<C>::new(),
+                    deals: DealInfo::<C>::new(),
+                    complaints: Vec::new(),
                 },
                 None,
             ));
         }
 
         let my_idx = self.info.new_index.unwrap();
-        let (mut shares, mut publics, mut statuses) = process_shares_get_all(
+        let (mut shares, mut publics, deals, mut statuses) = process_shares_get_all(
             &self.info.prev_group,
             &self.info.new_group,
             my_idx,
@@ -220,30 +221,13 @@ impl<C: Curve> Phase1<C> for RDKGWaitingShare<C> {
             statuses,
         );
 
-        bundles
-            .iter()
-            // this bundle was invalid for some reason
-            .filter(|b| publics.contains_key(&b.dealer_idx))
-            // only keep the ones that don't respect the rules to remove them
-            // from the list of valid shares and put their status to complaint
-            .filter(|b| {
-                !check_public_resharing::<C>(b.dealer_idx, &b.public, &self.info.prev_public)
-            })
-            .for_each(|b| {
-                println!("REMOVE BUNDLE: {}", b.dealer_idx);
-                shares.remove(&b.dealer_idx);
-                for n in &self.info.new_group.nodes {
-                    statuses.set(b.dealer_idx, n.id(), Status::Complaint);
-                }
-            });
-
         let mut info = self.info;
         if info.is_dealer() {
             let public = info.public.take().unwrap();
             let secret = info.secret.take().unwrap();
             // we register our own share and publics into the mix
             let didx = info.prev_index.unwrap();
-            shares.insert(didx, (secret.eval(didx).value, "".to_string()));
+            shares.insert(didx, secret.eval(didx).value);
             publics.insert(didx, public.clone());
             // we treat our own share as valid!
             statuses.set(didx, my_idx, Status::Success);
@@ -251,6 +235,30 @@ impl<C: Curve> Phase1<C> for RDKGWaitingShare<C> {
             info.secret = Some(secret);
         }
 
+        // a deal which does not reshare the dealer's current share is invalid
+        // for everyone, its dealer included, since no complaint against it
+        // could be proven by opening the cipher
+        let mut complaints = Vec::new();
+        deals
+            .iter()
+            // only keep the ones that don't respect the rules to remove them
+            // from the list of valid shares and put their status to complaint
+            .filter(|(&dealer_idx, b)| {
+                !check_public_resharing::<C>(dealer_idx, &b.public, &info.prev_public)
+            })
+            .for_each(|(&dealer_idx, _)| {
+                println!("REMOVE BUNDLE: {}", dealer_idx);
+                shares.remove(&dealer_idx);
+                for n in &info.new_group.nodes {
+                    statuses.set(dealer_idx, n.id(), Status::Complaint);
+                    complaints.push(Complaint {
+                        dealer_idx,
+                        holder_idx: n.id(),
+                        reason: ComplaintReason::InvalidResharing,
+                    });
+                }
+            });
+
         // we need at least a threshold of dealers to share their share to be
         // able to reconstruct a share of the same distributed private key.
         if shares.len() < info.prev_group.threshold {
@@ -260,12 +268,21 @@ impl<C: Curve> Phase1<C> for RDKGWaitingShare<C> {
             ));
         }
 
-        let bundle = compute_bundle_response(my_idx, &statuses, publish_all);
+        let bundle = compute_bundle_response(
+            my_idx,
+            &info.private_key,
+            &statuses,
+            &deals,
+            publish_all,
+            rng,
+        );
         let new_dkg = RDKGWaitingResponse {
             info,
             shares,
             publics,
+            deals,
             statuses,
+            complaints,
         };
         Ok((new_dkg, bundle))
     }
@@ -277,10 +294,14 @@ pub struct RDKGWaitingResponse<C: Curve> {
     info: ReshareInfo<C>,
     shares: ShareInfo<C>,
     publics: PublicInfo<C>,
+    deals: DealInfo<C>,
     statuses: StatusMatrix,
+    // the complaints found valid so far
+    complaints: Vec<Complaint>,
 }
 
 impl<C: Curve> Phase2<C> for RDKGWaitingResponse<C> {
+    type Output = DKGOutput<C>;
     type Next = RDKGWaitingJustification<C>;
 
     #[allow(clippy::type_complexity)]
@@ -291,7 +312,7 @@ impl<C: Curve> Phase2<C> for RDKGWaitingResponse<C> {
     /// error with the justifications required for Phase 3 of the DKG.
     fn process_responses(
         self,
-        responses: &[BundledResponses],
+        responses: &[BundledResponses<C>],
     ) -> Result<DKGOutput<C>, DKGResult<(Self::Next, Option<BundledJustification<C>>)>> {
         if !self.info.is_share_holder() {
             // we just silently pass
@@ -300,19 +321,23 @@ impl<C: Curve> Phase2<C> for RDKGWaitingResponse<C> {
                 shares: self.shares,
                 statuses: RefCell::new(self.statuses),
                 publics: self.publics,
+                complaints: self.complaints,
             };
             return Err(Ok((dkg, None)));
         }
 
         let info = self.info;
         let mut statuses = self.statuses;
-        set_statuses(
+        let mut complaints = self.complaints;
+        complaints.extend(set_statuses(
             info.new_index.unwrap(),
             &info.prev_group,
             &info.new_group,
             &mut statuses,
+            &self.deals,
             responses,
-        );
+            plain_share_correct::<C>,
+        ));
 
         // find out if justifications are required
         // if there is a least one participant that issued one complaint
@@ -336,12 +361,19 @@ impl<C: Curve> Phase2<C> for RDKGWaitingResponse<C> {
                 shares: self.shares,
                 statuses: RefCell::new(statuses),
                 publics: self.publics,
+                complaints,
             };
             return Err(Ok((dkg, bundled_justifications)));
         }
         // in case of error here, the protocol must be aborted
-        compute_resharing_output(info, self.shares, self.publics, RefCell::new(statuses))
-            .map_err(Err)
+        compute_resharing_output(
+            info,
+            self.shares,
+            self.publics,
+            RefCell::new(statuses),
+            complaints,
+        )
+        .map_err(Err)
     }
 }
 
@@ -353,12 +385,16 @@ pub struct RDKGWaitingJustification<C: Curve> {
     publics: PublicInfo<C>,
     // guaranteed to be of the right size (n)
     statuses: RefCell<StatusMatrix>,
+    // the complaints found valid so far
+    complaints: Vec<Complaint>,
 }
 
 impl<C> Phase3<C> for RDKGWaitingJustification<C>
 where
     C: Curve,
 {
+    type Output = DKGOutput<C>;
+
     /// Accept a justification if the following conditions are true:
     /// - bundle's dealer index is in range
     /// - a justification was required for the given share (no-op)
@@ -384,6 +420,7 @@ where
         let publics = self.publics;
         let shares = self.shares;
         let statuses = self.statuses;
+        let mut complaints = self.complaints;
         justifs
             .iter()
             // this bundle was already invalid for some reason
@@ -405,6 +442,11 @@ where
                     statuses
                         .borrow_mut()
                         .set(b.dealer_idx, n.id(), Status::Complaint);
+                    complaints.push(Complaint {
+                        dealer_idx: b.dealer_idx,
+                        holder_idx: n.id(),
+                        reason: ComplaintReason::InvalidResharing,
+                    });
                 }
             });
 
@@ -414,6 +456,7 @@ where
             valid_shares.into_iter().chain(shares).collect(),
             publics,
             statuses,
+            complaints,
         )
     }
 }
@@ -423,11 +466,12 @@ fn compute_resharing_output<C: Curve>(
     shares: ShareInfo<C>,
     publics: PublicInfo<C>,
     statuses: RefCell<StatusMatrix>,
+    complaints: Vec<Complaint>,
 ) -> DKGResult<DKGOutput<C>> {
     // to compute the final share, we interpolate all the valid shares received
     let mut shares_eval: Vec<Eval<C::Scalar>> = shares
         .into_iter()
-        .map(|(idx, (sh, _))| Eval {
+        .map(|(idx, sh)| Eval {
             value: sh,
             index: idx,
         })
@@ -487,6 +531,7 @@ fn compute_resharing_output<C: Curve>(
     let qual_group = Group::<C>::new(qual, info.new_group.threshold)?;
     Ok(DKGOutput {
         qual: qual_group,
+        transcript: DKGTranscript::new(statuses.into_inner(), complaints),
         public: recovered_public,
         share: Share {
             index: info.new_index.unwrap(),
@@ -584,6 +629,10 @@ mod tests {
                     },
                     public: public_poly.clone(),
                     qual: prev_group.clone(),
+                    transcript: DKGTranscript::new(
+                        StatusMatrix::new(old_n, old_n, Status::Success),
+                        Vec::new(),
+                    ),
                 };
                 RDKG::new_from_share(p, out, new_group.clone()).unwrap()
             })
@@ -631,7 +680,6 @@ mod tests {
                 s[target_idx].dealer_idx,
                 &nsecret,
                 &npublic,
-                "",
                 &group,
                 &mut thread_rng(),
             )
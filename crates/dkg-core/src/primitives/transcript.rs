@@ -0,0 +1,128 @@
+use crate::primitives::status::{Status, StatusMatrix};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use threshold_bls::poly::Idx;
+
+/// The reason of a complaint of a share holder against a dealer. Every reason
+/// can be checked by anyone from what was published on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ComplaintReason {
+    /// The dealer published no share for the holder
+    MissingShare,
+    /// The holder's cipher does not decrypt with the revealed key
+    InvalidCiphertext,
+    /// The share is not the evaluation of the dealer's public polynomial
+    InvalidShare,
+    /// The dealer's public polynomial does not reshare the previous
+    /// distributed key
+    InvalidResharing,
+    /// The dealer's public dealing does not verify
+    InvalidDealing,
+}
+
+/// A complaint of a share holder against a dealer which was found valid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Complaint {
+    /// The index of the dealer the complaint is against
+    pub dealer_idx: Idx,
+    /// The index of the share holder which complained
+    pub holder_idx: Idx,
+    /// Why the share holder complained
+    pub reason: ComplaintReason,
+}
+
+/// A `DKGTranscript` records why the dealers are qualified or not at the end of
+/// the DKG: the final status of the share of every dealer to every share
+/// holder, and the valid complaints against them. A complaint whose status is
+/// still `Complaint` at the end has failed to be justified by the dealer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DKGTranscript {
+    /// The final statuses, indexed by dealer and then by share holder
+    pub statuses: StatusMatrix,
+    /// The valid complaints, at most one for each dealer and share holder
+    pub complaints: Vec<Complaint>,
+}
+
+impl DKGTranscript {
+    /// Creates the transcript from the final statuses and the valid complaints,
+    /// keeping the first complaint of a share holder against a dealer. The
+    /// complaints are sorted by dealer and share holder so that every
+    /// participant serializes the same transcript.
+    pub fn new(statuses: StatusMatrix, complaints: Vec<Complaint>) -> Self {
+        let mut unique = Vec::with_capacity(complaints.len());
+        for complaint in complaints {
+            if !unique.iter().any(|c: &Complaint| {
+                c.dealer_idx == complaint.dealer_idx && c.holder_idx == complaint.holder_idx
+            }) {
+                unique.push(complaint);
+            }
+        }
+        unique.sort_by_key(|c| (c.dealer_idx, c.holder_idx));
+
+        DKGTranscript {
+            statuses,
+            complaints: unique,
+        }
+    }
+
+    /// Returns true if the dealer justified the complaint
+    pub fn is_justified(&self, complaint: &Complaint) -> bool {
+        self.statuses
+            .get(complaint.dealer_idx, complaint.holder_idx)
+            == Status::Success
+    }
+
+    /// Returns the complaints which were not justified, grouped by the
+    /// dealers they disqualify.
+    pub fn blames(&self) -> BTreeMap<Idx, Vec<Complaint>> {
+        let mut blames = BTreeMap::new();
+        for complaint in self.complaints.iter().filter(|c| !self.is_justified(c)) {
+            blames
+                .entry(complaint.dealer_idx)
+                .or_insert_with(Vec::new)
+                .push(*complaint);
+        }
+        blames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blames_unjustified_complaints() {
+        let mut statuses = StatusMatrix::new(3, 3, Status::Success);
+        statuses.set(0, 1, Status::Complaint);
+
+        let transcript = DKGTranscript::new(
+            statuses,
+            vec![
+                Complaint {
+                    dealer_idx: 0,
+                    holder_idx: 1,
+                    reason: ComplaintReason::InvalidShare,
+                },
+                // justified by the dealer
+                Complaint {
+                    dealer_idx: 2,
+                    holder_idx: 0,
+                    reason: ComplaintReason::InvalidCiphertext,
+                },
+                // only the first complaint of a holder against a dealer is kept
+                Complaint {
+                    dealer_idx: 0,
+                    holder_idx: 1,
+                    reason: ComplaintReason::MissingShare,
+                },
+            ],
+        );
+
+        assert_eq!(transcript.complaints.len(), 2);
+        assert!(transcript.is_justified(&transcript.complaints[1]));
+
+        let blames = transcript.blames();
+        assert_eq!(blames.len(), 1);
+        assert_eq!(blames[&0][0].reason, ComplaintReason::InvalidShare);
+    }
+}
@@ -1,4 +1,9 @@
-use crate::primitives::{group::Group, status::Status};
+use crate::primitives::{
+    group::Group,
+    nizk::{DLEQProof, DLogPairProof},
+    status::Status,
+    transcript::DKGTranscript,
+};
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt::Debug;
@@ -20,6 +25,8 @@ pub struct DKGOutput<C: Curve> {
     pub public: PublicPoly<C>,
     /// The private share which corresponds to the participant's index
     pub share: Share<C::Scalar>,
+    /// The statuses and complaints which decided the qualified nodes
+    pub transcript: DKGTranscript,
 }
 
 /// BundledShares holds all encrypted shares a dealer creates during the first
@@ -48,8 +55,6 @@ pub struct EncryptedShare<C: Curve> {
     pub share_idx: Idx,
     /// The ECIES encrypted share
     pub secret: EciesCipher<C>,
-
-    pub rpc_endpoint_secret: EciesCipher<C>,
 }
 
 /// A `BundledResponses` is sent during the second phase of the protocol by all
@@ -58,15 +63,17 @@ pub struct EncryptedShare<C: Curve> {
 /// shares, the one that created the response.  Each `Response` contains the
 /// index of the participant that created the share (a *dealer*),
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BundledResponses {
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct BundledResponses<C: Curve> {
     /// share_idx is the index of the node that received the shares
     pub share_idx: Idx,
     /// A vector of responses from each share creator
-    pub responses: Vec<Response>,
+    pub responses: Vec<Response<C>>,
 }
 
 /// A `Justification` contains the share of the share holder that issued a
-/// complaint, in plaintext.
+/// complaint, in plaintext. Since only complaints proving that the dealer's
+/// cipher was bad are accepted, a share is only revealed for a bad deal.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(bound = "C::Scalar: DeserializeOwned")]
 pub struct Justification<C: Curve> {
@@ -74,6 +81,9 @@ pub struct Justification<C: Curve> {
     pub share_idx: Idx,
     /// The plaintext share
     pub share: C::Scalar,
+    /// The plaintext blinding share, if the dealer committed to its polynomial
+    /// by Pedersen commitments
+    pub blinding: Option<C::Scalar>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,11 +99,44 @@ pub struct BundledJustification<C: Curve> {
     pub public: PublicPoly<C>,
 }
 
+/// A `BundledExtraction` is broadcast by every share holder once the qualified
+/// dealers are fixed in a DKG with Pedersen commitments. It reveals the public
+/// evaluation of the holder's share, from which the distributed public
+/// polynomial is recovered, along with the proof that it is the part of the
+/// holder's commitment in the group generator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct BundledExtraction<C: Curve> {
+    /// The share holder's index
+    pub share_idx: Idx,
+    /// The share multiplied by the group generator
+    pub public: C::Point,
+    /// The proof of the extraction
+    pub proof: DLogPairProof<C>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
 /// A response which gets generated when processing the shares from Phase 1
-pub struct Response {
+pub struct Response<C: Curve> {
     /// The index of the dealer (the person that created the share)
     pub dealer_idx: Idx,
     /// The status of the response (whether it suceeded or if there were complaints)
     pub status: Status,
+    /// The proof of a complaint against a dealer which dealt a cipher to the
+    /// share holder. Complaints about a missing deal do not need one.
+    pub proof: Option<ComplaintProof<C>>,
+}
+
+/// A `ComplaintProof` opens the cipher of a dealer to everyone, so that they
+/// can check that the share it encrypts is invalid. It reveals the
+/// Diffie-Hellman key of the cipher instead of the holder's private key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct ComplaintProof<C: Curve> {
+    /// The holder's private key multiplied by the ephemeral key of the cipher
+    pub dh_key: C::Point,
+    /// The proof that the DH key and the holder's public key have the same
+    /// discrete logarithm
+    pub proof: DLEQProof<C>,
 }
@@ -1,13 +1,16 @@
 use super::board::BoardPublisher;
-use super::primitives::types::{BundledJustification, BundledResponses, BundledShares};
+use super::primitives::types::{
+    BundledExtraction, BundledJustification, BundledResponses, BundledShares,
+};
 use async_trait::async_trait;
 use threshold_bls::group::Curve;
 
 /// An in-memory board used for testing
 pub struct InMemoryBoard<C: Curve> {
     pub shares: Vec<BundledShares<C>>,
-    pub responses: Vec<BundledResponses>,
+    pub responses: Vec<BundledResponses<C>>,
     pub justifs: Vec<BundledJustification<C>>,
+    pub extractions: Vec<BundledExtraction<C>>,
 }
 
 impl<C: Curve> InMemoryBoard<C> {
@@ -17,6 +20,7 @@ impl<C: Curve> InMemoryBoard<C> {
             shares: vec![],
             responses: vec![],
             justifs: vec![],
+            extractions: vec![],
         }
     }
 }
@@ -33,7 +37,7 @@ impl<C: Curve> BoardPublisher<C> for InMemoryBoard<C> {
         Ok(())
     }
 
-    async fn publish_responses(&mut self, bundle: BundledResponses) -> Result<(), Self::Error>
+    async fn publish_responses(&mut self, bundle: BundledResponses<C>) -> Result<(), Self::Error>
     where
         C: 'async_trait,
     {
@@ -51,4 +55,12 @@ impl<C: Curve> BoardPublisher<C> for InMemoryBoard<C> {
         self.justifs.push(bundle);
         Ok(())
     }
+
+    async fn publish_extraction(&mut self, bundle: BundledExtraction<C>) -> Result<(), Self::Error>
+    where
+        C: 'async_trait,
+    {
+        self.extractions.push(bundle);
+        Ok(())
+    }
 }
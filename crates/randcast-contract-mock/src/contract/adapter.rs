@@ -4,6 +4,7 @@ use super::types::{
     GroupRelayConfirmationTaskState, SignatureTask, UnresponsiveGroupEvent,
 };
 use super::utils::calculate_hash;
+use dkg_core::primitives::task_committers;
 use std::collections::HashMap;
 use threshold_bls::poly::Eval;
 use threshold_bls::schemes::bls12_381::G1Scheme as SigScheme;
@@ -19,6 +20,8 @@ pub const CHALLENGE_REWARD_PER_SIGNATURE: usize = 300;
 
 pub const SIGNATURE_TASK_EXCLUSIVE_WINDOW: usize = 30;
 
+pub const COMMITTER_EXCLUSIVE_WINDOW: usize = 10;
+
 pub const RELAY_CONFIRMATION_VALIDATION_WINDOW: usize = 30;
 
 pub const MAX_FAIL_RANDOMNESS_TASK_COUNT: usize = 3;
@@ -128,6 +131,15 @@ pub trait AdapterViews {
 
     fn get_signature_task_completion_state(&self, index: usize) -> bool;
 
+    /// The committers of a pending signature task within the group, followed by its backup
+    /// committers. They rotate over the members with every task, as many of each as the
+    /// committers of the group.
+    fn get_task_committers(
+        &self,
+        group_index: usize,
+        signature_index: usize,
+    ) -> Option<(Vec<String>, Vec<String>)>;
+
     fn get_group_relay_confirmation_task_state(&self, task_index: usize) -> i32;
 
     fn valid_group_indices(&self) -> Vec<usize>;
@@ -263,7 +275,17 @@ impl AdapterTransactions for Adapter {
             .ok_or(ControllerError::GroupNotExisted)?
             .clone();
 
-        if !group.committers.contains(&id_address.to_string()) {
+        let (task_committers, backup_committers) = self
+            .get_task_committers(group_index, signature_index)
+            .unwrap();
+
+        // backup committers can fulfill the task once the committers have missed their
+        // exclusive window
+        if !task_committers.contains(&id_address.to_string())
+            && (!backup_committers.contains(&id_address.to_string())
+                || self.block_height
+                    <= signature_task.assignment_block_height + COMMITTER_EXCLUSIVE_WINDOW)
+        {
             return Err(ControllerError::NotFromCommitter);
         }
 
@@ -615,6 +637,25 @@ impl AdapterViews for Adapter {
         index < self.signature_count && !self.pending_signature_tasks.contains_key(&index)
     }
 
+    fn get_task_committers(
+        &self,
+        group_index: usize,
+        signature_index: usize,
+    ) -> Option<(Vec<String>, Vec<String>)> {
+        let group = self.groups.get(&group_index)?;
+
+        let signature_task = self.pending_signature_tasks.get(&signature_index)?;
+
+        let members = group.members.keys().cloned().collect::<Vec<_>>();
+
+        Some(task_committers(
+            signature_task.index,
+            signature_task.message.as_bytes(),
+            &members,
+            group.committers.len(),
+        ))
+    }
+
     fn valid_group_indices(&self) -> Vec<usize> {
         self.groups
             .values()
@@ -699,3 +740,122 @@ impl AdapterInternal for Adapter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::{Adapter, AdapterMockHelper, AdapterTransactions, AdapterViews};
+    use crate::contract::{
+        types::{Group, Member},
+        utils::calculate_hash,
+    };
+    use arpa_node_core::{address_to_string, DKGStatus, Group as NodeGroup, Member as NodeMember};
+    use arpa_node_dal::{
+        cache::{GroupInfo, InMemoryGroupInfoCache},
+        GroupInfoFetcher,
+    };
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_task_committers_agree_with_nodes() {
+        let addresses = (0..7)
+            .map(|i| format!("0x{:040x}", calculate_hash(&i)))
+            .collect::<Vec<_>>();
+
+        let group = Group {
+            index: 0,
+            epoch: 1,
+            capacity: 10,
+            size: addresses.len(),
+            threshold: 4,
+            is_strictly_majority_consensus_reached: true,
+            public_key: vec![],
+            fail_randomness_task_count: 0,
+            members: addresses
+                .iter()
+                .enumerate()
+                .map(|(index, address)| {
+                    let member = Member {
+                        id_address: address.clone(),
+                        index,
+                        partial_public_key: vec![],
+                    };
+                    (address.clone(), member)
+                })
+                .collect(),
+            committers: addresses[..3].to_vec(),
+            commit_cache: BTreeMap::new(),
+        };
+
+        let mut adapter = Adapter::new(0x11, "0x00000000000000000000000000000000000000ac".into());
+        adapter
+            .set_initial_group(
+                "0x00000000000000000000000000000000000000ad",
+                bincode::serialize(&group).unwrap(),
+            )
+            .unwrap();
+
+        let mut node_group = NodeGroup::new();
+        node_group.index = 0;
+        node_group.epoch = 1;
+        node_group.size = addresses.len();
+        node_group.threshold = 4;
+        node_group.state = true;
+        for (index, address) in addresses.iter().enumerate() {
+            let member = NodeMember {
+                index,
+                id_address: address.parse().unwrap(),
+                partial_public_key: None,
+            };
+            node_group.members.insert(member.id_address, member);
+        }
+        node_group.committers = node_group
+            .members
+            .keys()
+            .filter(|member| addresses[..3].contains(&address_to_string(**member)))
+            .copied()
+            .collect();
+
+        let group_cache = InMemoryGroupInfoCache::rebuild(
+            vec![GroupInfo::rebuild(
+                None,
+                node_group,
+                DKGStatus::None,
+                0,
+                0,
+                None,
+            )],
+            vec![],
+        );
+
+        for _ in 0..10 {
+            adapter.request_randomness("test").unwrap();
+            let task = adapter.emit_signature_task().unwrap();
+
+            let (committers, backup_committers) =
+                adapter.get_task_committers(0, task.index).unwrap();
+            assert_eq!(committers.len(), 3);
+            assert_eq!(backup_committers.len(), 3);
+
+            let (node_committers, node_backup_committers) = group_cache
+                .get_task_committers(0, 1, task.index, task.message.as_bytes())
+                .unwrap();
+
+            assert_eq!(
+                node_committers
+                    .into_iter()
+                    .map(address_to_string)
+                    .collect::<Vec<_>>(),
+                committers
+            );
+            assert_eq!(
+                node_backup_committers
+                    .into_iter()
+                    .map(address_to_string)
+                    .collect::<Vec<_>>(),
+                backup_committers
+            );
+
+            adapter.mine(1).unwrap();
+        }
+    }
+}
@@ -8,15 +8,18 @@ use super::types::{
     CommitCache, CommitResult, DKGTask, Group, GroupRelayTask, Member, Node, SignatureTask,
 };
 use super::utils::{choose_randomly_from_indices, minimum_threshold};
+use dkg_core::primitives::{ComplaintReason, DKGTranscript};
 use std::cmp::{max, Ordering};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::{Deref, DerefMut};
-use threshold_bls::curve::bls12381::G1;
+use threshold_bls::{curve::bls12381::G1, poly::Idx};
 
 pub const NODE_STAKING_AMOUNT: usize = 50000;
 
 pub const DISQUALIFIED_NODE_PENALTY_AMOUNT: usize = 1000;
 
+pub const MISSING_SHARE_PENALTY_AMOUNT: usize = 200;
+
 pub const DKG_POST_PROCESS_REWARD: usize = 100;
 
 pub const DEFAULT_MINIMUM_THRESHOLD: usize = 3;
@@ -55,6 +58,8 @@ pub struct Controller {
     dkg_task: Option<DKGTask>,
     group_relay_task: Option<GroupRelayTask>,
     pub coordinators: HashMap<usize, (String, Coordinator)>,
+    // the DKG protocol of the emitted DKG tasks
+    dkg_protocol: usize,
 }
 
 impl Controller {
@@ -66,9 +71,14 @@ impl Controller {
             dkg_task: None,
             group_relay_task: None,
             coordinators: HashMap::new(),
+            dkg_protocol: 0,
         }
     }
 
+    pub fn set_dkg_protocol(&mut self, dkg_protocol: usize) {
+        self.dkg_protocol = dkg_protocol;
+    }
+
     // pub fn fulfill_relay(
     //     &mut self,
     //     _id_address: &str,
@@ -167,6 +177,7 @@ pub trait ControllerTransactions {
         public_key: Vec<u8>,
         partial_public_key: Vec<u8>,
         disqualified_nodes: Vec<String>,
+        dkg_transcript: Vec<u8>,
     ) -> ControllerResult<()>;
 
     fn post_process_dkg(
@@ -281,7 +292,7 @@ impl ControllerInternal for Controller {
 
         members.sort_by(|a, b| a.1.cmp(&b.1));
 
-        coordinator.initialize(self.block_height, members)?;
+        coordinator.initialize(self.block_height, members, self.dkg_protocol)?;
 
         let group_index = group.index;
 
@@ -308,6 +319,7 @@ impl ControllerInternal for Controller {
             members,
             assignment_block_height: self.block_height,
             coordinator_address,
+            dkg_protocol: self.dkg_protocol,
         };
 
         self.dkg_task = Some(dkg_task);
@@ -790,6 +802,7 @@ impl ControllerTransactions for Controller {
         public_key: Vec<u8>,
         partial_public_key: Vec<u8>,
         disqualified_nodes: Vec<String>,
+        dkg_transcript: Vec<u8>,
     ) -> ControllerResult<()> {
         if !self.groups.contains_key(&group_index) {
             return Err(ControllerError::GroupNotExisted);
@@ -826,6 +839,7 @@ impl ControllerTransactions for Controller {
             group_epoch,
             public_key,
             disqualified_nodes,
+            dkg_transcript,
         };
 
         let commit_cache = CommitCache {
@@ -895,18 +909,30 @@ impl ControllerTransactions for Controller {
                                 .push(index_member_map.get(c).unwrap().clone());
                         });
 
+                        let penalties = disqualified_nodes
+                            .iter()
+                            .map(|node| {
+                                group.members.get(node).map_or(
+                                    DISQUALIFIED_NODE_PENALTY_AMOUNT,
+                                    |member| {
+                                        disqualification_penalty(
+                                            &identical_commit.dkg_transcript,
+                                            member.index,
+                                        )
+                                    },
+                                )
+                            })
+                            .collect::<Vec<_>>();
+
                         // move out these disqualified_nodes from the group
                         group
                             .members
                             .retain(|node, _| !disqualified_nodes.contains(node));
 
-                        for disqualified_node in disqualified_nodes {
-                            self.slash_node(
-                                &disqualified_node,
-                                DISQUALIFIED_NODE_PENALTY_AMOUNT,
-                                0,
-                                false,
-                            )?;
+                        for (disqualified_node, penalty) in
+                            disqualified_nodes.into_iter().zip(penalties)
+                        {
+                            self.slash_node(&disqualified_node, penalty, 0, false)?;
                         }
                     }
                 }
@@ -1097,14 +1123,43 @@ impl ControllerViews for Controller {
     // }
 }
 
+/// A dealer which only failed to deal some shares may just have been offline,
+/// so it is slashed less than one proven by the transcript to have dealt
+/// invalid shares. A disqualified node the transcript can't tell about is
+/// slashed fully.
+fn disqualification_penalty(dkg_transcript: &[u8], member_index: usize) -> usize {
+    let blames = match bincode::deserialize::<DKGTranscript>(dkg_transcript) {
+        Ok(transcript) => transcript.blames(),
+        Err(_) => return DISQUALIFIED_NODE_PENALTY_AMOUNT,
+    };
+
+    match blames.get(&(member_index as Idx)) {
+        Some(complaints)
+            if complaints
+                .iter()
+                .all(|c| c.reason == ComplaintReason::MissingShare) =>
+        {
+            MISSING_SHARE_PENALTY_AMOUNT
+        }
+        _ => DISQUALIFIED_NODE_PENALTY_AMOUNT,
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
 
     use std::collections::HashMap;
 
     use crate::contract::adapter::AdapterTransactions;
+    use dkg_core::primitives::{
+        status::{Status, StatusMatrix},
+        Complaint, ComplaintReason, DKGTranscript,
+    };
 
-    use super::{Adapter, Controller};
+    use super::{
+        disqualification_penalty, Adapter, Controller, DISQUALIFIED_NODE_PENALTY_AMOUNT,
+        MISSING_SHARE_PENALTY_AMOUNT,
+    };
 
     #[test]
     fn test() {
@@ -1126,6 +1181,45 @@ pub mod tests {
         println!("{:?}", controller.rewards.get(node_address));
     }
 
+    #[test]
+    fn test_disqualification_penalty() {
+        let mut statuses = StatusMatrix::new(3, 3, Status::Success);
+        statuses.set(0, 1, Status::Complaint);
+        statuses.set(1, 2, Status::Complaint);
+
+        let complaint = |dealer_idx, holder_idx, reason| Complaint {
+            dealer_idx,
+            holder_idx,
+            reason,
+        };
+        let transcript = DKGTranscript::new(
+            statuses,
+            vec![
+                complaint(0, 1, ComplaintReason::MissingShare),
+                complaint(1, 2, ComplaintReason::InvalidShare),
+            ],
+        );
+        let transcript = bincode::serialize(&transcript).unwrap();
+
+        assert_eq!(
+            MISSING_SHARE_PENALTY_AMOUNT,
+            disqualification_penalty(&transcript, 0)
+        );
+        assert_eq!(
+            DISQUALIFIED_NODE_PENALTY_AMOUNT,
+            disqualification_penalty(&transcript, 1)
+        );
+        // not blamed by the transcript
+        assert_eq!(
+            DISQUALIFIED_NODE_PENALTY_AMOUNT,
+            disqualification_penalty(&transcript, 2)
+        );
+        assert_eq!(
+            DISQUALIFIED_NODE_PENALTY_AMOUNT,
+            disqualification_penalty(&[], 0)
+        );
+    }
+
     #[test]
     fn test2() {
         let vec1 = vec![String::from("232wer3")];
@@ -1,5 +1,19 @@
 use super::errors::{CoordinatorError, CoordinatorResult};
+use dkg_core::primitives::{
+    pvss::{verify_dealing, PublicDealing},
+    Group, Node, ShareError,
+};
 use std::collections::HashMap;
+use threshold_bls::{
+    curve::bls12381::{Curve, G1},
+    poly::Idx,
+};
+
+pub const JOINT_FELDMAN_DKG: usize = 0;
+
+pub const GENNARO_DKG: usize = 1;
+
+pub const PVSS_DKG: usize = 2;
 
 pub struct Coordinator {
     /// Mapping of Ethereum Address => BLS public keys
@@ -10,6 +24,8 @@ pub struct Coordinator {
     pub responses: HashMap<String, Vec<u8>>,
     /// Mapping of Ethereum Address => DKG Phase 3 Justifications
     pub justifications: HashMap<String, Vec<u8>>,
+    /// Mapping of Ethereum Address => DKG Phase 4 Extractions, only published in the Gennaro DKG
+    pub extractions: HashMap<String, Vec<u8>>,
     /// List of registered Ethereum keys (used for conveniently fetching data)
     pub participants: Vec<String>,
     // The duration of each phase
@@ -18,6 +34,8 @@ pub struct Coordinator {
     pub epoch: usize,
     /// The threshold of the DKG
     pub threshold: usize,
+    /// The DKG protocol of the group, only the Gennaro DKG has the extraction phase
+    pub dkg_protocol: usize,
     /// If it's 0 then the DKG is still pending start. If >0, it is the DKG's start block
     pub start_block: usize,
     /// for mock
@@ -31,11 +49,13 @@ impl Coordinator {
             shares: HashMap::new(),
             responses: HashMap::new(),
             justifications: HashMap::new(),
+            extractions: HashMap::new(),
             participants: vec![],
             start_block: 0,
             phase_duration,
             epoch,
             threshold,
+            dkg_protocol: JOINT_FELDMAN_DKG,
             block_height: 0,
         }
     }
@@ -47,12 +67,19 @@ pub trait Transactions {
         &mut self,
         block_height: usize,
         members: Vec<(String, usize, Vec<u8>)>,
+        dkg_protocol: usize,
     ) -> CoordinatorResult<()>;
 
     /// Participant publishes their data and depending on the phase the data gets inserted
-    /// in the shares, responses or justifications mapping. Reverts if the participant
+    /// in the shares, responses, justifications or extractions mapping. Reverts if the participant
     /// has already published their data for a phase or if the DKG has ended.
     fn publish(&mut self, id_address: String, value: Vec<u8>) -> CoordinatorResult<()>;
+
+    /// Participant publishes their dealing of the non-interactive PVSS DKG in its single round,
+    /// which is inserted in the shares mapping. Reverts unless the dealing is dealt at the participant's own index and
+    /// its encrypted shares are proven valid against the group, so that a bad or impersonated
+    /// dealing never reaches the share holders.
+    fn publish_dealing(&mut self, id_address: String, value: Vec<u8>) -> CoordinatorResult<()>;
 }
 
 pub trait Views {
@@ -68,6 +95,9 @@ pub trait Views {
     /// Gets the participants' justifications
     fn get_justifications(&self) -> CoordinatorResult<Vec<Vec<u8>>>;
 
+    /// Gets the participants' extractions
+    fn get_extractions(&self) -> CoordinatorResult<Vec<Vec<u8>>>;
+
     /// Gets the participants' ethereum addresses
     fn get_participants(&self) -> CoordinatorResult<Vec<String>>;
 
@@ -84,6 +114,9 @@ pub trait Internal {
 
     /// The DKG starts when startBlock > 0
     fn only_when_not_started(&self) -> CoordinatorResult<()>;
+
+    /// Builds the DKG group from the registered keys, indexed as the nodes do
+    fn group(&self) -> CoordinatorResult<Group<Curve>>;
 }
 
 pub trait MockHelper {
@@ -114,6 +147,23 @@ impl Internal for Coordinator {
 
         Ok(())
     }
+
+    fn group(&self) -> CoordinatorResult<Group<Curve>> {
+        let nodes = self
+            .participants
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let key: G1 = bincode::deserialize(self.keys.get(p).unwrap())?;
+                Ok(Node::<Curve>::new(i as Idx, key))
+            })
+            .collect::<CoordinatorResult<_>>()?;
+
+        Ok(Group {
+            threshold: self.threshold,
+            nodes,
+        })
+    }
 }
 
 impl Transactions for Coordinator {
@@ -121,9 +171,16 @@ impl Transactions for Coordinator {
         &mut self,
         block_height: usize,
         members: Vec<(String, usize, Vec<u8>)>,
+        dkg_protocol: usize,
     ) -> CoordinatorResult<()> {
         self.only_when_not_started()?;
 
+        if ![JOINT_FELDMAN_DKG, GENNARO_DKG, PVSS_DKG].contains(&dkg_protocol) {
+            return Err(CoordinatorError::UnknownDKGProtocol(dkg_protocol));
+        }
+
+        self.dkg_protocol = dkg_protocol;
+
         self.start_block = block_height;
 
         self.block_height = block_height;
@@ -142,6 +199,10 @@ impl Transactions for Coordinator {
     fn publish(&mut self, id_address: String, value: Vec<u8>) -> CoordinatorResult<()> {
         self.only_allowed(&id_address)?;
 
+        if self.dkg_protocol == PVSS_DKG {
+            return Err(CoordinatorError::UnexpectedDKGData(self.dkg_protocol));
+        }
+
         let blocks_since_start = self.block_height - self.start_block;
 
         if blocks_since_start <= self.phase_duration {
@@ -162,12 +223,55 @@ impl Transactions for Coordinator {
             }
 
             self.justifications.insert(id_address, value);
+        } else if self.dkg_protocol == GENNARO_DKG && blocks_since_start <= 4 * self.phase_duration
+        {
+            if self.extractions.contains_key(&id_address) {
+                return Err(CoordinatorError::ExtractionsExisted);
+            }
+
+            self.extractions.insert(id_address, value);
         } else {
             return Err(CoordinatorError::DKGPublishEnded);
         }
 
         Ok(())
     }
+
+    fn publish_dealing(&mut self, id_address: String, value: Vec<u8>) -> CoordinatorResult<()> {
+        self.only_allowed(&id_address)?;
+
+        if self.dkg_protocol != PVSS_DKG {
+            return Err(CoordinatorError::UnexpectedDKGData(self.dkg_protocol));
+        }
+
+        // the dealings are published in a single round
+        if self.block_height - self.start_block > self.phase_duration {
+            return Err(CoordinatorError::DKGPublishEnded);
+        }
+
+        if self.shares.contains_key(&id_address) {
+            return Err(CoordinatorError::SharesExisted);
+        }
+
+        // the dealing leads the published value, which may carry more data of the node after it
+        let dealing: PublicDealing<Curve> = bincode::deserialize(&value)?;
+
+        // binds the dealing to its publisher, a dealing only claims its dealer's index
+        let dealer_idx = self
+            .participants
+            .iter()
+            .position(|p| p == &id_address)
+            .unwrap() as Idx;
+        if dealing.dealer_idx != dealer_idx {
+            return Err(ShareError::InvalidDealer(dealing.dealer_idx).into());
+        }
+
+        verify_dealing(&self.group()?, &dealing)?;
+
+        self.shares.insert(id_address, value);
+
+        Ok(())
+    }
 }
 
 impl Views for Coordinator {
@@ -183,6 +287,10 @@ impl Views for Coordinator {
         Ok(self.justifications.values().cloned().collect::<Vec<_>>())
     }
 
+    fn get_extractions(&self) -> CoordinatorResult<Vec<Vec<u8>>> {
+        Ok(self.extractions.values().cloned().collect::<Vec<_>>())
+    }
+
     fn get_participants(&self) -> CoordinatorResult<Vec<String>> {
         Ok(self.participants.clone())
     }
@@ -204,6 +312,14 @@ impl Views for Coordinator {
             return Ok(0);
         }
 
+        if self.dkg_protocol == PVSS_DKG {
+            if blocks_since_start <= 2 * self.phase_duration {
+                return Ok(1);
+            }
+
+            return Err(CoordinatorError::DKGEnded);
+        }
+
         if blocks_since_start <= 2 * self.phase_duration {
             return Ok(1);
         }
@@ -212,10 +328,223 @@ impl Views for Coordinator {
             return Ok(2);
         }
 
-        if blocks_since_start <= 4 * self.phase_duration {
+        if self.dkg_protocol == GENNARO_DKG {
+            if blocks_since_start <= 4 * self.phase_duration {
+                return Ok(3);
+            }
+
+            if blocks_since_start <= 5 * self.phase_duration {
+                return Ok(4);
+            }
+        } else if blocks_since_start <= 4 * self.phase_duration {
             return Ok(3);
         }
 
         Err(CoordinatorError::DKGEnded)
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::{
+        Coordinator, MockHelper, Transactions, Views, GENNARO_DKG, JOINT_FELDMAN_DKG, PVSS_DKG,
+    };
+    use crate::contract::errors::CoordinatorError;
+    use dkg_core::primitives::{
+        pvss::{PublicDealing, NIDKG},
+        Group, Node, ShareError, SingleRound,
+    };
+    use rand::thread_rng;
+    use threshold_bls::{
+        curve::bls12381::{Curve, Scalar, G1},
+        group::Element,
+        poly::Idx,
+    };
+
+    fn setup(
+        n: usize,
+        thr: usize,
+        dkg_protocol: usize,
+    ) -> (Coordinator, Vec<String>, Vec<NIDKG<Curve>>) {
+        let privs = (0..n)
+            .map(|_| Scalar::rand(&mut thread_rng()))
+            .collect::<Vec<_>>();
+        let keys = privs
+            .iter()
+            .map(|p| {
+                let mut key = G1::one();
+                key.mul(p);
+                key
+            })
+            .collect::<Vec<_>>();
+        let addresses = (0..n)
+            .map(|i| format!("0x{:040x}", i + 1))
+            .collect::<Vec<_>>();
+
+        let mut coordinator = Coordinator::new(1, thr, 10);
+        coordinator
+            .initialize(
+                1,
+                addresses
+                    .iter()
+                    .zip(keys.iter())
+                    .enumerate()
+                    .map(|(i, (a, k))| (a.clone(), i, bincode::serialize(k).unwrap()))
+                    .collect(),
+                dkg_protocol,
+            )
+            .unwrap();
+
+        let group = Group::<Curve> {
+            threshold: thr,
+            nodes: keys
+                .into_iter()
+                .enumerate()
+                .map(|(i, k)| Node::new(i as Idx, k))
+                .collect(),
+        };
+        let dkgs = privs
+            .into_iter()
+            .map(|p| NIDKG::new(p, group.clone()).unwrap())
+            .collect();
+
+        (coordinator, addresses, dkgs)
+    }
+
+    #[test]
+    fn test_publish_dealing() {
+        let (mut coordinator, addresses, dkgs) = setup(3, 2, PVSS_DKG);
+        let dealings = dkgs
+            .iter()
+            .map(|dkg| dkg.deal(&mut thread_rng()).unwrap())
+            .collect::<Vec<_>>();
+
+        // a dealing published by another participant than its dealer
+        let res = coordinator.publish_dealing(
+            addresses[1].clone(),
+            bincode::serialize(&dealings[0]).unwrap(),
+        );
+        assert!(matches!(
+            res,
+            Err(CoordinatorError::InvalidDealing(ShareError::InvalidDealer(
+                0
+            )))
+        ));
+
+        // a dealing whose share is not the one proven
+        let mut invalid_dealing: PublicDealing<Curve> = dealings[1].clone();
+        invalid_dealing.shares[2] = invalid_dealing.shares[0].clone();
+        invalid_dealing.shares[2].share_idx = 2;
+        let res = coordinator.publish_dealing(
+            addresses[1].clone(),
+            bincode::serialize(&invalid_dealing).unwrap(),
+        );
+        assert!(matches!(
+            res,
+            Err(CoordinatorError::InvalidDealing(
+                ShareError::InvalidEncryptionProof(1, 2)
+            ))
+        ));
+
+        for (address, dealing) in addresses.iter().zip(dealings.iter()) {
+            coordinator
+                .publish_dealing(address.clone(), bincode::serialize(dealing).unwrap())
+                .unwrap();
+        }
+        assert_eq!(coordinator.shares.len(), 3);
+
+        let res = coordinator.publish_dealing(
+            addresses[0].clone(),
+            bincode::serialize(&dealings[0]).unwrap(),
+        );
+        assert!(matches!(res, Err(CoordinatorError::SharesExisted)));
+    }
+
+    #[test]
+    fn test_joint_feldman_phases() {
+        let (mut coordinator, addresses, _) = setup(3, 2, JOINT_FELDMAN_DKG);
+
+        coordinator.mine(30);
+        assert_eq!(coordinator.in_phase().unwrap(), 2);
+
+        // the phase after the justifications is the commit phase, there is no extraction
+        coordinator.mine(1);
+        assert_eq!(coordinator.in_phase().unwrap(), 3);
+        let res = coordinator.publish(addresses[0].clone(), vec![1]);
+        assert!(matches!(res, Err(CoordinatorError::DKGPublishEnded)));
+        assert!(coordinator.extractions.is_empty());
+
+        coordinator.mine(10);
+        assert!(matches!(
+            coordinator.in_phase(),
+            Err(CoordinatorError::DKGEnded)
+        ));
+    }
+
+    #[test]
+    fn test_gennaro_phases() {
+        let (mut coordinator, addresses, _) = setup(3, 2, GENNARO_DKG);
+
+        coordinator.mine(31);
+        assert_eq!(coordinator.in_phase().unwrap(), 3);
+        coordinator.publish(addresses[0].clone(), vec![1]).unwrap();
+        assert_eq!(coordinator.get_extractions().unwrap(), vec![vec![1]]);
+
+        coordinator.mine(10);
+        assert_eq!(coordinator.in_phase().unwrap(), 4);
+        let res = coordinator.publish(addresses[1].clone(), vec![1]);
+        assert!(matches!(res, Err(CoordinatorError::DKGPublishEnded)));
+
+        coordinator.mine(10);
+        assert!(matches!(
+            coordinator.in_phase(),
+            Err(CoordinatorError::DKGEnded)
+        ));
+    }
+
+    #[test]
+    fn test_pvss_phases() {
+        let (mut coordinator, addresses, dkgs) = setup(3, 2, PVSS_DKG);
+
+        // the dealings are the only data of the PVSS DKG
+        let res = coordinator.publish(addresses[0].clone(), vec![1]);
+        assert!(matches!(
+            res,
+            Err(CoordinatorError::UnexpectedDKGData(PVSS_DKG))
+        ));
+
+        coordinator.mine(10);
+        assert_eq!(coordinator.in_phase().unwrap(), 0);
+
+        // the dealing window is the first phase only
+        coordinator.mine(1);
+        assert_eq!(coordinator.in_phase().unwrap(), 1);
+        let dealing = dkgs[0].deal(&mut thread_rng()).unwrap();
+        let res = coordinator
+            .publish_dealing(addresses[0].clone(), bincode::serialize(&dealing).unwrap());
+        assert!(matches!(res, Err(CoordinatorError::DKGPublishEnded)));
+
+        coordinator.mine(10);
+        assert!(matches!(
+            coordinator.in_phase(),
+            Err(CoordinatorError::DKGEnded)
+        ));
+
+        // the other protocols take no dealing
+        let (mut coordinator, addresses, dkgs) = setup(3, 2, JOINT_FELDMAN_DKG);
+        let dealing = dkgs[0].deal(&mut thread_rng()).unwrap();
+        let res = coordinator
+            .publish_dealing(addresses[0].clone(), bincode::serialize(&dealing).unwrap());
+        assert!(matches!(
+            res,
+            Err(CoordinatorError::UnexpectedDKGData(JOINT_FELDMAN_DKG))
+        ));
+    }
+
+    #[test]
+    fn test_initialize_unknown_protocol() {
+        let mut coordinator = Coordinator::new(1, 2, 10);
+        let res = coordinator.initialize(1, vec![], 3);
+        assert!(matches!(res, Err(CoordinatorError::UnknownDKGProtocol(3))));
+    }
+}
@@ -1,3 +1,4 @@
+use dkg_core::primitives::ShareError;
 use thiserror::Error;
 use threshold_bls::sig::BLSError;
 
@@ -127,6 +128,12 @@ pub enum CoordinatorError {
     #[error("DKG has already started")]
     AlreadyStarted,
 
+    #[error("unknown DKG protocol: {0}")]
+    UnknownDKGProtocol(usize),
+
+    #[error("the data is not published in the DKG protocol: {0}")]
+    UnexpectedDKGData(usize),
+
     #[error("DKG has already ended")]
     DKGEnded,
 
@@ -141,4 +148,13 @@ pub enum CoordinatorError {
 
     #[error("you already published your justifications")]
     JustificationsExisted,
+
+    #[error("you already published your extraction")]
+    ExtractionsExisted,
+
+    #[error("deserialization failed: the dealing is malformed {0}")]
+    DealingBadFormat(#[from] bincode::Error),
+
+    #[error("the dealing is rejected: {0}")]
+    InvalidDealing(#[from] ShareError),
 }
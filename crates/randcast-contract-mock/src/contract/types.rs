@@ -45,6 +45,8 @@ pub struct CommitResult {
     pub(crate) group_epoch: usize,
     pub(crate) public_key: Vec<u8>,
     pub(crate) disqualified_nodes: Vec<String>,
+    /// The bincode serialized `DKGTranscript` blaming the disqualified nodes
+    pub(crate) dkg_transcript: Vec<u8>,
 }
 
 impl PartialEq for CommitResult {
@@ -52,6 +54,7 @@ impl PartialEq for CommitResult {
         self.group_epoch == other.group_epoch
             && self.public_key == other.public_key
             && self.disqualified_nodes == other.disqualified_nodes
+            && self.dkg_transcript == other.dkg_transcript
     }
 }
 
@@ -60,6 +63,7 @@ impl Hash for CommitResult {
         self.group_epoch.hash(state);
         self.public_key.hash(state);
         self.disqualified_nodes.hash(state);
+        self.dkg_transcript.hash(state);
     }
 }
 
@@ -80,6 +84,8 @@ pub struct DKGTask {
     pub members: BTreeMap<String, usize>,
     pub assignment_block_height: usize,
     pub coordinator_address: String,
+    /// 0 for the Joint-Feldman DKG, 1 for the Gennaro DKG
+    pub dkg_protocol: usize,
 }
 
 #[derive(Clone)]
@@ -17,6 +17,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         None => panic!("Didn't get a controller rpc endpoint string"),
     };
 
+    // 0 for the Joint-Feldman DKG, 1 for the Gennaro DKG, 2 for the PVSS DKG
+    let dkg_protocol = match args.next() {
+        Some(arg) => arg.parse().expect("Didn't get a valid DKG protocol"),
+        None => 0,
+    };
+
     let initial_entropy = 0x1111_1111_1111_1111;
 
     println!(
@@ -26,7 +32,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let adapter = Adapter::new(initial_entropy, controller_rpc_endpoint.clone());
 
-    let controller = Controller::new(adapter);
+    let mut controller = Controller::new(adapter);
+
+    controller.set_dkg_protocol(dkg_protocol);
 
     let controller = Arc::new(RwLock::new(controller));
 
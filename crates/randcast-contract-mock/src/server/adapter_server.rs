@@ -16,11 +16,12 @@ use crate::contract::{
     types::{Group, GroupRelayConfirmationTask, Member as ModelMember, SignatureTask},
 };
 use adapter::{
-    CancelInvalidRelayConfirmationTaskRequest, ConfirmRelayRequest, FulfillRandomnessRequest,
-    FulfillRelayRequest, GetGroupRelayCacheRequest, GetGroupRelayConfirmationTaskStateReply,
-    GetGroupRelayConfirmationTaskStateRequest, GetSignatureTaskCompletionStateReply,
-    GetSignatureTaskCompletionStateRequest, GroupRelayConfirmationTaskReply, LastOutputReply,
-    MineReply, MineRequest, RequestRandomnessRequest, SetInitialGroupRequest, SignatureTaskReply,
+    CancelInvalidRelayConfirmationTaskRequest, ClaimRequest, ConfirmRelayRequest,
+    FulfillRandomnessRequest, FulfillRelayRequest, GetGroupRelayCacheRequest,
+    GetGroupRelayConfirmationTaskStateReply, GetGroupRelayConfirmationTaskStateRequest,
+    GetSignatureTaskCompletionStateReply, GetSignatureTaskCompletionStateRequest,
+    GroupRelayConfirmationTaskReply, LastOutputReply, MineReply, MineRequest,
+    RequestRandomnessRequest, SetInitialGroupRequest, SignatureTaskReply,
 };
 use parking_lot::RwLock;
 use std::{collections::BTreeMap, sync::Arc};
@@ -150,6 +151,20 @@ impl AdapterTransactions for MockAdapter {
             .map(|()| Response::new(()))
             .map_err(|e| Status::internal(e.to_string()))
     }
+
+    async fn claim(&self, request: Request<ClaimRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+
+        self.adapter
+            .write()
+            .claim(
+                &req.id_address,
+                &req.reward_address,
+                req.token_amount as usize,
+            )
+            .map(|()| Response::new(()))
+            .map_err(|e| Status::internal(e.to_string()))
+    }
 }
 
 #[tonic::async_trait]
@@ -3,8 +3,8 @@ use self::controller::{
         Transactions as ControllerTransactions, TransactionsServer as ControllerTransactionsServer,
     },
     views_server::{Views as ControllerViews, ViewsServer as ControllerViewsServer},
-    CommitDkgRequest, GetNodeRequest, Member, NodeRegisterRequest, NodeReply,
-    PostProcessDkgRequest,
+    CommitDkgRequest, GetNodeRequest, Member, NodeActivateRequest, NodeQuitRequest,
+    NodeRegisterRequest, NodeReply, PostProcessDkgRequest,
 };
 use self::coordinator::{
     transactions_server::{
@@ -12,8 +12,8 @@ use self::coordinator::{
         TransactionsServer as CoordinatorTransactionsServer,
     },
     views_server::{Views as CoordinatorViews, ViewsServer as CoordinatorViewsServer},
-    BlsKeysReply, InPhaseReply, JustificationsReply, ParticipantsReply, PublishRequest,
-    ResponsesReply, SharesReply,
+    BlsKeysReply, ExtractionsReply, InPhaseReply, JustificationsReply, ParticipantsReply,
+    PublishRequest, ResponsesReply, SharesReply,
 };
 use super::adapter_server::{
     adapter::{
@@ -104,6 +104,29 @@ impl ControllerTransactions for MockController {
             .map_err(|e| Status::internal(e.to_string()))
     }
 
+    async fn node_activate(
+        &self,
+        request: Request<NodeActivateRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+
+        self.controller
+            .write()
+            .node_activate(req.id_address)
+            .map(|()| Response::new(()))
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+
+    async fn node_quit(&self, request: Request<NodeQuitRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+
+        self.controller
+            .write()
+            .node_quit(&req.id_address)
+            .map(|()| Response::new(()))
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+
     async fn commit_dkg(&self, request: Request<CommitDkgRequest>) -> Result<Response<()>, Status> {
         let req = request.into_inner();
 
@@ -116,6 +139,7 @@ impl ControllerTransactions for MockController {
                 req.public_key,
                 req.partial_public_key,
                 req.disqualified_nodes,
+                req.dkg_transcript,
             )
             .map(|()| Response::new(()))
             .map_err(|e| Status::internal(e.to_string()))
@@ -182,6 +206,7 @@ impl ControllerViews for MockController {
                     members,
                     assignment_block_height,
                     coordinator_address,
+                    dkg_protocol,
                 } = dkg_task;
 
                 let members = members
@@ -197,6 +222,7 @@ impl ControllerViews for MockController {
                     members,
                     assignment_block_height: assignment_block_height as u32,
                     coordinator_address,
+                    dkg_protocol: dkg_protocol as u32,
                 })
             })
             .map_err(|e| Status::not_found(e.to_string()))
@@ -245,6 +271,25 @@ impl CoordinatorTransactions for MockCoordinator {
             .map(|()| Response::new(()))
             .map_err(|e| Status::internal(e.to_string()))
     }
+
+    async fn publish_dealing(
+        &self,
+        request: Request<PublishRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req_index = self.check_and_fetch_coordinator_group_index_from_request(&request)?;
+
+        let req = request.into_inner();
+
+        self.controller
+            .write()
+            .coordinators
+            .get_mut(&req_index)
+            .unwrap()
+            .1
+            .publish_dealing(req.id_address, req.value)
+            .map(|()| Response::new(()))
+            .map_err(|e| Status::internal(e.to_string()))
+    }
 }
 
 #[tonic::async_trait]
@@ -297,6 +342,23 @@ impl CoordinatorViews for MockCoordinator {
             .map_err(|e| Status::internal(e.to_string()))
     }
 
+    async fn get_extractions(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<ExtractionsReply>, Status> {
+        let req_index = self.check_and_fetch_coordinator_group_index_from_request(&request)?;
+
+        self.controller
+            .read()
+            .coordinators
+            .get(&req_index)
+            .unwrap()
+            .1
+            .get_extractions()
+            .map(|extractions| Response::new(ExtractionsReply { extractions }))
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+
     async fn get_participants(
         &self,
         request: Request<()>,
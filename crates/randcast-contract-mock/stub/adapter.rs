@@ -66,6 +66,15 @@ pub struct ConfirmRelayRequest {
     pub signature: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimRequest {
+    #[prost(string, tag = "1")]
+    pub id_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub reward_address: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub token_amount: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetGroupRequest {
     #[prost(uint32, tag = "1")]
     pub index: u32,
@@ -204,6 +213,10 @@ pub mod transactions_server {
             &self,
             request: tonic::Request<super::ConfirmRelayRequest>,
         ) -> Result<tonic::Response<()>, tonic::Status>;
+        async fn claim(
+            &self,
+            request: tonic::Request<super::ClaimRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct TransactionsServer<T: Transactions> {
@@ -483,6 +496,37 @@ pub mod transactions_server {
                     };
                     Box::pin(fut)
                 }
+                "/adapter.Transactions/Claim" => {
+                    #[allow(non_camel_case_types)]
+                    struct ClaimSvc<T: Transactions>(pub Arc<T>);
+                    impl<T: Transactions> tonic::server::UnaryService<super::ClaimRequest> for ClaimSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ClaimRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).claim(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ClaimSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     Ok(http::Response::builder()
                         .status(200)
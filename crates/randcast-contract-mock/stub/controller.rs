@@ -6,6 +6,16 @@ pub struct NodeRegisterRequest {
     pub id_public_key: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NodeActivateRequest {
+    #[prost(string, tag = "1")]
+    pub id_address: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NodeQuitRequest {
+    #[prost(string, tag = "1")]
+    pub id_address: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CommitDkgRequest {
     #[prost(string, tag = "1")]
     pub id_address: ::prost::alloc::string::String,
@@ -19,6 +29,8 @@ pub struct CommitDkgRequest {
     pub partial_public_key: ::prost::alloc::vec::Vec<u8>,
     #[prost(string, repeated, tag = "6")]
     pub disqualified_nodes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub dkg_transcript: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PostProcessDkgRequest {
@@ -105,6 +117,14 @@ pub mod transactions_server {
             &self,
             request: tonic::Request<super::NodeRegisterRequest>,
         ) -> Result<tonic::Response<()>, tonic::Status>;
+        async fn node_activate(
+            &self,
+            request: tonic::Request<super::NodeActivateRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+        async fn node_quit(
+            &self,
+            request: tonic::Request<super::NodeQuitRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
         async fn commit_dkg(
             &self,
             request: tonic::Request<super::CommitDkgRequest>,
@@ -190,6 +210,70 @@ pub mod transactions_server {
                     };
                     Box::pin(fut)
                 }
+                "/controller.Transactions/NodeActivate" => {
+                    #[allow(non_camel_case_types)]
+                    struct NodeActivateSvc<T: Transactions>(pub Arc<T>);
+                    impl<T: Transactions> tonic::server::UnaryService<super::NodeActivateRequest>
+                        for NodeActivateSvc<T>
+                    {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::NodeActivateRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).node_activate(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = NodeActivateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/controller.Transactions/NodeQuit" => {
+                    #[allow(non_camel_case_types)]
+                    struct NodeQuitSvc<T: Transactions>(pub Arc<T>);
+                    impl<T: Transactions> tonic::server::UnaryService<super::NodeQuitRequest> for NodeQuitSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::NodeQuitRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).node_quit(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = NodeQuitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/controller.Transactions/CommitDkg" => {
                     #[allow(non_camel_case_types)]
                     struct CommitDkgSvc<T: Transactions>(pub Arc<T>);
@@ -62,6 +62,15 @@ impl Sc for Scalar {
     fn sub(&mut self, other: &Self) {
         self.sub_assign(other);
     }
+
+    fn to_bits(&self) -> Vec<bool> {
+        self.into_repr()
+            .as_ref()
+            .iter()
+            .flat_map(|limb| (0..64).map(move |i| (limb >> i) & 1 == 1))
+            .take(Fr::NUM_BITS as usize)
+            .collect()
+    }
 }
 
 /// G1 points can be multiplied by Fr elements
@@ -218,4 +227,14 @@ mod tests {
         let mut ii = Scalar::new();
         ii.set_int(4);
     }
+
+    #[test]
+    fn scalar_to_bits() {
+        let mut s = Scalar::new();
+        s.set_int(6);
+        let bits = s.to_bits();
+        assert_eq!(bits.len(), Fr::NUM_BITS as usize);
+        assert_eq!(&bits[..4], &[false, true, true, false]);
+        assert!(bits[4..].iter().all(|b| !b));
+    }
 }
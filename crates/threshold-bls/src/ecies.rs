@@ -63,6 +63,13 @@ pub struct EciesCipher<C: Curve> {
     nonce: [u8; NONCE_LEN],
 }
 
+impl<C: Curve> EciesCipher<C> {
+    /// Returns the ephemeral public key of the cipher
+    pub fn ephemeral(&self) -> &C::Point {
+        &self.ephemeral
+    }
+}
+
 /// Encrypts the message with a public key (curve point) and returns a ciphertext
 pub fn encrypt<C: Curve, R: RngCore>(to: &C::Point, msg: &[u8], rng: &mut R) -> EciesCipher<C> {
     let eph_secret = C::Scalar::rand(rng);
@@ -102,7 +109,18 @@ pub fn decrypt<C: Curve>(private: &C::Scalar, cipher: &EciesCipher<C>) -> Result
     let mut dh = cipher.ephemeral.clone();
     dh.mul(private);
 
-    let ephemeral_key = derive::<C>(&dh);
+    decrypt_with_dh(&dh, cipher)
+}
+
+/// Decrypts the message with the Diffie-Hellman key of the cipher, i.e. the
+/// receiver's secret key multiplied by the ephemeral public key, and returns
+/// the cleartext. Revealing that key opens the cipher to anyone without
+/// revealing the secret key.
+pub fn decrypt_with_dh<C: Curve>(
+    dh: &C::Point,
+    cipher: &EciesCipher<C>,
+) -> Result<Vec<u8>, AError> {
+    let ephemeral_key = derive::<C>(dh);
 
     let aead = ChaCha20Poly1305::new(Key::from_slice(ephemeral_key.as_slice()));
 
@@ -158,4 +176,24 @@ mod tests {
         cipher.aead = vec![0; 32];
         decrypt::<Curve>(&s2, &cipher).unwrap_err();
     }
+
+    #[test]
+    fn test_decryption_with_dh() {
+        let (s1, _) = kp();
+        let (s2, p2) = kp();
+        let data = vec![1, 2, 3, 4];
+
+        let cipher = encrypt::<Curve, _>(&p2, &data, &mut thread_rng());
+
+        // the receiver's DH key opens the cipher
+        let mut dh = cipher.ephemeral().clone();
+        dh.mul(&s2);
+        let deciphered = decrypt_with_dh::<Curve>(&dh, &cipher).unwrap();
+        assert_eq!(data, deciphered);
+
+        // any other DH key does not
+        let mut dh = cipher.ephemeral().clone();
+        dh.mul(&s1);
+        decrypt_with_dh::<Curve>(&dh, &cipher).unwrap_err();
+    }
 }
@@ -42,6 +42,8 @@ pub trait Scalar: Element {
     fn inverse(&self) -> Option<Self>;
     fn negate(&mut self);
     fn sub(&mut self, other: &Self);
+    /// Returns the bits of the canonical representation, least significant first
+    fn to_bits(&self) -> Vec<bool>;
     // TODO
 }
 